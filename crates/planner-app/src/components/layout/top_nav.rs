@@ -1,13 +1,28 @@
-use dioxus::logger::tracing::info;
+use dioxus::logger::tracing::{error, info};
 use dioxus::prelude::*;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen::JsCast;
 
-use crate::components::ui::SettingsModal;
+use crate::components::ui::{
+    AutoScheduleModal, CompareModal, CsvImportModal, MergeModal, PlanHealthModal, PortfolioModal,
+    ScenariosModal, SettingsModal,
+};
 use crate::plan_io::trigger_plan_download;
-use crate::state::{create_sample_plan, use_plan_state, use_preferences, use_viewing_session};
+use crate::state::{
+    create_sample_plan, use_app_context, use_history, use_plan_state, use_preferences,
+    use_rejected_import, use_storage_error, use_viewing_session,
+};
 use crate::storage;
-use planner_core::models::{PlanExport, PlanState, Preferences};
+use planner_core::formats::csv::{allocation_matrix_csv, allocations_long_csv};
+use planner_core::formats::html::report_html;
+use planner_core::formats::ical::{plan_calendar_ics, IcsExportOptions};
+use planner_core::formats::markdown::report_markdown;
+use planner_core::formats::mermaid::gantt_mermaid;
+use planner_core::formats::svg::{gantt_svg, GanttTheme};
+use planner_core::migration::load_json;
+use planner_core::models::{EditHistory, PlanExport, PlanState, PlanView, Preferences};
+use planner_core::report::PlanReport;
+use planner_core::validation::{validate_export, validate_plan, ValidationReport};
 
 /// Represents the different views in the application
 #[derive(Clone, Copy, PartialEq)]
@@ -15,6 +30,7 @@ pub enum View {
     Roadmap,
     Technical,
     Allocation,
+    Timeline,
 }
 
 /// Top navigation component with view tabs, plan menu, and capacity indicator
@@ -22,10 +38,37 @@ pub enum View {
 pub fn TopNav(active_view: Signal<View>) -> Element {
     let show_plan_menu = use_signal(|| false);
     let mut show_settings = use_signal(|| false);
+    let mut show_auto_schedule = use_signal(|| false);
+    let mut show_scenarios = use_signal(|| false);
+    let mut show_compare = use_signal(|| false);
+    let mut show_merge = use_signal(|| false);
+    let mut show_portfolio = use_signal(|| false);
+    let mut show_plan_health = use_signal(|| false);
+    let mut show_csv_import = use_signal(|| false);
 
     let mut plan_state = use_plan_state();
     let mut preferences = use_preferences();
     let viewing_session = use_viewing_session();
+    let mut history = use_history();
+    let storage_error = use_storage_error();
+    let mut rejected_import = use_rejected_import();
+
+    // Open Plan health when an imported plan has problems. Tracks the
+    // imported JSON so edits to the viewed plan don't reopen it.
+    let mut checked_import = use_signal(|| None::<String>);
+    use_effect(move || {
+        let Some(session) = viewing_session() else {
+            checked_import.set(None);
+            return;
+        };
+        if checked_import.peek().as_deref() == Some(session.original_json.as_str()) {
+            return;
+        }
+        checked_import.set(Some(session.original_json));
+        if !validate_plan(&preferences.peek(), &plan_state.peek()).is_empty() {
+            show_plan_health.set(true);
+        }
+    });
 
     let plan = plan_state();
     let prefs = preferences();
@@ -45,13 +88,14 @@ pub fn TopNav(active_view: Signal<View>) -> Element {
     let plan_menu_title = viewing_filename
         .clone()
         .unwrap_or_else(|| plan.quarter_name.clone());
+    let active_scenario = plan.active_scenario_name().map(str::to_string);
 
     // Hidden file input for web imports
     let file_input_id = use_signal(|| "plan-file-input".to_string());
 
     // Register keyboard shortcuts (web only)
     #[cfg(target_family = "wasm")]
-    register_keyboard_shortcuts(file_input_id, preferences, plan_state);
+    register_keyboard_shortcuts(file_input_id, use_app_context());
 
     rsx! {
         // Hidden file input for import (web only - desktop uses native dialog)
@@ -62,7 +106,14 @@ pub fn TopNav(active_view: Signal<View>) -> Element {
             style: "display: none;",
             onchange: move |_| {
                 #[cfg(target_family = "wasm")]
-                handle_file_import(file_input_id(), preferences, plan_state, viewing_session);
+                handle_file_import(
+                    file_input_id(),
+                    preferences,
+                    plan_state,
+                    viewing_session,
+                    history,
+                    rejected_import,
+                );
             },
         }
 
@@ -77,12 +128,21 @@ pub fn TopNav(active_view: Signal<View>) -> Element {
             PlanMenu {
                 is_viewing,
                 plan_menu_title,
+                active_scenario,
                 viewing_modified,
                 show_plan_menu,
+                show_auto_schedule,
+                show_scenarios,
+                show_compare,
+                show_merge,
+                show_portfolio,
+                show_plan_health,
+                show_csv_import,
                 file_input_id,
                 preferences,
                 plan_state,
                 viewing_session,
+                history,
             }
 
             // View tabs
@@ -100,7 +160,15 @@ pub fn TopNav(active_view: Signal<View>) -> Element {
             if is_viewing {
                 button {
                     class: "btn btn-secondary",
-                    onclick: move |_| restore_from_local_storage(preferences, plan_state, viewing_session),
+                    onclick: move |_| {
+                        restore_from_local_storage(
+                            preferences,
+                            plan_state,
+                            viewing_session,
+                            history,
+                            storage_error,
+                        )
+                    },
                     "Close"
                 }
             }
@@ -114,21 +182,76 @@ pub fn TopNav(active_view: Signal<View>) -> Element {
             }
         }
 
+        // Read-only storage banner
+        if let Some(error) = storage_error() {
+            div { class: "storage-error-banner",
+                span {
+                    "Your saved plan can't be opened by this version of Planner, so changes won't be saved. "
+                    "Update Planner to keep it, or reset to start over. ({error})"
+                }
+                button {
+                    class: "btn btn-secondary",
+                    onclick: move |_| reset_saved_data(preferences, plan_state, history, storage_error),
+                    "Reset Saved Data"
+                }
+            }
+        }
+
+        // Scenarios Modal
+        if show_scenarios() {
+            ScenariosModal { on_close: move |_| show_scenarios.set(false) }
+        }
+
+        // Compare Modal
+        if show_compare() {
+            CompareModal { on_close: move |_| show_compare.set(false) }
+        }
+
+        // Merge Modal
+        if show_merge() {
+            MergeModal { on_close: move |_| show_merge.set(false) }
+        }
+
+        // Portfolio Modal
+        if show_portfolio() {
+            PortfolioModal { on_close: move |_| show_portfolio.set(false) }
+        }
+
+        // Plan Health Modal
+        if show_plan_health() {
+            PlanHealthModal { on_close: move |_| show_plan_health.set(false) }
+        }
+
+        // Plan Health for a plan that couldn't be opened
+        if let Some(report) = rejected_import() {
+            PlanHealthModal {
+                report,
+                on_close: move |_| rejected_import.set(None),
+            }
+        }
+
+        // CSV Import Modal
+        if show_csv_import() {
+            CsvImportModal { on_close: move |_| show_csv_import.set(false) }
+        }
+
+        // Auto-schedule Modal
+        if show_auto_schedule() {
+            AutoScheduleModal { on_close: move |_| show_auto_schedule.set(false) }
+        }
+
         // Settings Modal
         if show_settings() {
             SettingsModal {
                 on_clear_preferences: move |_| {
-                    info!("Clearing all preferences and plan state");
-                    let _ = storage::clear_preferences();
-                    let _ = storage::clear_plan_state();
-                    preferences.set(Preferences::default());
-                    plan_state.set(PlanState::default());
+                    reset_saved_data(preferences, plan_state, history, storage_error);
                 },
                 on_load_sample_data: move |_| {
                     info!("Loading sample plan data");
                     let (sample_prefs, sample_state) = create_sample_plan();
                     preferences.set(sample_prefs);
                     plan_state.set(sample_state);
+                    history.with_mut(|h| h.clear());
                 },
                 on_close: move |_| show_settings.set(false),
             }
@@ -163,12 +286,21 @@ fn AppIcon() -> Element {
 fn PlanMenu(
     is_viewing: bool,
     plan_menu_title: String,
+    active_scenario: Option<String>,
     viewing_modified: bool,
     mut show_plan_menu: Signal<bool>,
+    mut show_auto_schedule: Signal<bool>,
+    mut show_scenarios: Signal<bool>,
+    mut show_compare: Signal<bool>,
+    mut show_merge: Signal<bool>,
+    mut show_portfolio: Signal<bool>,
+    mut show_plan_health: Signal<bool>,
+    mut show_csv_import: Signal<bool>,
     file_input_id: Signal<String>,
     mut preferences: Signal<Preferences>,
     mut plan_state: Signal<PlanState>,
     mut viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    history: Signal<EditHistory>,
 ) -> Element {
     rsx! {
         div { class: "plan-menu-wrapper",
@@ -180,6 +312,9 @@ fn PlanMenu(
                     span { class: "plan-menu-icon", "📄" }
                 }
                 span { class: "plan-menu-title", "{plan_menu_title}" }
+                if let Some(scenario) = active_scenario {
+                    span { class: "plan-menu-scenario", title: "Editing scenario", "{scenario}" }
+                }
                 if viewing_modified {
                     span { class: "plan-menu-modified", "•" }
                 }
@@ -193,6 +328,79 @@ fn PlanMenu(
                     onclick: move |_| show_plan_menu.set(false),
                 }
                 div { class: "plan-menu-dropdown",
+                    HistoryMenuItems { show_plan_menu }
+
+                    div { class: "plan-menu-separator" }
+
+                    MenuItem {
+                        icon: "🔀",
+                        label: "Scenarios...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_scenarios.set(true);
+                        },
+                    }
+
+                    MenuItem {
+                        icon: "⚖",
+                        label: "Compare...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_compare.set(true);
+                        },
+                    }
+
+                    MenuItem {
+                        icon: "⑂",
+                        label: "Merge...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_merge.set(true);
+                        },
+                    }
+
+                    MenuItem {
+                        icon: "▦",
+                        label: "Portfolio...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_portfolio.set(true);
+                        },
+                    }
+
+                    MenuItem {
+                        icon: "✚",
+                        label: "Plan health...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_plan_health.set(true);
+                        },
+                    }
+
+                    MenuItem {
+                        icon: "✨",
+                        label: "Auto-schedule...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_auto_schedule.set(true);
+                        },
+                    }
+
+                    div { class: "plan-menu-separator" }
+
+                    ExportMenuItems { show_plan_menu, preferences, plan_state }
+
+                    MenuItem {
+                        icon: "⇪",
+                        label: "Import from CSV...",
+                        onclick: move |_| {
+                            show_plan_menu.set(false);
+                            show_csv_import.set(true);
+                        },
+                    }
+
+                    div { class: "plan-menu-separator" }
+
                     if is_viewing {
                         ViewingModeMenu {
                             viewing_modified,
@@ -200,6 +408,7 @@ fn PlanMenu(
                             preferences,
                             plan_state,
                             viewing_session,
+                            history,
                         }
                     } else {
                         NormalModeMenu {
@@ -208,6 +417,7 @@ fn PlanMenu(
                             preferences,
                             plan_state,
                             viewing_session,
+                            history,
                         }
                     }
                 }
@@ -224,7 +434,10 @@ fn ViewingModeMenu(
     mut preferences: Signal<Preferences>,
     mut plan_state: Signal<PlanState>,
     mut viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    mut history: Signal<EditHistory>,
 ) -> Element {
+    let storage_error = use_storage_error();
+
     rsx! {
         // Save to File
         MenuItem {
@@ -238,19 +451,22 @@ fn ViewingModeMenu(
             },
         }
 
-        // Adopt This Plan
-        MenuItem {
-            icon: "📌",
-            label: "Adopt This Plan",
+        // Adopt This Plan (not while saved data from a newer version is kept)
+        button {
+            class: "plan-menu-item",
+            disabled: storage_error().is_some(),
             onclick: move |_| {
                 info!("Adopting viewed plan as local plan");
                 show_plan_menu.set(false);
                 let _ = storage::save_preferences(&preferences());
                 let _ = storage::save_plan_state(&plan_state());
+                let _ = storage::save_history(&history());
                 viewing_session.set(None);
                 // Clear URL since user now owns this plan
                 crate::plan_io::clear_url_plan_param();
             },
+            span { class: "menu-icon", "📌" }
+            span { class: "menu-label", "Adopt This Plan" }
         }
 
         div { class: "plan-menu-separator" }
@@ -262,10 +478,11 @@ fn ViewingModeMenu(
             onclick: move |_| {
                 show_plan_menu.set(false);
                 if let Some(session) = viewing_session() {
-                    if let Ok(export) = serde_json::from_str::<PlanExport>(&session.original_json) {
+                    if let Ok(export) = load_json::<PlanExport>(&session.original_json) {
                         let (original_prefs, original_state) = export.into_signals();
                         preferences.set(original_prefs);
                         plan_state.set(original_state);
+                        history.with_mut(|h| h.clear());
                         viewing_session.set(Some(crate::state::ViewingSession {
                             filename: session.filename,
                             original_json: session.original_json,
@@ -284,7 +501,13 @@ fn ViewingModeMenu(
             label: "Close",
             onclick: move |_| {
                 show_plan_menu.set(false);
-                restore_from_local_storage(preferences, plan_state, viewing_session);
+                restore_from_local_storage(
+                    preferences,
+                    plan_state,
+                    viewing_session,
+                    history,
+                    storage_error,
+                );
             },
         }
     }
@@ -298,7 +521,10 @@ fn NormalModeMenu(
     mut preferences: Signal<Preferences>,
     mut plan_state: Signal<PlanState>,
     mut viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    mut history: Signal<EditHistory>,
 ) -> Element {
+    let mut rejected_import = use_rejected_import();
+
     rsx! {
         // Open Plan
         button {
@@ -308,7 +534,7 @@ fn NormalModeMenu(
                 #[cfg(target_family = "wasm")]
                 {
                     show_plan_menu.set(false);
-                    trigger_file_open(file_input_id());
+                    crate::plan_io::trigger_file_open(&file_input_id());
                 }
                 #[cfg(not(target_family = "wasm"))]
                 {
@@ -338,6 +564,8 @@ fn NormalModeMenu(
                                     &mut preferences,
                                     &mut plan_state,
                                     &mut viewing_session,
+                                    &mut history,
+                                    &mut rejected_import,
                                 ) {
                                     Ok(()) => info!("Plan loaded successfully!"),
                                     Err(e) => error!("Failed to load plan: {}", e),
@@ -438,10 +666,186 @@ fn NormalModeMenu(
             onclick: move |_| {
                 show_plan_menu.set(false);
                 #[cfg(target_family = "wasm")]
-                handle_paste_from_clipboard(preferences, plan_state, viewing_session);
+                handle_paste_from_clipboard(
+                    preferences,
+                    plan_state,
+                    viewing_session,
+                    history,
+                    rejected_import,
+                );
                 #[cfg(not(target_family = "wasm"))]
-                handle_paste_from_clipboard_desktop(preferences, plan_state, viewing_session);
+                handle_paste_from_clipboard_desktop(
+                    preferences,
+                    plan_state,
+                    viewing_session,
+                    history,
+                    rejected_import,
+                );
+            },
+        }
+    }
+}
+
+/// Export menu items (shared by both menu modes)
+#[component]
+fn ExportMenuItems(
+    mut show_plan_menu: Signal<bool>,
+    preferences: Signal<Preferences>,
+    plan_state: Signal<PlanState>,
+) -> Element {
+    rsx! {
+        MenuItem {
+            icon: "▤",
+            label: "Export Grid as CSV",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let csv = allocation_matrix_csv(&preferences(), &plan_state());
+                let filename = crate::plan_io::export_filename(&export, "grid", "csv");
+                if let Err(e) = crate::plan_io::trigger_text_download(&csv, &filename, "text/csv") {
+                    error!("Failed to export CSV: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "☰",
+            label: "Export Allocations as CSV",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let csv = allocations_long_csv(&preferences(), &plan_state());
+                let filename = crate::plan_io::export_filename(&export, "allocations", "csv");
+                if let Err(e) = crate::plan_io::trigger_text_download(&csv, &filename, "text/csv") {
+                    error!("Failed to export CSV: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "📝",
+            label: "Export Report as Markdown",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let report = report_markdown(&PlanReport::new(&preferences(), &plan_state()));
+                let filename = crate::plan_io::export_filename(&export, "report", "md");
+                if let Err(e) = crate::plan_io::trigger_text_download(&report, &filename, "text/markdown") {
+                    error!("Failed to export report: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "🌐",
+            label: "Export Report as HTML",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let report = report_html(&PlanReport::new(&preferences(), &plan_state()));
+                let filename = crate::plan_io::export_filename(&export, "report", "html");
+                if let Err(e) = crate::plan_io::trigger_text_download(&report, &filename, "text/html") {
+                    error!("Failed to export report: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "📅",
+            label: "Export Calendar (.ics)",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let options = IcsExportOptions { member_assignments: false };
+                let ics = plan_calendar_ics(&preferences(), &plan_state(), options);
+                let filename = crate::plan_io::export_filename(&export, "calendar", "ics");
+                if let Err(e) = crate::plan_io::trigger_text_download(&ics, &filename, "text/calendar") {
+                    error!("Failed to export calendar: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "📅",
+            label: "Export Calendar with Assignments",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let options = IcsExportOptions { member_assignments: true };
+                let ics = plan_calendar_ics(&preferences(), &plan_state(), options);
+                let filename = crate::plan_io::export_filename(&export, "calendar-assignments", "ics");
+                if let Err(e) = crate::plan_io::trigger_text_download(&ics, &filename, "text/calendar") {
+                    error!("Failed to export calendar: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "📊",
+            label: "Export Timeline as SVG",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let export = PlanExport::from_signals(preferences(), plan_state());
+                let svg = gantt_svg(&preferences(), &plan_state(), GanttTheme::Light);
+                let filename = crate::plan_io::export_filename(&export, "timeline", "svg");
+                if let Err(e) = crate::plan_io::trigger_text_download(&svg, &filename, "image/svg+xml") {
+                    error!("Failed to export timeline: {}", e);
+                }
+            },
+        }
+
+        MenuItem {
+            icon: "🧜",
+            label: "Copy Timeline as Mermaid",
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                let diagram = gantt_mermaid(&preferences(), &plan_state());
+                let markdown = format!("```mermaid\n{}```\n", diagram);
+                if let Err(e) = crate::plan_io::copy_text_to_clipboard(&markdown) {
+                    error!("Failed to copy timeline: {}", e);
+                }
+            },
+        }
+    }
+}
+
+/// Undo/Redo menu items (shared by both menu modes)
+#[component]
+fn HistoryMenuItems(mut show_plan_menu: Signal<bool>) -> Element {
+    let ctx = use_app_context();
+    let history = use_history();
+
+    let undo_label = history()
+        .undo_label()
+        .map(|l| format!("Undo {}", l))
+        .unwrap_or_else(|| "Undo".to_string());
+    let redo_label = history()
+        .redo_label()
+        .map(|l| format!("Redo {}", l))
+        .unwrap_or_else(|| "Redo".to_string());
+
+    rsx! {
+        button {
+            class: "plan-menu-item",
+            disabled: !history().can_undo(),
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                ctx.undo();
+            },
+            span { class: "menu-icon", "↶" }
+            span { class: "menu-label", "{undo_label}" }
+            span { class: "menu-shortcut", "⌘Z" }
+        }
+        button {
+            class: "plan-menu-item",
+            disabled: !history().can_redo(),
+            onclick: move |_| {
+                show_plan_menu.set(false);
+                ctx.redo();
             },
+            span { class: "menu-icon", "↷" }
+            span { class: "menu-label", "{redo_label}" }
+            span { class: "menu-shortcut", "⇧⌘Z" }
         }
     }
 }
@@ -475,6 +879,7 @@ fn ViewTabs(mut active_view: Signal<View>) -> Element {
             ViewTab { view: View::Allocation, label: "Allocation", active_view }
             ViewTab { view: View::Technical, label: "Technical", active_view }
             ViewTab { view: View::Roadmap, label: "Roadmap", active_view }
+            ViewTab { view: View::Timeline, label: "Timeline", active_view }
         }
     }
 }
@@ -536,13 +941,11 @@ fn ChevronIcon() -> Element {
 
 /// Calculate capacity metrics for the team
 fn calculate_capacity_metrics(plan: &PlanState, prefs: &Preferences) -> (f32, f32, &'static str) {
-    let total_capacity: f32 = prefs.team_members.iter().map(|m| m.capacity).sum();
-    let total_allocated: f32 = plan
-        .allocations
-        .iter()
-        .flat_map(|a| &a.assignments)
-        .map(|a| a.percentage / 100.0)
-        .sum();
+    // Capacity reflects holidays and time off, not just nominal weeks;
+    // allocations for people no longer on the roster aren't counted
+    let view = PlanView::new(prefs, plan);
+    let total_capacity = view.total_capacity().total();
+    let total_allocated = view.total_allocated().total();
 
     let utilization_ratio = if total_capacity > 0.0 {
         total_allocated / total_capacity
@@ -575,29 +978,44 @@ fn restore_from_local_storage(
     mut preferences: Signal<Preferences>,
     mut plan_state: Signal<PlanState>,
     mut viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    mut history: Signal<EditHistory>,
+    mut storage_error: Signal<Option<String>>,
 ) {
     info!("Closing viewed plan, restoring from local storage");
-    let restored_prefs = storage::load_preferences().unwrap_or_default();
-    let restored_state = storage::load_plan_state().unwrap_or_default();
-    preferences.set(restored_prefs);
-    plan_state.set(restored_state);
+    let saved = storage::load_saved_plan();
+    let restored_history = match &saved.plan_state {
+        Some(_) => storage::load_history().unwrap_or_default(),
+        None => EditHistory::default(),
+    };
+    if let Some(e) = saved.error {
+        storage_error.set(Some(e.to_string()));
+    }
+    preferences.set(saved.preferences.unwrap_or_default());
+    plan_state.set(saved.plan_state.unwrap_or_default());
+    history.set(restored_history);
     viewing_session.set(None);
     // Clear ?plan= from URL so refresh doesn't reload the shared plan
     crate::plan_io::clear_url_plan_param();
 }
 
-/// Trigger file open dialog (web only - clicks hidden file input)
-#[cfg(target_family = "wasm")]
-fn trigger_file_open(file_input_id: String) {
-    if let Some(window) = web_sys::window() {
-        if let Some(document) = window.document() {
-            if let Some(input) = document.get_element_by_id(&file_input_id) {
-                if let Ok(html_input) = input.dyn_into::<web_sys::HtmlInputElement>() {
-                    html_input.click();
-                }
-            }
-        }
-    }
+/// Clear saved data and start over with an empty plan
+///
+/// This is also how the user leaves read-only mode after saved data from a
+/// newer version couldn't be loaded.
+fn reset_saved_data(
+    mut preferences: Signal<Preferences>,
+    mut plan_state: Signal<PlanState>,
+    mut history: Signal<EditHistory>,
+    mut storage_error: Signal<Option<String>>,
+) {
+    info!("Clearing all preferences and plan state");
+    let _ = storage::clear_preferences();
+    let _ = storage::clear_plan_state();
+    let _ = storage::clear_history();
+    storage_error.set(None);
+    preferences.set(Preferences::default());
+    plan_state.set(PlanState::default());
+    history.with_mut(|h| h.clear());
 }
 
 /// Load plan from JSON string (shared logic)
@@ -607,11 +1025,18 @@ fn load_plan_from_json(
     prefs_signal: &mut Signal<Preferences>,
     state_signal: &mut Signal<PlanState>,
     viewing_signal: &mut Signal<Option<crate::state::ViewingSession>>,
+    history_signal: &mut Signal<EditHistory>,
+    rejected_signal: &mut Signal<Option<ValidationReport>>,
 ) -> Result<(), String> {
-    let export: PlanExport =
-        serde_json::from_str(json).map_err(|e| format!("Failed to parse: {}", e))?;
-
-    export.validate().map_err(|e| format!("{:?}", e))?;
+    let export: PlanExport = load_json(json).map_err(|e| e.to_string())?;
+
+    // Plans with errors aren't opened; Plan health shows why
+    let report = validate_export(&export);
+    if report.has_errors() {
+        let summary = report.summary();
+        rejected_signal.set(Some(report));
+        return Err(summary);
+    }
 
     let original_json = serde_json::to_string(&export).unwrap_or_default();
     let (loaded_prefs, loaded_state) = export.into_signals();
@@ -624,6 +1049,8 @@ fn load_plan_from_json(
 
     prefs_signal.set(loaded_prefs);
     state_signal.set(loaded_state);
+    // Edits made to the previous plan don't apply to the loaded one
+    history_signal.with_mut(|h| h.clear());
 
     Ok(())
 }
@@ -635,10 +1062,14 @@ fn handle_file_import(
     preferences: Signal<Preferences>,
     plan_state: Signal<PlanState>,
     viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    history: Signal<EditHistory>,
+    rejected_import: Signal<Option<ValidationReport>>,
 ) {
     let mut prefs_signal = preferences;
     let mut state_signal = plan_state;
     let mut viewing_signal = viewing_session;
+    let mut history_signal = history;
+    let mut rejected_signal = rejected_import;
 
     wasm_bindgen_futures::spawn_local(async move {
         if let Ok(result) = crate::plan_io::read_file_from_input(&file_input_id).await {
@@ -648,6 +1079,8 @@ fn handle_file_import(
                 &mut prefs_signal,
                 &mut state_signal,
                 &mut viewing_signal,
+                &mut history_signal,
+                &mut rejected_signal,
             ) {
                 web_sys::console::error_1(&format!("Failed to load plan: {}", e).into());
             }
@@ -661,10 +1094,14 @@ fn handle_paste_from_clipboard(
     preferences: Signal<Preferences>,
     plan_state: Signal<PlanState>,
     viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    history: Signal<EditHistory>,
+    rejected_import: Signal<Option<ValidationReport>>,
 ) {
     let mut prefs_signal = preferences;
     let mut state_signal = plan_state;
     let mut viewing_signal = viewing_session;
+    let mut history_signal = history;
+    let mut rejected_signal = rejected_import;
 
     wasm_bindgen_futures::spawn_local(async move {
         if let Ok(content) = crate::plan_io::read_from_clipboard().await {
@@ -689,6 +1126,8 @@ fn handle_paste_from_clipboard(
                 &mut prefs_signal,
                 &mut state_signal,
                 &mut viewing_signal,
+                &mut history_signal,
+                &mut rejected_signal,
             ) {
                 web_sys::console::error_1(&format!("Failed to load plan: {}", e).into());
             }
@@ -698,13 +1137,12 @@ fn handle_paste_from_clipboard(
 
 /// Register global keyboard shortcuts (web only)
 #[cfg(target_family = "wasm")]
-fn register_keyboard_shortcuts(
-    file_input_id: Signal<String>,
-    preferences: Signal<Preferences>,
-    plan_state: Signal<PlanState>,
-) {
+fn register_keyboard_shortcuts(file_input_id: Signal<String>, ctx: crate::state::AppContext) {
     use wasm_bindgen::prelude::*;
 
+    let preferences = ctx.preferences;
+    let plan_state = ctx.plan_state;
+
     use_effect(move || {
         let closure = Closure::wrap(Box::new(move |e: web_sys::KeyboardEvent| {
             let meta = e.meta_key() || e.ctrl_key();
@@ -714,6 +1152,21 @@ fn register_keyboard_shortcuts(
                 e.prevent_default();
                 let export = PlanExport::from_signals(preferences(), plan_state());
                 let _ = trigger_plan_download(&export);
+            } else if meta && (key == "z" || key == "Z") {
+                // Leave text fields to the browser's own undo
+                let in_text_field = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                    .map(|el| matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+                    .unwrap_or(false);
+                if !in_text_field {
+                    e.prevent_default();
+                    if e.shift_key() {
+                        ctx.redo();
+                    } else {
+                        ctx.undo();
+                    }
+                }
             } else if meta && (key == "o" || key == "O") {
                 e.prevent_default();
                 if let Some(window) = web_sys::window() {
@@ -745,6 +1198,8 @@ fn handle_paste_from_clipboard_desktop(
     mut preferences: Signal<Preferences>,
     mut plan_state: Signal<PlanState>,
     mut viewing_session: Signal<Option<crate::state::ViewingSession>>,
+    mut history: Signal<EditHistory>,
+    mut rejected_import: Signal<Option<ValidationReport>>,
 ) {
     use dioxus::logger::tracing::{error, warn};
 
@@ -775,6 +1230,8 @@ fn handle_paste_from_clipboard_desktop(
         &mut preferences,
         &mut plan_state,
         &mut viewing_session,
+        &mut history,
+        &mut rejected_import,
     ) {
         error!("Failed to load plan: {}", e);
     }
@@ -9,4 +9,4 @@ pub mod views;
 pub use layout::TopNav;
 #[allow(unused_imports)] // Reserved for future use in editing UI
 pub use ui::{Badge, BadgeType, Button, ButtonVariant};
-pub use views::{AllocationView, RoadmapView, TechnicalView};
+pub use views::{AllocationView, RoadmapView, TechnicalView, TimelineView};
@@ -8,6 +8,8 @@ use planner_core::models::ProjectColor;
 #[component]
 pub fn AssignProjectModal(
     visible: bool,
+    /// Member whose cell is being assigned (used to show skill matches)
+    team_member_id: Option<Uuid>,
     selected_project_id: Option<Uuid>,
     on_project_select: EventHandler<Uuid>,
     on_apply: EventHandler<()>,
@@ -18,7 +20,12 @@ pub fn AssignProjectModal(
     }
 
     let plan = crate::state::use_plan_state();
+    let plan_index = crate::state::use_plan_index();
     let plan_data = plan();
+    let index = plan_index.read();
+    let preferences = crate::state::use_preferences();
+    let prefs_data = preferences();
+    let member = team_member_id.and_then(|id| prefs_data.team_members.iter().find(|m| m.id == id));
 
     rsx! {
         // Modal backdrop
@@ -51,12 +58,16 @@ pub fn AssignProjectModal(
                             for project in &plan_data.technical_projects {
                                 {
                                     let project_id = project.id;
-                                    let allocated = plan_data.calculate_project_allocated_weeks(&project_id);
+                                    let allocated = index.project_allocated_weeks(&project_id);
                                     let color = project.roadmap_project_id
                                         .and_then(|id| plan_data.get_roadmap_project(&id))
                                         .map(|rp| rp.color)
                                         .unwrap_or(ProjectColor::Blue);
                                     let is_selected = selected_project_id == Some(project_id);
+                                    // Skill fit for the member (only for projects with requirements)
+                                    let skill_fit = member
+                                        .filter(|_| !project.required_skills.is_empty())
+                                        .map(|m| project.missing_skills_for(m).join(", "));
 
                                     rsx! {
                                         button {
@@ -67,6 +78,19 @@ pub fn AssignProjectModal(
                                                 style: "background: {color.to_hex()};",
                                             }
                                             span { class: "project-name", "{project.name}" }
+                                            match skill_fit {
+                                                Some(missing) if missing.is_empty() => rsx! {
+                                                    span { class: "skill-fit match", "✓ Skills" }
+                                                },
+                                                Some(missing) => rsx! {
+                                                    span {
+                                                        class: "skill-fit missing",
+                                                        title: "Missing: {missing}",
+                                                        "Missing skills"
+                                                    }
+                                                },
+                                                None => rsx! {},
+                                            }
                                             span { class: "project-allocated", "{allocated:.1}w" }
                                         }
                                     }
@@ -0,0 +1,209 @@
+/// Auto-schedule modal: previews scheduler-proposed allocations before applying
+use dioxus::prelude::*;
+
+use crate::components::ui::{Button, ButtonVariant};
+use crate::state::{use_app_context, use_plan_state, use_preferences};
+use planner_core::scheduler::{schedule, unallocated_estimates, ScheduleOptions};
+
+/// Props for AutoScheduleModal
+#[derive(Props, Clone, PartialEq)]
+pub struct AutoScheduleModalProps {
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// Auto-schedule modal component
+///
+/// The proposal is recomputed from the current plan on every render, so it
+/// always reflects what applying would do.
+#[component]
+pub fn AutoScheduleModal(props: AutoScheduleModalProps) -> Element {
+    let ctx = use_app_context();
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+    let mut keep_existing = use_signal(|| true);
+
+    let plan_data = plan_state();
+    let prefs_data = preferences();
+
+    let proposal = schedule(
+        &prefs_data,
+        &plan_data,
+        ScheduleOptions {
+            keep_existing: keep_existing(),
+        },
+    );
+    let total_weeks = proposal.total_weeks();
+    let removed_count = proposal.removed.len();
+    let can_apply = !proposal.is_empty();
+
+    let unallocated = unallocated_estimates(&prefs_data, &plan_data)
+        .iter()
+        .map(|(role, weeks)| format!("{} {:.1}w", prefs_data.role_short_name(role), weeks))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let member_name = |id: &uuid::Uuid| {
+        prefs_data
+            .team_members
+            .iter()
+            .find(|m| &m.id == id)
+            .map(|m| m.name.clone())
+            .unwrap_or_default()
+    };
+    let project_name = |id: &uuid::Uuid| {
+        plan_data
+            .get_technical_project(id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default()
+    };
+
+    // (label, detail) rows for the review lists
+    let added_rows: Vec<(String, String)> = proposal
+        .assignments()
+        .iter()
+        .map(|a| {
+            let last_day = a.last_week + chrono::Duration::days(6);
+            (
+                format!(
+                    "{} → {}",
+                    member_name(&a.team_member_id),
+                    project_name(&a.technical_project_id)
+                ),
+                format!(
+                    "{} – {} · {:.1}w",
+                    a.first_week.format("%b %-d"),
+                    last_day.format("%b %-d"),
+                    a.weeks
+                ),
+            )
+        })
+        .collect();
+    let shortfall_rows: Vec<(String, String)> = proposal
+        .shortfalls
+        .iter()
+        .map(|s| {
+            (
+                format!(
+                    "{}: {:.1} {} weeks",
+                    project_name(&s.technical_project_id),
+                    s.weeks,
+                    prefs_data.role_short_name(&s.role)
+                ),
+                s.reason.description().to_string(),
+            )
+        })
+        .collect();
+    let assignment_count = added_rows.len();
+
+    // Apply the proposal as a single undoable edit
+    let pending = proposal.clone();
+    let handle_apply = move |_| {
+        let proposal = pending.clone();
+        ctx.edit("Auto-schedule", |prefs, p| {
+            proposal.apply(p);
+            let project_ids: Vec<_> = p.technical_projects.iter().map(|tp| tp.id).collect();
+            for id in project_ids {
+                p.update_technical_project_dates(
+                    &id,
+                    prefs.sprint_anchor_date,
+                    prefs.sprint_length_weeks,
+                );
+            }
+        });
+        props.on_close.call(());
+    };
+
+    rsx! {
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container auto-schedule-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Auto-schedule" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    p { class: "settings-section-description",
+                        "Fills empty weeks from technical project estimates, earliest launch first. Review the proposal below before applying."
+                    }
+                    if !unallocated.is_empty() {
+                        div { class: "form-hint", "Unallocated estimates: {unallocated}" }
+                    }
+
+                    label { class: "schedule-option",
+                        input {
+                            r#type: "checkbox",
+                            checked: keep_existing(),
+                            onchange: move |e: FormEvent| keep_existing.set(e.checked()),
+                        }
+                        span { "Keep existing allocations" }
+                    }
+
+                    // Proposed changes
+                    div { class: "schedule-summary",
+                        if assignment_count == 0 {
+                            "Nothing to schedule"
+                        } else {
+                            "Adds {total_weeks:.1} weeks across {assignment_count} assignments"
+                        }
+                        if removed_count > 0 {
+                            span { class: "schedule-removed", " · removes {removed_count} existing allocations" }
+                        }
+                    }
+                    div { class: "date-range-list",
+                        for (label, detail) in added_rows {
+                            div { class: "date-range-item schedule-added",
+                                span { class: "date-range-label", "{label}" }
+                                span { class: "date-range-meta", "{detail}" }
+                            }
+                        }
+                    }
+
+                    // Estimates that didn't fit
+                    if !shortfall_rows.is_empty() {
+                        div { class: "schedule-shortfalls",
+                            h3 { class: "settings-section-title", "Couldn't fit" }
+                            div { class: "date-range-list",
+                                for (label, reason) in shortfall_rows {
+                                    div { class: "date-range-item skipped",
+                                        span { class: "date-range-label", "{label}" }
+                                        span { class: "date-range-meta", "{reason}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !can_apply,
+                        onclick: handle_apply,
+                        "Apply"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,432 @@
+/// Compare modal: side-by-side report of two versions of a plan
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::components::ui::{Badge, Button, ButtonVariant};
+use crate::state::{use_plan_state, use_preferences, use_viewing_session};
+use crate::storage;
+use planner_core::comparison::{compare_plans, ComparisonRow, PlanVersion};
+use planner_core::diff::{diff_exports, ChangeKind, PlanChange};
+use planner_core::migration::load_json;
+use planner_core::models::{PlanExport, PlanState, Preferences};
+
+/// Where one side of the comparison comes from
+#[derive(Clone, Copy, PartialEq)]
+enum CompareSource {
+    /// Main plan of whatever is open
+    Main,
+    /// A named scenario of whatever is open
+    Scenario(Uuid),
+    /// The local plan, while an imported file is being viewed
+    SavedPlan,
+    /// A plan file opened from this modal
+    File,
+}
+
+impl CompareSource {
+    fn to_value(self) -> String {
+        match self {
+            CompareSource::Main => "main".to_string(),
+            CompareSource::Scenario(id) => id.to_string(),
+            CompareSource::SavedPlan => "saved".to_string(),
+            CompareSource::File => "file".to_string(),
+        }
+    }
+
+    fn from_value(value: &str) -> Option<Self> {
+        match value {
+            "main" => Some(CompareSource::Main),
+            "saved" => Some(CompareSource::SavedPlan),
+            "file" => Some(CompareSource::File),
+            _ => Uuid::parse_str(value).ok().map(CompareSource::Scenario),
+        }
+    }
+}
+
+/// A plan version loaded from outside the open plan
+#[derive(Clone, PartialEq)]
+struct LoadedPlan {
+    label: String,
+    preferences: Preferences,
+    state: PlanState,
+}
+
+/// Props for CompareModal
+#[derive(Props, Clone, PartialEq)]
+pub struct CompareModalProps {
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// Compare modal component
+#[component]
+pub fn CompareModal(props: CompareModalProps) -> Element {
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+    let viewing_session = use_viewing_session();
+
+    // The local plan is only a separate version while viewing a file
+    let saved_plan = use_signal(|| {
+        viewing_session.peek().as_ref()?;
+        Some(LoadedPlan {
+            label: "Saved local plan".to_string(),
+            preferences: storage::load_preferences()
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+            state: storage::load_plan_state().ok().flatten()?,
+        })
+    });
+    let mut loaded_file = use_signal(|| None::<LoadedPlan>);
+    let mut file_error = use_signal(|| None::<String>);
+
+    let mut left = use_signal(|| CompareSource::Main);
+    let mut right = use_signal(|| match plan_state.peek().scenarios.first() {
+        Some(scenario) => CompareSource::Scenario(scenario.id),
+        None if saved_plan.peek().is_some() => CompareSource::SavedPlan,
+        None => CompareSource::Main,
+    });
+    let mut only_differences = use_signal(|| false);
+
+    let prefs_data = preferences();
+    let plan_data = plan_state();
+
+    // Available sources, as (value, label)
+    let mut sources = vec![(CompareSource::Main.to_value(), "Main plan".to_string())];
+    for scenario in &plan_data.scenarios {
+        sources.push((
+            CompareSource::Scenario(scenario.id).to_value(),
+            scenario.name.clone(),
+        ));
+    }
+    if let Some(saved) = saved_plan() {
+        sources.push((CompareSource::SavedPlan.to_value(), saved.label));
+    }
+    if let Some(file) = loaded_file() {
+        sources.push((CompareSource::File.to_value(), file.label));
+    }
+
+    // Each side is compared as a standalone plan; scenarios themselves aren't compared
+    let resolve = |source: CompareSource| -> Option<LoadedPlan> {
+        let (label, preferences, state) = match source {
+            CompareSource::Main => (
+                "Main plan".to_string(),
+                prefs_data.clone(),
+                plan_data.branch_as_plan(None)?,
+            ),
+            CompareSource::Scenario(id) => (
+                plan_data.get_scenario(&id)?.name.clone(),
+                prefs_data.clone(),
+                plan_data.branch_as_plan(Some(id))?,
+            ),
+            CompareSource::SavedPlan | CompareSource::File => {
+                let other = if source == CompareSource::File {
+                    loaded_file()?
+                } else {
+                    saved_plan()?
+                };
+                (
+                    other.label,
+                    other.preferences,
+                    other.state.branch_as_plan(None)?,
+                )
+            }
+        };
+        Some(LoadedPlan {
+            label,
+            preferences,
+            state,
+        })
+    };
+
+    let versions = resolve(left()).zip(resolve(right()));
+    let comparison = versions.as_ref().map(|(l, r)| {
+        compare_plans(
+            PlanVersion {
+                label: &l.label,
+                preferences: &l.preferences,
+                state: &l.state,
+            },
+            PlanVersion {
+                label: &r.label,
+                preferences: &r.preferences,
+                state: &r.state,
+            },
+        )
+    });
+
+    // Itemized changes from left to right, as (class, line)
+    let changes: Vec<(&'static str, String)> = versions
+        .map(|(l, r)| {
+            let before = PlanExport::from_signals(l.preferences, l.state);
+            let after = PlanExport::from_signals(r.preferences, r.state);
+            diff_exports(&before, &after)
+                .changes
+                .iter()
+                .map(|change| (change_class(change), change.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Open another plan file as the right-hand version
+    let mut load_file = move |filename: String, json: String| match load_json::<PlanExport>(&json) {
+        Ok(export) => {
+            let (preferences, state) = export.into_signals();
+            loaded_file.set(Some(LoadedPlan {
+                label: filename,
+                preferences,
+                state,
+            }));
+            right.set(CompareSource::File);
+            file_error.set(None);
+        }
+        Err(e) => file_error.set(Some(format!("Couldn't read plan file: {}", e))),
+    };
+
+    rsx! {
+        // Hidden file input (web only - desktop uses native dialog)
+        input {
+            r#type: "file",
+            id: "compare-file-input",
+            accept: ".json,application/json",
+            style: "display: none;",
+            onchange: move |_| {
+                #[cfg(target_family = "wasm")]
+                spawn(async move {
+                    if let Ok(result) = crate::plan_io::read_file_from_input("compare-file-input").await {
+                        load_file(result.filename, result.content);
+                    }
+                });
+            },
+        }
+
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container compare-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Compare Plans" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    // Version pickers
+                    div { class: "compare-sources",
+                        select {
+                            class: "select",
+                            value: left().to_value(),
+                            onchange: move |e| {
+                                if let Some(source) = CompareSource::from_value(&e.value()) {
+                                    left.set(source);
+                                }
+                            },
+                            for (value, label) in sources.clone() {
+                                option { value: "{value}", "{label}" }
+                            }
+                        }
+                        span { class: "compare-vs", "vs" }
+                        select {
+                            class: "select",
+                            value: right().to_value(),
+                            onchange: move |e| {
+                                if let Some(source) = CompareSource::from_value(&e.value()) {
+                                    right.set(source);
+                                }
+                            },
+                            for (value, label) in sources {
+                                option { value: "{value}", "{label}" }
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: move |_| async move {
+                                #[cfg(target_family = "wasm")]
+                                crate::plan_io::trigger_file_open("compare-file-input");
+                                #[cfg(not(target_family = "wasm"))]
+                                {
+                                    let file = rfd::AsyncFileDialog::new()
+                                        .add_filter("Plan Files", &["json"])
+                                        .set_title("Open Plan to Compare")
+                                        .pick_file()
+                                        .await;
+                                    if let Some(file) = file {
+                                        match std::fs::read_to_string(file.path()) {
+                                            Ok(json) => load_file(file.file_name(), json),
+                                            Err(e) => file_error.set(Some(format!("Couldn't read file: {}", e))),
+                                        }
+                                    }
+                                }
+                            },
+                            "Open File..."
+                        }
+                    }
+                    if let Some(error) = file_error() {
+                        div { class: "form-error", "{error}" }
+                    }
+                    label { class: "schedule-option",
+                        input {
+                            r#type: "checkbox",
+                            checked: only_differences(),
+                            onchange: move |e: FormEvent| only_differences.set(e.checked()),
+                        }
+                        span { "Only show differences" }
+                    }
+
+                    if let Some(comparison) = comparison {
+                        // Team capacity
+                        table { class: "comparison-table",
+                            thead {
+                                tr {
+                                    th { "" }
+                                    th { "{comparison.left_label}" }
+                                    th { "{comparison.right_label}" }
+                                }
+                            }
+                            tbody {
+                                tr {
+                                    td { class: "comparison-name", "Team allocation" }
+                                    td {
+                                        Badge { badge_type: comparison.left_totals.status,
+                                            "{comparison.left_totals.allocated:.1} / {comparison.left_totals.capacity:.1}w"
+                                        }
+                                    }
+                                    td {
+                                        Badge { badge_type: comparison.right_totals.status,
+                                            "{comparison.right_totals.allocated:.1} / {comparison.right_totals.capacity:.1}w"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Roadmap projects
+                        h3 { class: "settings-section-title", "Roadmap projects" }
+                        table { class: "comparison-table",
+                            thead {
+                                tr {
+                                    th { "Project" }
+                                    th { "{comparison.left_label}" }
+                                    th { "{comparison.right_label}" }
+                                }
+                            }
+                            tbody {
+                                for row in visible_rows(&comparison.roadmap_projects, only_differences()) {
+                                    tr { key: "{row.id}",
+                                        td { class: "comparison-name", "{row.name}" }
+                                        for side in [&row.left, &row.right] {
+                                            td {
+                                                if let Some(summary) = side {
+                                                    div { class: "comparison-cell",
+                                                        Badge { badge_type: summary.status,
+                                                            "{summary.allocated.total():.1} / {summary.estimate:.1}w"
+                                                        }
+                                                        Badge { badge_type: summary.feasibility.badge_type(),
+                                                            "{summary.feasibility.label()}"
+                                                        }
+                                                    }
+                                                } else {
+                                                    span { class: "comparison-missing", "Not in plan" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Team members
+                        h3 { class: "settings-section-title", "Team members" }
+                        table { class: "comparison-table",
+                            thead {
+                                tr {
+                                    th { "Member" }
+                                    th { "{comparison.left_label}" }
+                                    th { "{comparison.right_label}" }
+                                }
+                            }
+                            tbody {
+                                for row in visible_rows(&comparison.members, only_differences()) {
+                                    tr { key: "{row.id}",
+                                        td { class: "comparison-name", "{row.name}" }
+                                        for side in [row.left, row.right] {
+                                            td {
+                                                if let Some(member) = side {
+                                                    Badge { badge_type: member.status,
+                                                        "{member.allocated:.1} / {member.capacity:.1}w ({member.utilization_pct():.0}%)"
+                                                    }
+                                                } else {
+                                                    span { class: "comparison-missing", "Not on team" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Itemized changes
+                        h3 { class: "settings-section-title", "Changes ({changes.len()})" }
+                        div { class: "diff-list",
+                            if changes.is_empty() {
+                                div { class: "date-range-empty", "No differences" }
+                            }
+                            for (class, line) in changes {
+                                div { class: "diff-line {class}", "{line}" }
+                            }
+                        }
+                    } else {
+                        div { class: "date-range-empty", "Choose two plan versions to compare." }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rows to display, optionally hiding those that are the same on both sides
+fn visible_rows<T: Clone + PartialEq>(
+    rows: &[ComparisonRow<T>],
+    only_differences: bool,
+) -> Vec<ComparisonRow<T>> {
+    rows.iter()
+        .filter(|row| !only_differences || !row.is_unchanged())
+        .cloned()
+        .collect()
+}
+
+/// CSS class for a change line
+pub(super) fn change_class(change: &PlanChange) -> &'static str {
+    let kind = match change {
+        PlanChange::RoadmapProject(item)
+        | PlanChange::TechnicalProject(item)
+        | PlanChange::TeamMember(item)
+        | PlanChange::Scenario(item) => &item.kind,
+        PlanChange::Plan(_) | PlanChange::Allocation(_) => return "modified",
+    };
+    match kind {
+        ChangeKind::Added => "added",
+        ChangeKind::Removed => "removed",
+        ChangeKind::Modified(_) => "modified",
+    }
+}
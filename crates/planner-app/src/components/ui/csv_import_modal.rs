@@ -0,0 +1,294 @@
+/// CSV import modal: add a team roster or allocations from a spreadsheet
+use dioxus::prelude::*;
+
+use crate::components::ui::{Button, ButtonVariant};
+use crate::state::{use_app_context, use_plan_state, use_preferences};
+use planner_core::formats::csv::{plan_allocation_import, plan_roster_import};
+
+/// Hidden file input used on web
+const CSV_FILE_INPUT_ID: &str = "csv-import-input";
+
+/// What the sheet contains
+#[derive(Clone, Copy, PartialEq)]
+enum CsvImportKind {
+    /// Name, Role, Capacity (and optionally Email)
+    Roster,
+    /// Member, Week Of, Project, Roadmap Project, Percentage
+    Allocations,
+}
+
+impl CsvImportKind {
+    fn columns(self) -> &'static str {
+        match self {
+            CsvImportKind::Roster => {
+                "Columns: Name, Role, Capacity (weeks, optional), Email (optional). Roles match a configured role's name or short name."
+            }
+            CsvImportKind::Allocations => {
+                "Columns: Member, Week Of, Project, Roadmap Project (optional), Percentage - the same layout as Export Allocations as CSV. Projects that don't exist yet are created. Each imported week replaces that member's allocation for the week and can hold at most two projects adding up to 100%."
+            }
+        }
+    }
+}
+
+/// Props for CsvImportModal
+#[derive(Props, Clone, PartialEq)]
+pub struct CsvImportModalProps {
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// CSV import modal component
+///
+/// The chosen file is parsed against the current roster and plan on every
+/// render, so the preview stays accurate until it's applied.
+#[component]
+pub fn CsvImportModal(props: CsvImportModalProps) -> Element {
+    let ctx = use_app_context();
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+
+    let mut kind = use_signal(|| CsvImportKind::Roster);
+    // (filename, contents) of the chosen file
+    let mut file = use_signal(|| None::<(String, String)>);
+    let mut file_error = use_signal(|| None::<String>);
+
+    let mut load_file = move |filename: String, content: String| {
+        file.set(Some((filename, content)));
+        file_error.set(None);
+    };
+
+    let open_file = move |_| async move {
+        #[cfg(target_family = "wasm")]
+        crate::plan_io::trigger_file_open(CSV_FILE_INPUT_ID);
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let picked = rfd::AsyncFileDialog::new()
+                .add_filter("CSV Files", &["csv"])
+                .add_filter("All Files", &["*"])
+                .set_title("Import CSV")
+                .pick_file()
+                .await;
+            if let Some(picked) = picked {
+                match std::fs::read_to_string(picked.path()) {
+                    Ok(content) => load_file(picked.file_name(), content),
+                    Err(e) => file_error.set(Some(format!("Couldn't read file: {}", e))),
+                }
+            }
+        }
+    };
+
+    // Preview rows as (label, meta) pairs, plus per-row errors
+    let mut parse_error = None;
+    let mut rows: Vec<(String, String)> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    let mut summary = String::new();
+    let mut can_apply = false;
+    if let Some((filename, content)) = file() {
+        let prefs = preferences();
+        let state = plan_state();
+        match kind() {
+            CsvImportKind::Roster => match plan_roster_import(&content, &prefs) {
+                Ok(preview) => {
+                    summary = format!(
+                        "{}: {} team members to add, {} rows skipped",
+                        filename,
+                        preview.members.len(),
+                        preview.errors.len()
+                    );
+                    rows = preview
+                        .members
+                        .iter()
+                        .map(|m| {
+                            (
+                                m.name.clone(),
+                                format!("{} · {} weeks", prefs.role_name(&m.role), m.capacity),
+                            )
+                        })
+                        .collect();
+                    errors = preview
+                        .errors
+                        .iter()
+                        .map(|e| format!("Line {}: {}", e.line, e.message))
+                        .collect();
+                    can_apply = !preview.is_empty();
+                }
+                Err(e) => parse_error = Some(format!("{}: {}", filename, e)),
+            },
+            CsvImportKind::Allocations => match plan_allocation_import(&content, &prefs, &state) {
+                Ok(preview) => {
+                    summary = format!(
+                        "{}: {} weeks of allocations, {} new projects, {} rows skipped",
+                        filename,
+                        preview.allocations.len(),
+                        preview.new_projects.len(),
+                        preview.errors.len()
+                    );
+                    rows = preview
+                        .rows
+                        .iter()
+                        .map(|row| {
+                            let project = if row.new_project {
+                                format!("{} (new)", row.project_name)
+                            } else {
+                                row.project_name.clone()
+                            };
+                            (
+                                format!("{} · {}", row.member_name, project),
+                                format!(
+                                    "Week of {} · {}%",
+                                    row.week_start_date.format("%b %d"),
+                                    row.percentage
+                                ),
+                            )
+                        })
+                        .collect();
+                    errors = preview
+                        .errors
+                        .iter()
+                        .map(|e| format!("Line {}: {}", e.line, e.message))
+                        .collect();
+                    can_apply = !preview.is_empty();
+                }
+                Err(e) => parse_error = Some(format!("{}: {}", filename, e)),
+            },
+        }
+    }
+
+    // Re-read against the latest state and apply as one undoable edit
+    let handle_apply = move |_| {
+        let Some((_, content)) = file() else {
+            return;
+        };
+        match kind() {
+            CsvImportKind::Roster => {
+                if let Ok(preview) = plan_roster_import(&content, &preferences()) {
+                    ctx.edit("Import roster", |prefs, _| preview.apply(prefs));
+                }
+            }
+            CsvImportKind::Allocations => {
+                if let Ok(preview) = plan_allocation_import(&content, &preferences(), &plan_state())
+                {
+                    ctx.edit("Import allocations", |prefs, state| {
+                        preview.apply(prefs, state)
+                    });
+                }
+            }
+        }
+        props.on_close.call(());
+    };
+
+    rsx! {
+        // Hidden file input (web only - desktop uses native dialog)
+        input {
+            r#type: "file",
+            id: CSV_FILE_INPUT_ID,
+            accept: ".csv,text/csv",
+            style: "display: none;",
+            onchange: move |_| {
+                #[cfg(target_family = "wasm")]
+                spawn(async move {
+                    match crate::plan_io::read_file_from_input(CSV_FILE_INPUT_ID).await {
+                        Ok(result) => load_file(result.filename, result.content),
+                        Err(e) => file_error.set(Some(e)),
+                    }
+                });
+            },
+        }
+
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container csv-import-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Import from CSV" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    p { class: "settings-section-description",
+                        "Import a sheet exported from a spreadsheet. Rows with problems are listed and skipped; nothing changes until you import."
+                    }
+
+                    div { class: "calendar-import",
+                        select {
+                            class: "form-input",
+                            onchange: move |e: FormEvent| {
+                                kind.set(if e.value() == "allocations" {
+                                    CsvImportKind::Allocations
+                                } else {
+                                    CsvImportKind::Roster
+                                });
+                            },
+                            option {
+                                value: "roster",
+                                selected: kind() == CsvImportKind::Roster,
+                                "Team roster"
+                            }
+                            option {
+                                value: "allocations",
+                                selected: kind() == CsvImportKind::Allocations,
+                                "Allocations"
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: open_file,
+                            "Open File..."
+                        }
+                    }
+                    div { class: "form-hint", "{kind().columns()}" }
+
+                    for error in [file_error(), parse_error].into_iter().flatten() {
+                        div { class: "form-error", "{error}" }
+                    }
+
+                    if !summary.is_empty() {
+                        div { class: "calendar-import-preview",
+                            div { class: "calendar-import-summary", "{summary}" }
+                            div { class: "date-range-list csv-import-rows",
+                                for (index, (label, meta)) in rows.into_iter().enumerate() {
+                                    div { key: "{index}", class: "date-range-item",
+                                        span { class: "date-range-label", "{label}" }
+                                        span { class: "date-range-meta", "{meta}" }
+                                    }
+                                }
+                                for (index, error) in errors.into_iter().enumerate() {
+                                    div { key: "error-{index}", class: "date-range-item skipped",
+                                        span { class: "date-range-label csv-import-error", "{error}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !can_apply,
+                        onclick: handle_apply,
+                        "Import"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -19,7 +19,9 @@ pub fn FloatingProjectPanel(
     }
 
     let plan = crate::state::use_plan_state();
+    let plan_index = crate::state::use_plan_index();
     let plan_data = plan();
+    let index = plan_index.read();
 
     let mut is_collapsed = use_signal(|| false);
 
@@ -79,7 +81,7 @@ pub fn FloatingProjectPanel(
                     for project in filtered_projects {
                         {
                             let project_id = project.id;
-                            let allocated = plan_data.calculate_project_allocated_weeks(&project_id);
+                            let allocated = index.project_allocated_weeks(&project_id);
                             let color = project.get_color_from_state(&plan_data);
                             let is_selected = selected_project_id == Some(project_id);
 
@@ -37,6 +37,14 @@ pub fn KeybindingsOverlay(visible: bool, on_close: EventHandler<()>) -> Element
                             span { class: "keybinding-keys", "Esc" }
                             span { class: "keybinding-desc", "Close modals / Exit paintbrush mode" }
                         }
+                        div { class: "keybinding-row",
+                            span { class: "keybinding-keys", "Cmd/Ctrl + Z" }
+                            span { class: "keybinding-desc", "Undo last change" }
+                        }
+                        div { class: "keybinding-row",
+                            span { class: "keybinding-keys", "Cmd/Ctrl + Shift + Z" }
+                            span { class: "keybinding-desc", "Redo last undone change" }
+                        }
                     }
 
                     div { class: "keybindings-section",
@@ -0,0 +1,337 @@
+/// Merge modal: three-way merge of the open plan with another copy of it
+use dioxus::prelude::*;
+
+use super::compare_modal::change_class;
+use crate::components::ui::{Button, ButtonVariant};
+use crate::state::{use_app_context, use_plan_state, use_preferences};
+use planner_core::diff::diff_exports;
+use planner_core::merge::{merge_exports, MergeSide};
+use planner_core::migration::load_json;
+use planner_core::models::PlanExport;
+use planner_core::validation::{validate_export, Severity};
+
+/// Which plan file is being opened
+#[derive(Clone, Copy, PartialEq)]
+enum MergeInput {
+    /// Common ancestor of both copies
+    Base,
+    /// The other copy to merge in
+    Theirs,
+}
+
+impl MergeInput {
+    fn input_id(self) -> &'static str {
+        match self {
+            MergeInput::Base => "merge-base-input",
+            MergeInput::Theirs => "merge-theirs-input",
+        }
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn dialog_title(self) -> &'static str {
+        match self {
+            MergeInput::Base => "Open Common Ancestor Plan",
+            MergeInput::Theirs => "Open Plan to Merge",
+        }
+    }
+}
+
+/// Props for MergeModal
+#[derive(Props, Clone, PartialEq)]
+pub struct MergeModalProps {
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// Merge modal component
+#[component]
+pub fn MergeModal(props: MergeModalProps) -> Element {
+    let ctx = use_app_context();
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+
+    let mut base = use_signal(|| None::<(String, PlanExport)>);
+    let mut theirs = use_signal(|| None::<(String, PlanExport)>);
+    let mut file_error = use_signal(|| None::<String>);
+    let mut choices = use_signal(Vec::<MergeSide>::new);
+
+    // Our side is the open plan, always merged as its main plan
+    let ours = PlanExport::from_signals(preferences(), plan_state());
+    let merge = match (base(), theirs()) {
+        (Some((_, base)), Some((_, theirs))) => Some(merge_exports(&base, &ours, &theirs)),
+        _ => None,
+    };
+    let merge_error = match &merge {
+        Some(Err(e)) => Some(e.to_string()),
+        _ => None,
+    };
+    let merge = merge.and_then(Result::ok);
+
+    // Conflicts default to our side
+    let conflict_count = merge.as_ref().map_or(0, |m| m.conflicts.len());
+    let selected = selected_for(choices(), conflict_count);
+    let conflicts: Vec<(usize, String, String, String, MergeSide)> = merge
+        .iter()
+        .flat_map(|m| m.conflicts.iter())
+        .zip(&selected)
+        .enumerate()
+        .map(|(index, (conflict, side))| {
+            (
+                index,
+                conflict.label.clone(),
+                conflict.ours.clone(),
+                conflict.theirs.clone(),
+                *side,
+            )
+        })
+        .collect();
+
+    let resolved = merge.as_ref().map(|m| m.resolve(&selected));
+    let (merged, resolve_error) = match resolved {
+        Some(Ok(merged)) => (Some(merged), None),
+        Some(Err(e)) => (None, Some(e.to_string())),
+        None => (None, None),
+    };
+    // Errors in the merged plan block applying it; warnings don't
+    let report = merged.as_ref().map(validate_export);
+    let validation_errors: Vec<String> = report
+        .iter()
+        .filter(|r| r.has_errors())
+        .flat_map(|r| {
+            let summary = format!("The merged plan has problems: {}", r.summary());
+            let errors = r
+                .issues
+                .iter()
+                .filter(|issue| issue.severity == Severity::Error)
+                .map(|issue| issue.message.clone());
+            std::iter::once(summary).chain(errors)
+        })
+        .collect();
+
+    // What applying the merge would change in the open plan, as (class, line)
+    let changes: Vec<(&'static str, String)> = merged
+        .as_ref()
+        .map(|merged| {
+            diff_exports(&ours, merged)
+                .changes
+                .iter()
+                .map(|change| (change_class(change), change.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let can_apply = validation_errors.is_empty() && !changes.is_empty();
+
+    let base_label = base()
+        .map(|(name, _)| name)
+        .unwrap_or_else(|| "No file chosen".to_string());
+    let theirs_label = theirs()
+        .map(|(name, _)| name)
+        .unwrap_or_else(|| "No file chosen".to_string());
+
+    let mut load_file = move |input: MergeInput, filename: String, json: String| match load_json::<
+        PlanExport,
+    >(&json)
+    {
+        Ok(export) => {
+            match input {
+                MergeInput::Base => base.set(Some((filename, export))),
+                MergeInput::Theirs => theirs.set(Some((filename, export))),
+            }
+            choices.set(Vec::new());
+            file_error.set(None);
+        }
+        Err(e) => file_error.set(Some(format!("Couldn't read plan file: {}", e))),
+    };
+
+    let open_file = move |input: MergeInput| async move {
+        #[cfg(target_family = "wasm")]
+        crate::plan_io::trigger_file_open(input.input_id());
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let file = rfd::AsyncFileDialog::new()
+                .add_filter("Plan Files", &["json"])
+                .set_title(input.dialog_title())
+                .pick_file()
+                .await;
+            if let Some(file) = file {
+                match std::fs::read_to_string(file.path()) {
+                    Ok(json) => load_file(input, file.file_name(), json),
+                    Err(e) => file_error.set(Some(format!("Couldn't read file: {}", e))),
+                }
+            }
+        }
+    };
+
+    // Apply the merge to the main plan; it replaces settings history doesn't
+    // track, so it isn't undoable
+    let handle_apply = move |_| {
+        let Some(merged) = merged.clone() else {
+            return;
+        };
+        if plan_state.peek().active_scenario_id.is_some() {
+            ctx.update_scenarios(|state| {
+                state.switch_scenario(None);
+            });
+        }
+        // Sprint configuration is local, so it's kept as is
+        ctx.replace_plan(|prefs, state| {
+            prefs.team_name = merged.team_name;
+            prefs.team_members = merged.team_members;
+            prefs.holidays = merged.holidays;
+            prefs.roles = merged.roles;
+            state.quarter_name = merged.quarter_name;
+            state.quarter_start_date = merged.quarter_start_date;
+            state.num_weeks = merged.num_weeks;
+            state.roadmap_projects = merged.roadmap_projects;
+            state.technical_projects = merged.technical_projects;
+            state.allocations = merged.allocations;
+            state.scenarios = merged.scenarios;
+            state.mark_modified();
+        });
+        props.on_close.call(());
+    };
+
+    rsx! {
+        // Hidden file inputs (web only - desktop uses native dialog)
+        for source in [MergeInput::Base, MergeInput::Theirs] {
+            input {
+                key: "{source.input_id()}",
+                r#type: "file",
+                id: "{source.input_id()}",
+                accept: ".json,application/json",
+                style: "display: none;",
+                onchange: move |_| {
+                    #[cfg(target_family = "wasm")]
+                    spawn(async move {
+                        if let Ok(result) = crate::plan_io::read_file_from_input(source.input_id()).await {
+                            load_file(source, result.filename, result.content);
+                        }
+                    });
+                },
+            }
+        }
+
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container merge-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Merge Plans" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    p { class: "settings-section-description",
+                        "Merge changes from another copy of this plan. Choose the version both copies started from, then the copy to merge in. Changes made on only one side are merged automatically."
+                    }
+
+                    // File pickers
+                    div { class: "merge-sources",
+                        div { class: "merge-source",
+                            span { class: "date-range-label", "Common ancestor" }
+                            span { class: "date-range-meta", "{base_label}" }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                onclick: move |_| open_file(MergeInput::Base),
+                                "Open File..."
+                            }
+                        }
+                        div { class: "merge-source",
+                            span { class: "date-range-label", "Their version" }
+                            span { class: "date-range-meta", "{theirs_label}" }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                onclick: move |_| open_file(MergeInput::Theirs),
+                                "Open File..."
+                            }
+                        }
+                    }
+                    for error in [file_error(), merge_error, resolve_error].into_iter().flatten().chain(validation_errors) {
+                        div { class: "form-error", "{error}" }
+                    }
+
+                    if merge.is_some() {
+                        // Conflicts
+                        h3 { class: "settings-section-title", "Conflicts ({conflicts.len()})" }
+                        if conflicts.is_empty() {
+                            div { class: "date-range-empty", "No conflicts - everything merged automatically" }
+                        }
+                        div { class: "date-range-list",
+                            for (index, label, ours_text, theirs_text, side) in conflicts {
+                                div { key: "{index}", class: "date-range-item merge-conflict",
+                                    div { class: "date-range-label", "{label}" }
+                                    for (option, text) in [(MergeSide::Ours, ours_text), (MergeSide::Theirs, theirs_text)] {
+                                        label {
+                                            class: if side == option { "merge-choice selected" } else { "merge-choice" },
+                                            input {
+                                                r#type: "radio",
+                                                name: "merge-conflict-{index}",
+                                                checked: side == option,
+                                                onchange: move |_| {
+                                                    let mut next = selected_for(choices(), conflict_count);
+                                                    next[index] = option;
+                                                    choices.set(next);
+                                                },
+                                            }
+                                            span { class: "merge-choice-side",
+                                                if option == MergeSide::Ours { "Ours" } else { "Theirs" }
+                                            }
+                                            span { class: "merge-choice-value", "{text}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Resulting changes to the open plan
+                        h3 { class: "settings-section-title", "Changes to this plan ({changes.len()})" }
+                        div { class: "diff-list",
+                            if changes.is_empty() {
+                                div { class: "date-range-empty", "Nothing to merge" }
+                            }
+                            for (class, line) in changes {
+                                div { class: "diff-line {class}", "{line}" }
+                            }
+                        }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !can_apply,
+                        onclick: handle_apply,
+                        "Apply Merge"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Current choices, reset to our side if the conflicts changed
+fn selected_for(choices: Vec<MergeSide>, conflict_count: usize) -> Vec<MergeSide> {
+    if choices.len() == conflict_count {
+        choices
+    } else {
+        vec![MergeSide::Ours; conflict_count]
+    }
+}
@@ -1,29 +1,39 @@
 //! Basic UI components (Button, Badge, Input, etc.)
 
 mod assign_project_modal;
+mod auto_schedule_modal;
 mod badge;
 mod button;
 mod color_picker;
+mod compare_modal;
 mod confirmation_dialog;
 mod context_menu;
+mod csv_import_modal;
 mod data_table;
 mod floating_fab;
 mod floating_project_panel;
 mod grid_cell;
 mod input;
 mod keybindings_overlay;
+mod merge_modal;
+mod plan_health_modal;
+mod portfolio_modal;
 mod roadmap_project_modal;
+mod scenarios_modal;
 mod settings_modal;
 mod split_modal;
 mod team_member_modal;
 mod technical_project_modal;
 
 pub use assign_project_modal::AssignProjectModal;
+pub use auto_schedule_modal::AutoScheduleModal;
 pub use badge::{Badge, BadgeType};
 pub use button::{Button, ButtonVariant};
 pub use color_picker::ColorPicker;
+pub use compare_modal::CompareModal;
 pub use confirmation_dialog::ConfirmationDialog;
 pub use context_menu::{ContextMenu, MenuAction};
+pub use csv_import_modal::CsvImportModal;
 pub use data_table::{
     CellStyle, DataTable, ProjectName, TableCell, TableHeader, TableHeaderCell, TableRow,
 };
@@ -32,7 +42,11 @@ pub use floating_project_panel::FloatingProjectPanel;
 pub use grid_cell::{GridCell, GridCellVariant};
 pub use input::Input;
 pub use keybindings_overlay::KeybindingsOverlay;
+pub use merge_modal::MergeModal;
+pub use plan_health_modal::PlanHealthModal;
+pub use portfolio_modal::PortfolioModal;
 pub use roadmap_project_modal::{ModalMode, RoadmapProjectModal};
+pub use scenarios_modal::ScenariosModal;
 pub use settings_modal::SettingsModal;
 pub use split_modal::SplitAllocationModal;
 pub use team_member_modal::{TeamMemberModal, TeamMemberModalMode};
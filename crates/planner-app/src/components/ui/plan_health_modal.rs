@@ -0,0 +1,119 @@
+/// Plan health modal: every validation error and warning in the open plan
+use dioxus::prelude::*;
+
+use crate::components::ui::{Badge, BadgeType, Button, ButtonVariant};
+use crate::state::{use_plan_state, use_preferences};
+use planner_core::validation::{validate_plan, ValidationReport};
+
+/// Props for PlanHealthModal
+#[derive(Props, Clone, PartialEq)]
+pub struct PlanHealthModalProps {
+    /// Report for a plan that was rejected on import; the open plan is
+    /// checked when this is `None`
+    #[props(default)]
+    pub report: Option<ValidationReport>,
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// Plan health modal component
+///
+/// Re-validates on every change, so fixing an issue removes it from the list.
+#[component]
+pub fn PlanHealthModal(props: PlanHealthModalProps) -> Element {
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+
+    let rejected = props.report.is_some();
+    let report = props
+        .report
+        .clone()
+        .unwrap_or_else(|| validate_plan(&preferences(), &plan_state()));
+    let summary_badge = if report.has_errors() {
+        BadgeType::Error
+    } else if report.is_empty() {
+        BadgeType::Success
+    } else {
+        BadgeType::Warning
+    };
+    let summary = report.summary();
+
+    // Display rows, precomputed
+    let issue_rows: Vec<_> = report
+        .issues
+        .iter()
+        .enumerate()
+        .map(|(index, issue)| {
+            (
+                index,
+                issue.severity.badge_type(),
+                issue.severity.label(),
+                issue.subject.kind(),
+                issue.message.clone(),
+            )
+        })
+        .collect();
+
+    rsx! {
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container plan-health-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Plan Health" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    p { class: "settings-section-description",
+                        if rejected {
+                            "This plan wasn't opened because it has errors. Fix them in the file and open it again."
+                        } else {
+                            "Errors are inconsistent data that can't be shown correctly. Warnings are probably unintended but valid."
+                        }
+                    }
+                    div { class: "plan-health-summary",
+                        Badge { badge_type: summary_badge, "{summary}" }
+                    }
+
+                    if issue_rows.is_empty() {
+                        div { class: "date-range-empty", "The main plan and all scenarios look consistent." }
+                    } else {
+                        ul { class: "plan-health-list",
+                            for (index, badge_type, severity, kind, message) in issue_rows {
+                                li { key: "{index}", class: "plan-health-item",
+                                    Badge { badge_type, "{severity}" }
+                                    div {
+                                        div { class: "plan-health-message", "{message}" }
+                                        div { class: "date-range-meta", "{kind}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+    }
+}
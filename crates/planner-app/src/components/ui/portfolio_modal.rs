@@ -0,0 +1,353 @@
+/// Portfolio modal: combined capacity across several teams' plans
+use dioxus::prelude::*;
+
+use crate::components::ui::{Badge, Button, ButtonVariant};
+use crate::state::{use_plan_state, use_preferences};
+use planner_core::migration::load_json;
+use planner_core::models::PlanExport;
+use planner_core::portfolio::{build_portfolio, RoleLoad};
+
+/// Props for PortfolioModal
+#[derive(Props, Clone, PartialEq)]
+pub struct PortfolioModalProps {
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// Portfolio modal component
+///
+/// Team files are only read, never imported, so the local plan is untouched.
+#[component]
+pub fn PortfolioModal(props: PortfolioModalProps) -> Element {
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+
+    let mut include_local = use_signal(|| true);
+    let mut team_files = use_signal(Vec::<(String, PlanExport)>::new);
+    let mut file_error = use_signal(|| None::<String>);
+
+    let mut exports = Vec::new();
+    if include_local() {
+        exports.push(PlanExport::from_signals(preferences(), plan_state()));
+    }
+    exports.extend(team_files().into_iter().map(|(_, export)| export));
+    let portfolio = build_portfolio(&exports);
+
+    // Display rows, precomputed
+    let file_rows: Vec<(usize, String, String)> = team_files()
+        .iter()
+        .enumerate()
+        .map(|(index, (filename, export))| {
+            (
+                index,
+                export.team_name.clone(),
+                format!("{} · {}", filename, export.quarter_name),
+            )
+        })
+        .collect();
+    let mismatch_warning = portfolio.as_ref().and_then(|p| {
+        let teams: Vec<_> = p
+            .mismatched_teams()
+            .map(|t| format!("{} ({})", t.team_name, t.quarter_name))
+            .collect();
+        (!teams.is_empty()).then(|| {
+            format!(
+                "Planned for a different quarter than {}: {}",
+                p.quarter_name,
+                teams.join(", ")
+            )
+        })
+    });
+    let role_breakdown = |roles: &[RoleLoad]| {
+        roles
+            .iter()
+            .map(|r| format!("{} {:.1}/{:.1}w", r.name, r.allocated, r.capacity))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    };
+    let team_rows: Vec<_> = portfolio
+        .iter()
+        .flat_map(|p| &p.teams)
+        .enumerate()
+        .map(|(index, team)| {
+            (
+                index,
+                team.team_name.clone(),
+                team.quarter_name.clone(),
+                team.member_count,
+                team.status,
+                format!(
+                    "{:.1} / {:.1}w ({:.0}%)",
+                    team.allocated,
+                    team.capacity,
+                    team.utilization_pct()
+                ),
+                role_breakdown(&team.roles),
+            )
+        })
+        .collect();
+    let role_rows: Vec<_> = portfolio
+        .iter()
+        .flat_map(|p| &p.roles)
+        .map(|role| {
+            (
+                role.role.as_str().to_string(),
+                role.name.clone(),
+                role.status,
+                format!(
+                    "{:.1} / {:.1}w ({:.0}%)",
+                    role.allocated,
+                    role.capacity,
+                    role.utilization_pct()
+                ),
+            )
+        })
+        .collect();
+    let shared_rows: Vec<_> = portfolio
+        .iter()
+        .flat_map(|p| p.shared_projects())
+        .map(|project| {
+            let teams = project
+                .teams
+                .iter()
+                .map(|t| format!("{} {:.1}/{:.1}w", t.team_name, t.allocated, t.estimate))
+                .collect::<Vec<_>>()
+                .join(" · ");
+            let launch = project
+                .launch_date()
+                .map(|d| d.format("%b %-d").to_string())
+                .unwrap_or_default();
+            (
+                project.id,
+                project.name.clone(),
+                teams,
+                project.status(),
+                format!("{:.1} / {:.1}w", project.allocated(), project.estimate()),
+                launch,
+            )
+        })
+        .collect();
+    let combined = portfolio.as_ref().map(|p| {
+        (
+            p.status,
+            format!(
+                "{:.1} / {:.1}w ({:.0}%) across {} teams",
+                p.allocated,
+                p.capacity,
+                p.utilization_pct(),
+                p.teams.len()
+            ),
+        )
+    });
+
+    let mut add_team = move |filename: String, json: String| match load_json::<PlanExport>(&json) {
+        Ok(export) => {
+            team_files.with_mut(|files| files.push((filename, export)));
+            file_error.set(None);
+        }
+        Err(e) => file_error.set(Some(format!("Couldn't read plan file: {}", e))),
+    };
+
+    rsx! {
+        // Hidden file input (web only - desktop uses native dialog)
+        input {
+            r#type: "file",
+            id: "portfolio-file-input",
+            accept: ".json,application/json",
+            style: "display: none;",
+            onchange: move |_| {
+                #[cfg(target_family = "wasm")]
+                spawn(async move {
+                    if let Ok(result) = crate::plan_io::read_file_from_input("portfolio-file-input").await {
+                        add_team(result.filename, result.content);
+                    }
+                });
+            },
+        }
+
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container portfolio-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Portfolio" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    p { class: "settings-section-description",
+                        "Combine exported plans from several teams for the same quarter. Files are only read; your plan isn't changed."
+                    }
+
+                    // Teams included
+                    div { class: "date-range-list",
+                        label { class: "schedule-option",
+                            input {
+                                r#type: "checkbox",
+                                checked: include_local(),
+                                onchange: move |e: FormEvent| include_local.set(e.checked()),
+                            }
+                            span { "Include this plan ({preferences().team_name})" }
+                        }
+                        for (index, team_name, meta) in file_rows {
+                            div { key: "{index}", class: "date-range-item",
+                                div {
+                                    div { class: "date-range-label", "{team_name}" }
+                                    div { class: "date-range-meta", "{meta}" }
+                                }
+                                button {
+                                    class: "icon-button danger",
+                                    title: "Remove team",
+                                    onclick: move |_| {
+                                        team_files.with_mut(|files| {
+                                            files.remove(index);
+                                        });
+                                    },
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| async move {
+                            #[cfg(target_family = "wasm")]
+                            crate::plan_io::trigger_file_open("portfolio-file-input");
+                            #[cfg(not(target_family = "wasm"))]
+                            {
+                                let file = rfd::AsyncFileDialog::new()
+                                    .add_filter("Plan Files", &["json"])
+                                    .set_title("Add Team Plan")
+                                    .pick_file()
+                                    .await;
+                                if let Some(file) = file {
+                                    match std::fs::read_to_string(file.path()) {
+                                        Ok(json) => add_team(file.file_name(), json),
+                                        Err(e) => file_error.set(Some(format!("Couldn't read file: {}", e))),
+                                    }
+                                }
+                            }
+                        },
+                        "Add Team Plan..."
+                    }
+                    if let Some(error) = file_error() {
+                        div { class: "form-error", "{error}" }
+                    }
+                    if let Some(warning) = mismatch_warning {
+                        div { class: "form-error", "{warning}" }
+                    }
+
+                    if let Some((status, combined)) = combined {
+                        // Combined capacity
+                        div { class: "portfolio-total",
+                            span { class: "date-range-label", "Combined allocation" }
+                            Badge { badge_type: status, "{combined}" }
+                        }
+
+                        // By team
+                        h3 { class: "settings-section-title", "Teams" }
+                        table { class: "comparison-table",
+                            thead {
+                                tr {
+                                    th { "Team" }
+                                    th { "Quarter" }
+                                    th { "Members" }
+                                    th { "Allocation" }
+                                }
+                            }
+                            tbody {
+                                for (index, team_name, quarter, members, status, allocation, roles) in team_rows {
+                                    tr { key: "{index}",
+                                        td { class: "comparison-name",
+                                            "{team_name}"
+                                            div { class: "portfolio-breakdown", "{roles}" }
+                                        }
+                                        td { "{quarter}" }
+                                        td { "{members}" }
+                                        td {
+                                            Badge { badge_type: status, "{allocation}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // By role
+                        h3 { class: "settings-section-title", "Roles" }
+                        table { class: "comparison-table",
+                            thead {
+                                tr {
+                                    th { "Role" }
+                                    th { "Allocation" }
+                                }
+                            }
+                            tbody {
+                                for (key, name, status, allocation) in role_rows {
+                                    tr { key: "{key}",
+                                        td { class: "comparison-name", "{name}" }
+                                        td {
+                                            Badge { badge_type: status, "{allocation}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Roadmap projects spanning teams
+                        h3 { class: "settings-section-title", "Cross-team roadmap projects" }
+                        if shared_rows.is_empty() {
+                            div { class: "date-range-empty", "No roadmap project is shared by more than one team" }
+                        } else {
+                            table { class: "comparison-table",
+                                thead {
+                                    tr {
+                                        th { "Project" }
+                                        th { "Launch" }
+                                        th { "Allocation" }
+                                    }
+                                }
+                                tbody {
+                                    for (id, name, teams, status, allocation, launch) in shared_rows {
+                                        tr { key: "{id}",
+                                            td { class: "comparison-name",
+                                                "{name}"
+                                                div { class: "portfolio-breakdown", "{teams}" }
+                                            }
+                                            td { "{launch}" }
+                                            td {
+                                                Badge { badge_type: status, "{allocation}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    } else {
+                        div { class: "date-range-empty", "Add team plans to see combined capacity." }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+    }
+}
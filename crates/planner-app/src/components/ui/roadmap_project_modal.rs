@@ -3,7 +3,8 @@ use dioxus::prelude::*;
 use uuid::Uuid;
 
 use crate::components::ui::{Button, ButtonVariant, ColorPicker, Input};
-use planner_core::models::{ProjectColor, RoadmapProject};
+use crate::state::use_preferences;
+use planner_core::models::{ProjectColor, RoadmapProject, RoleWeeks};
 
 /// Mode for the roadmap project modal (Add or Edit)
 #[derive(Clone, Copy, PartialEq)]
@@ -19,8 +20,7 @@ pub struct RoadmapProjectModalProps {
     pub mode: ModalMode,
     /// Initial values for the form
     pub initial_name: String,
-    pub initial_eng_estimate: f32,
-    pub initial_sci_estimate: f32,
+    pub initial_estimates: RoleWeeks,
     pub initial_start_date: NaiveDate,
     pub initial_launch_date: NaiveDate,
     pub initial_color: ProjectColor,
@@ -35,8 +35,8 @@ pub struct RoadmapProjectModalProps {
 pub fn RoadmapProjectModal(props: RoadmapProjectModalProps) -> Element {
     // Form state - simple signals initialized from props
     let name = use_signal(|| props.initial_name.clone());
-    let mut eng_estimate = use_signal(|| props.initial_eng_estimate);
-    let mut sci_estimate = use_signal(|| props.initial_sci_estimate);
+    let preferences = use_preferences();
+    let mut estimates = use_signal(|| props.initial_estimates.clone());
     let mut start_date = use_signal(|| props.initial_start_date);
     let mut launch_date = use_signal(|| props.initial_launch_date);
     let mut color = use_signal(|| props.initial_color);
@@ -44,8 +44,7 @@ pub fn RoadmapProjectModal(props: RoadmapProjectModalProps) -> Element {
 
     // Validation errors
     let mut name_error = use_signal(String::new);
-    let mut eng_estimate_error = use_signal(String::new);
-    let mut sci_estimate_error = use_signal(String::new);
+    let mut estimate_error = use_signal(String::new);
     let mut date_error = use_signal(String::new);
 
     // Validation function
@@ -60,20 +59,12 @@ pub fn RoadmapProjectModal(props: RoadmapProjectModalProps) -> Element {
             name_error.set(String::new());
         }
 
-        // Validate eng estimate (allow 0 for placeholder projects)
-        if eng_estimate() < 0.0 {
-            eng_estimate_error.set("Engineering estimate cannot be negative".to_string());
+        // Validate estimates (allow 0 for placeholder projects)
+        if estimates().iter().any(|(_, weeks)| weeks < 0.0) {
+            estimate_error.set("Estimates cannot be negative".to_string());
             is_valid = false;
         } else {
-            eng_estimate_error.set(String::new());
-        }
-
-        // Validate sci estimate
-        if sci_estimate() < 0.0 {
-            sci_estimate_error.set("Science estimate cannot be negative".to_string());
-            is_valid = false;
-        } else {
-            sci_estimate_error.set(String::new());
+            estimate_error.set(String::new());
         }
 
         // Validate dates
@@ -97,8 +88,7 @@ pub fn RoadmapProjectModal(props: RoadmapProjectModalProps) -> Element {
             ModalMode::Add => RoadmapProject {
                 id: Uuid::new_v4(),
                 name: name().trim().to_string(),
-                eng_estimate: eng_estimate(),
-                sci_estimate: sci_estimate(),
+                estimates: estimates(),
                 start_date: start_date(),
                 launch_date: launch_date(),
                 color: color(),
@@ -111,8 +101,7 @@ pub fn RoadmapProjectModal(props: RoadmapProjectModalProps) -> Element {
             ModalMode::Edit(id) => RoadmapProject {
                 id,
                 name: name().trim().to_string(),
-                eng_estimate: eng_estimate(),
-                sci_estimate: sci_estimate(),
+                estimates: estimates(),
                 start_date: start_date(),
                 launch_date: launch_date(),
                 color: color(),
@@ -166,44 +155,34 @@ pub fn RoadmapProjectModal(props: RoadmapProjectModalProps) -> Element {
                         div { class: "form-error", "{name_error()}" }
                     }
 
-                    // Estimates row
+                    // Estimates row (one field per configured role)
                     div { class: "form-row",
-                        div { class: "form-field",
-                            label { class: "form-label", "Engineering Estimate (weeks) *" }
-                            input {
-                                r#type: "number",
-                                class: "input",
-                                step: "0.5",
-                                min: "0",
-                                placeholder: "0.0",
-                                value: "{eng_estimate()}",
-                                oninput: move |e| {
-                                    if let Ok(v) = e.value().parse::<f32>() {
-                                        eng_estimate.set(v);
-                                    }
-                                },
-                            }
-                            div { class: "form-error", "{eng_estimate_error()}" }
-                        }
-
-                        div { class: "form-field",
-                            label { class: "form-label", "Science Estimate (weeks) *" }
-                            input {
-                                r#type: "number",
-                                class: "input",
-                                step: "0.5",
-                                min: "0",
-                                placeholder: "0.0",
-                                value: "{sci_estimate()}",
-                                oninput: move |e| {
-                                    if let Ok(v) = e.value().parse::<f32>() {
-                                        sci_estimate.set(v);
+                        for definition in preferences().roles {
+                            {
+                                let role = definition.id.clone();
+                                let weeks = estimates().get(&role);
+                                rsx! {
+                                    div { class: "form-field", key: "{definition.id}",
+                                        label { class: "form-label", "{definition.name} Estimate (weeks)" }
+                                        input {
+                                            r#type: "number",
+                                            class: "input",
+                                            step: "0.5",
+                                            min: "0",
+                                            placeholder: "0.0",
+                                            value: "{weeks}",
+                                            oninput: move |e| {
+                                                if let Ok(v) = e.value().parse::<f32>() {
+                                                    estimates.write().set(role.clone(), v);
+                                                }
+                                            },
+                                        }
                                     }
-                                },
+                                }
                             }
-                            div { class: "form-error", "{sci_estimate_error()}" }
                         }
                     }
+                    div { class: "form-error", "{estimate_error()}" }
 
                     // Dates row
                     div { class: "form-row",
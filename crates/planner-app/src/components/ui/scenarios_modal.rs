@@ -0,0 +1,262 @@
+/// Scenarios modal: create, switch, rename, delete and promote what-if scenarios
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::components::ui::{Button, ButtonVariant, ConfirmationDialog};
+use crate::state::{use_app_context, use_plan_state};
+
+/// Scenario actions that need confirmation
+#[derive(Clone, Copy, PartialEq)]
+enum PendingAction {
+    Delete(Uuid),
+    Promote(Uuid),
+}
+
+/// Props for ScenariosModal
+#[derive(Props, Clone, PartialEq)]
+pub struct ScenariosModalProps {
+    /// Event handler for closing the modal
+    pub on_close: EventHandler<()>,
+}
+
+/// Scenarios modal component
+///
+/// Changes apply immediately; switching drops undo history because it
+/// replaces the data being edited.
+#[component]
+pub fn ScenariosModal(props: ScenariosModalProps) -> Element {
+    let ctx = use_app_context();
+    let plan_state = use_plan_state();
+
+    let mut new_name = use_signal(String::new);
+    let mut name_error = use_signal(|| None::<String>);
+    let mut pending_action = use_signal(|| None::<PendingAction>);
+
+    let plan_data = plan_state();
+    let active_id = plan_data.active_scenario_id;
+    let main_allocations = plan_data.main_branch().allocations.len();
+
+    // (id, name, allocation count) per scenario, counting live edits for the active one
+    let scenario_rows: Vec<(Uuid, String, usize)> = plan_data
+        .scenarios
+        .iter()
+        .map(|s| {
+            let allocations = plan_data
+                .scenario_branch(&s.id)
+                .map(|b| b.allocations.len())
+                .unwrap_or_default();
+            (s.id, s.name.clone(), allocations)
+        })
+        .collect();
+
+    let is_name_taken = move |name: &str, except: Option<Uuid>| {
+        plan_state()
+            .scenarios
+            .iter()
+            .any(|s| Some(s.id) != except && s.name.eq_ignore_ascii_case(name))
+    };
+
+    // Branch a new scenario from whatever is being edited and switch to it
+    let handle_create = move |_| {
+        let name = new_name().trim().to_string();
+        if name.is_empty() {
+            name_error.set(Some("Scenario name is required.".to_string()));
+            return;
+        }
+        if is_name_taken(&name, None) {
+            name_error.set(Some(format!(
+                "A scenario named \"{}\" already exists.",
+                name
+            )));
+            return;
+        }
+        ctx.update_scenarios(|state| {
+            let id = state.create_scenario(&name);
+            state.switch_scenario(Some(id));
+        });
+        new_name.set(String::new());
+        name_error.set(None);
+    };
+
+    let (confirm_title, confirm_message, confirm_warning, confirm_label) = match pending_action() {
+        Some(PendingAction::Delete(id)) => (
+            "Delete Scenario".to_string(),
+            format!(
+                "Are you sure you want to delete \"{}\"?",
+                plan_data
+                    .get_scenario(&id)
+                    .map(|s| s.name.as_str())
+                    .unwrap_or_default()
+            ),
+            "Its projects and allocations will be lost.".to_string(),
+            "Delete".to_string(),
+        ),
+        Some(PendingAction::Promote(id)) => (
+            "Promote Scenario".to_string(),
+            format!(
+                "Make \"{}\" the main plan?",
+                plan_data
+                    .get_scenario(&id)
+                    .map(|s| s.name.as_str())
+                    .unwrap_or_default()
+            ),
+            "The current main plan's projects and allocations will be replaced.".to_string(),
+            "Promote".to_string(),
+        ),
+        None => Default::default(),
+    };
+
+    rsx! {
+        // Modal backdrop
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+
+            // Modal container
+            div {
+                class: "modal-container scenarios-modal",
+                onclick: move |e| e.stop_propagation(),
+
+                // Header
+                div { class: "modal-header",
+                    h2 { class: "modal-title", "Scenarios" }
+                    button {
+                        class: "modal-close",
+                        onclick: move |_| props.on_close.call(()),
+                        "×"
+                    }
+                }
+
+                // Body
+                div { class: "modal-body",
+                    p { class: "settings-section-description",
+                        "Scenarios are copies of this plan's projects and allocations that share the team roster. Edit one to try an alternative, then promote it to replace the main plan."
+                    }
+
+                    div { class: "date-range-list",
+                        // Main plan
+                        div {
+                            class: if active_id.is_none() { "date-range-item scenario-item active" } else { "date-range-item scenario-item" },
+                            span { class: "date-range-label", "Main plan" }
+                            span { class: "date-range-meta", "{main_allocations} allocations" }
+                            if active_id.is_none() {
+                                span { class: "scenario-active-badge", "Editing" }
+                            } else {
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: move |_| {
+                                        ctx.update_scenarios(|state| {
+                                            state.switch_scenario(None);
+                                        });
+                                    },
+                                    "Switch"
+                                }
+                            }
+                        }
+
+                        for (id, name, allocations) in scenario_rows {
+                            div {
+                                key: "{id}",
+                                class: if active_id == Some(id) { "date-range-item scenario-item active" } else { "date-range-item scenario-item" },
+                                input {
+                                    r#type: "text",
+                                    class: "form-input",
+                                    value: "{name}",
+                                    onchange: move |e: FormEvent| {
+                                        let name = e.value();
+                                        if name.trim().is_empty() || is_name_taken(name.trim(), Some(id)) {
+                                            return;
+                                        }
+                                        ctx.update_scenarios(|state| {
+                                            state.rename_scenario(&id, &name);
+                                        });
+                                    },
+                                }
+                                span { class: "date-range-meta", "{allocations} allocations" }
+                                if active_id == Some(id) {
+                                    span { class: "scenario-active-badge", "Editing" }
+                                } else {
+                                    Button {
+                                        variant: ButtonVariant::Secondary,
+                                        onclick: move |_| {
+                                            ctx.update_scenarios(|state| {
+                                                state.switch_scenario(Some(id));
+                                            });
+                                        },
+                                        "Switch"
+                                    }
+                                }
+                                button {
+                                    class: "icon-button",
+                                    title: "Promote to main plan",
+                                    onclick: move |_| pending_action.set(Some(PendingAction::Promote(id))),
+                                    "⤴"
+                                }
+                                button {
+                                    class: "icon-button danger",
+                                    title: "Delete scenario",
+                                    onclick: move |_| pending_action.set(Some(PendingAction::Delete(id))),
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+
+                    // New scenario form
+                    div { class: "date-range-add",
+                        input {
+                            r#type: "text",
+                            class: "form-input",
+                            placeholder: "Name (e.g., Payment Gateway first)",
+                            value: "{new_name()}",
+                            oninput: move |e: FormEvent| new_name.set(e.value()),
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: handle_create,
+                            "Branch"
+                        }
+                    }
+                    div { class: "form-hint", "New scenarios start as a copy of the plan you're editing." }
+                    if let Some(error) = name_error() {
+                        div { class: "form-error", "{error}" }
+                    }
+                }
+
+                // Footer
+                div { class: "modal-footer",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        onclick: move |_| props.on_close.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+
+        ConfirmationDialog {
+            visible: pending_action().is_some(),
+            title: confirm_title,
+            message: confirm_message,
+            warning: confirm_warning,
+            confirm_label,
+            on_confirm: move |_| {
+                match pending_action() {
+                    Some(PendingAction::Delete(id)) => {
+                        ctx.update_scenarios(|state| {
+                            state.delete_scenario(&id);
+                        });
+                    }
+                    Some(PendingAction::Promote(id)) => {
+                        ctx.update_scenarios(|state| {
+                            state.promote_scenario(&id);
+                        });
+                    }
+                    None => {}
+                }
+                pending_action.set(None);
+            },
+            on_cancel: move |_| pending_action.set(None),
+        }
+    }
+}
@@ -1,9 +1,18 @@
 /// Settings modal for app-level configuration and tools
+use std::collections::HashSet;
+
 use chrono::NaiveDate;
 use dioxus::prelude::*;
 
 use crate::components::ui::{Button, ButtonVariant, ConfirmationDialog, Input};
-use crate::state::{use_plan_state, use_preferences};
+use crate::state::{use_app_context, use_plan_state, use_preferences};
+use planner_core::formats::ical::{
+    parse_ics, plan_import, CalendarEvent, IcsImportMode, SkipReason,
+};
+use planner_core::models::{Holiday, Preferences, Role, RoleDefinition};
+
+/// Hidden file input used for calendar imports on web
+const ICS_FILE_INPUT_ID: &str = "ics-file-input";
 
 /// Props for SettingsModal
 #[derive(Props, Clone, PartialEq)]
@@ -20,8 +29,9 @@ pub struct SettingsModalProps {
 #[component]
 pub fn SettingsModal(props: SettingsModalProps) -> Element {
     // Access state
-    let mut plan_state = use_plan_state();
-    let mut preferences = use_preferences();
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+    let ctx = use_app_context();
     let plan_data = plan_state();
     let prefs_data = preferences();
 
@@ -39,10 +49,133 @@ pub fn SettingsModal(props: SettingsModalProps) -> Element {
         use_signal(|| prefs_data.sprint_anchor_date.format("%Y-%m-%d").to_string());
     let mut sprint_length = use_signal(|| prefs_data.sprint_length_weeks.to_string());
 
+    // Holiday calendar form state (applied with the rest of the settings)
+    let mut holidays = use_signal(|| prefs_data.holidays.clone());
+    let mut new_holiday_name = use_signal(String::new);
+    let mut new_holiday_start = use_signal(String::new);
+    let mut new_holiday_end = use_signal(String::new);
+    let mut holiday_error = use_signal(|| None::<String>);
+
+    // Role list form state (applied with the rest of the settings)
+    let mut roles = use_signal(|| prefs_data.roles.clone());
+    let mut new_role_name = use_signal(String::new);
+    let mut new_role_short_name = use_signal(String::new);
+    let mut role_error = use_signal(|| None::<String>);
+
+    // Roles referenced by team members or estimates can't be removed
+    let used_roles: HashSet<Role> = prefs_data
+        .team_members
+        .iter()
+        .map(|m| m.role.clone())
+        .chain(
+            plan_data
+                .roadmap_projects
+                .iter()
+                .flat_map(|p| p.estimates.iter().map(|(role, _)| role.clone())),
+        )
+        .chain(
+            plan_data
+                .technical_projects
+                .iter()
+                .flat_map(|p| p.estimates.iter().map(|(role, _)| role.clone())),
+        )
+        .collect();
+
+    // Calendar import state: parsed events are previewed before being applied
+    let mut ics_events = use_signal(|| None::<Vec<CalendarEvent>>);
+    let ics_filename = use_signal(String::new);
+    let mut ics_mode = use_signal(|| IcsImportMode::Holidays);
+    let mut ics_error = use_signal(|| None::<String>);
+    let ics_preview = use_memo(move || {
+        // Compare against the staged holiday list so duplicates are skipped
+        let prefs = Preferences {
+            holidays: holidays(),
+            ..preferences()
+        };
+        ics_events().map(|events| plan_import(&events, ics_mode(), &prefs))
+    });
+
     // Validation state
     let mut plan_error = use_signal(|| None::<String>);
     let mut sprint_error = use_signal(|| None::<String>);
 
+    // Add a holiday from the inline form
+    let handle_add_holiday = move |_| {
+        let start = NaiveDate::parse_from_str(&new_holiday_start(), "%Y-%m-%d");
+        // End date defaults to the start date for single-day holidays
+        let end = if new_holiday_end().is_empty() {
+            start
+        } else {
+            NaiveDate::parse_from_str(&new_holiday_end(), "%Y-%m-%d")
+        };
+
+        match (start, end) {
+            _ if new_holiday_name().trim().is_empty() => {
+                holiday_error.set(Some("Holiday name is required.".to_string()));
+            }
+            (Ok(start), Ok(end)) if end >= start => {
+                holidays.with_mut(|h| {
+                    h.push(Holiday::new(
+                        new_holiday_name().trim().to_string(),
+                        start,
+                        end,
+                    ));
+                    h.sort_by_key(|holiday| holiday.start_date);
+                });
+                new_holiday_name.set(String::new());
+                new_holiday_start.set(String::new());
+                new_holiday_end.set(String::new());
+                holiday_error.set(None);
+            }
+            (Ok(_), Ok(_)) => {
+                holiday_error.set(Some("End date must be on or after start date.".to_string()));
+            }
+            _ => {
+                holiday_error.set(Some("Invalid date format. Use YYYY-MM-DD.".to_string()));
+            }
+        }
+    };
+
+    // Add a role from the inline form
+    let handle_add_role = move |_| {
+        let name = new_role_name().trim().to_string();
+        let short_name = new_role_short_name().trim().to_string();
+        if name.is_empty() {
+            role_error.set(Some("Role name is required.".to_string()));
+            return;
+        }
+        if roles().iter().any(|r| r.name.eq_ignore_ascii_case(&name)) {
+            role_error.set(Some(format!("A role named \"{}\" already exists.", name)));
+            return;
+        }
+        // Short name defaults to the full name
+        let short_name = if short_name.is_empty() {
+            name.clone()
+        } else {
+            short_name
+        };
+        roles.with_mut(|r| {
+            let id = role_key(&name, r);
+            r.push(RoleDefinition::new(id, name, short_name));
+        });
+        new_role_name.set(String::new());
+        new_role_short_name.set(String::new());
+        role_error.set(None);
+    };
+
+    // Confirm a previewed calendar import (undoable)
+    let handle_confirm_import = move |_| {
+        let Some(preview) = ics_preview() else {
+            return;
+        };
+        holidays.with_mut(|h| {
+            h.extend(preview.holidays.iter().cloned());
+            h.sort_by_key(|holiday| holiday.start_date);
+        });
+        ctx.edit("Import calendar", |p, _| preview.apply(p));
+        ics_events.set(None);
+    };
+
     // Handle applying all settings
     let handle_apply = move |_| {
         let mut has_errors = false;
@@ -53,7 +186,7 @@ pub fn SettingsModal(props: SettingsModalProps) -> Element {
 
         match (parsed_date, parsed_weeks) {
             (Ok(date), Ok(weeks)) if (1..=52).contains(&weeks) => {
-                plan_state.with_mut(|p| {
+                ctx.edit("Edit quarter", |_, p| {
                     p.quarter_name = plan_name();
                     p.quarter_start_date = date;
                     p.num_weeks = weeks;
@@ -83,7 +216,7 @@ pub fn SettingsModal(props: SettingsModalProps) -> Element {
 
         match (parsed_anchor, parsed_length) {
             (Ok(anchor), Ok(length)) if (1..=4).contains(&length) => {
-                preferences.with_mut(|p| {
+                ctx.edit("Edit sprints", |p, _| {
                     p.sprint_anchor_date = anchor;
                     p.sprint_length_weeks = length;
                 });
@@ -106,6 +239,26 @@ pub fn SettingsModal(props: SettingsModalProps) -> Element {
             _ => {}
         }
 
+        // Validate and apply role list (undoable)
+        let updated_roles = roles();
+        if updated_roles
+            .iter()
+            .any(|r| r.name.trim().is_empty() || r.short_name.trim().is_empty())
+        {
+            role_error.set(Some("Every role needs a name and short name.".to_string()));
+            has_errors = true;
+        } else {
+            ctx.edit("Edit roles", |p, _| {
+                p.roles = updated_roles;
+            });
+        }
+
+        // Apply holiday calendar (undoable)
+        let updated_holidays = holidays();
+        ctx.edit("Edit holidays", |p, _| {
+            p.holidays = updated_holidays;
+        });
+
         // Close modal if no errors
         if !has_errors {
             props.on_close.call(());
@@ -219,6 +372,283 @@ pub fn SettingsModal(props: SettingsModalProps) -> Element {
                         }
                     }
 
+                    // Roles section
+                    div { class: "settings-section",
+                        h3 { class: "settings-section-title", "Roles" }
+                        p { class: "settings-section-description",
+                            "Roles team members can hold. Each role gets its own estimate and allocation columns."
+                        }
+
+                        div { class: "settings-form",
+                            div { class: "date-range-list",
+                                for (index, definition) in roles().into_iter().enumerate() {
+                                    {
+                                        let in_use = used_roles.contains(&definition.id);
+
+                                        rsx! {
+                                            div { class: "date-range-item role-item", key: "{definition.id}",
+                                                input {
+                                                    r#type: "text",
+                                                    class: "form-input",
+                                                    value: "{definition.name}",
+                                                    oninput: move |e: FormEvent| {
+                                                        roles.with_mut(|r| r[index].name = e.value());
+                                                    },
+                                                }
+                                                input {
+                                                    r#type: "text",
+                                                    class: "form-input role-short-name",
+                                                    value: "{definition.short_name}",
+                                                    oninput: move |e: FormEvent| {
+                                                        roles.with_mut(|r| r[index].short_name = e.value());
+                                                    },
+                                                }
+                                                button {
+                                                    class: "icon-button danger",
+                                                    disabled: in_use,
+                                                    title: if in_use { "Role is assigned to team members or estimates" } else { "Remove role" },
+                                                    onclick: move |_| {
+                                                        roles.with_mut(|r| {
+                                                            r.remove(index);
+                                                        });
+                                                    },
+                                                    "✕"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            div { class: "date-range-add",
+                                input {
+                                    r#type: "text",
+                                    class: "form-input",
+                                    placeholder: "Name (e.g., Design)",
+                                    value: "{new_role_name()}",
+                                    oninput: move |e: FormEvent| new_role_name.set(e.value()),
+                                }
+                                input {
+                                    r#type: "text",
+                                    class: "form-input role-short-name",
+                                    placeholder: "Short name",
+                                    value: "{new_role_short_name()}",
+                                    oninput: move |e: FormEvent| new_role_short_name.set(e.value()),
+                                }
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: handle_add_role,
+                                    "Add"
+                                }
+                            }
+
+                            if let Some(error) = role_error() {
+                                div { class: "form-error", "{error}" }
+                            }
+                        }
+                    }
+
+                    // Holidays section
+                    div { class: "settings-section",
+                        h3 { class: "settings-section-title", "Holidays" }
+                        p { class: "settings-section-description",
+                            "Team-wide non-working days. Holidays reduce every member's capacity for the weeks they fall in."
+                        }
+
+                        div { class: "settings-form",
+                            div { class: "date-range-list",
+                                if holidays().is_empty() {
+                                    div { class: "date-range-empty", "No holidays configured" }
+                                }
+                                for holiday in holidays() {
+                                    {
+                                        let holiday_id = holiday.id;
+                                        let range = format_range(holiday.start_date, holiday.end_date);
+
+                                        rsx! {
+                                            div { class: "date-range-item",
+                                                span { class: "date-range-label", "{holiday.name}" }
+                                                span { class: "date-range-meta", "{range}" }
+                                                button {
+                                                    class: "icon-button danger",
+                                                    title: "Remove holiday",
+                                                    onclick: move |_| {
+                                                        holidays.with_mut(|h| h.retain(|e| e.id != holiday_id));
+                                                    },
+                                                    "✕"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            div { class: "date-range-add",
+                                input {
+                                    r#type: "text",
+                                    class: "form-input",
+                                    placeholder: "Name",
+                                    value: "{new_holiday_name()}",
+                                    oninput: move |e: FormEvent| new_holiday_name.set(e.value()),
+                                }
+                                input {
+                                    r#type: "date",
+                                    class: "form-input",
+                                    value: "{new_holiday_start()}",
+                                    oninput: move |e: FormEvent| new_holiday_start.set(e.value()),
+                                }
+                                input {
+                                    r#type: "date",
+                                    class: "form-input",
+                                    value: "{new_holiday_end()}",
+                                    oninput: move |e: FormEvent| new_holiday_end.set(e.value()),
+                                }
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: handle_add_holiday,
+                                    "Add"
+                                }
+                            }
+
+                            if let Some(error) = holiday_error() {
+                                div { class: "form-error", "{error}" }
+                            }
+
+                            // Calendar (.ics) import
+                            div { class: "calendar-import",
+                                input {
+                                    r#type: "file",
+                                    id: ICS_FILE_INPUT_ID,
+                                    accept: ".ics,text/calendar",
+                                    style: "display: none;",
+                                    onchange: move |_| {
+                                        #[cfg(target_family = "wasm")]
+                                        wasm_bindgen_futures::spawn_local(async move {
+                                            match crate::plan_io::read_file_from_input(ICS_FILE_INPUT_ID).await {
+                                                Ok(result) => load_calendar(
+                                                    &result.content,
+                                                    &result.filename,
+                                                    ics_events,
+                                                    ics_filename,
+                                                    ics_error,
+                                                ),
+                                                Err(e) => ics_error.set(Some(e)),
+                                            }
+                                        });
+                                    },
+                                }
+                                select {
+                                    class: "form-input",
+                                    onchange: move |e: FormEvent| {
+                                        ics_mode.set(if e.value() == "time-off" {
+                                            IcsImportMode::TimeOff
+                                        } else {
+                                            IcsImportMode::Holidays
+                                        });
+                                    },
+                                    option {
+                                        value: "holidays",
+                                        selected: ics_mode() == IcsImportMode::Holidays,
+                                        "As team holidays"
+                                    }
+                                    option {
+                                        value: "time-off",
+                                        selected: ics_mode() == IcsImportMode::TimeOff,
+                                        "As member time off"
+                                    }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: move |_| {
+                                        #[cfg(target_family = "wasm")]
+                                        crate::plan_io::trigger_file_open(ICS_FILE_INPUT_ID);
+                                        #[cfg(not(target_family = "wasm"))]
+                                        spawn(async move {
+                                            let file = rfd::AsyncFileDialog::new()
+                                                .add_filter("Calendar Files", &["ics"])
+                                                .add_filter("All Files", &["*"])
+                                                .set_title("Import Calendar")
+                                                .pick_file()
+                                                .await;
+                                            if let Some(file) = file {
+                                                // Use sync read - async read() doesn't complete properly in Dioxus
+                                                match std::fs::read_to_string(file.path()) {
+                                                    Ok(content) => load_calendar(
+                                                        &content,
+                                                        &file.file_name(),
+                                                        ics_events,
+                                                        ics_filename,
+                                                        ics_error,
+                                                    ),
+                                                    Err(e) => ics_error.set(Some(format!("Failed to read file: {}", e))),
+                                                }
+                                            }
+                                        });
+                                    },
+                                    "Import .ics…"
+                                }
+                            }
+                            div { class: "form-hint",
+                                "Time off is matched to members by email, or by name in the event title."
+                            }
+
+                            if let Some(error) = ics_error() {
+                                div { class: "form-error", "{error}" }
+                            }
+
+                            if let Some(preview) = ics_preview() {
+                                div { class: "calendar-import-preview",
+                                    div { class: "calendar-import-summary",
+                                        "{ics_filename()}: {preview.holidays.len()} holidays, {preview.time_off.len()} time off entries, {preview.skipped.len()} skipped"
+                                    }
+                                    div { class: "date-range-list",
+                                        for holiday in preview.holidays.iter() {
+                                            div { class: "date-range-item",
+                                                span { class: "date-range-label", "{holiday.name}" }
+                                                span { class: "date-range-meta",
+                                                    {format_range(holiday.start_date, holiday.end_date)}
+                                                }
+                                            }
+                                        }
+                                        for entry in preview.time_off.iter() {
+                                            div { class: "date-range-item",
+                                                span { class: "date-range-label", "{entry.member_name}" }
+                                                span { class: "date-range-meta",
+                                                    {format_range(entry.time_off.start_date, entry.time_off.end_date)}
+                                                }
+                                            }
+                                        }
+                                        for skipped in preview.skipped.iter() {
+                                            div { class: "date-range-item skipped",
+                                                span { class: "date-range-label", "{skipped.event.summary}" }
+                                                span { class: "date-range-meta",
+                                                    match skipped.reason {
+                                                        SkipReason::Duplicate => "Already added",
+                                                        SkipReason::NoMatchingMember => "No matching member",
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    div { class: "calendar-import-actions",
+                                        Button {
+                                            variant: ButtonVariant::Secondary,
+                                            onclick: move |_| ics_events.set(None),
+                                            "Discard"
+                                        }
+                                        if !preview.is_empty() {
+                                            Button {
+                                                variant: ButtonVariant::Primary,
+                                                onclick: handle_confirm_import,
+                                                "Import"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // Storage section
                     div { class: "settings-section",
                         h3 { class: "settings-section-title", "Storage" }
@@ -322,3 +752,60 @@ pub fn SettingsModal(props: SettingsModalProps) -> Element {
         }
     }
 }
+
+/// Derive a unique role key from a display name (e.g., "UX Design" -> "ux-design")
+fn role_key(name: &str, existing: &[RoleDefinition]) -> Role {
+    let base = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = if base.is_empty() {
+        "role".to_string()
+    } else {
+        base
+    };
+
+    let mut key = base.clone();
+    let mut suffix = 2;
+    while existing.iter().any(|r| r.id.as_str() == key) {
+        key = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    Role::new(key)
+}
+
+/// Format an inclusive date range for display
+fn format_range(start: NaiveDate, end: NaiveDate) -> String {
+    if start == end {
+        start.format("%b %d, %Y").to_string()
+    } else {
+        format!("{} – {}", start.format("%b %d"), end.format("%b %d, %Y"))
+    }
+}
+
+/// Parse a calendar file and stage its events for preview
+fn load_calendar(
+    content: &str,
+    filename: &str,
+    mut events_signal: Signal<Option<Vec<CalendarEvent>>>,
+    mut filename_signal: Signal<String>,
+    mut error_signal: Signal<Option<String>>,
+) {
+    match parse_ics(content) {
+        Ok(events) if events.is_empty() => {
+            error_signal.set(Some(format!("{}: no events found", filename)));
+            events_signal.set(None);
+        }
+        Ok(events) => {
+            filename_signal.set(filename.to_string());
+            events_signal.set(Some(events));
+            error_signal.set(None);
+        }
+        Err(e) => {
+            error_signal.set(Some(format!("{}: {}", filename, e)));
+            events_signal.set(None);
+        }
+    }
+}
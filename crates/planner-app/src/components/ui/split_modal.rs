@@ -132,7 +132,9 @@ pub fn SplitAllocationModal(
 #[component]
 fn ProjectDropdown(selected_id: Option<Uuid>, on_select: EventHandler<Uuid>) -> Element {
     let plan = crate::state::use_plan_state();
+    let plan_index = crate::state::use_plan_index();
     let plan_data = plan();
+    let index = plan_index.read();
 
     rsx! {
         select {
@@ -149,7 +151,7 @@ fn ProjectDropdown(selected_id: Option<Uuid>, on_select: EventHandler<Uuid>) ->
             for project in &plan_data.technical_projects {
                 {
                     let project_id = project.id;
-                    let allocated = plan_data.calculate_project_allocated_weeks(&project_id);
+                    let allocated = index.project_allocated_weeks(&project_id);
 
                     rsx! {
                         option {
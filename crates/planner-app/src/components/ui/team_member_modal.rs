@@ -1,8 +1,10 @@
+use chrono::NaiveDate;
 use dioxus::prelude::*;
 use uuid::Uuid;
 
 use crate::components::ui::{Button, ButtonVariant, Input};
-use planner_core::models::{Role, TeamMember};
+use crate::state::use_preferences;
+use planner_core::models::{parse_skills, Role, TeamMember, TimeOff};
 
 /// Mode for the team member modal (Add or Edit)
 #[derive(Clone, Copy, PartialEq)]
@@ -20,6 +22,16 @@ pub struct TeamMemberModalProps {
     pub initial_name: String,
     pub initial_role: Role,
     pub initial_capacity: f32,
+    #[props(default)]
+    pub initial_time_off: Vec<TimeOff>,
+    #[props(default)]
+    pub initial_email: Option<String>,
+    #[props(default)]
+    pub initial_skills: Vec<String>,
+    #[props(default)]
+    pub initial_start_date: Option<NaiveDate>,
+    #[props(default)]
+    pub initial_end_date: Option<NaiveDate>,
     /// Default capacity for new team members (from preferences)
     pub default_capacity: f32,
     /// Current allocated weeks (for warning when reducing capacity below allocated)
@@ -34,7 +46,10 @@ pub struct TeamMemberModalProps {
 pub fn TeamMemberModal(props: TeamMemberModalProps) -> Element {
     // Form state - signals initialized from props
     let name = use_signal(|| props.initial_name.clone());
-    let mut role = use_signal(|| props.initial_role);
+    let email = use_signal(|| props.initial_email.clone().unwrap_or_default());
+    let skills = use_signal(|| props.initial_skills.join(", "));
+    let preferences = use_preferences();
+    let mut role = use_signal(|| props.initial_role.clone());
     let mut capacity = use_signal(|| {
         if matches!(props.mode, TeamMemberModalMode::Add) && props.initial_capacity == 0.0 {
             props.default_capacity
@@ -43,9 +58,32 @@ pub fn TeamMemberModal(props: TeamMemberModalProps) -> Element {
         }
     });
 
+    // Employment dates (empty = on the team for the whole plan)
+    let format_date = |date: Option<NaiveDate>| {
+        date.map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+    let mut start_date = use_signal(|| format_date(props.initial_start_date));
+    let mut end_date = use_signal(|| format_date(props.initial_end_date));
+    let mut tenure_error = use_signal(String::new);
+
+    // Time off form state
+    let mut time_off = use_signal(|| props.initial_time_off.clone());
+    let mut new_time_off_start = use_signal(String::new);
+    let mut new_time_off_end = use_signal(String::new);
+    let mut new_time_off_fraction = use_signal(|| 1.0_f32);
+    let mut time_off_error = use_signal(String::new);
+
     // Validation errors and warnings
     let mut name_error = use_signal(String::new);
     let mut capacity_error = use_signal(String::new);
+    let parse_optional_date = |value: &str| -> Result<Option<NaiveDate>, chrono::ParseError> {
+        if value.is_empty() {
+            Ok(None)
+        } else {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map(Some)
+        }
+    };
     let capacity_warning = use_memo(move || {
         if capacity() < props.allocated_weeks && props.allocated_weeks > 0.0 {
             format!(
@@ -78,27 +116,82 @@ pub fn TeamMemberModal(props: TeamMemberModalProps) -> Element {
             capacity_error.set(String::new());
         }
 
+        // Validate employment dates
+        match (
+            parse_optional_date(&start_date()),
+            parse_optional_date(&end_date()),
+        ) {
+            (Ok(Some(start)), Ok(Some(end))) if end < start => {
+                tenure_error.set("End date must be on or after start date".to_string());
+                is_valid = false;
+            }
+            (Ok(_), Ok(_)) => tenure_error.set(String::new()),
+            _ => {
+                tenure_error.set("Enter valid dates".to_string());
+                is_valid = false;
+            }
+        }
+
         is_valid
     };
 
+    // Add a time off range from the inline form
+    let handle_add_time_off = move |_| {
+        let start = NaiveDate::parse_from_str(&new_time_off_start(), "%Y-%m-%d");
+        // End date defaults to the start date for single days
+        let end = if new_time_off_end().is_empty() {
+            start
+        } else {
+            NaiveDate::parse_from_str(&new_time_off_end(), "%Y-%m-%d")
+        };
+
+        match (start, end) {
+            (Ok(start), Ok(end)) if end >= start => {
+                time_off.with_mut(|t| t.push(TimeOff::new(start, end, new_time_off_fraction())));
+                new_time_off_start.set(String::new());
+                new_time_off_end.set(String::new());
+                time_off_error.set(String::new());
+            }
+            (Ok(_), Ok(_)) => {
+                time_off_error.set("End date must be on or after start date".to_string())
+            }
+            _ => time_off_error.set("Enter a valid start date".to_string()),
+        }
+    };
+
     // Handle save
     let handle_save = move |_| {
         if !validate_form() {
             return;
         }
 
+        let email = email().trim().to_string();
+        let email = (!email.is_empty()).then_some(email);
+        let start = parse_optional_date(&start_date()).ok().flatten();
+        let end = parse_optional_date(&end_date()).ok().flatten();
+
         let member = match props.mode {
             TeamMemberModalMode::Add => TeamMember {
                 id: Uuid::new_v4(),
                 name: name().trim().to_string(),
                 role: role(),
                 capacity: capacity(),
+                time_off: time_off(),
+                email: email.clone(),
+                start_date: start,
+                end_date: end,
+                skills: parse_skills(&skills()),
             },
             TeamMemberModalMode::Edit(id) => TeamMember {
                 id,
                 name: name().trim().to_string(),
                 role: role(),
                 capacity: capacity(),
+                time_off: time_off(),
+                email,
+                start_date: start,
+                end_date: end,
+                skills: parse_skills(&skills()),
             },
         };
 
@@ -143,29 +236,59 @@ pub fn TeamMemberModal(props: TeamMemberModalProps) -> Element {
                         div { class: "form-error", "{name_error()}" }
                     }
 
+                    // Email field (optional, used to match calendar imports)
+                    div { class: "form-field",
+                        label { class: "form-label", "Email" }
+                        Input {
+                            value: email,
+                            placeholder: "e.g., alice@example.com".to_string(),
+                        }
+                        div { class: "form-hint", "Used to match time off when importing calendars" }
+                    }
+
                     // Role selection (radio buttons)
                     div { class: "form-field",
                         label { class: "form-label", "Role *" }
                         div { class: "role-selector",
-                            label {
-                                class: if role() == Role::Engineering { "role-option selected" } else { "role-option" },
-                                input {
-                                    r#type: "radio",
-                                    name: "role",
-                                    checked: role() == Role::Engineering,
-                                    onchange: move |_| role.set(Role::Engineering),
+                            for definition in preferences().roles {
+                                {
+                                    let selected = role() == definition.id;
+                                    let role_id = definition.id.clone();
+                                    rsx! {
+                                        label {
+                                            key: "{definition.id}",
+                                            class: if selected { "role-option selected" } else { "role-option" },
+                                            input {
+                                                r#type: "radio",
+                                                name: "role",
+                                                checked: selected,
+                                                onchange: move |_| role.set(role_id.clone()),
+                                            }
+                                            span { class: "role-label", "{definition.name} ({definition.short_name})" }
+                                        }
+                                    }
                                 }
-                                span { class: "role-label", "Engineer (SDE)" }
                             }
-                            label {
-                                class: if role() == Role::Science { "role-option selected" } else { "role-option" },
-                                input {
-                                    r#type: "radio",
-                                    name: "role",
-                                    checked: role() == Role::Science,
-                                    onchange: move |_| role.set(Role::Science),
+                        }
+                    }
+
+                    // Skills field (comma-separated tags)
+                    div { class: "form-field",
+                        label { class: "form-label", "Skills" }
+                        Input {
+                            value: skills,
+                            placeholder: "e.g., Kafka, ML modeling".to_string(),
+                        }
+                        {
+                            let known = preferences().skill_vocabulary().join(", ");
+                            rsx! {
+                                div { class: "form-hint",
+                                    if known.is_empty() {
+                                        "Comma-separated. Used to match people to project needs"
+                                    } else {
+                                        "Comma-separated. Known skills: {known}"
+                                    }
                                 }
-                                span { class: "role-label", "Scientist (AS)" }
                             }
                         }
                     }
@@ -192,6 +315,101 @@ pub fn TeamMemberModal(props: TeamMemberModalProps) -> Element {
                             div { class: "form-warning", "{capacity_warning()}" }
                         }
                     }
+
+                    // Employment dates (mid-quarter joiners and leavers)
+                    div { class: "form-field",
+                        label { class: "form-label", "Employment Dates" }
+                        div { class: "date-range-add",
+                            input {
+                                r#type: "date",
+                                class: "input",
+                                title: "First day on the team",
+                                value: "{start_date()}",
+                                oninput: move |e| start_date.set(e.value()),
+                            }
+                            input {
+                                r#type: "date",
+                                class: "input",
+                                title: "Last day on the team",
+                                value: "{end_date()}",
+                                oninput: move |e| end_date.set(e.value()),
+                            }
+                        }
+                        div { class: "form-hint", "Leave blank if on the team for the whole plan. Weeks outside these dates can't be allocated." }
+                        div { class: "form-error", "{tenure_error()}" }
+                    }
+
+                    // Time off (reduces weekly availability)
+                    div { class: "form-field",
+                        label { class: "form-label", "Time Off" }
+                        div { class: "date-range-list",
+                            for entry in time_off() {
+                                {
+                                    let entry_id = entry.id;
+                                    let range = if entry.start_date == entry.end_date {
+                                        entry.start_date.format("%b %d, %Y").to_string()
+                                    } else {
+                                        format!(
+                                            "{} – {}",
+                                            entry.start_date.format("%b %d"),
+                                            entry.end_date.format("%b %d, %Y")
+                                        )
+                                    };
+                                    let amount = if entry.fraction < 1.0 {
+                                        format!("{:.0}% of each day", entry.fraction * 100.0)
+                                    } else {
+                                        "Full days".to_string()
+                                    };
+
+                                    rsx! {
+                                        div { class: "date-range-item",
+                                            span { class: "date-range-label", "{range}" }
+                                            span { class: "date-range-meta", "{amount}" }
+                                            button {
+                                                class: "icon-button danger",
+                                                title: "Remove time off",
+                                                onclick: move |_| {
+                                                    time_off.with_mut(|t| t.retain(|e| e.id != entry_id));
+                                                },
+                                                "✕"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "date-range-add",
+                            input {
+                                r#type: "date",
+                                class: "input",
+                                value: "{new_time_off_start()}",
+                                oninput: move |e| new_time_off_start.set(e.value()),
+                            }
+                            input {
+                                r#type: "date",
+                                class: "input",
+                                value: "{new_time_off_end()}",
+                                oninput: move |e| new_time_off_end.set(e.value()),
+                            }
+                            select {
+                                class: "input",
+                                onchange: move |e| {
+                                    if let Ok(v) = e.value().parse::<f32>() {
+                                        new_time_off_fraction.set(v);
+                                    }
+                                },
+                                option { value: "1", selected: new_time_off_fraction() == 1.0, "Full days" }
+                                option { value: "0.5", selected: new_time_off_fraction() == 0.5, "Half days" }
+                            }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                onclick: handle_add_time_off,
+                                "Add"
+                            }
+                        }
+                        div { class: "form-hint", "Vacations and leave reduce available capacity for those weeks" }
+                        div { class: "form-error", "{time_off_error()}" }
+                    }
                 }
 
                 // Footer
@@ -3,7 +3,11 @@ use dioxus::prelude::*;
 use uuid::Uuid;
 
 use crate::components::ui::{Button, ButtonVariant, Input};
-use planner_core::models::{ProjectColor, RoadmapProject, TechnicalProject};
+use crate::state::use_preferences;
+use planner_core::models::{
+    find_dependency_cycle, parse_skills, DependencyKind, ProjectColor, ProjectDependency,
+    RoadmapProject, RoleWeeks, TechnicalProject,
+};
 
 /// Mode for the technical project modal (Add or Edit)
 #[derive(Clone, Copy, PartialEq)]
@@ -20,13 +24,19 @@ pub struct TechnicalProjectModalProps {
     /// Initial values for the form
     pub initial_name: String,
     pub initial_roadmap_project_id: Option<Uuid>,
-    pub initial_eng_estimate: f32,
-    pub initial_sci_estimate: f32,
+    pub initial_estimates: RoleWeeks,
     pub initial_start_date: NaiveDate,
     pub initial_expected_completion: Option<NaiveDate>,
     pub initial_notes: String,
+    #[props(default)]
+    pub initial_required_skills: Vec<String>,
+    #[props(default)]
+    pub initial_dependencies: Vec<ProjectDependency>,
     /// Available roadmap projects for dropdown
     pub roadmap_projects: Vec<RoadmapProject>,
+    /// All technical projects (candidate predecessors and cycle checking)
+    #[props(default)]
+    pub technical_projects: Vec<TechnicalProject>,
     /// Event handlers
     pub on_save: EventHandler<TechnicalProject>,
     pub on_cancel: EventHandler<()>,
@@ -38,15 +48,23 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
     // Form state - signals initialized from props
     let name = use_signal(|| props.initial_name.clone());
     let mut roadmap_project_id = use_signal(|| props.initial_roadmap_project_id);
-    let mut eng_estimate = use_signal(|| props.initial_eng_estimate);
-    let mut sci_estimate = use_signal(|| props.initial_sci_estimate);
+    let preferences = use_preferences();
+    let mut estimates = use_signal(|| props.initial_estimates.clone());
     let mut start_date = use_signal(|| props.initial_start_date);
     let mut expected_completion = use_signal(|| props.initial_expected_completion);
     let mut notes = use_signal(|| props.initial_notes.clone());
+    let required_skills = use_signal(|| props.initial_required_skills.join(", "));
+    let mut dependencies = use_signal(|| props.initial_dependencies.clone());
+    let mut new_dependency_id = use_signal(|| None::<Uuid>);
+    let mut new_dependency_kind = use_signal(|| DependencyKind::FinishToStart);
 
     // Validation errors
     let mut name_error = use_signal(String::new);
     let mut estimate_error = use_signal(String::new);
+    let mut dependency_error = use_signal(String::new);
+
+    let all_projects = props.technical_projects.clone();
+    let mode = props.mode;
 
     // Validation function
     let mut validate_form = move || -> bool {
@@ -61,16 +79,47 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
         }
 
         // Validate at least one estimate is > 0
-        if eng_estimate() <= 0.0 && sci_estimate() <= 0.0 {
+        if estimates().total() <= 0.0 {
             estimate_error.set("At least one estimate must be greater than 0".to_string());
             is_valid = false;
         } else {
             estimate_error.set(String::new());
         }
 
+        // Validate dependencies don't form a cycle (a new project has no
+        // dependents yet, so only edits can close one)
+        if let TechnicalModalMode::Edit(id) = mode {
+            let mut projects = all_projects.clone();
+            if let Some(project) = projects.iter_mut().find(|p| p.id == id) {
+                project.dependencies = dependencies();
+            }
+            if let Some(cycle) = find_dependency_cycle(&projects) {
+                let names: Vec<&str> = cycle
+                    .iter()
+                    .filter_map(|id| projects.iter().find(|p| &p.id == id))
+                    .map(|p| p.name.as_str())
+                    .collect();
+                dependency_error.set(format!("Dependencies form a cycle: {}", names.join(" → ")));
+                is_valid = false;
+            } else {
+                dependency_error.set(String::new());
+            }
+        }
+
         is_valid
     };
 
+    // Add the predecessor selected in the inline form
+    let handle_add_dependency = move |_| {
+        if let Some(predecessor_id) = new_dependency_id() {
+            dependencies.write().push(ProjectDependency::new(
+                predecessor_id,
+                new_dependency_kind(),
+            ));
+            new_dependency_id.set(None);
+        }
+    };
+
     // Handle save
     let handle_save = move |_| {
         if !validate_form() {
@@ -82,8 +131,7 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
                 id: Uuid::new_v4(),
                 name: name().trim().to_string(),
                 roadmap_project_id: roadmap_project_id(),
-                eng_estimate: eng_estimate(),
-                sci_estimate: sci_estimate(),
+                estimates: estimates(),
                 start_date: start_date(),
                 expected_completion: expected_completion(),
                 notes: if notes().trim().is_empty() {
@@ -91,13 +139,14 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
                 } else {
                     Some(notes().trim().to_string())
                 },
+                required_skills: parse_skills(&required_skills()),
+                dependencies: dependencies(),
             },
             TechnicalModalMode::Edit(id) => TechnicalProject {
                 id,
                 name: name().trim().to_string(),
                 roadmap_project_id: roadmap_project_id(),
-                eng_estimate: eng_estimate(),
-                sci_estimate: sci_estimate(),
+                estimates: estimates(),
                 start_date: start_date(),
                 expected_completion: expected_completion(),
                 notes: if notes().trim().is_empty() {
@@ -105,6 +154,8 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
                 } else {
                     Some(notes().trim().to_string())
                 },
+                required_skills: parse_skills(&required_skills()),
+                dependencies: dependencies(),
             },
         };
 
@@ -123,7 +174,7 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
         .unwrap_or(ProjectColor::Blue);
 
     // Calculate total estimate for display
-    let total_estimate = eng_estimate() + sci_estimate();
+    let total_estimate = estimates().total();
 
     rsx! {
         // Modal backdrop
@@ -190,39 +241,30 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
                         div { class: "form-hint", "Color is inherited from the linked roadmap project" }
                     }
 
-                    // Estimates row (Eng, Sci, Total)
+                    // Estimates row (one field per configured role, then the total)
                     div { class: "form-row form-row-3",
-                        div { class: "form-field",
-                            label { class: "form-label", "Eng Estimate *" }
-                            input {
-                                r#type: "number",
-                                class: "input",
-                                step: "0.5",
-                                min: "0",
-                                placeholder: "0.0",
-                                value: "{eng_estimate()}",
-                                oninput: move |e| {
-                                    if let Ok(v) = e.value().parse::<f32>() {
-                                        eng_estimate.set(v.max(0.0));
+                        for definition in preferences().roles {
+                            {
+                                let role = definition.id.clone();
+                                let weeks = estimates().get(&role);
+                                rsx! {
+                                    div { class: "form-field", key: "{definition.id}",
+                                        label { class: "form-label", "{definition.short_name} Estimate" }
+                                        input {
+                                            r#type: "number",
+                                            class: "input",
+                                            step: "0.5",
+                                            min: "0",
+                                            placeholder: "0.0",
+                                            value: "{weeks}",
+                                            oninput: move |e| {
+                                                if let Ok(v) = e.value().parse::<f32>() {
+                                                    estimates.write().set(role.clone(), v.max(0.0));
+                                                }
+                                            },
+                                        }
                                     }
-                                },
-                            }
-                        }
-
-                        div { class: "form-field",
-                            label { class: "form-label", "Sci Estimate *" }
-                            input {
-                                r#type: "number",
-                                class: "input",
-                                step: "0.5",
-                                min: "0",
-                                placeholder: "0.0",
-                                value: "{sci_estimate()}",
-                                oninput: move |e| {
-                                    if let Ok(v) = e.value().parse::<f32>() {
-                                        sci_estimate.set(v.max(0.0));
-                                    }
-                                },
+                                }
                             }
                         }
 
@@ -274,6 +316,104 @@ pub fn TechnicalProjectModal(props: TechnicalProjectModalProps) -> Element {
                         }
                     }
 
+                    // Required skills (comma-separated tags)
+                    div { class: "form-field",
+                        label { class: "form-label", "Required Skills" }
+                        Input {
+                            value: required_skills,
+                            placeholder: "e.g., Kafka, ML modeling".to_string(),
+                        }
+                        {
+                            let known = preferences().skill_vocabulary().join(", ");
+                            rsx! {
+                                div { class: "form-hint",
+                                    if known.is_empty() {
+                                        "Comma-separated. Staffing is flagged when nobody assigned has one"
+                                    } else {
+                                        "Comma-separated. Team skills: {known}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Dependencies (predecessor projects)
+                    div { class: "form-field",
+                        label { class: "form-label", "Depends On" }
+                        div { class: "date-range-list",
+                            if dependencies().is_empty() {
+                                div { class: "date-range-empty", "No dependencies" }
+                            }
+                            for (index, dependency) in dependencies().into_iter().enumerate() {
+                                {
+                                    let predecessor_name = props
+                                        .technical_projects
+                                        .iter()
+                                        .find(|p| p.id == dependency.predecessor_id)
+                                        .map(|p| p.name.clone())
+                                        .unwrap_or_else(|| "Unknown project".to_string());
+
+                                    rsx! {
+                                        div { class: "date-range-item",
+                                            span { class: "date-range-label", "{predecessor_name}" }
+                                            span { class: "date-range-meta", "{dependency.kind.label()}" }
+                                            button {
+                                                class: "icon-button danger",
+                                                title: "Remove dependency",
+                                                onclick: move |_| {
+                                                    dependencies.write().remove(index);
+                                                },
+                                                "✕"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "date-range-add",
+                            select {
+                                class: "select",
+                                value: new_dependency_id().map(|id| id.to_string()).unwrap_or_default(),
+                                onchange: move |e| {
+                                    new_dependency_id.set(Uuid::parse_str(&e.value()).ok());
+                                },
+                                option { value: "", "Select a project..." }
+                                for project in props.technical_projects.iter().filter(|p| {
+                                    !matches!(props.mode, TechnicalModalMode::Edit(id) if id == p.id)
+                                        && !dependencies().iter().any(|d| d.predecessor_id == p.id)
+                                }) {
+                                    option { value: "{project.id}", "{project.name}" }
+                                }
+                            }
+                            select {
+                                class: "select",
+                                onchange: move |e| {
+                                    new_dependency_kind.set(if e.value() == "start" {
+                                        DependencyKind::StartToStart
+                                    } else {
+                                        DependencyKind::FinishToStart
+                                    });
+                                },
+                                option {
+                                    value: "finish",
+                                    selected: new_dependency_kind() == DependencyKind::FinishToStart,
+                                    "{DependencyKind::FinishToStart.label()}"
+                                }
+                                option {
+                                    value: "start",
+                                    selected: new_dependency_kind() == DependencyKind::StartToStart,
+                                    "{DependencyKind::StartToStart.label()}"
+                                }
+                            }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                onclick: handle_add_dependency,
+                                "Add"
+                            }
+                        }
+                        div { class: "form-error", "{dependency_error()}" }
+                    }
+
                     // Notes
                     div { class: "form-field",
                         label { class: "form-label", "Notes (optional)" }
@@ -1,12 +1,11 @@
 use dioxus::prelude::*;
-use std::collections::HashMap;
 
 use crate::components::ui::{
     AssignProjectModal, ConfirmationDialog, ContextMenu, FloatingFab, FloatingProjectPanel,
     GridCell, KeybindingsOverlay, SplitAllocationModal, TeamMemberModal, TeamMemberModalMode,
 };
-use crate::state::{use_plan_state, use_preferences};
-use planner_core::models::{Allocation, Assignment, TeamMember};
+use crate::state::{use_app_context, use_plan_index, use_plan_state, use_preferences};
+use planner_core::models::{Allocation, Assignment, PlanView, TeamMember};
 use planner_core::utils::generate_quarter_weeks;
 
 use super::grid_helpers::{calculate_cell_class, calculate_cell_variant};
@@ -17,11 +16,16 @@ use super::paintbrush::{allocate_project_to_cell, SelectedProject};
 #[component]
 pub fn AllocationView() -> Element {
     // Two-signal architecture (M9)
-    let mut plan_state = use_plan_state();
-    let mut preferences = use_preferences();
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+    let ctx = use_app_context();
+
+    let plan_index = use_plan_index();
 
     let plan_data = plan_state();
     let prefs_data = preferences();
+    // Indexed lookups for grid cells and member totals
+    let index = plan_index.read();
 
     // Capture sprint config for use in update_technical_project_dates calls
     let sprint_anchor = prefs_data.sprint_anchor_date;
@@ -94,6 +98,16 @@ pub fn AllocationView() -> Element {
     // Check if we have team members (for empty state)
     let has_team_members = !prefs_data.team_members.is_empty();
 
+    // Cells outside a member's employment dates can't be allocated
+    let is_allocatable = move |team_member_id: uuid::Uuid, week_start: chrono::NaiveDate| {
+        preferences
+            .read()
+            .team_members
+            .iter()
+            .find(|m| m.id == team_member_id)
+            .is_some_and(|m| m.is_active_in_week(week_start))
+    };
+
     // Event handlers
     let handle_keydown = move |evt: KeyboardEvent| {
         // Escape - exit paintbrush mode, close panel, or close modals
@@ -117,7 +131,7 @@ pub fn AllocationView() -> Element {
         if matches!(evt.key(), Key::Delete | Key::Backspace) {
             if let Some((team_member_id, week_start)) = focused_cell() {
                 // Clear allocation
-                plan_state.with_mut(|p| {
+                ctx.edit("Clear cell", |_, p| {
                     // Find which project(s) were allocated to update their dates
                     let affected_projects: Vec<uuid::Uuid> = p
                         .allocations
@@ -146,15 +160,7 @@ pub fn AllocationView() -> Element {
             && (evt.modifiers().meta() || evt.modifiers().ctrl())
         {
             if let Some((team_member_id, week_start)) = focused_cell() {
-                let plan_data = plan_state();
-                let allocation_map: HashMap<(uuid::Uuid, chrono::NaiveDate), &Allocation> =
-                    plan_data
-                        .allocations
-                        .iter()
-                        .map(|a| ((a.team_member_id, a.week_start_date), a))
-                        .collect();
-
-                if let Some(alloc) = allocation_map.get(&(team_member_id, week_start)) {
+                if let Some(alloc) = plan_index.read().allocation(&team_member_id, week_start) {
                     clipboard.set(Some(alloc.assignments.clone()));
                 }
             }
@@ -164,9 +170,11 @@ pub fn AllocationView() -> Element {
         if evt.key() == Key::Character("v".to_string())
             && (evt.modifiers().meta() || evt.modifiers().ctrl())
         {
-            if let Some((team_member_id, week_start)) = focused_cell() {
+            if let Some((team_member_id, week_start)) =
+                focused_cell().filter(|&(id, week)| is_allocatable(id, week))
+            {
                 if let Some(assignments) = clipboard() {
-                    plan_state.with_mut(|p| {
+                    ctx.edit("Paste cell", |_, p| {
                         // Find which project(s) were previously allocated
                         let previous_projects: Vec<uuid::Uuid> = p
                             .allocations
@@ -227,6 +235,14 @@ pub fn AllocationView() -> Element {
         use crate::components::ui::MenuAction;
 
         if let Some((team_member_id, week_start)) = context_menu_cell() {
+            // Only clearing is allowed outside a member's employment dates
+            if !matches!(action, MenuAction::ClearAssignment)
+                && !is_allocatable(team_member_id, week_start)
+            {
+                error_cell.set(Some((team_member_id, week_start)));
+                return;
+            }
+
             match action {
                 MenuAction::AssignProject => {
                     // Open floating panel in assign mode
@@ -234,17 +250,8 @@ pub fn AllocationView() -> Element {
                     panel_visible.set(true);
                     panel_search_query.set(String::new());
                     // Pre-fill selected project if cell has allocation
-                    let plan_data = plan_state();
-                    let allocation_map: std::collections::HashMap<
-                        (uuid::Uuid, chrono::NaiveDate),
-                        &planner_core::models::Allocation,
-                    > = plan_data
-                        .allocations
-                        .iter()
-                        .map(|a| ((a.team_member_id, a.week_start_date), a))
-                        .collect();
-
-                    if let Some(alloc) = allocation_map.get(&(team_member_id, week_start)) {
+                    let index = plan_index.read();
+                    if let Some(alloc) = index.allocation(&team_member_id, week_start) {
                         if let Some(assignment) = alloc.assignments.first() {
                             selected_project
                                 .set(SelectedProject::Technical(assignment.technical_project_id));
@@ -259,17 +266,8 @@ pub fn AllocationView() -> Element {
                     // Open split modal
                     split_modal_visible.set(true);
                     // Pre-fill with current allocation if exists
-                    let plan_data = plan_state();
-                    let allocation_map: std::collections::HashMap<
-                        (uuid::Uuid, chrono::NaiveDate),
-                        &planner_core::models::Allocation,
-                    > = plan_data
-                        .allocations
-                        .iter()
-                        .map(|a| ((a.team_member_id, a.week_start_date), a))
-                        .collect();
-
-                    if let Some(alloc) = allocation_map.get(&(team_member_id, week_start)) {
+                    let index = plan_index.read();
+                    if let Some(alloc) = index.allocation(&team_member_id, week_start) {
                         if alloc.assignments.len() == 2 {
                             split_project1_id.set(Some(alloc.assignments[0].technical_project_id));
                             split_project2_id.set(Some(alloc.assignments[1].technical_project_id));
@@ -287,7 +285,7 @@ pub fn AllocationView() -> Element {
                 }
                 MenuAction::ClearAssignment => {
                     // Clear allocation
-                    plan_state.with_mut(|p| {
+                    ctx.edit("Clear cell", |_, p| {
                         // Find which project(s) were allocated to update their dates
                         let affected_projects: Vec<uuid::Uuid> = p
                             .allocations
@@ -322,7 +320,7 @@ pub fn AllocationView() -> Element {
     let handle_assign_apply = move |_| {
         if let Some((team_member_id, week_start)) = context_menu_cell() {
             if let Some(proj_id) = assign_project_id() {
-                plan_state.with_mut(|p| {
+                ctx.edit("Assign project", |_, p| {
                     // Find which project(s) were previously allocated
                     let previous_projects: Vec<uuid::Uuid> = p
                         .allocations
@@ -377,7 +375,7 @@ pub fn AllocationView() -> Element {
         if let Some((team_member_id, week_start)) = context_menu_cell() {
             if let (Some(proj1_id), Some(proj2_id)) = (split_project1_id(), split_project2_id()) {
                 if proj1_id != proj2_id {
-                    plan_state.with_mut(|p| {
+                    ctx.edit("Split allocation", |_, p| {
                         // Find which project(s) were previously allocated
                         let previous_projects: Vec<uuid::Uuid> = p
                             .allocations
@@ -465,7 +463,7 @@ pub fn AllocationView() -> Element {
         if assign_mode() {
             // Direct assign mode - assign to cell and close panel
             if let Some((team_member_id, week_start)) = context_menu_cell() {
-                plan_state.with_mut(|p| {
+                ctx.edit("Assign project", |_, p| {
                     // Find which project(s) were previously allocated
                     let previous_projects: Vec<uuid::Uuid> = p
                         .allocations
@@ -519,7 +517,7 @@ pub fn AllocationView() -> Element {
         if assign_mode() {
             // Direct assign mode - clear cell and close panel
             if let Some((team_member_id, week_start)) = context_menu_cell() {
-                plan_state.with_mut(|p| {
+                ctx.edit("Clear cell", |_, p| {
                     // Remove existing allocation
                     p.allocations.retain(|a| {
                         !(a.team_member_id == team_member_id && a.week_start_date == week_start)
@@ -538,6 +536,7 @@ pub fn AllocationView() -> Element {
         if is_dragging() {
             is_dragging.set(false);
             drag_cells.set(Vec::new());
+            ctx.end_batch();
         }
     };
 
@@ -547,14 +546,25 @@ pub fn AllocationView() -> Element {
             return;
         }
 
-        let success = allocate_project_to_cell(
-            &mut plan_state,
-            &selected_project(),
-            team_member_id,
-            week_start,
-            sprint_anchor,
-            sprint_length,
-        );
+        // Painting a project outside the member's employment dates is an error
+        if matches!(selected_project(), SelectedProject::Technical(_))
+            && !is_allocatable(team_member_id, week_start)
+        {
+            error_cell.set(Some((team_member_id, week_start)));
+            return;
+        }
+
+        let mut success = false;
+        ctx.edit("Paint cells", |_, p| {
+            success = allocate_project_to_cell(
+                p,
+                &selected_project(),
+                team_member_id,
+                week_start,
+                sprint_anchor,
+                sprint_length,
+            );
+        });
 
         if success {
             error_cell.set(None);
@@ -579,6 +589,8 @@ pub fn AllocationView() -> Element {
             if paintbrush_active() {
                 is_dragging.set(true);
                 drag_cells.set(vec![(team_member_id, week_start)]);
+                // Group the whole drag into one undo step
+                ctx.begin_batch("Paint cells");
                 allocate_to_cell(team_member_id, week_start);
             }
         };
@@ -599,15 +611,11 @@ pub fn AllocationView() -> Element {
             }
         };
 
-    // Pre-compute allocation map for O(1) lookups
-    let allocation_map: HashMap<
-        (uuid::Uuid, chrono::NaiveDate),
-        &planner_core::models::Allocation,
-    > = plan_data
-        .allocations
-        .iter()
-        .map(|a| ((a.team_member_id, a.week_start_date), a))
-        .collect();
+    // Project being painted (used to highlight members with matching skills)
+    let brush_project = match selected_project() {
+        SelectedProject::Technical(id) => plan_data.get_technical_project(&id).cloned(),
+        SelectedProject::None => None,
+    };
 
     // Get selected project color for FAB
     let fab_project_color = match selected_project() {
@@ -639,6 +647,7 @@ pub fn AllocationView() -> Element {
                 if is_dragging() {
                     is_dragging.set(false);
                     drag_cells.set(Vec::new());
+                    ctx.end_batch();
                 }
             },
 
@@ -685,9 +694,14 @@ pub fn AllocationView() -> Element {
                         {
                             let engineer_id = engineer.id;
                             let engineer_name = engineer.name.clone();
-                            let engineer_role = engineer.role;
-                            let allocated = plan_data.calculate_team_member_allocated_weeks(&engineer_id);
-                            let capacity = engineer.capacity;
+                            let engineer_role = prefs_data.role_short_name(&engineer.role).to_string();
+                            let allocated = index.team_member_allocated_weeks(&engineer_id);
+                            // Capacity net of holidays and time off, rounded for display
+                            let capacity = (PlanView::new(&prefs_data, &plan_data)
+                                .member_capacity(engineer)
+                                * 10.0)
+                                .round()
+                                / 10.0;
                             let diff = (allocated - capacity).abs();
                             let capacity_status = if diff <= 0.5 {
                                 "success"
@@ -701,15 +715,25 @@ pub fn AllocationView() -> Element {
                             } else {
                                 0.0
                             };
-                            let header_class = if capacity_status == "error" {
-                                "grid-engineer-header over-allocated"
-                            } else {
-                                "grid-engineer-header"
+                            let weeks_outside_tenure = plan_data
+                                .allocations_outside_tenure(std::slice::from_ref(engineer))
+                                .len();
+                            // Highlight members with the skills the brushed project needs
+                            let skill_match = brush_project
+                                .as_ref()
+                                .is_some_and(|project| project.is_skill_match(engineer));
+                            let header_class = match (capacity_status == "error", skill_match) {
+                                (true, true) => "grid-engineer-header over-allocated skill-match",
+                                (true, false) => "grid-engineer-header over-allocated",
+                                (false, true) => "grid-engineer-header skill-match",
+                                (false, false) => "grid-engineer-header",
                             };
+                            let skills_title = engineer.skills.join(", ");
 
                             rsx! {
                                 div {
                                     class: "{header_class}",
+                                    title: "{skills_title}",
                                     onclick: move |e| {
                                         // Only trigger edit if not clicking on action buttons
                                         e.stop_propagation();
@@ -741,7 +765,14 @@ pub fn AllocationView() -> Element {
                                     }
                                     div { class: "engineer-name-row",
                                         span { class: "engineer-name", "{engineer_name}" }
-                                        span { class: "role-badge", "{engineer_role.short_name()}" }
+                                        span { class: "role-badge", "{engineer_role}" }
+                                        if weeks_outside_tenure > 0 {
+                                            span {
+                                                class: "tenure-warning",
+                                                title: "{weeks_outside_tenure} week(s) allocated outside employment dates",
+                                                "⚠"
+                                            }
+                                        }
                                     }
                                     div { class: "capacity-row",
                                         span {
@@ -789,14 +820,12 @@ pub fn AllocationView() -> Element {
                                         let engineer_id = engineer.id;
 
                                         // Look up allocation (O(1))
-                                        let allocation = allocation_map
-                                            .get(&(engineer_id, week_start_date))
-                                            .copied();
+                                        let allocation = index.allocation(&engineer_id, week_start_date);
 
                                         // Calculate cell variant using helper
                                         let variant = calculate_cell_variant(
                                             allocation,
-                                            &plan_data,
+                                            &index,
                                             week_start_date
                                         );
 
@@ -811,15 +840,41 @@ pub fn AllocationView() -> Element {
                                             is_success,
                                             is_drag_target
                                         );
-                                        let cell_class_with_separator = if is_sprint_start {
+                                        let mut cell_class_with_separator = if is_sprint_start {
                                             format!("{} sprint-separator", cell_class)
                                         } else {
                                             cell_class.to_string()
                                         };
 
+                                        // Shade weeks reduced by holidays or time off,
+                                        // and block weeks outside employment dates
+                                        let is_active = engineer.is_active_in_week(week_start_date);
+                                        let availability = engineer
+                                            .week_availability(&prefs_data.holidays, week_start_date);
+                                        let availability_title = if !is_active {
+                                            cell_class_with_separator.push_str(" outside-tenure");
+                                            if allocation.is_some() {
+                                                "Allocated outside employment dates".to_string()
+                                            } else {
+                                                "Not on the team this week".to_string()
+                                            }
+                                        } else if availability <= 0.0 {
+                                            cell_class_with_separator.push_str(" unavailable");
+                                            "Unavailable (holiday / time off)".to_string()
+                                        } else if availability < 1.0 {
+                                            cell_class_with_separator.push_str(" partially-unavailable");
+                                            format!(
+                                                "{:.0}% available (holiday / time off)",
+                                                availability * 100.0
+                                            )
+                                        } else {
+                                            String::new()
+                                        };
+
                                         rsx! {
                                             div {
                                                 class: "{cell_class_with_separator}",
+                                                title: "{availability_title}",
                                                 onmousedown: move |_| {
                                                     handle_cell_mousedown(engineer_id, week_start_date)
                                                 },
@@ -857,14 +912,14 @@ pub fn AllocationView() -> Element {
                 visible: context_menu_visible(),
                 has_allocation: {
                     if let Some((team_member_id, week_start)) = context_menu_cell() {
-                        allocation_map.contains_key(&(team_member_id, week_start))
+                        index.allocation(&team_member_id, week_start).is_some()
                     } else {
                         false
                     }
                 },
                 is_split: {
                     if let Some((team_member_id, week_start)) = context_menu_cell() {
-                        allocation_map.get(&(team_member_id, week_start))
+                        index.allocation(&team_member_id, week_start)
                             .map(|alloc| alloc.assignments.len() == 2)
                             .unwrap_or(false)
                     } else {
@@ -878,6 +933,7 @@ pub fn AllocationView() -> Element {
             // Assign Project Modal
             AssignProjectModal {
                 visible: assign_modal_visible(),
+                team_member_id: context_menu_cell().map(|(member_id, _)| member_id),
                 selected_project_id: assign_project_id(),
                 on_project_select: move |id| assign_project_id.set(Some(id)),
                 on_apply: handle_assign_apply,
@@ -938,9 +994,14 @@ pub fn AllocationView() -> Element {
                 {
                     let member_id = member.id;
                     let member_name = member.name.clone();
-                    let member_role = member.role;
+                    let member_role = member.role.clone();
                     let member_capacity = member.capacity;
-                    let member_allocated = plan_data.calculate_team_member_allocated_weeks(&member_id);
+                    let member_time_off = member.time_off.clone();
+                    let member_email = member.email.clone();
+                    let member_start_date = member.start_date;
+                    let member_end_date = member.end_date;
+                    let member_skills = member.skills.clone();
+                    let member_allocated = index.team_member_allocated_weeks(&member_id);
 
                     rsx! {
                         TeamMemberModal {
@@ -948,14 +1009,24 @@ pub fn AllocationView() -> Element {
                             initial_name: member_name,
                             initial_role: member_role,
                             initial_capacity: member_capacity,
+                            initial_time_off: member_time_off,
+                            initial_email: member_email,
+                            initial_start_date: member_start_date,
+                            initial_end_date: member_end_date,
+                            initial_skills: member_skills,
                             default_capacity: prefs_data.default_capacity,
                             allocated_weeks: member_allocated,
                             on_save: move |updated_member: TeamMember| {
-                                preferences.with_mut(|p| {
+                                ctx.edit("Edit team member", |p, _| {
                                     if let Some(existing) = p.team_members.iter_mut().find(|m| m.id == member_id) {
                                         existing.name = updated_member.name;
                                         existing.role = updated_member.role;
                                         existing.capacity = updated_member.capacity;
+                                        existing.time_off = updated_member.time_off;
+                                        existing.email = updated_member.email;
+                                        existing.start_date = updated_member.start_date;
+                                        existing.end_date = updated_member.end_date;
+                                        existing.skills = updated_member.skills;
                                     }
                                 });
                                 show_team_member_modal.set(false);
@@ -975,12 +1046,16 @@ pub fn AllocationView() -> Element {
                 TeamMemberModal {
                     mode: TeamMemberModalMode::Add,
                     initial_name: String::new(),
-                    initial_role: planner_core::models::Role::Engineering,
+                    initial_role: prefs_data
+                        .roles
+                        .first()
+                        .map(|r| r.id.clone())
+                        .unwrap_or(planner_core::models::Role::ENGINEERING),
                     initial_capacity: 0.0,
                     default_capacity: prefs_data.default_capacity,
                     allocated_weeks: 0.0,
                     on_save: move |new_member: TeamMember| {
-                        preferences.with_mut(|p| {
+                        ctx.edit("Add team member", |p, _| {
                             p.team_members.push(new_member);
                         });
                         show_team_member_modal.set(false);
@@ -999,12 +1074,11 @@ pub fn AllocationView() -> Element {
                 {
                     let member_id = member.id;
                     let member_name = member.name.clone();
-                    let member_allocated = plan_data.calculate_team_member_allocated_weeks(&member_id);
+                    let member_allocated = index.team_member_allocated_weeks(&member_id);
 
                     // Build warning text with affected projects
                     let warning_text = if member_allocated > 0.0 {
-                        let project_names: Vec<_> = plan_data
-                            .get_assigned_project_names_for_member(&member_id);
+                        let project_names = index.assigned_project_names_for_member(&member_id);
                         format!(
                             "This will remove {:.1} weeks of allocations across {} project(s): {}",
                             member_allocated,
@@ -1023,13 +1097,11 @@ pub fn AllocationView() -> Element {
                             warning: warning_text,
                             confirm_label: "Delete".to_string(),
                             on_confirm: move |_| {
-                                // Cascade delete: remove all allocations for this member
-                                plan_state.with_mut(|p| {
-                                    p.allocations.retain(|a| a.team_member_id != member_id);
-                                });
-                                // Remove team member from preferences
-                                preferences.with_mut(|p| {
-                                    p.team_members.retain(|m| m.id != member_id);
+                                ctx.edit("Delete team member", |prefs, p| {
+                                    // Cascade delete: remove the member's allocations in every scenario
+                                    p.remove_member_everywhere(&member_id);
+                                    // Remove team member from preferences
+                                    prefs.team_members.retain(|m| m.id != member_id);
                                 });
                                 show_delete_confirmation.set(false);
                                 deleting_member_id.set(None);
@@ -2,12 +2,12 @@
 use chrono::NaiveDate;
 
 use crate::components::ui::GridCellVariant;
-use planner_core::models::{Allocation, PlanState, ProjectColor};
+use planner_core::models::{Allocation, PlanIndex, ProjectColor};
 
 /// Calculate the appropriate GridCellVariant for a given allocation
 pub fn calculate_cell_variant(
     allocation: Option<&Allocation>,
-    plan_state: &PlanState,
+    index: &PlanIndex,
     week_start_date: NaiveDate,
 ) -> GridCellVariant {
     let Some(alloc) = allocation else {
@@ -16,33 +16,33 @@ pub fn calculate_cell_variant(
 
     // Split allocation (2 projects)
     if alloc.assignments.len() == 2 {
-        return create_split_variant(alloc, plan_state);
+        return create_split_variant(alloc, index);
     }
 
     // Single project allocation
     if let Some(assignment) = alloc.assignments.first() {
-        return create_single_week_variant(assignment, plan_state, week_start_date);
+        return create_single_week_variant(assignment, index, week_start_date);
     }
 
     GridCellVariant::Empty
 }
 
 /// Create a split allocation cell variant
-fn create_split_variant(alloc: &Allocation, plan_state: &PlanState) -> GridCellVariant {
+fn create_split_variant(alloc: &Allocation, index: &PlanIndex) -> GridCellVariant {
     let assignment1 = &alloc.assignments[0];
     let assignment2 = &alloc.assignments[1];
 
-    let project1 = plan_state.get_technical_project(&assignment1.technical_project_id);
-    let project2 = plan_state.get_technical_project(&assignment2.technical_project_id);
+    let project1 = index.technical_project(&assignment1.technical_project_id);
+    let project2 = index.technical_project(&assignment2.technical_project_id);
 
     match (project1, project2) {
         (Some(p1), Some(p2)) => {
             let rp1 = p1
                 .roadmap_project_id
-                .and_then(|id| plan_state.get_roadmap_project(&id));
+                .and_then(|id| index.roadmap_project(&id));
             let rp2 = p2
                 .roadmap_project_id
-                .and_then(|id| plan_state.get_roadmap_project(&id));
+                .and_then(|id| index.roadmap_project(&id));
 
             GridCellVariant::Split {
                 project1_name: p1.name.clone(),
@@ -60,16 +60,16 @@ fn create_split_variant(alloc: &Allocation, plan_state: &PlanState) -> GridCellV
 /// Create a single week allocation cell variant
 fn create_single_week_variant(
     assignment: &planner_core::models::Assignment,
-    plan_state: &PlanState,
+    index: &PlanIndex,
     week_start_date: NaiveDate,
 ) -> GridCellVariant {
-    let Some(proj) = plan_state.get_technical_project(&assignment.technical_project_id) else {
+    let Some(proj) = index.technical_project(&assignment.technical_project_id) else {
         return GridCellVariant::Empty;
     };
 
     let roadmap_project = proj
         .roadmap_project_id
-        .and_then(|id| plan_state.get_roadmap_project(&id));
+        .and_then(|id| index.roadmap_project(&id));
     let project_color = roadmap_project
         .map(|rp| rp.color)
         .unwrap_or(ProjectColor::Blue);
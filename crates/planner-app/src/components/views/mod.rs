@@ -5,7 +5,9 @@ mod grid_helpers;
 mod paintbrush;
 mod roadmap_view;
 mod technical_view;
+mod timeline_view;
 
 pub use allocation_view::AllocationView;
 pub use roadmap_view::RoadmapView;
 pub use technical_view::TechnicalView;
+pub use timeline_view::TimelineView;
@@ -4,18 +4,21 @@ use crate::components::ui::{
     Badge, Button, ButtonVariant, CellStyle, ConfirmationDialog, DataTable, Input, ModalMode,
     ProjectName, RoadmapProjectModal, TableCell, TableHeader, TableHeaderCell, TableRow,
 };
-use crate::state::{use_plan_state, use_preferences};
-use planner_core::models::{get_capacity_status, ProjectColor, Role};
+use crate::state::{use_app_context, use_plan_index, use_plan_state, use_preferences};
+use planner_core::models::{get_capacity_status, PlanView, ProjectColor, RoleWeeks};
 
 /// Roadmap view - displays roadmap projects table and quarter summary
 /// Reference: docs/ui-design.md section 7.1
 #[component]
 pub fn RoadmapView() -> Element {
-    let mut plan_state = use_plan_state();
+    let plan_state = use_plan_state();
+    let ctx = use_app_context();
     let preferences = use_preferences();
+    let plan_index = use_plan_index();
 
     let plan_data = plan_state();
     let prefs_data = preferences();
+    let index = plan_index.read();
 
     // Search filter state
     let search_query = use_signal(String::new);
@@ -24,8 +27,7 @@ pub fn RoadmapView() -> Element {
     let mut modal_visible = use_signal(|| false);
     let mut modal_mode = use_signal(|| ModalMode::Add);
     let mut modal_initial_name = use_signal(String::new);
-    let mut modal_initial_eng_estimate = use_signal(|| 0.0);
-    let mut modal_initial_sci_estimate = use_signal(|| 0.0);
+    let mut modal_initial_estimates = use_signal(RoleWeeks::new);
     let mut modal_initial_start_date = use_signal(|| plan_data.quarter_start_date);
     let mut modal_initial_launch_date = use_signal(|| {
         plan_data.quarter_start_date + chrono::Duration::weeks(plan_data.num_weeks as i64)
@@ -48,37 +50,13 @@ pub fn RoadmapView() -> Element {
         })
         .collect();
 
-    // Calculate quarter summary stats
-    let (eng_capacity, sci_capacity, total_capacity) = {
-        let mut eng = 0.0;
-        let mut sci = 0.0;
-        for member in &prefs_data.team_members {
-            match member.role {
-                Role::Engineering => eng += member.capacity,
-                Role::Science => sci += member.capacity,
-            }
-        }
-        (eng, sci, eng + sci)
-    };
+    // Calculate quarter summary stats (capacity net of holidays and time off)
+    let view = PlanView::new(&prefs_data, &plan_data);
+    let capacity = view.total_capacity();
+    let total_capacity = capacity.total();
 
-    let (eng_allocated, sci_allocated, total_allocated) = {
-        let mut eng = 0.0;
-        let mut sci = 0.0;
-        for alloc in &plan_data.allocations {
-            if let Some(member) = prefs_data
-                .team_members
-                .iter()
-                .find(|m| m.id == alloc.team_member_id)
-            {
-                let weeks = alloc.total_percentage() / 100.0;
-                match member.role {
-                    Role::Engineering => eng += weeks,
-                    Role::Science => sci += weeks,
-                }
-            }
-        }
-        (eng, sci, eng + sci)
-    };
+    let allocated = view.total_allocated();
+    let total_allocated = allocated.total();
 
     let utilization = if total_capacity > 0.0 {
         (total_allocated / total_capacity * 100.0).round() as i32
@@ -86,17 +64,43 @@ pub fn RoadmapView() -> Element {
         0
     };
 
-    // Calculate team composition
-    let eng_count = prefs_data
-        .team_members
+    // Calculate team composition (e.g., "3 SDE × 11.5 + 1 AS × 6.0")
+    let composition = prefs_data
+        .roles
         .iter()
-        .filter(|e| e.role == Role::Engineering)
-        .count();
-    let sci_count = prefs_data
-        .team_members
+        .filter_map(|definition| {
+            let count = prefs_data
+                .team_members
+                .iter()
+                .filter(|m| m.role == definition.id)
+                .count();
+            (count > 0).then(|| {
+                format!(
+                    "{} {} × {:.1}",
+                    count,
+                    definition.short_name,
+                    capacity.get(&definition.id) / count as f32
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let composition = if composition.is_empty() {
+        "No team members".to_string()
+    } else {
+        composition.join(" + ")
+    };
+    let allocated_breakdown = prefs_data
+        .roles
         .iter()
-        .filter(|e| e.role == Role::Science)
-        .count();
+        .map(|definition| {
+            format!(
+                "{}: {}",
+                definition.short_name,
+                allocated.get(&definition.id)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" / ");
 
     rsx! {
         div { class: "view active",
@@ -112,8 +116,7 @@ pub fn RoadmapView() -> Element {
                         // Reset modal to Add mode with default values
                         modal_mode.set(ModalMode::Add);
                         modal_initial_name.set(String::new());
-                        modal_initial_eng_estimate.set(0.0);
-                        modal_initial_sci_estimate.set(0.0);
+                        modal_initial_estimates.set(RoleWeeks::new());
                         modal_initial_start_date.set(plan_data.quarter_start_date);
                         modal_initial_launch_date.set(plan_data.quarter_start_date + chrono::Duration::weeks(plan_data.num_weeks as i64));
                         modal_initial_color.set(ProjectColor::Blue);
@@ -137,8 +140,7 @@ pub fn RoadmapView() -> Element {
                         onclick: move |_| {
                             modal_mode.set(ModalMode::Add);
                             modal_initial_name.set(String::new());
-                            modal_initial_eng_estimate.set(0.0);
-                            modal_initial_sci_estimate.set(0.0);
+                            modal_initial_estimates.set(RoleWeeks::new());
                             modal_initial_start_date.set(plan_data.quarter_start_date);
                             modal_initial_launch_date.set(plan_data.quarter_start_date + chrono::Duration::weeks(plan_data.num_weeks as i64));
                             modal_initial_color.set(ProjectColor::Blue);
@@ -155,11 +157,13 @@ pub fn RoadmapView() -> Element {
                 DataTable {
                     TableHeader {
                         TableHeaderCell { "Project Name" }
-                        TableHeaderCell { "Eng Est." }
-                        TableHeaderCell { "Sci Est." }
+                        for definition in &prefs_data.roles {
+                            TableHeaderCell { "{definition.short_name} Est." }
+                        }
                         TableHeaderCell { "Total Est." }
-                        TableHeaderCell { "Eng Alloc." }
-                        TableHeaderCell { "Sci Alloc." }
+                        for definition in &prefs_data.roles {
+                            TableHeaderCell { "{definition.short_name} Alloc." }
+                        }
                         TableHeaderCell { "Total Alloc." }
                         TableHeaderCell { "Start Date" }
                         TableHeaderCell { "Launch Date" }
@@ -169,16 +173,20 @@ pub fn RoadmapView() -> Element {
                     // Table rows
                     for project in filtered_projects {
                     {
-                        let get_role = |member_id: &uuid::Uuid| {
-                            prefs_data.team_members.iter()
-                                .find(|m| &m.id == member_id)
-                                .map(|m| m.role)
-                        };
-                        let (eng_alloc, sci_alloc, total_alloc) = plan_data.calculate_roadmap_allocated_weeks(&project.id, get_role);
-
-                        let eng_status = get_capacity_status(eng_alloc, project.eng_estimate);
-                        let sci_status = get_capacity_status(sci_alloc, project.sci_estimate);
-                        let total_estimate = project.eng_estimate + project.sci_estimate;
+                        let project_allocated = index.roadmap_allocated_weeks(&project.id);
+                        let total_alloc = project_allocated.total();
+
+                        // Estimate, allocation, and status for each configured role
+                        let role_columns: Vec<_> = prefs_data
+                            .roles
+                            .iter()
+                            .map(|definition| {
+                                let estimate = project.estimates.get(&definition.id);
+                                let alloc = project_allocated.get(&definition.id);
+                                (estimate, alloc, get_capacity_status(alloc, estimate))
+                            })
+                            .collect();
+                        let total_estimate = project.total_estimate();
                         let total_status = get_capacity_status(total_alloc, total_estimate);
 
                         rsx! {
@@ -196,8 +204,7 @@ pub fn RoadmapView() -> Element {
                                         {
                                             let project_id = project.id;
                                             let project_name = project.name.clone();
-                                            let project_eng_estimate = project.eng_estimate;
-                                            let project_sci_estimate = project.sci_estimate;
+                                            let project_estimates = project.estimates.clone();
                                             let project_start_date = project.start_date;
                                             let project_launch_date = project.launch_date;
                                             let project_color = project.color;
@@ -210,6 +217,7 @@ pub fn RoadmapView() -> Element {
                                                     {
                                                         let edit_name = project_name.clone();
                                                         let edit_notes = project_notes.clone();
+                                                        let edit_estimates = project_estimates.clone();
                                                         rsx! {
                                                             button {
                                                                 class: "icon-button",
@@ -217,8 +225,7 @@ pub fn RoadmapView() -> Element {
                                                                 onclick: move |_| {
                                                                     modal_mode.set(ModalMode::Edit(project_id));
                                                                     modal_initial_name.set(edit_name.clone());
-                                                                    modal_initial_eng_estimate.set(project_eng_estimate);
-                                                                    modal_initial_sci_estimate.set(project_sci_estimate);
+                                                                    modal_initial_estimates.set(edit_estimates.clone());
                                                                     modal_initial_start_date.set(project_start_date);
                                                                     modal_initial_launch_date.set(project_launch_date);
                                                                     modal_initial_color.set(project_color);
@@ -251,16 +258,12 @@ pub fn RoadmapView() -> Element {
                                     }
                                 }
 
-                                // Engineering estimate
-                                TableCell {
-                                    style: CellStyle::MonospaceSecondary,
-                                    "{project.eng_estimate}"
-                                }
-
-                                // Science estimate
-                                TableCell {
-                                    style: CellStyle::MonospaceSecondary,
-                                    "{project.sci_estimate}"
+                                // Per-role estimates
+                                for (estimate, _, _) in &role_columns {
+                                    TableCell {
+                                        style: CellStyle::MonospaceSecondary,
+                                        "{estimate}"
+                                    }
                                 }
 
                                 // Total estimate
@@ -269,21 +272,14 @@ pub fn RoadmapView() -> Element {
                                     "{total_estimate}"
                                 }
 
-                                // Engineering allocation
-                                TableCell {
-                                    style: CellStyle::Default,
-                                    Badge {
-                                        badge_type: eng_status,
-                                        "{eng_alloc}"
-                                    }
-                                }
-
-                                // Science allocation
-                                TableCell {
-                                    style: CellStyle::Default,
-                                    Badge {
-                                        badge_type: sci_status,
-                                        "{sci_alloc}"
+                                // Per-role allocations
+                                for (_, alloc, status) in &role_columns {
+                                    TableCell {
+                                        style: CellStyle::Default,
+                                        Badge {
+                                            badge_type: *status,
+                                            "{alloc}"
+                                        }
                                     }
                                 }
 
@@ -331,29 +327,15 @@ pub fn RoadmapView() -> Element {
                     // Total capacity
                     div { class: "metric",
                         div { class: "metric-label", "Total Capacity" }
-                        div { class: "metric-value", "{total_capacity} weeks" }
-                        div { class: "metric-label",
-                            {
-                                let eng_part = if eng_count > 0 {
-                                    format!("{} Engineers × {:.1}", eng_count, eng_capacity / eng_count as f32)
-                                } else {
-                                    "No engineers".to_string()
-                                };
-                                let sci_part = if sci_count > 0 {
-                                    format!("{} Scientists × {:.1}", sci_count, sci_capacity / sci_count as f32)
-                                } else {
-                                    "No scientists".to_string()
-                                };
-                                format!("{} + {}", eng_part, sci_part)
-                            }
-                        }
+                        div { class: "metric-value", "{total_capacity:.1} weeks" }
+                        div { class: "metric-label", "{composition}" }
                     }
 
                     // Total allocated
                     div { class: "metric",
                         div { class: "metric-label", "Total Allocated" }
                         div { class: "metric-value", "{total_allocated} weeks" }
-                        div { class: "metric-label", "Eng: {eng_allocated} / Sci: {sci_allocated}" }
+                        div { class: "metric-label", "{allocated_breakdown}" }
                     }
 
                     // Utilization
@@ -380,8 +362,7 @@ pub fn RoadmapView() -> Element {
                 RoadmapProjectModal {
                     mode: modal_mode(),
                     initial_name: modal_initial_name(),
-                    initial_eng_estimate: modal_initial_eng_estimate(),
-                    initial_sci_estimate: modal_initial_sci_estimate(),
+                    initial_estimates: modal_initial_estimates(),
                     initial_start_date: modal_initial_start_date(),
                     initial_launch_date: modal_initial_launch_date(),
                     initial_color: modal_initial_color(),
@@ -390,17 +371,18 @@ pub fn RoadmapView() -> Element {
                         // Add or update project in plan_state
                         match modal_mode() {
                             ModalMode::Add => {
-                                plan_state.write().roadmap_projects.push(project);
+                                ctx.edit("Add roadmap project", |_, p| {
+                                    p.roadmap_projects.push(project);
+                                });
                             }
                             ModalMode::Edit(id) => {
-                                if let Some(existing) = plan_state
-                                    .write()
-                                    .roadmap_projects
-                                    .iter_mut()
-                                    .find(|p| p.id == id)
-                                {
-                                    *existing = project;
-                                }
+                                ctx.edit("Edit roadmap project", |_, p| {
+                                    if let Some(existing) =
+                                        p.roadmap_projects.iter_mut().find(|p| p.id == id)
+                                    {
+                                        *existing = project;
+                                    }
+                                });
                             }
                         }
                         modal_visible.set(false);
@@ -444,7 +426,7 @@ pub fn RoadmapView() -> Element {
                         cancel_label: "Cancel".to_string(),
                         on_confirm: move |_| {
                             if let Some(id) = delete_project_id() {
-                                plan_state.with_mut(|p| {
+                                ctx.edit("Delete roadmap project", |_, p| {
                                     // Remove the roadmap project
                                     p.roadmap_projects.retain(|proj| proj.id != id);
                                     // Unlink all technical projects
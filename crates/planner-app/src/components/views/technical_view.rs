@@ -6,8 +6,10 @@ use crate::components::ui::{
     Badge, Button, ButtonVariant, CellStyle, ConfirmationDialog, DataTable, Input, ProjectName,
     TableCell, TableHeader, TableHeaderCell, TableRow, TechnicalModalMode, TechnicalProjectModal,
 };
-use crate::state::{use_plan_state, use_preferences};
-use planner_core::models::{get_capacity_status, ProjectColor};
+use crate::state::{use_app_context, use_plan_index, use_plan_state, use_preferences};
+use planner_core::models::{
+    get_capacity_status, DependencyKind, ProjectColor, ProjectDependency, RoleWeeks,
+};
 
 /// Filter options for technical projects
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -30,10 +32,13 @@ enum SortOption {
 /// Reference: docs/ui-design.md section 7.2
 #[component]
 pub fn TechnicalView() -> Element {
-    let mut plan = use_plan_state();
+    let plan = use_plan_state();
+    let ctx = use_app_context();
     let preferences = use_preferences();
+    let plan_index = use_plan_index();
     let plan_data = plan();
     let prefs_data = preferences();
+    let index = plan_index.read();
 
     // Local state for filters and sorting
     let search_query = use_signal(String::new);
@@ -49,11 +54,12 @@ pub fn TechnicalView() -> Element {
     let mut modal_mode = use_signal(|| TechnicalModalMode::Add);
     let mut modal_initial_name = use_signal(String::new);
     let mut modal_initial_roadmap_id = use_signal(|| None::<Uuid>);
-    let mut modal_initial_eng_estimate = use_signal(|| 0.0_f32);
-    let mut modal_initial_sci_estimate = use_signal(|| 0.0_f32);
+    let mut modal_initial_estimates = use_signal(RoleWeeks::new);
     let mut modal_initial_start_date = use_signal(|| plan_data.quarter_start_date);
     let mut modal_initial_completion = use_signal(|| None::<chrono::NaiveDate>);
     let mut modal_initial_notes = use_signal(String::new);
+    let mut modal_initial_required_skills = use_signal(Vec::<String>::new);
+    let mut modal_initial_dependencies = use_signal(Vec::<ProjectDependency>::new);
 
     // Delete confirmation dialog state
     let mut delete_dialog_visible = use_signal(|| false);
@@ -61,6 +67,9 @@ pub fn TechnicalView() -> Element {
     let mut delete_project_name = use_signal(String::new);
     let mut delete_allocated_weeks = use_signal(|| 0.0_f32);
 
+    // Dependency ordering problems (flagged per project row)
+    let dependency_violations = plan_data.dependency_violations();
+
     // Filter and sort technical projects
     let mut filtered_projects: Vec<_> = plan_data
         .technical_projects
@@ -81,7 +90,7 @@ pub fn TechnicalView() -> Element {
                 return true;
             }
 
-            let allocated = plan_data.calculate_project_allocated_weeks(&project.id);
+            let allocated = index.project_allocated_weeks(&project.id);
             let estimated = project.total_estimate();
 
             // Check allocation ratio
@@ -119,7 +128,7 @@ pub fn TechnicalView() -> Element {
         SortOption::Status => {
             // Sort by allocation status: needs attention first (under/over allocated)
             filtered_projects.sort_by_key(|p| {
-                let allocated = plan_data.calculate_project_allocated_weeks(&p.id);
+                let allocated = index.project_allocated_weeks(&p.id);
                 let estimated = p.total_estimate();
                 if estimated == 0.0 {
                     if allocated == 0.0 {
@@ -139,8 +148,8 @@ pub fn TechnicalView() -> Element {
         }
         SortOption::Allocation => {
             filtered_projects.sort_by(|a, b| {
-                let a_allocated = plan_data.calculate_project_allocated_weeks(&a.id);
-                let b_allocated = plan_data.calculate_project_allocated_weeks(&b.id);
+                let a_allocated = index.project_allocated_weeks(&a.id);
+                let b_allocated = index.project_allocated_weeks(&b.id);
                 b_allocated.partial_cmp(&a_allocated).unwrap()
             });
         }
@@ -285,8 +294,9 @@ pub fn TechnicalView() -> Element {
                             modal_mode.set(TechnicalModalMode::Add);
                             modal_initial_name.set(String::new());
                             modal_initial_roadmap_id.set(None);
-                            modal_initial_eng_estimate.set(0.0);
-                            modal_initial_sci_estimate.set(0.0);
+                            modal_initial_estimates.set(RoleWeeks::new());
+                            modal_initial_required_skills.set(Vec::new());
+                            modal_initial_dependencies.set(Vec::new());
                             modal_initial_start_date.set(plan_data.quarter_start_date);
                             modal_initial_completion.set(None);
                             modal_initial_notes.set(String::new());
@@ -310,8 +320,9 @@ pub fn TechnicalView() -> Element {
                                 modal_mode.set(TechnicalModalMode::Add);
                                 modal_initial_name.set(String::new());
                                 modal_initial_roadmap_id.set(None);
-                                modal_initial_eng_estimate.set(0.0);
-                                modal_initial_sci_estimate.set(0.0);
+                                modal_initial_estimates.set(RoleWeeks::new());
+                                modal_initial_required_skills.set(Vec::new());
+                                modal_initial_dependencies.set(Vec::new());
                                 modal_initial_start_date.set(plan_data.quarter_start_date);
                                 modal_initial_completion.set(None);
                                 modal_initial_notes.set(String::new());
@@ -328,11 +339,13 @@ pub fn TechnicalView() -> Element {
                         TableHeader {
                             TableHeaderCell { "Project Name" }
                             TableHeaderCell { "Roadmap" }
-                            TableHeaderCell { "Eng Est." }
-                            TableHeaderCell { "Sci Est." }
+                            for definition in &prefs_data.roles {
+                                TableHeaderCell { "{definition.short_name} Est." }
+                            }
                             TableHeaderCell { "Total Est." }
-                            TableHeaderCell { "Eng Alloc." }
-                            TableHeaderCell { "Sci Alloc." }
+                            for definition in &prefs_data.roles {
+                                TableHeaderCell { "{definition.short_name} Alloc." }
+                            }
                             TableHeaderCell { "Total Alloc." }
                             TableHeaderCell { "Team" }
                             TableHeaderCell { "Start" }
@@ -341,34 +354,54 @@ pub fn TechnicalView() -> Element {
 
                         for project in filtered_projects {
                         {
-                            // Get role lookup function
-                            let get_role = |member_id: &Uuid| {
-                                prefs_data.team_members.iter()
-                                    .find(|m| &m.id == member_id)
-                                    .map(|m| m.role)
-                            };
-
                             // Calculate allocations by role
-                            let (eng_alloc, sci_alloc, total_alloc) = plan_data
-                                .calculate_technical_project_allocated_by_role(&project.id, get_role);
+                            let allocated = index.technical_project_allocated_by_role(&project.id);
 
                             // Normalize to avoid -0.0 display
-                            let eng_alloc = if eng_alloc == 0.0 { 0.0 } else { eng_alloc };
-                            let sci_alloc = if sci_alloc == 0.0 { 0.0 } else { sci_alloc };
-                            let total_alloc = if total_alloc == 0.0 { 0.0 } else { total_alloc };
-
-                            // Get estimates
-                            let eng_estimate = project.eng_estimate;
-                            let sci_estimate = project.sci_estimate;
+                            let normalize = |weeks: f32| if weeks == 0.0 { 0.0 } else { weeks };
+                            let total_alloc = normalize(allocated.total());
                             let total_estimate = project.total_estimate();
 
-                            // Get allocation status for each category
-                            let eng_status = get_capacity_status(eng_alloc, eng_estimate);
-                            let sci_status = get_capacity_status(sci_alloc, sci_estimate);
+                            // Estimate, allocation, and status for each configured role
+                            let role_columns: Vec<_> = prefs_data
+                                .roles
+                                .iter()
+                                .map(|definition| {
+                                    let estimate = project.estimates.get(&definition.id);
+                                    let alloc = normalize(allocated.get(&definition.id));
+                                    (estimate, alloc, get_capacity_status(alloc, estimate))
+                                })
+                                .collect();
                             let total_status = get_capacity_status(total_alloc, total_estimate);
 
+                            // Required skills nobody staffed on the project has
+                            let missing_skills = plan_data
+                                .missing_project_skills(&project.id, &prefs_data.team_members)
+                                .join(", ");
+
+                            // Dependencies broken by the current allocations
+                            let ordering_issues = dependency_violations
+                                .iter()
+                                .filter(|v| v.project_id == project.id)
+                                .map(|v| {
+                                    let predecessor = plan_data
+                                        .get_technical_project(&v.predecessor_id)
+                                        .map(|p| p.name.as_str())
+                                        .unwrap_or("unknown project");
+                                    match v.kind {
+                                        DependencyKind::FinishToStart => {
+                                            format!("Starts before \"{}\" finishes", predecessor)
+                                        }
+                                        DependencyKind::StartToStart => {
+                                            format!("Starts before \"{}\" starts", predecessor)
+                                        }
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n");
+
                             // Get assigned team members
-                            let assigned_member_ids = plan_data.get_assigned_team_members(&project.id);
+                            let assigned_member_ids = index.assigned_team_members(&project.id);
                             let assigned_names: Vec<String> = assigned_member_ids
                                 .iter()
                                 .filter_map(|id| {
@@ -400,7 +433,7 @@ pub fn TechnicalView() -> Element {
                             };
 
                             // Get date range from allocations
-                            let date_range = plan_data.get_project_allocation_date_range(&project.id);
+                            let date_range = index.project_allocation_date_range(&project.id);
                             let (start_display, end_display) = match date_range {
                                 Some((start, end)) => {
                                     // End date is end of the week (start + 6 days)
@@ -424,8 +457,9 @@ pub fn TechnicalView() -> Element {
                             let project_id = project.id;
                             let project_name = project.name.clone();
                             let project_roadmap_id = project.roadmap_project_id;
-                            let project_eng_estimate = project.eng_estimate;
-                            let project_sci_estimate = project.sci_estimate;
+                            let project_estimates = project.estimates.clone();
+                            let project_required_skills = project.required_skills.clone();
+                            let project_dependencies = project.dependencies.clone();
                             let project_start = project.start_date;
                             let project_completion = project.expected_completion;
                             let project_notes = project.notes.clone().unwrap_or_default();
@@ -441,10 +475,26 @@ pub fn TechnicalView() -> Element {
                                                 name: project.name.clone(),
                                                 color: project_color.to_hex().to_string(),
                                             }
+                                            if !ordering_issues.is_empty() {
+                                                span {
+                                                    class: "dependency-warning",
+                                                    title: "{ordering_issues}",
+                                                    "⛓"
+                                                }
+                                            }
+                                            if !missing_skills.is_empty() {
+                                                span {
+                                                    class: "skill-gap-warning",
+                                                    title: "Nobody assigned has: {missing_skills}",
+                                                    "⚠"
+                                                }
+                                            }
                                             // Hover actions
                                             {
                                                 let edit_name = project_name.clone();
                                                 let edit_notes = project_notes.clone();
+                                                let edit_skills = project_required_skills.clone();
+                                                let edit_dependencies = project_dependencies.clone();
                                                 let delete_name = project_name.clone();
 
                                                 rsx! {
@@ -458,8 +508,9 @@ pub fn TechnicalView() -> Element {
                                                                 modal_mode.set(TechnicalModalMode::Edit(project_id));
                                                                 modal_initial_name.set(edit_name.clone());
                                                                 modal_initial_roadmap_id.set(project_roadmap_id);
-                                                                modal_initial_eng_estimate.set(project_eng_estimate);
-                                                                modal_initial_sci_estimate.set(project_sci_estimate);
+                                                                modal_initial_estimates.set(project_estimates.clone());
+                                                                modal_initial_required_skills.set(edit_skills.clone());
+                                                                modal_initial_dependencies.set(edit_dependencies.clone());
                                                                 modal_initial_start_date.set(project_start);
                                                                 modal_initial_completion.set(project_completion);
                                                                 modal_initial_notes.set(edit_notes.clone());
@@ -493,35 +544,26 @@ pub fn TechnicalView() -> Element {
                                             "—"
                                         }
                                     }
-                                    // Engineering estimate
-                                    TableCell {
-                                        style: CellStyle::MonospaceSecondary,
-                                        "{eng_estimate}"
-                                    }
-                                    // Science estimate
-                                    TableCell {
-                                        style: CellStyle::MonospaceSecondary,
-                                        "{sci_estimate}"
+                                    // Per-role estimates
+                                    for (estimate, _, _) in &role_columns {
+                                        TableCell {
+                                            style: CellStyle::MonospaceSecondary,
+                                            "{estimate}"
+                                        }
                                     }
                                     // Total estimate
                                     TableCell {
                                         style: CellStyle::MonospaceEmphasis,
                                         "{total_estimate}"
                                     }
-                                    // Engineering allocation (with status badge)
-                                    TableCell {
-                                        style: CellStyle::Default,
-                                        Badge {
-                                            badge_type: eng_status,
-                                            "{eng_alloc}"
-                                        }
-                                    }
-                                    // Science allocation (with status badge)
-                                    TableCell {
-                                        style: CellStyle::Default,
-                                        Badge {
-                                            badge_type: sci_status,
-                                            "{sci_alloc}"
+                                    // Per-role allocations (with status badges)
+                                    for (_, alloc, status) in &role_columns {
+                                        TableCell {
+                                            style: CellStyle::Default,
+                                            Badge {
+                                                badge_type: *status,
+                                                "{alloc}"
+                                            }
                                         }
                                     }
                                     // Total allocation (with status badge)
@@ -561,29 +603,33 @@ pub fn TechnicalView() -> Element {
                     mode: modal_mode(),
                     initial_name: modal_initial_name(),
                     initial_roadmap_project_id: modal_initial_roadmap_id(),
-                    initial_eng_estimate: modal_initial_eng_estimate(),
-                    initial_sci_estimate: modal_initial_sci_estimate(),
+                    initial_estimates: modal_initial_estimates(),
                     initial_start_date: modal_initial_start_date(),
                     initial_expected_completion: modal_initial_completion(),
                     initial_notes: modal_initial_notes(),
+                    initial_required_skills: modal_initial_required_skills(),
+                    initial_dependencies: modal_initial_dependencies(),
                     roadmap_projects: roadmap_projects.clone(),
+                    technical_projects: plan_data.technical_projects.clone(),
                     on_save: move |project| {
                         match modal_mode() {
                             TechnicalModalMode::Add => {
-                                plan.write().technical_projects.push(project);
+                                ctx.edit("Add technical project", |_, p| {
+                                    p.technical_projects.push(project);
+                                    p.mark_modified();
+                                });
                             }
                             TechnicalModalMode::Edit(id) => {
-                                if let Some(existing) = plan
-                                    .write()
-                                    .technical_projects
-                                    .iter_mut()
-                                    .find(|p| p.id == id)
-                                {
-                                    *existing = project;
-                                }
+                                ctx.edit("Edit technical project", |_, p| {
+                                    if let Some(existing) =
+                                        p.technical_projects.iter_mut().find(|p| p.id == id)
+                                    {
+                                        *existing = project;
+                                    }
+                                    p.mark_modified();
+                                });
                             }
                         }
-                        plan.write().mark_modified();
                         modal_visible.set(false);
                     },
                     on_cancel: move |_| {
@@ -614,15 +660,18 @@ pub fn TechnicalView() -> Element {
                         cancel_label: "Cancel".to_string(),
                         on_confirm: move |_| {
                             if let Some(id) = delete_project_id() {
-                                plan.with_mut(|p| {
+                                ctx.edit("Delete technical project", |_, p| {
                                     // Remove all allocations referencing this project
                                     for alloc in &mut p.allocations {
                                         alloc.assignments.retain(|a| a.technical_project_id != id);
                                     }
                                     // Remove empty allocations
                                     p.allocations.retain(|alloc| !alloc.assignments.is_empty());
-                                    // Remove the technical project
+                                    // Remove the technical project and dependencies on it
                                     p.technical_projects.retain(|proj| proj.id != id);
+                                    for tech_project in &mut p.technical_projects {
+                                        tech_project.dependencies.retain(|d| d.predecessor_id != id);
+                                    }
                                 });
                             }
                             delete_dialog_visible.set(false);
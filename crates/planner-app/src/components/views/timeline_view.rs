@@ -0,0 +1,44 @@
+use dioxus::logger::tracing::error;
+use dioxus::prelude::*;
+
+use crate::components::ui::{Button, ButtonVariant};
+use crate::state::{use_plan_state, use_preferences};
+use planner_core::formats::svg::{gantt_svg, GanttTheme};
+use planner_core::models::PlanExport;
+
+/// Timeline view - Gantt chart of roadmap projects and their technical projects
+///
+/// The chart is the same SVG the download produces, drawn in the app's dark
+/// theme; the download uses the light theme for slides.
+#[component]
+pub fn TimelineView() -> Element {
+    let plan_state = use_plan_state();
+    let preferences = use_preferences();
+
+    let svg = gantt_svg(&preferences(), &plan_state(), GanttTheme::Dark);
+
+    let handle_download = move |_| {
+        let export = PlanExport::from_signals(preferences(), plan_state());
+        let svg = gantt_svg(&preferences(), &plan_state(), GanttTheme::Light);
+        let filename = crate::plan_io::export_filename(&export, "timeline", "svg");
+        if let Err(e) = crate::plan_io::trigger_text_download(&svg, &filename, "image/svg+xml") {
+            error!("Failed to export timeline: {}", e);
+        }
+    };
+
+    rsx! {
+        div { class: "view active timeline-view",
+            div { class: "view-header",
+                span { class: "timeline-hint",
+                    "Roadmap projects run from start to launch (◆); technical projects to their expected completion."
+                }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    onclick: handle_download,
+                    "Download SVG"
+                }
+            }
+            div { class: "timeline-chart", dangerous_inner_html: "{svg}" }
+        }
+    }
+}
@@ -4,8 +4,11 @@ use dioxus::logger::tracing::{debug, info};
 use dioxus::prelude::*;
 
 use components::layout::View;
-use components::{AllocationView, RoadmapView, TechnicalView, TopNav};
-use planner_core::models::{self, PlanExport};
+use components::{AllocationView, RoadmapView, TechnicalView, TimelineView, TopNav};
+use planner_core::models::{self, EditHistory, PlanExport, PlanIndex};
+#[cfg(target_family = "wasm")]
+use planner_core::validation::validate_export;
+use planner_core::validation::ValidationReport;
 use state::AppContext;
 
 /// Platform-specific modules (not in library due to GUI dependencies)
@@ -14,14 +17,20 @@ mod plan_io;
 mod state;
 mod storage;
 
+/// A plan shared in the URL, or its validation report if it has errors
+type UrlPlan = Result<
+    (
+        models::Preferences,
+        models::PlanState,
+        state::ViewingSession,
+    ),
+    ValidationReport,
+>;
+
 /// Check URL for plan data query parameter (?plan=<base64>)
 /// Returns (preferences, plan_state, viewing_session) if found and valid
 #[cfg(target_family = "wasm")]
-fn load_plan_from_url() -> Option<(
-    models::Preferences,
-    models::PlanState,
-    state::ViewingSession,
-)> {
+fn load_plan_from_url() -> Option<UrlPlan> {
     let window = web_sys::window()?;
     let location = window.location();
     let search = location.search().ok()?;
@@ -47,18 +56,26 @@ fn load_plan_from_url() -> Option<(
     // Decode base64
     let json = plan_io::base64_decode(&encoded_str).ok()?;
 
-    // Parse JSON
-    let export: PlanExport = serde_json::from_str(&json).ok()?;
+    // Parse JSON, upgrading older formats
+    let export: PlanExport = match planner_core::migration::load_json(&json) {
+        Ok(export) => export,
+        Err(e) => {
+            dioxus::logger::tracing::warn!("Couldn't load shared plan from URL: {}", e);
+            return None;
+        }
+    };
 
-    // Validate
-    if export.validate().is_err() {
-        return None;
+    // Plans with errors aren't opened; Plan health shows why
+    let report = validate_export(&export);
+    if report.has_errors() {
+        dioxus::logger::tracing::warn!("Shared plan has errors: {}", report.summary());
+        return Some(Err(report));
     }
 
     let original_json = serde_json::to_string(&export).unwrap_or_default();
     let (prefs, plan) = export.into_signals();
 
-    Some((
+    Some(Ok((
         prefs,
         plan,
         state::ViewingSession {
@@ -66,15 +83,11 @@ fn load_plan_from_url() -> Option<(
             original_json,
             modified: false,
         },
-    ))
+    )))
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn load_plan_from_url() -> Option<(
-    models::Preferences,
-    models::PlanState,
-    state::ViewingSession,
-)> {
+fn load_plan_from_url() -> Option<UrlPlan> {
     None // URL parameters not supported on desktop
 }
 
@@ -106,10 +119,19 @@ fn main() {
 #[component]
 fn App() -> Element {
     // Check for plan in URL first (takes priority over localStorage)
-    let url_plan = load_plan_from_url();
+    let (url_plan, url_report) = match load_plan_from_url() {
+        Some(Ok(plan)) => (Some(plan), None),
+        Some(Err(report)) => (None, Some(report)),
+        None => (None, None),
+    };
+
+    // Saved data from a newer version makes storage read-only, even while
+    // viewing a shared plan, so adopting or closing it can't overwrite it
+    let saved = storage::load_saved_plan();
+    let initial_storage_error = saved.error.map(|e| e.to_string());
 
     // Load preferences and plan state - from URL if present, otherwise from storage
-    let (initial_prefs, initial_state, initial_viewing) =
+    let (initial_prefs, initial_state, initial_viewing, initial_history) =
         if let Some((prefs, state, session)) = url_plan {
             info!(
                 "Loaded shared plan from URL: {} ({} team members, {} allocations)",
@@ -117,46 +139,76 @@ fn App() -> Element {
                 prefs.team_members.len(),
                 state.allocations.len()
             );
-            (prefs, state, Some(session))
+            (prefs, state, Some(session), EditHistory::default())
         } else {
-            let prefs = storage::load_preferences();
-            let state = storage::load_plan_state();
+            let prefs = saved.preferences;
+            let state = saved.plan_state;
 
             if prefs.is_none() && state.is_none() {
                 debug!("No saved data found, using defaults");
             }
 
-            (prefs.unwrap_or_default(), state.unwrap_or_default(), None)
+            // History only applies to the plan it was recorded against
+            let history = if state.is_some() {
+                storage::load_history().unwrap_or_default()
+            } else {
+                EditHistory::default()
+            };
+
+            (
+                prefs.unwrap_or_default(),
+                state.unwrap_or_default(),
+                None,
+                history,
+            )
         };
 
     // Create signals for persistent data
     let preferences = use_signal(|| initial_prefs);
     let plan_state = use_signal(|| initial_state);
+    let history = use_signal(|| initial_history);
+    let storage_error = use_signal(|| initial_storage_error);
+    let rejected_import = use_signal(|| url_report);
 
     // Viewing session signal for imported plan files (ephemeral, not persisted)
     // When Some, the app is displaying a loaded file instead of the localStorage plan
     let mut viewing_session: Signal<Option<state::ViewingSession>> = use_signal(|| initial_viewing);
 
+    // Index of the plan for fast lookups, rebuilt once per plan or roster change
+    let plan_index =
+        use_memo(move || PlanIndex::new(plan_state(), &preferences.read().team_members));
+
     // Auto-save preferences to localStorage when they change
     // IMPORTANT: Skip saving when in viewing mode (viewing an imported file)
+    // or when the saved data is read-only
     use_effect(move || {
         let prefs = preferences();
         // Only save if NOT in viewing mode
-        if viewing_session().is_none() {
+        if viewing_session().is_none() && storage_error().is_none() {
             let _ = storage::save_preferences(&prefs);
         }
     });
 
     // Auto-save plan state to localStorage when it changes
     // IMPORTANT: Skip saving when in viewing mode (viewing an imported file)
+    // or when the saved data is read-only
     use_effect(move || {
         let state = plan_state();
         // Only save if NOT in viewing mode
-        if viewing_session().is_none() {
+        if viewing_session().is_none() && storage_error().is_none() {
             let _ = storage::save_plan_state(&state);
         }
     });
 
+    // Auto-save undo/redo history so it survives reloads
+    // IMPORTANT: Skip saving when in viewing mode (history belongs to the viewed file)
+    use_effect(move || {
+        let history = history();
+        if viewing_session().is_none() && storage_error().is_none() {
+            let _ = storage::save_history(&history);
+        }
+    });
+
     // Detect unsaved changes when in viewing mode
     // Compare current state with original JSON to set the modified flag
     use_effect(move || {
@@ -184,15 +236,39 @@ fn App() -> Element {
     });
 
     // Provide the app context with all signals to child components
-    use_context_provider(|| AppContext {
+    let app_context = AppContext {
         preferences,
         plan_state,
         viewing_session,
-    });
+        history,
+        plan_index,
+        storage_error,
+        rejected_import,
+    };
+    use_context_provider(|| app_context);
 
     // Active view state
     let active_view = use_signal(|| View::Allocation);
 
+    // Undo/redo shortcuts (desktop; web registers a document listener in TopNav)
+    let handle_keydown = move |evt: KeyboardEvent| {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let meta = evt.modifiers().meta() || evt.modifiers().ctrl();
+            let is_z = matches!(evt.key(), Key::Character(ref c) if c.eq_ignore_ascii_case("z"));
+            if meta && is_z {
+                evt.prevent_default();
+                if evt.modifiers().shift() {
+                    app_context.redo();
+                } else {
+                    app_context.undo();
+                }
+            }
+        }
+        #[cfg(target_family = "wasm")]
+        let _ = evt;
+    };
+
     rsx! {
         // Critical CSS to prevent white flash on load
         document::Style { "html, body {{ background-color: #0f0f11; }}" }
@@ -200,7 +276,7 @@ fn App() -> Element {
         document::Link { rel: "stylesheet", href: THEME_CSS }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
 
-        div { class: "app-container",
+        div { class: "app-container", onkeydown: handle_keydown,
             // Top navigation bar
             // Note: + Add Member button moved to grid corner cell in M13
             TopNav { active_view }
@@ -212,6 +288,7 @@ fn App() -> Element {
                     View::Roadmap => rsx! { RoadmapView {} },
                     View::Technical => rsx! { TechnicalView {} },
                     View::Allocation => rsx! { AllocationView {} },
+                    View::Timeline => rsx! { TimelineView {} },
                 }
             }
         }
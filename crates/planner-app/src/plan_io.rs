@@ -69,6 +69,87 @@ fn generate_plan_filename(export: &PlanExport) -> String {
     format!("plan-{}-{}.json", team_name, quarter)
 }
 
+/// Filename for an export other than the plan file itself,
+/// e.g. "plan-payments-q1-2025-allocations.csv"
+pub fn export_filename(export: &PlanExport, kind: &str, extension: &str) -> String {
+    let stem = generate_plan_filename(export);
+    let stem = stem.trim_end_matches(".json");
+    format!("{}-{}.{}", stem, kind, extension)
+}
+
+/// Trigger a file download of a text export (CSV, Markdown, ...)
+#[cfg(target_family = "wasm")]
+pub fn trigger_text_download(
+    contents: &str,
+    filename: &str,
+    mime_type: &str,
+) -> Result<(), String> {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function download_text(contents, filename, mime_type) {
+            const blob = new Blob([contents], { type: mime_type });
+            const url = URL.createObjectURL(blob);
+            const a = document.createElement('a');
+            a.href = url;
+            a.download = filename;
+            document.body.appendChild(a);
+            a.click();
+            document.body.removeChild(a);
+            URL.revokeObjectURL(url);
+        }
+    "#)]
+    extern "C" {
+        fn download_text(contents: &str, filename: &str, mime_type: &str);
+    }
+
+    info!("Downloading {} ({} bytes)", filename, contents.len());
+    download_text(contents, filename, mime_type);
+    Ok(())
+}
+
+/// Desktop/native implementation of text export download using save dialog
+#[cfg(not(target_family = "wasm"))]
+pub fn trigger_text_download(
+    contents: &str,
+    filename: &str,
+    _mime_type: &str,
+) -> Result<(), String> {
+    let extension = filename.rsplit('.').next().unwrap_or_default();
+
+    debug!("Opening save dialog for {}", filename);
+    let path = rfd::FileDialog::new()
+        .add_filter("Export Files", &[extension])
+        .set_file_name(filename)
+        .set_title("Export Plan")
+        .save_file();
+
+    if let Some(path) = path {
+        std::fs::write(&path, contents).map_err(|e| format!("Failed to write file: {}", e))?;
+        info!("Exported plan to {:?} ({} bytes)", path, contents.len());
+    } else {
+        debug!("Save dialog cancelled");
+    }
+
+    Ok(())
+}
+
+/// Trigger file open dialog (web only - clicks hidden file input)
+#[cfg(target_family = "wasm")]
+pub fn trigger_file_open(file_input_id: &str) {
+    use wasm_bindgen::JsCast;
+
+    if let Some(window) = web_sys::window() {
+        if let Some(document) = window.document() {
+            if let Some(input) = document.get_element_by_id(file_input_id) {
+                if let Ok(html_input) = input.dyn_into::<web_sys::HtmlInputElement>() {
+                    html_input.click();
+                }
+            }
+        }
+    }
+}
+
 /// Result of reading a file from input
 #[cfg(target_family = "wasm")]
 pub struct FileReadResult {
@@ -178,6 +259,40 @@ pub fn copy_plan_to_clipboard(export: &PlanExport) -> Result<(), String> {
     Ok(())
 }
 
+/// Copy text to clipboard as-is
+#[cfg(target_family = "wasm")]
+pub fn copy_text_to_clipboard(text: &str) -> Result<(), String> {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function copy_to_clipboard(text) {
+            navigator.clipboard.writeText(text).catch(err => {
+                console.error('Failed to copy:', err);
+            });
+        }
+    "#)]
+    extern "C" {
+        fn copy_to_clipboard(text: &str);
+    }
+
+    copy_to_clipboard(text);
+    info!("Copied {} bytes to clipboard", text.len());
+    Ok(())
+}
+
+/// Copy text to clipboard as-is (desktop - using arboard)
+#[cfg(not(target_family = "wasm"))]
+pub fn copy_text_to_clipboard(text: &str) -> Result<(), String> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| format!("Failed to copy: {}", e))?;
+
+    info!("Copied {} bytes to clipboard", text.len());
+    Ok(())
+}
+
 /// Read from clipboard and decode (desktop)
 #[cfg(not(target_family = "wasm"))]
 pub fn read_from_clipboard_sync() -> Result<String, String> {
@@ -15,12 +15,37 @@
 //! - On close, reload from localStorage to restore user's own plan
 //!
 //! See ADR-005 for design rationale.
+//!
+//! ## Read-Only Storage
+//!
+//! - `storage_error`: Set when the saved plan came from a newer version
+//! - Auto-save is disabled so the newer data isn't overwritten
+//! - Cleared when the user resets their saved data
+//!
+//! ## Undo/Redo
+//!
+//! - `history`: Undo/redo stacks (persisted alongside the plan)
+//! - All plan and roster mutations go through `AppContext::edit` so they
+//!   are recorded as a single undoable step
+//!
+//! ## Scenarios
+//!
+//! Named what-if branches live inside `plan_state`; the active branch is
+//! swapped into its working fields, so views never need to know about them.
+//! Scenario changes go through `AppContext::update_scenarios`.
+//!
+//! ## Plan Index
+//!
+//! `plan_index` is a memo rebuilt whenever the plan or roster changes. Views
+//! use it for per-cell, per-member and per-project queries instead of the
+//! linear `PlanState` methods.
 
 use chrono::NaiveDate;
 use dioxus::prelude::*;
 
 use planner_core::models::*;
 use planner_core::utils::get_quarter_start_date;
+use planner_core::validation::ValidationReport;
 
 /// State for viewing an imported/loaded plan file
 ///
@@ -46,11 +71,117 @@ pub struct ViewingSession {
 /// - `preferences`: Long-term team configuration
 /// - `plan_state`: Quarter-specific planning data
 /// - `viewing_session`: Optional viewing state for imported plans
+/// - `storage_error`: Why saved data is read-only, if it is
+/// - `rejected_import`: Problems in a plan that couldn't be opened
 #[derive(Clone, Copy)]
 pub struct AppContext {
     pub preferences: Signal<Preferences>,
     pub plan_state: Signal<PlanState>,
     pub viewing_session: Signal<Option<ViewingSession>>,
+    pub history: Signal<EditHistory>,
+    pub plan_index: Memo<PlanIndex>,
+    pub storage_error: Signal<Option<String>>,
+    pub rejected_import: Signal<Option<ValidationReport>>,
+}
+
+impl AppContext {
+    /// Apply a mutation to preferences and plan state as one undoable step
+    ///
+    /// While a batch is open (e.g., during a paintbrush drag) the change is
+    /// applied without recording; `end_batch` records the whole batch.
+    /// Changes to fields history doesn't track (team and quarter settings,
+    /// scenarios) are applied but can't be undone.
+    pub fn edit(mut self, label: &str, mutate: impl FnOnce(&mut Preferences, &mut PlanState)) {
+        let before_prefs = self.preferences.peek().clone();
+        let before_state = self.plan_state.peek().clone();
+        let mut prefs = before_prefs.clone();
+        let mut state = before_state.clone();
+        mutate(&mut prefs, &mut state);
+
+        let entry = HistoryEntry::from_diff(label, &before_prefs, &before_state, &prefs, &state);
+        if before_prefs != prefs {
+            self.preferences.set(prefs);
+        }
+        if before_state != state {
+            self.plan_state.set(state);
+        }
+        if !entry.is_empty() && !self.history.peek().is_batching() {
+            self.history.with_mut(|h| h.record(entry));
+        }
+    }
+
+    /// Replace the plan wholesale (e.g., applying a merge)
+    ///
+    /// Not undoable: the change touches fields history doesn't track, so
+    /// history is cleared rather than left half able to revert it.
+    pub fn replace_plan(mut self, mutate: impl FnOnce(&mut Preferences, &mut PlanState)) {
+        let mut prefs = self.preferences.peek().clone();
+        let mut state = self.plan_state.peek().clone();
+        mutate(&mut prefs, &mut state);
+        self.history.with_mut(|h| h.clear());
+        self.preferences.set(prefs);
+        self.plan_state.set(state);
+    }
+
+    /// Create, switch, rename, delete or promote scenarios
+    ///
+    /// Scenario changes aren't undoable. When the working branch changes,
+    /// history recorded against the previous branch is dropped.
+    pub fn update_scenarios(mut self, mutate: impl FnOnce(&mut PlanState)) {
+        let mut state = self.plan_state.peek().clone();
+        let before_active = state.active_scenario_id;
+        let before_branch = state.working_branch();
+        mutate(&mut state);
+        if state.active_scenario_id != before_active || state.working_branch() != before_branch {
+            self.history.with_mut(|h| h.clear());
+        }
+        self.plan_state.set(state);
+    }
+
+    /// Start grouping edits into a single undo step
+    pub fn begin_batch(mut self, label: &str) {
+        let prefs = self.preferences.peek().clone();
+        let state = self.plan_state.peek().clone();
+        self.history
+            .with_mut(|h| h.begin_batch(label, &prefs, &state));
+    }
+
+    /// Finish a batch started with `begin_batch`
+    pub fn end_batch(mut self) {
+        if !self.history.peek().is_batching() {
+            return;
+        }
+        let prefs = self.preferences.peek().clone();
+        let state = self.plan_state.peek().clone();
+        self.history.with_mut(|h| h.end_batch(&prefs, &state));
+    }
+
+    /// Revert the most recent edit
+    pub fn undo(mut self) -> Option<String> {
+        let mut prefs = self.preferences.peek().clone();
+        let mut state = self.plan_state.peek().clone();
+        let label = self.history.with_mut(|h| h.undo(&mut prefs, &mut state))?;
+        self.preferences.set(prefs);
+        self.plan_state.set(state);
+        Some(label)
+    }
+
+    /// Reapply the most recently undone edit
+    pub fn redo(mut self) -> Option<String> {
+        let mut prefs = self.preferences.peek().clone();
+        let mut state = self.plan_state.peek().clone();
+        let label = self.history.with_mut(|h| h.redo(&mut prefs, &mut state))?;
+        self.preferences.set(prefs);
+        self.plan_state.set(state);
+        Some(label)
+    }
+}
+
+/// Hook to access the full application context
+///
+/// Use this for mutations that should be undoable (`ctx.edit(...)`).
+pub fn use_app_context() -> AppContext {
+    use_context::<AppContext>()
 }
 
 /// Hook to access team preferences (persisted to localStorage)
@@ -87,6 +218,34 @@ pub fn use_viewing_session() -> Signal<Option<ViewingSession>> {
     use_context::<AppContext>().viewing_session
 }
 
+/// Hook to access undo/redo history
+pub fn use_history() -> Signal<EditHistory> {
+    use_context::<AppContext>().history
+}
+
+/// Hook to access why saved data couldn't be loaded
+///
+/// `Some` while the app is read-only to protect data from a newer version.
+pub fn use_storage_error() -> Signal<Option<String>> {
+    use_context::<AppContext>().storage_error
+}
+
+/// Hook to access the validation report of a plan that failed to open
+///
+/// Set when an opened file, pasted plan or shared link has errors; Plan
+/// health shows it until closed.
+pub fn use_rejected_import() -> Signal<Option<ValidationReport>> {
+    use_context::<AppContext>().rejected_import
+}
+
+/// Hook to access the plan index (read-only, derived from plan state and roster)
+///
+/// Read it with `.read()` for lookups during render; cloning copies the
+/// whole plan.
+pub fn use_plan_index() -> Memo<PlanIndex> {
+    use_context::<AppContext>().plan_index
+}
+
 /// Initialize the application state with sample data
 /// This creates team members, projects, and allocations for Q1 2025
 ///
@@ -102,16 +261,36 @@ pub fn create_sample_plan() -> (Preferences, PlanState) {
         sprint_anchor_date: NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid anchor date"),
         sprint_length_weeks: 2,
         default_capacity: 12.0,
+        holidays: vec![
+            Holiday::new(
+                "Martin Luther King Jr. Day".to_string(),
+                NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
+            ),
+            Holiday::new(
+                "Presidents' Day".to_string(),
+                NaiveDate::from_ymd_opt(2025, 2, 17).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 2, 17).unwrap(),
+            ),
+        ],
+        roles: default_roles(),
     };
 
     // Create plan state (quarter-specific data)
     let mut plan_state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
 
     // Create team members
-    let alice = TeamMember::new("Alice Kim".to_string(), Role::Engineering, 12.0);
-    let bob = TeamMember::new("Bob Martinez".to_string(), Role::Engineering, 12.0);
-    let carol = TeamMember::new("Carol Smith".to_string(), Role::Science, 6.0);
-    let dave = TeamMember::new("Dave Roberts".to_string(), Role::Engineering, 12.0);
+    let mut alice = TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0);
+    alice.skills = vec!["Rust".to_string(), "Kafka".to_string()];
+    let mut bob = TeamMember::new("Bob Martinez".to_string(), Role::ENGINEERING, 12.0);
+    bob.time_off.push(TimeOff::new(
+        NaiveDate::from_ymd_opt(2025, 2, 10).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 2, 14).unwrap(),
+        1.0,
+    ));
+    let mut carol = TeamMember::new("Carol Smith".to_string(), Role::SCIENCE, 6.0);
+    carol.skills = vec!["ML modeling".to_string(), "Python".to_string()];
+    let dave = TeamMember::new("Dave Roberts".to_string(), Role::ENGINEERING, 12.0);
 
     let alice_id = alice.id;
     let bob_id = bob.id;
@@ -126,8 +305,7 @@ pub fn create_sample_plan() -> (Preferences, PlanState) {
     // Create roadmap projects
     let platform_project = RoadmapProject::new(
         "Q1 Platform Improvements".to_string(),
-        24.0, // Engineering estimate
-        8.0,  // Science estimate
+        RoleWeeks::from([(Role::ENGINEERING, 24.0), (Role::SCIENCE, 8.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
         NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
         ProjectColor::Blue,
@@ -135,8 +313,7 @@ pub fn create_sample_plan() -> (Preferences, PlanState) {
 
     let payment_project = RoadmapProject::new(
         "Payment Gateway".to_string(),
-        8.0,
-        0.0,
+        RoleWeeks::from([(Role::ENGINEERING, 8.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
         NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
         ProjectColor::Green,
@@ -144,8 +321,7 @@ pub fn create_sample_plan() -> (Preferences, PlanState) {
 
     let data_project = RoadmapProject::new(
         "Data Pipeline Overhaul".to_string(),
-        16.0,
-        6.0,
+        RoleWeeks::from([(Role::ENGINEERING, 16.0), (Role::SCIENCE, 6.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
         NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
         ProjectColor::Yellow,
@@ -159,44 +335,42 @@ pub fn create_sample_plan() -> (Preferences, PlanState) {
     plan_state.roadmap_projects.push(payment_project);
     plan_state.roadmap_projects.push(data_project);
 
-    // Create technical projects (with eng_estimate, sci_estimate split)
+    // Create technical projects (estimates split by role)
     let auth_service = TechnicalProject::new(
         "Auth Service Refactor".to_string(),
         Some(platform_roadmap_id),
-        6.0, // eng_estimate
-        0.0, // sci_estimate
+        RoleWeeks::from([(Role::ENGINEERING, 6.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
     );
 
     let payment_api = TechnicalProject::new(
         "Payment API Integration".to_string(),
         Some(payment_roadmap_id),
-        8.0, // eng_estimate
-        0.0, // sci_estimate
+        RoleWeeks::from([(Role::ENGINEERING, 8.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
     );
 
-    let ml_pipeline = TechnicalProject::new(
+    let mut ml_pipeline = TechnicalProject::new(
         "ML Pipeline Optimization".to_string(),
         Some(platform_roadmap_id),
-        6.0, // eng_estimate
-        6.0, // sci_estimate
+        RoleWeeks::from([(Role::ENGINEERING, 6.0), (Role::SCIENCE, 6.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
     );
 
-    let data_pipeline = TechnicalProject::new(
+    ml_pipeline.required_skills = vec!["ML modeling".to_string()];
+
+    let mut data_pipeline = TechnicalProject::new(
         "Data Pipeline Migration".to_string(),
         Some(data_roadmap_id),
-        6.0, // eng_estimate
-        4.0, // sci_estimate
+        RoleWeeks::from([(Role::ENGINEERING, 6.0), (Role::SCIENCE, 4.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
     );
+    data_pipeline.required_skills = vec!["Kafka".to_string()];
 
     let research = TechnicalProject::new(
         "Algorithm Research".to_string(),
         Some(data_roadmap_id),
-        0.0, // eng_estimate
-        6.0, // sci_estimate
+        RoleWeeks::from([(Role::SCIENCE, 6.0)]),
         NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
     );
 
@@ -265,78 +439,3 @@ pub fn create_sample_plan() -> (Preferences, PlanState) {
 
     (preferences, plan_state)
 }
-
-/// Validation errors for the plan
-#[allow(dead_code)] // Reserved for validation UI
-#[derive(Debug, Clone, PartialEq)]
-pub enum ValidationError {
-    /// Allocation doesn't sum to 100%
-    InvalidAllocationPercentage {
-        engineer_id: uuid::Uuid,
-        week_start: NaiveDate,
-        total: f32,
-    },
-    /// Engineer over-allocated (exceeds capacity)
-    OverAllocated {
-        engineer_name: String,
-        capacity: f32,
-        allocated: f32,
-    },
-    /// Allocation before project start date
-    BeforeStartDate {
-        engineer_name: String,
-        project_name: String,
-        week_start: NaiveDate,
-        project_start: NaiveDate,
-    },
-}
-
-/// Validate the entire plan
-#[allow(dead_code)] // Reserved for validation UI
-pub fn validate_plan(plan: &Plan) -> Vec<ValidationError> {
-    let mut errors = Vec::new();
-
-    // Check allocation percentages
-    for allocation in &plan.allocations {
-        if !allocation.is_valid() {
-            errors.push(ValidationError::InvalidAllocationPercentage {
-                engineer_id: allocation.team_member_id,
-                week_start: allocation.week_start_date,
-                total: allocation.total_percentage(),
-            });
-        }
-    }
-
-    // Check engineer capacity
-    for engineer in &plan.team_members {
-        let allocated = plan.calculate_allocated_weeks(&engineer.id);
-        if allocated > engineer.capacity {
-            errors.push(ValidationError::OverAllocated {
-                engineer_name: engineer.name.clone(),
-                capacity: engineer.capacity,
-                allocated,
-            });
-        }
-    }
-
-    // Check allocation dates vs project start dates
-    for allocation in &plan.allocations {
-        for assignment in &allocation.assignments {
-            if let Some(tech_project) = plan.get_technical_project(&assignment.technical_project_id)
-            {
-                if allocation.week_start_date < tech_project.start_date {
-                    if let Some(engineer) = plan.get_team_member(&allocation.team_member_id) {
-                        errors.push(ValidationError::BeforeStartDate {
-                            engineer_name: engineer.name.clone(),
-                            project_name: tech_project.name.clone(),
-                            week_start: allocation.week_start_date,
-                            project_start: tech_project.start_date,
-                        });
-                    }
-                }
-            }
-        }
-    }
-
-    errors
-}
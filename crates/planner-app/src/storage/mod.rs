@@ -13,7 +13,38 @@
 use dioxus::logger::tracing::{debug, error, info, warn};
 #[cfg(target_family = "wasm")]
 use dioxus::logger::tracing::{debug, info, warn};
-use planner_core::models::{PlanState, Preferences};
+use planner_core::migration::{load_json, MigrationError};
+use planner_core::models::{EditHistory, PlanState, Preferences};
+
+// ============================================================================
+// Shared
+// ============================================================================
+
+/// Saved preferences and plan state, as far as they could be loaded
+pub struct SavedPlan {
+    pub preferences: Option<Preferences>,
+    pub plan_state: Option<PlanState>,
+    /// Set when either was saved by a newer version; saving would lose it
+    pub error: Option<MigrationError>,
+}
+
+/// Load saved preferences and plan state together
+pub fn load_saved_plan() -> SavedPlan {
+    let (preferences, prefs_error) = split(load_preferences());
+    let (plan_state, state_error) = split(load_plan_state());
+    SavedPlan {
+        preferences,
+        plan_state,
+        error: prefs_error.or(state_error),
+    }
+}
+
+fn split<T>(result: Result<Option<T>, MigrationError>) -> (Option<T>, Option<MigrationError>) {
+    match result {
+        Ok(value) => (value, None),
+        Err(e) => (None, Some(e)),
+    }
+}
 
 // ============================================================================
 // Web Implementation (localStorage) - only for WASM targets
@@ -23,6 +54,8 @@ use planner_core::models::{PlanState, Preferences};
 const PREFERENCES_KEY: &str = "planner_preferences";
 #[cfg(target_family = "wasm")]
 const PLAN_STATE_KEY: &str = "planner_plan_state";
+#[cfg(target_family = "wasm")]
+const HISTORY_KEY: &str = "planner_history";
 
 /// Save preferences to localStorage
 #[cfg(target_family = "wasm")]
@@ -48,24 +81,42 @@ pub fn save_preferences(prefs: &Preferences) -> Result<(), String> {
     Ok(())
 }
 
-/// Load preferences from localStorage
+/// Read a value from localStorage, if there is one
 #[cfg(target_family = "wasm")]
-pub fn load_preferences() -> Option<Preferences> {
+fn get_item(key: &str) -> Option<String> {
     let window = web_sys::window()?;
     let storage = window.local_storage().ok()??;
-    let json = storage.get_item(PREFERENCES_KEY).ok()??;
+    storage.get_item(key).ok()?
+}
 
-    match serde_json::from_str::<Preferences>(&json) {
+/// Load preferences from localStorage
+///
+/// Preferences saved by a newer version are an error, so the caller can
+/// avoid overwriting them; other unreadable data is ignored.
+#[cfg(target_family = "wasm")]
+pub fn load_preferences() -> Result<Option<Preferences>, MigrationError> {
+    let Some(json) = get_item(PREFERENCES_KEY) else {
+        return Ok(None);
+    };
+
+    match load_json::<Preferences>(&json) {
         Ok(prefs) => {
             info!(
                 "Loaded preferences from localStorage ({} team members)",
                 prefs.team_members.len()
             );
-            Some(prefs)
+            Ok(Some(prefs))
+        }
+        Err(e @ MigrationError::NewerVersion { .. }) => {
+            warn!(
+                "Preferences in localStorage are from a newer version: {}",
+                e
+            );
+            Err(e)
         }
         Err(e) => {
             warn!("Failed to parse preferences from localStorage: {}", e);
-            None
+            Ok(None)
         }
     }
 }
@@ -112,24 +163,30 @@ pub fn save_plan_state(state: &PlanState) -> Result<(), String> {
 }
 
 /// Load plan state from localStorage
+///
+/// A plan saved by a newer version is an error, like `load_preferences`.
 #[cfg(target_family = "wasm")]
-pub fn load_plan_state() -> Option<PlanState> {
-    let window = web_sys::window()?;
-    let storage = window.local_storage().ok()??;
-    let json = storage.get_item(PLAN_STATE_KEY).ok()??;
+pub fn load_plan_state() -> Result<Option<PlanState>, MigrationError> {
+    let Some(json) = get_item(PLAN_STATE_KEY) else {
+        return Ok(None);
+    };
 
-    match serde_json::from_str::<PlanState>(&json) {
+    match load_json::<PlanState>(&json) {
         Ok(state) => {
             info!(
                 "Loaded plan state from localStorage ({}, {} allocations)",
                 state.quarter_name,
                 state.allocations.len()
             );
-            Some(state)
+            Ok(Some(state))
+        }
+        Err(e @ MigrationError::NewerVersion { .. }) => {
+            warn!("Plan state in localStorage is from a newer version: {}", e);
+            Err(e)
         }
         Err(e) => {
             warn!("Failed to parse plan state from localStorage: {}", e);
-            None
+            Ok(None)
         }
     }
 }
@@ -151,6 +208,57 @@ pub fn clear_plan_state() -> Result<(), String> {
     Ok(())
 }
 
+/// Save undo/redo history to localStorage
+#[cfg(target_family = "wasm")]
+pub fn save_history(history: &EditHistory) -> Result<(), String> {
+    let json = serde_json::to_string(history)
+        .map_err(|e| format!("Failed to serialize history: {}", e))?;
+
+    let window = web_sys::window().ok_or("No window object")?;
+    let storage = window
+        .local_storage()
+        .map_err(|_| "Failed to access localStorage")?
+        .ok_or("localStorage not available")?;
+
+    storage
+        .set_item(HISTORY_KEY, &json)
+        .map_err(|e| format!("Failed to save to localStorage: {:?}", e))?;
+
+    debug!("Saved history to localStorage ({} bytes)", json.len());
+    Ok(())
+}
+
+/// Load undo/redo history from localStorage
+#[cfg(target_family = "wasm")]
+pub fn load_history() -> Option<EditHistory> {
+    let json = get_item(HISTORY_KEY)?;
+
+    match serde_json::from_str::<EditHistory>(&json) {
+        Ok(history) => Some(history),
+        Err(e) => {
+            warn!("Failed to parse history from localStorage: {}", e);
+            None
+        }
+    }
+}
+
+/// Clear undo/redo history from localStorage
+#[cfg(target_family = "wasm")]
+pub fn clear_history() -> Result<(), String> {
+    let window = web_sys::window().ok_or("No window object")?;
+    let storage = window
+        .local_storage()
+        .map_err(|_| "Failed to access localStorage")?
+        .ok_or("localStorage not available")?;
+
+    storage
+        .remove_item(HISTORY_KEY)
+        .map_err(|e| format!("Failed to clear localStorage: {:?}", e))?;
+
+    info!("Cleared history from localStorage");
+    Ok(())
+}
+
 // ============================================================================
 // Native Implementation (file-based) - for desktop, mobile, and other native targets
 // ============================================================================
@@ -174,6 +282,12 @@ fn get_plan_state_path() -> Option<std::path::PathBuf> {
     Some(get_app_config_dir()?.join("plan_state.json"))
 }
 
+/// Get the undo/redo history file path for native platforms
+#[cfg(not(target_family = "wasm"))]
+fn get_history_path() -> Option<std::path::PathBuf> {
+    Some(get_app_config_dir()?.join("history.json"))
+}
+
 /// Save preferences to config file
 #[cfg(not(target_family = "wasm"))]
 pub fn save_preferences(prefs: &Preferences) -> Result<(), String> {
@@ -200,35 +314,44 @@ pub fn save_preferences(prefs: &Preferences) -> Result<(), String> {
 }
 
 /// Load preferences from config file
+///
+/// Preferences saved by a newer version are an error, so the caller can
+/// avoid overwriting them; other unreadable data is ignored.
 #[cfg(not(target_family = "wasm"))]
-pub fn load_preferences() -> Option<Preferences> {
-    let path = get_preferences_path()?;
+pub fn load_preferences() -> Result<Option<Preferences>, MigrationError> {
+    let Some(path) = get_preferences_path() else {
+        return Ok(None);
+    };
 
     if !path.exists() {
         debug!("No preferences file found at {:?}", path);
-        return None;
+        return Ok(None);
     }
 
     let json = match std::fs::read_to_string(&path) {
         Ok(j) => j,
         Err(e) => {
             error!("Failed to read preferences file {:?}: {}", path, e);
-            return None;
+            return Ok(None);
         }
     };
 
-    match serde_json::from_str::<Preferences>(&json) {
+    match load_json::<Preferences>(&json) {
         Ok(prefs) => {
             info!(
                 "Loaded preferences from {:?} ({} team members)",
                 path,
                 prefs.team_members.len()
             );
-            Some(prefs)
+            Ok(Some(prefs))
+        }
+        Err(e @ MigrationError::NewerVersion { .. }) => {
+            warn!("Preferences in {:?} are from a newer version: {}", path, e);
+            Err(e)
         }
         Err(e) => {
             warn!("Failed to parse preferences from {:?}: {}", path, e);
-            None
+            Ok(None)
         }
     }
 }
@@ -275,24 +398,28 @@ pub fn save_plan_state(state: &PlanState) -> Result<(), String> {
 }
 
 /// Load plan state from config file
+///
+/// A plan saved by a newer version is an error, like `load_preferences`.
 #[cfg(not(target_family = "wasm"))]
-pub fn load_plan_state() -> Option<PlanState> {
-    let path = get_plan_state_path()?;
+pub fn load_plan_state() -> Result<Option<PlanState>, MigrationError> {
+    let Some(path) = get_plan_state_path() else {
+        return Ok(None);
+    };
 
     if !path.exists() {
         debug!("No plan state file found at {:?}", path);
-        return None;
+        return Ok(None);
     }
 
     let json = match std::fs::read_to_string(&path) {
         Ok(j) => j,
         Err(e) => {
             error!("Failed to read plan state file {:?}: {}", path, e);
-            return None;
+            return Ok(None);
         }
     };
 
-    match serde_json::from_str::<PlanState>(&json) {
+    match load_json::<PlanState>(&json) {
         Ok(state) => {
             info!(
                 "Loaded plan state from {:?} ({}, {} allocations)",
@@ -300,11 +427,15 @@ pub fn load_plan_state() -> Option<PlanState> {
                 state.quarter_name,
                 state.allocations.len()
             );
-            Some(state)
+            Ok(Some(state))
+        }
+        Err(e @ MigrationError::NewerVersion { .. }) => {
+            warn!("Plan state in {:?} is from a newer version: {}", path, e);
+            Err(e)
         }
         Err(e) => {
             warn!("Failed to parse plan state from {:?}: {}", path, e);
-            None
+            Ok(None)
         }
     }
 }
@@ -324,3 +455,65 @@ pub fn clear_plan_state() -> Result<(), String> {
 
     Ok(())
 }
+
+/// Save undo/redo history to config file
+#[cfg(not(target_family = "wasm"))]
+pub fn save_history(history: &EditHistory) -> Result<(), String> {
+    let path = get_history_path().ok_or("Could not determine config directory")?;
+
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string(history)
+        .map_err(|e| format!("Failed to serialize history: {}", e))?;
+
+    std::fs::write(&path, &json).map_err(|e| format!("Failed to write history file: {}", e))?;
+
+    debug!("Saved history to {:?} ({} bytes)", path, json.len());
+    Ok(())
+}
+
+/// Load undo/redo history from config file
+#[cfg(not(target_family = "wasm"))]
+pub fn load_history() -> Option<EditHistory> {
+    let path = get_history_path()?;
+
+    if !path.exists() {
+        debug!("No history file found at {:?}", path);
+        return None;
+    }
+
+    let json = match std::fs::read_to_string(&path) {
+        Ok(j) => j,
+        Err(e) => {
+            error!("Failed to read history file {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    match serde_json::from_str::<EditHistory>(&json) {
+        Ok(history) => Some(history),
+        Err(e) => {
+            warn!("Failed to parse history from {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Clear undo/redo history by removing history file
+#[cfg(not(target_family = "wasm"))]
+pub fn clear_history() -> Result<(), String> {
+    let path = get_history_path().ok_or("Could not determine config directory")?;
+
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove history file: {}", e))?;
+        info!("Cleared history file at {:?}", path);
+    } else {
+        debug!("No history file to clear at {:?}", path);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! Command-line parsing
+//!
+//! Hand-rolled rather than pulling in an argument parser: the commands take
+//! one or two paths and a couple of flags.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "\
+Usage: planner-cli <COMMAND> [OPTIONS]
+
+Commands:
+  validate [--strict] <FILE>...       Check plan files; exits 1 if any has errors
+                                      (or warnings, with --strict)
+  summary <FILE>                      Print capacity and utilization
+  diff <OLD> <NEW>                    List changes between two plan files;
+                                      exits 1 if there are any
+  render <FILE> [--format <FORMAT>]   Render a report: markdown (default), html,
+         [--output <PATH>]            csv (allocation grid) or csv-long
+  migrate <FILE> [--output <PATH>]    Upgrade a plan file to the current format,
+         [--in-place]                 writing to stdout unless told otherwise
+
+Options:
+  -h, --help     Print this help
+  -V, --version  Print the version
+
+FILE is a plan file saved from Planner; \"-\" reads it from stdin.";
+
+/// A parsed invocation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Validate {
+        files: Vec<PathBuf>,
+        /// Treat warnings as errors
+        strict: bool,
+    },
+    Summary {
+        file: PathBuf,
+    },
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+    },
+    Render {
+        file: PathBuf,
+        format: RenderFormat,
+        output: Option<PathBuf>,
+    },
+    Migrate {
+        file: PathBuf,
+        output: Option<PathBuf>,
+        in_place: bool,
+    },
+    Help,
+    Version,
+}
+
+/// Report formats for `render`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Markdown,
+    Html,
+    /// Allocation grid: one row per week, one column per member
+    Csv,
+    /// One row per assignment
+    CsvLong,
+}
+
+impl RenderFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Some(RenderFormat::Markdown),
+            "html" => Some(RenderFormat::Html),
+            "csv" => Some(RenderFormat::Csv),
+            "csv-long" => Some(RenderFormat::CsvLong),
+            _ => None,
+        }
+    }
+}
+
+/// A command line that doesn't match the usage
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// Parse the arguments after the program name
+pub fn parse_args<I>(args: I) -> Result<Command, UsageError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let Some(name) = args.next() else {
+        return Err(UsageError("No command given".to_string()));
+    };
+    match name.as_str() {
+        "-h" | "--help" | "help" => return Ok(Command::Help),
+        "-V" | "--version" => return Ok(Command::Version),
+        _ => {}
+    }
+
+    let mut paths = Vec::new();
+    let mut strict = false;
+    let mut in_place = false;
+    let mut format = None;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--strict" if name == "validate" => strict = true,
+            "--in-place" if name == "migrate" => in_place = true,
+            "-f" | "--format" if name == "render" => {
+                let value = flag_value(&arg, args.next())?;
+                format = Some(RenderFormat::parse(&value).ok_or_else(|| {
+                    UsageError(format!(
+                        "Unknown format \"{}\" (expected markdown, html, csv or csv-long)",
+                        value
+                    ))
+                })?);
+            }
+            "-o" | "--output" if name == "render" || name == "migrate" => {
+                output = Some(PathBuf::from(flag_value(&arg, args.next())?));
+            }
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(UsageError(format!(
+                    "Unknown option \"{}\" for {}",
+                    flag, name
+                )))
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    match name.as_str() {
+        "validate" => {
+            if paths.is_empty() {
+                return Err(UsageError("validate needs at least one file".to_string()));
+            }
+            Ok(Command::Validate {
+                files: paths,
+                strict,
+            })
+        }
+        "summary" => {
+            let [file] = exact_paths(&name, paths)?;
+            Ok(Command::Summary { file })
+        }
+        "diff" => {
+            let [old, new] = exact_paths(&name, paths)?;
+            Ok(Command::Diff { old, new })
+        }
+        "render" => {
+            let [file] = exact_paths(&name, paths)?;
+            Ok(Command::Render {
+                file,
+                format: format.unwrap_or(RenderFormat::Markdown),
+                output,
+            })
+        }
+        "migrate" => {
+            let [file] = exact_paths(&name, paths)?;
+            if in_place && output.is_some() {
+                return Err(UsageError(
+                    "migrate takes --output or --in-place, not both".to_string(),
+                ));
+            }
+            if in_place && file == Path::new("-") {
+                return Err(UsageError("--in-place needs a file, not stdin".to_string()));
+            }
+            Ok(Command::Migrate {
+                file,
+                output,
+                in_place,
+            })
+        }
+        _ => Err(UsageError(format!("Unknown command \"{}\"", name))),
+    }
+}
+
+fn flag_value(flag: &str, value: Option<String>) -> Result<String, UsageError> {
+    value.ok_or_else(|| UsageError(format!("{} needs a value", flag)))
+}
+
+fn exact_paths<const N: usize>(
+    command: &str,
+    paths: Vec<PathBuf>,
+) -> Result<[PathBuf; N], UsageError> {
+    let count = paths.len();
+    paths.try_into().map_err(|_| {
+        let expected = match N {
+            1 => "one file".to_string(),
+            n => format!("{} files", n),
+        };
+        UsageError(format!("{} takes {}, got {}", command, expected, count))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Command, UsageError> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse("validate --strict q1.json q2.json"),
+            Ok(Command::Validate {
+                files: vec![PathBuf::from("q1.json"), PathBuf::from("q2.json")],
+                strict: true,
+            })
+        );
+        assert_eq!(
+            parse("render q1.json --format csv-long -o grid.csv"),
+            Ok(Command::Render {
+                file: PathBuf::from("q1.json"),
+                format: RenderFormat::CsvLong,
+                output: Some(PathBuf::from("grid.csv")),
+            })
+        );
+        assert_eq!(
+            parse("render q1.json"),
+            Ok(Command::Render {
+                file: PathBuf::from("q1.json"),
+                format: RenderFormat::Markdown,
+                output: None,
+            })
+        );
+        assert_eq!(parse("summary --help"), Ok(Command::Help));
+
+        assert_eq!(
+            parse("diff q1.json"),
+            Err(UsageError("diff takes 2 files, got 1".to_string()))
+        );
+        assert_eq!(
+            parse("summary --strict q1.json"),
+            Err(UsageError(
+                "Unknown option \"--strict\" for summary".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("render q1.json --format pdf"),
+            Err(UsageError(
+                "Unknown format \"pdf\" (expected markdown, html, csv or csv-long)".to_string()
+            ))
+        );
+        assert!(parse("migrate q1.json --in-place -o out.json").is_err());
+        assert!(parse("").is_err());
+        assert!(parse("plan q1.json").is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! Subcommand implementations
+//!
+//! Each command returns its exit status; problems that stop a command from
+//! running at all (unreadable files, bad JSON) are `CliError`s.
+
+use std::fmt::{self, Write as _};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use planner_core::diff::diff_exports;
+use planner_core::formats::csv::{allocation_matrix_csv, allocations_long_csv};
+use planner_core::formats::html::report_html;
+use planner_core::formats::markdown::report_markdown;
+use planner_core::migration::{load_json, DocumentKind, MigrationError};
+use planner_core::models::PlanExport;
+use planner_core::report::{format_date_range, status_label, PlanReport};
+use planner_core::validation::validate_export;
+
+use crate::args::RenderFormat;
+
+/// Exit status when a check fails: validation errors, or a non-empty diff
+pub const EXIT_FAILURE: u8 = 1;
+
+/// Exit status when a command can't run
+pub const EXIT_ERROR: u8 = 2;
+
+/// Errors that stop a command
+#[derive(Debug)]
+pub enum CliError {
+    Read {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Write {
+        path: PathBuf,
+        error: io::Error,
+    },
+    /// The file isn't a plan this version can load
+    Load {
+        path: PathBuf,
+        error: MigrationError,
+    },
+    Serialize(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Read { path, error } => {
+                write!(f, "Couldn't read {}: {}", path.display(), error)
+            }
+            CliError::Write { path, error } => {
+                write!(f, "Couldn't write {}: {}", path.display(), error)
+            }
+            CliError::Load { path, error } => write!(f, "{}: {}", path.display(), error),
+            CliError::Serialize(message) => write!(f, "Failed to serialize: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Read a file, or stdin for "-"
+fn read_file(path: &Path) -> Result<String, CliError> {
+    let result = if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        std::fs::read_to_string(path)
+    };
+    result.map_err(|error| CliError::Read {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Load a plan file, upgrading older formats
+fn load_plan(path: &Path) -> Result<PlanExport, CliError> {
+    load_json(&read_file(path)?).map_err(|error| CliError::Load {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Write to a file, or stdout when there's no path
+fn write_output(output: Option<&Path>, contents: &str) -> Result<(), CliError> {
+    match output {
+        Some(path) => std::fs::write(path, contents).map_err(|error| CliError::Write {
+            path: path.to_path_buf(),
+            error,
+        }),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+/// Check each file and print its issues
+///
+/// Files that fail to load count as failing; the rest are still checked so
+/// one run reports everything.
+pub fn validate(files: &[PathBuf], strict: bool) -> ExitCode {
+    let mut failed = false;
+    for path in files {
+        match load_plan(path) {
+            Ok(export) => {
+                let report = validate_export(&export);
+                print!("{}: {}", path.display(), report);
+                if report.has_errors() || (strict && !report.is_empty()) {
+                    failed = true;
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::from(EXIT_FAILURE)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Print capacity and utilization for the team and each member
+pub fn summary(file: &Path) -> Result<ExitCode, CliError> {
+    let (prefs, state) = load_plan(file)?.into_signals();
+    print!("{}", summary_text(&PlanReport::new(&prefs, &state)));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn summary_text(report: &PlanReport) -> String {
+    let mut out = String::new();
+    // Writing to a String can't fail
+    let _ = write_summary(&mut out, report);
+    out
+}
+
+fn write_summary(out: &mut String, report: &PlanReport) -> fmt::Result {
+    writeln!(out, "{} · {}", report.team_name, report.quarter_name)?;
+    writeln!(
+        out,
+        "{} ({} weeks)",
+        format_date_range(report.quarter_start, report.quarter_end),
+        report.num_weeks
+    )?;
+    writeln!(
+        out,
+        "Capacity {:.1} weeks, allocated {:.1} weeks ({:.0}%): {}",
+        report.capacity,
+        report.allocated,
+        report.utilization_pct(),
+        status_label(report.status)
+    )?;
+
+    if !report.members.is_empty() {
+        writeln!(out, "\nTeam members")?;
+        let width = column_width(report.members.iter().map(|m| m.name.as_str()));
+        for member in &report.members {
+            writeln!(
+                out,
+                "  {:<width$}  {:>5.1} / {:>5.1} weeks  {:>4.0}%  {}",
+                member.name,
+                member.allocated,
+                member.capacity,
+                member.utilization_pct(),
+                status_label(member.status)
+            )?;
+        }
+    }
+
+    if !report.roadmap_projects.is_empty() {
+        writeln!(out, "\nRoadmap projects")?;
+        let width = column_width(report.roadmap_projects.iter().map(|p| p.name.as_str()));
+        for project in &report.roadmap_projects {
+            writeln!(
+                out,
+                "  {:<width$}  {:>5.1} / {:>5.1} weeks  launch {}  {}",
+                project.name,
+                project.allocated,
+                project.estimate,
+                project.launch_date.format("%b %-d"),
+                status_label(project.status)
+            )?;
+        }
+    }
+
+    if !report.callouts.is_empty() {
+        writeln!(out, "\nCallouts")?;
+        for callout in &report.callouts {
+            writeln!(out, "  {}", callout.message())?;
+        }
+    }
+    Ok(())
+}
+
+fn column_width<'a>(names: impl Iterator<Item = &'a str>) -> usize {
+    names.map(|name| name.chars().count()).max().unwrap_or(0)
+}
+
+/// Print the changes from `old` to `new`
+pub fn diff(old: &Path, new: &Path) -> Result<ExitCode, CliError> {
+    let changes = diff_exports(&load_plan(old)?, &load_plan(new)?);
+    print!("{}", changes);
+    Ok(if changes.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_FAILURE)
+    })
+}
+
+/// Render a report in the chosen format
+pub fn render(
+    file: &Path,
+    format: RenderFormat,
+    output: Option<&Path>,
+) -> Result<ExitCode, CliError> {
+    let (prefs, state) = load_plan(file)?.into_signals();
+    let rendered = match format {
+        RenderFormat::Markdown => report_markdown(&PlanReport::new(&prefs, &state)),
+        RenderFormat::Html => report_html(&PlanReport::new(&prefs, &state)),
+        RenderFormat::Csv => allocation_matrix_csv(&prefs, &state),
+        RenderFormat::CsvLong => allocations_long_csv(&prefs, &state),
+    };
+    write_output(output, &rendered)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Rewrite a plan file in the current format
+pub fn migrate(file: &Path, output: Option<&Path>, in_place: bool) -> Result<ExitCode, CliError> {
+    let export = load_plan(file)?;
+    let json =
+        serde_json::to_string_pretty(&export).map_err(|e| CliError::Serialize(e.to_string()))?;
+    let output = if in_place { Some(file) } else { output };
+    write_output(output, &format!("{}\n", json))?;
+    if let Some(path) = output {
+        eprintln!(
+            "Wrote {} in format {}",
+            path.display(),
+            DocumentKind::PlanExport.current_version()
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+    use planner_core::models::{
+        Allocation, Assignment, PlanState, Preferences, Role, RoleWeeks, TeamMember,
+        TechnicalProject,
+    };
+
+    fn clean_plan() -> PlanExport {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.team_members = vec![TeamMember::new(
+            "Alice Kim".to_string(),
+            Role::ENGINEERING,
+            12.0,
+        )];
+        PlanExport::from_signals(prefs, PlanState::new("Q1 2025".to_string(), start, 13))
+    }
+
+    /// Write a plan file to the temp directory
+    fn write_plan(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("planner-cli-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn validate_plan(name: &str, export: &PlanExport, strict: bool) -> ExitCode {
+        let path = write_plan(name, &serde_json::to_string(export).unwrap());
+        let status = validate(std::slice::from_ref(&path), strict);
+        std::fs::remove_file(path).unwrap();
+        status
+    }
+
+    #[test]
+    fn test_validate_clean_plan_passes() {
+        let export = clean_plan();
+        assert!(validate_export(&export).is_empty());
+        assert_eq!(validate_plan("clean", &export, false), ExitCode::SUCCESS);
+        assert_eq!(
+            validate_plan("clean-strict", &export, true),
+            ExitCode::SUCCESS
+        );
+    }
+
+    #[test]
+    fn test_validate_warnings_fail_only_when_strict() {
+        let mut export = clean_plan();
+        // Not a Monday: a warning
+        export.quarter_start_date += Duration::days(1);
+        let report = validate_export(&export);
+        assert!(!report.is_empty() && !report.has_errors(), "{}", report);
+
+        assert_eq!(validate_plan("warnings", &export, false), ExitCode::SUCCESS);
+        assert_eq!(
+            validate_plan("warnings-strict", &export, true),
+            ExitCode::from(EXIT_FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_validate_errors_fail() {
+        let mut export = clean_plan();
+        export.team_name = String::new();
+        assert!(validate_export(&export).has_errors());
+        assert_eq!(
+            validate_plan("errors", &export, false),
+            ExitCode::from(EXIT_FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_validate_load_failures_fail() {
+        let missing = std::env::temp_dir().join("planner-cli-missing.json");
+        assert_eq!(validate(&[missing], false), ExitCode::from(EXIT_FAILURE));
+
+        // The other files are still checked, but the run fails
+        let garbled = write_plan("garbled", "{ not json");
+        let clean = write_plan("loads", &serde_json::to_string(&clean_plan()).unwrap());
+        assert_eq!(
+            validate(&[clean.clone(), garbled.clone()], false),
+            ExitCode::from(EXIT_FAILURE)
+        );
+        std::fs::remove_file(garbled).unwrap();
+        std::fs::remove_file(clean).unwrap();
+    }
+
+    #[test]
+    fn test_summary_text() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.team_members = vec![
+            TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 2.0),
+            TeamMember::new("Bo".to_string(), Role::ENGINEERING, 2.0),
+        ];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 2);
+        let api = TechnicalProject::new("API".to_string(), None, RoleWeeks::new(), start);
+        for week in [0, 7] {
+            let mut alloc = Allocation::new(prefs.team_members[0].id, start + Duration::days(week));
+            alloc.assignments.push(Assignment::new(api.id, 100.0));
+            state.allocations.push(alloc);
+        }
+        state.technical_projects.push(api);
+
+        assert_eq!(
+            summary_text(&PlanReport::new(&prefs, &state)),
+            "Payments · Q1 2025\n\
+             Jan 6 – Jan 19, 2025 (2 weeks)\n\
+             Capacity 4.0 weeks, allocated 2.0 weeks (50%): Off target\n\
+             \n\
+             Team members\n\
+             \x20 Alice Kim    2.0 /   2.0 weeks   100%  On target\n\
+             \x20 Bo           0.0 /   2.0 weeks     0%  Off target\n\
+             \n\
+             Callouts\n\
+             \x20 Bo is under-allocated: 0.0 of 2.0 weeks\n"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! planner-cli: check, summarize and convert plan files without the UI
+//!
+//! Built on planner-core, so it reads the same files the app saves and applies
+//! the same validation, reports and migrations. Exit codes are meant for CI:
+//! 0 on success, 1 when a check fails (validation errors, or differences for
+//! `diff`), 2 when the command can't run.
+
+use std::process::ExitCode;
+
+use args::{parse_args, Command, USAGE};
+use commands::EXIT_ERROR;
+
+mod args;
+mod commands;
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Version => {
+            println!("planner-cli {}", env!("CARGO_PKG_VERSION"));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Validate { files, strict } => Ok(commands::validate(&files, strict)),
+        Command::Summary { file } => commands::summary(&file),
+        Command::Diff { old, new } => commands::diff(&old, &new),
+        Command::Render {
+            file,
+            format,
+            output,
+        } => commands::render(&file, format, output.as_deref()),
+        Command::Migrate {
+            file,
+            output,
+            in_place,
+        } => commands::migrate(&file, output.as_deref(), in_place),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        ExitCode::from(EXIT_ERROR)
+    })
+}
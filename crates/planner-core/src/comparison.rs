@@ -0,0 +1,412 @@
+//! Side-by-side comparison of two versions of a plan
+//!
+//! Each version is summarized with the same `calculate_*` methods the views
+//! use, so the numbers match what each plan shows on its own. Versions may
+//! have different rosters (e.g., a local plan vs. an imported file), which is
+//! why each side brings its own `Preferences`.
+//!
+//! Roadmap projects and team members are matched by ID, falling back to a
+//! case-insensitive name match for plans that were built independently.
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use crate::models::{
+    get_capacity_status, BadgeType, PlanState, PlanView, Preferences, RoadmapProject, RoleWeeks,
+    TeamMember,
+};
+
+/// One version of a plan to compare
+#[derive(Debug, Clone, Copy)]
+pub struct PlanVersion<'a> {
+    pub label: &'a str,
+    pub preferences: &'a Preferences,
+    pub state: &'a PlanState,
+}
+
+impl<'a> PlanVersion<'a> {
+    fn view(&self) -> PlanView<'a> {
+        PlanView::new(self.preferences, self.state)
+    }
+}
+
+/// Whether a roadmap project's allocations deliver it by its launch date
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LaunchFeasibility {
+    /// Fully allocated, finishing on or before the launch date
+    OnTrack,
+    /// Last allocated week ends after the launch date
+    Late { finishes: NaiveDate },
+    /// Finishes in time, but with fewer weeks than estimated
+    Understaffed,
+    /// No allocations on any linked technical project
+    Unscheduled,
+}
+
+impl LaunchFeasibility {
+    pub fn label(self) -> String {
+        match self {
+            LaunchFeasibility::OnTrack => "On track".to_string(),
+            LaunchFeasibility::Late { finishes } => {
+                format!("Late ({})", finishes.format("%b %-d"))
+            }
+            LaunchFeasibility::Understaffed => "Understaffed".to_string(),
+            LaunchFeasibility::Unscheduled => "Unscheduled".to_string(),
+        }
+    }
+
+    pub fn badge_type(self) -> BadgeType {
+        match self {
+            LaunchFeasibility::OnTrack => BadgeType::Success,
+            LaunchFeasibility::Understaffed => BadgeType::Warning,
+            LaunchFeasibility::Late { .. } => BadgeType::Error,
+            LaunchFeasibility::Unscheduled => BadgeType::Neutral,
+        }
+    }
+}
+
+/// A roadmap project as it stands in one version
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadmapProjectSummary {
+    pub estimate: f32,
+    pub allocated: RoleWeeks,
+    pub launch_date: NaiveDate,
+    /// Last day of the last allocated week across linked technical projects
+    pub finishes: Option<NaiveDate>,
+    pub feasibility: LaunchFeasibility,
+    pub status: BadgeType,
+}
+
+impl RoadmapProjectSummary {
+    fn new(project: &RoadmapProject, version: &PlanVersion) -> Self {
+        let state = version.state;
+        let allocated = version.view().roadmap_allocated_weeks(&project.id);
+        let estimate = project.total_estimate();
+
+        let finishes = state
+            .technical_projects
+            .iter()
+            .filter(|tp| tp.roadmap_project_id == Some(project.id))
+            .filter_map(|tp| state.get_project_allocation_date_range(&tp.id))
+            .map(|(_, last_week)| last_week + Duration::days(6))
+            .max();
+
+        // Allow a rounding error's worth of shortfall
+        let feasibility = match finishes {
+            None => LaunchFeasibility::Unscheduled,
+            Some(finishes) if finishes > project.launch_date => {
+                LaunchFeasibility::Late { finishes }
+            }
+            Some(_) if allocated.total() + 0.01 < estimate => LaunchFeasibility::Understaffed,
+            Some(_) => LaunchFeasibility::OnTrack,
+        };
+
+        Self {
+            estimate,
+            status: get_capacity_status(allocated.total(), estimate),
+            allocated,
+            launch_date: project.launch_date,
+            finishes,
+            feasibility,
+        }
+    }
+}
+
+/// A team member's load in one version
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemberUtilization {
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+}
+
+impl MemberUtilization {
+    fn new(member: &TeamMember, version: &PlanVersion) -> Self {
+        let view = version.view();
+        let capacity = view.member_capacity(member);
+        let allocated = view.team_member_allocated_weeks(&member.id);
+        Self {
+            capacity,
+            allocated,
+            status: get_capacity_status(allocated, capacity),
+        }
+    }
+
+    /// Allocated share of capacity, as a percentage
+    pub fn utilization_pct(&self) -> f32 {
+        if self.capacity > 0.0 {
+            self.allocated / self.capacity * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Whole-team numbers for one version
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanTotals {
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+}
+
+impl PlanTotals {
+    fn new(version: &PlanVersion) -> Self {
+        let capacity = version.view().total_capacity().total();
+        let allocated = version.view().total_allocated().total();
+        Self {
+            capacity,
+            allocated,
+            status: get_capacity_status(allocated, capacity),
+        }
+    }
+}
+
+/// One row of a side-by-side comparison; a side is None if the item only
+/// exists in the other version
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonRow<T> {
+    pub id: Uuid,
+    pub name: String,
+    pub left: Option<T>,
+    pub right: Option<T>,
+}
+
+impl<T: PartialEq> ComparisonRow<T> {
+    /// True if both sides exist and are identical
+    pub fn is_unchanged(&self) -> bool {
+        self.left.is_some() && self.left == self.right
+    }
+}
+
+/// Side-by-side comparison of two plan versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanComparison {
+    pub left_label: String,
+    pub right_label: String,
+    pub left_totals: PlanTotals,
+    pub right_totals: PlanTotals,
+    pub roadmap_projects: Vec<ComparisonRow<RoadmapProjectSummary>>,
+    pub members: Vec<ComparisonRow<MemberUtilization>>,
+}
+
+/// Compare two plan versions
+///
+/// Rows follow the left version's order, with items only in the right
+/// version appended.
+pub fn compare_plans(left: PlanVersion, right: PlanVersion) -> PlanComparison {
+    let roadmap_projects = pair_by_identity(
+        &left.state.roadmap_projects,
+        &right.state.roadmap_projects,
+        |p| (p.id, p.name.as_str()),
+    )
+    .into_iter()
+    .map(|(id, name, l, r)| ComparisonRow {
+        id,
+        name,
+        left: l.map(|p| RoadmapProjectSummary::new(p, &left)),
+        right: r.map(|p| RoadmapProjectSummary::new(p, &right)),
+    })
+    .collect();
+
+    let members = pair_by_identity(
+        &left.preferences.team_members,
+        &right.preferences.team_members,
+        |m| (m.id, m.name.as_str()),
+    )
+    .into_iter()
+    .map(|(id, name, l, r)| ComparisonRow {
+        id,
+        name,
+        left: l.map(|m| MemberUtilization::new(m, &left)),
+        right: r.map(|m| MemberUtilization::new(m, &right)),
+    })
+    .collect();
+
+    PlanComparison {
+        left_label: left.label.to_string(),
+        right_label: right.label.to_string(),
+        left_totals: PlanTotals::new(&left),
+        right_totals: PlanTotals::new(&right),
+        roadmap_projects,
+        members,
+    }
+}
+
+/// Pair up items by ID, then by case-insensitive name
+///
+/// Returns (id, name, left, right) per row; id and name come from the left
+/// item when both exist.
+#[allow(clippy::type_complexity)]
+fn pair_by_identity<'a, T>(
+    left: &'a [T],
+    right: &'a [T],
+    identity: impl Fn(&T) -> (Uuid, &str),
+) -> Vec<(Uuid, String, Option<&'a T>, Option<&'a T>)> {
+    let mut unmatched: Vec<&T> = right.iter().collect();
+    let mut take = |matches: &dyn Fn(&T) -> bool| {
+        let index = unmatched.iter().position(|item| matches(item))?;
+        Some(unmatched.remove(index))
+    };
+
+    let mut pairs: Vec<(&T, Option<&T>)> = left
+        .iter()
+        .map(|item| {
+            let id = identity(item).0;
+            (item, take(&|other: &T| identity(other).0 == id))
+        })
+        .collect();
+    // Items with no ID match may still match by name
+    for pair in pairs.iter_mut().filter(|(_, other)| other.is_none()) {
+        let name = identity(pair.0).1;
+        pair.1 = take(&|other: &T| identity(other).1.eq_ignore_ascii_case(name));
+    }
+
+    pairs
+        .into_iter()
+        .map(|(item, other)| (Some(item), other))
+        .chain(unmatched.into_iter().map(|other| (None, Some(other))))
+        .filter_map(|(l, r)| {
+            let (id, name) = identity(l.or(r)?);
+            Some((id, name.to_string(), l, r))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Allocation, Assignment, ProjectColor, Role, TechnicalProject};
+
+    fn quarter_start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    fn sample() -> (Preferences, PlanState) {
+        let prefs = Preferences {
+            team_members: vec![
+                TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0),
+                TeamMember::new("Bob Li".to_string(), Role::ENGINEERING, 12.0),
+            ],
+            ..Preferences::default()
+        };
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start(), 13);
+        let roadmap = RoadmapProject::new(
+            "Payment Gateway".to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+            quarter_start(),
+            quarter_start() + Duration::weeks(4),
+            ProjectColor::Blue,
+        );
+        state.technical_projects.push(TechnicalProject::new(
+            "Gateway API".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+            quarter_start(),
+        ));
+        state.roadmap_projects.push(roadmap);
+        (prefs, state)
+    }
+
+    fn allocate(state: &mut PlanState, member_id: Uuid, week: i64) {
+        let project_id = state.technical_projects[0].id;
+        let mut alloc = Allocation::new(member_id, quarter_start() + Duration::weeks(week));
+        alloc.assignments.push(Assignment::new(project_id, 100.0));
+        state.allocations.push(alloc);
+    }
+
+    fn version<'a>(
+        label: &'a str,
+        prefs: &'a Preferences,
+        state: &'a PlanState,
+    ) -> PlanVersion<'a> {
+        PlanVersion {
+            label,
+            preferences: prefs,
+            state,
+        }
+    }
+
+    #[test]
+    fn test_launch_feasibility() {
+        let (prefs, mut state) = sample();
+        let alice = prefs.team_members[0].id;
+        let unscheduled = state.clone();
+        allocate(&mut state, alice, 0);
+        let understaffed = state.clone();
+        allocate(&mut state, alice, 1);
+        let on_track = state.clone();
+        allocate(&mut state, alice, 6);
+
+        let feasibility = |state: &PlanState| {
+            compare_plans(version("a", &prefs, state), version("b", &prefs, state)).roadmap_projects
+                [0]
+            .left
+            .as_ref()
+            .unwrap()
+            .feasibility
+        };
+        assert_eq!(feasibility(&unscheduled), LaunchFeasibility::Unscheduled);
+        assert_eq!(feasibility(&understaffed), LaunchFeasibility::Understaffed);
+        assert_eq!(feasibility(&on_track), LaunchFeasibility::OnTrack);
+        assert_eq!(
+            feasibility(&state),
+            LaunchFeasibility::Late {
+                finishes: quarter_start() + Duration::weeks(6) + Duration::days(6)
+            }
+        );
+    }
+
+    #[test]
+    fn test_compare_scenario_with_main_plan() {
+        let (prefs, mut main) = sample();
+        let (alice, bob) = (prefs.team_members[0].id, prefs.team_members[1].id);
+        allocate(&mut main, alice, 0);
+        let mut scenario = main.clone();
+        allocate(&mut scenario, bob, 0);
+
+        let comparison = compare_plans(
+            version("Main plan", &prefs, &main),
+            version("Bob helps", &prefs, &scenario),
+        );
+        assert_eq!(comparison.left_totals.allocated, 1.0);
+        assert_eq!(comparison.right_totals.allocated, 2.0);
+
+        let gateway = &comparison.roadmap_projects[0];
+        assert_eq!(gateway.name, "Payment Gateway");
+        assert_eq!(gateway.left.as_ref().unwrap().status, BadgeType::Error);
+        assert_eq!(gateway.right.as_ref().unwrap().status, BadgeType::Success);
+
+        let members = &comparison.members;
+        assert!(members[0].is_unchanged());
+        assert!(!members[1].is_unchanged());
+        assert_eq!(members[1].right.unwrap().allocated, 1.0);
+    }
+
+    #[test]
+    fn test_rows_pair_by_name_and_keep_unmatched() {
+        let (left_prefs, left_state) = sample();
+        let (mut right_prefs, right_state) = sample();
+        // Same people built independently get different IDs
+        right_prefs.team_members[1] =
+            TeamMember::new("bob li".to_string(), Role::ENGINEERING, 12.0);
+        right_prefs.team_members.push(TeamMember::new(
+            "Cara Diaz".to_string(),
+            Role::SCIENCE,
+            12.0,
+        ));
+
+        let comparison = compare_plans(
+            version("Mine", &left_prefs, &left_state),
+            version("Theirs", &right_prefs, &right_state),
+        );
+        let names: Vec<_> = comparison.members.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["Alice Kim", "Bob Li", "Cara Diaz"]);
+        assert!(comparison.members[1].right.is_some());
+        assert!(comparison.members[2].left.is_none());
+
+        // Independently built roadmap projects only match by name
+        assert_eq!(comparison.roadmap_projects.len(), 1);
+        assert!(comparison.roadmap_projects[0].right.is_some());
+    }
+}
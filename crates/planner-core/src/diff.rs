@@ -0,0 +1,770 @@
+//! Structural diff between two plan exports
+//!
+//! Projects, team members and scenarios are matched by ID, so renames show up
+//! as a changed name rather than a removal plus an addition. Allocations are
+//! compared cell by cell, keyed by `(team_member_id, week_start_date)`; a cell
+//! with no assignments counts as empty.
+//!
+//! Field values are rendered to strings when the diff is built, resolving
+//! IDs to names from whichever export still has them, so a change list can
+//! be displayed without the exports at hand.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::{PlanExport, ProjectDependency, Role, RoleWeeks, Scenario};
+
+/// A field that changed on a plan, project, member or scenario
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    TeamName,
+    QuarterName,
+    QuarterStartDate,
+    NumWeeks,
+    Name,
+    Estimate(Role),
+    StartDate,
+    LaunchDate,
+    ExpectedCompletion,
+    Color,
+    Notes,
+    RoadmapProject,
+    RequiredSkills,
+    Dependencies,
+    Role,
+    Capacity,
+    Email,
+    EmploymentStart,
+    EmploymentEnd,
+    TimeOff,
+    Skills,
+    /// A scenario's projects and allocations
+    Contents,
+}
+
+impl Field {
+    pub fn label(&self) -> String {
+        match self {
+            Field::TeamName => "team name".to_string(),
+            Field::QuarterName => "quarter name".to_string(),
+            Field::QuarterStartDate => "quarter start".to_string(),
+            Field::NumWeeks => "weeks".to_string(),
+            Field::Name => "name".to_string(),
+            Field::Estimate(role) => format!("{} estimate", role),
+            Field::StartDate => "start date".to_string(),
+            Field::LaunchDate => "launch date".to_string(),
+            Field::ExpectedCompletion => "expected completion".to_string(),
+            Field::Color => "color".to_string(),
+            Field::Notes => "notes".to_string(),
+            Field::RoadmapProject => "roadmap project".to_string(),
+            Field::RequiredSkills => "required skills".to_string(),
+            Field::Dependencies => "dependencies".to_string(),
+            Field::Role => "role".to_string(),
+            Field::Capacity => "capacity".to_string(),
+            Field::Email => "email".to_string(),
+            Field::EmploymentStart => "employment start".to_string(),
+            Field::EmploymentEnd => "employment end".to_string(),
+            Field::TimeOff => "time off".to_string(),
+            Field::Skills => "skills".to_string(),
+            Field::Contents => "contents".to_string(),
+        }
+    }
+}
+
+/// Old and new value of a single field, rendered for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: Field,
+    pub before: String,
+    pub after: String,
+}
+
+/// How an item differs between the two exports
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified(Vec<FieldChange>),
+}
+
+/// A project, team member or scenario that was added, removed or modified
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemChange {
+    pub id: Uuid,
+    /// Current name (the old name for removed items)
+    pub name: String,
+    pub kind: ChangeKind,
+}
+
+impl ItemChange {
+    /// Previous name, if the item was renamed
+    pub fn renamed_from(&self) -> Option<&str> {
+        match &self.kind {
+            ChangeKind::Modified(fields) => fields
+                .iter()
+                .find(|f| f.field == Field::Name)
+                .map(|f| f.before.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// One project's share of an allocation cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellAssignment {
+    pub technical_project_id: Uuid,
+    pub project_name: String,
+    pub percentage: f32,
+}
+
+/// An allocation cell whose assignments differ
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellChange {
+    pub team_member_id: Uuid,
+    pub member_name: String,
+    pub week_start_date: NaiveDate,
+    /// Empty if the cell was unallocated before
+    pub before: Vec<CellAssignment>,
+    /// Empty if the cell is unallocated now
+    pub after: Vec<CellAssignment>,
+}
+
+/// A single difference between two exports
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanChange {
+    /// Team name or quarter settings
+    Plan(FieldChange),
+    RoadmapProject(ItemChange),
+    TechnicalProject(ItemChange),
+    TeamMember(ItemChange),
+    Scenario(ItemChange),
+    Allocation(CellChange),
+}
+
+/// Everything that changed between two exports, in a stable order:
+/// plan settings, roadmap projects, technical projects, team members,
+/// scenarios, then allocation cells by member and week
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanDiff {
+    pub changes: Vec<PlanChange>,
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Allocation cell changes only
+    pub fn cell_changes(&self) -> impl Iterator<Item = &CellChange> {
+        self.changes.iter().filter_map(|change| match change {
+            PlanChange::Allocation(cell) => Some(cell),
+            _ => None,
+        })
+    }
+}
+
+/// Compute the structural diff from `old` to `new`
+pub fn diff_exports(old: &PlanExport, new: &PlanExport) -> PlanDiff {
+    let names = Names { old, new };
+    let mut changes = Vec::new();
+
+    // Plan settings
+    let plan_fields = [
+        field(Field::TeamName, &old.team_name, &new.team_name),
+        field(Field::QuarterName, &old.quarter_name, &new.quarter_name),
+        field(
+            Field::QuarterStartDate,
+            old.quarter_start_date,
+            new.quarter_start_date,
+        ),
+        field(Field::NumWeeks, old.num_weeks, new.num_weeks),
+    ];
+    changes.extend(plan_fields.into_iter().flatten().map(PlanChange::Plan));
+
+    changes.extend(
+        diff_items(
+            &old.roadmap_projects,
+            &new.roadmap_projects,
+            |p| (p.id, p.name.as_str()),
+            |a, b| {
+                let mut fields = vec![field(Field::Name, &a.name, &b.name)];
+                fields.extend(estimate_fields(&a.estimates, &b.estimates));
+                fields.extend([
+                    field(Field::StartDate, a.start_date, b.start_date),
+                    field(Field::LaunchDate, a.launch_date, b.launch_date),
+                    field(
+                        Field::Color,
+                        format!("{:?}", a.color),
+                        format!("{:?}", b.color),
+                    ),
+                    field(Field::Notes, optional(&a.notes), optional(&b.notes)),
+                ]);
+                fields.into_iter().flatten().collect()
+            },
+        )
+        .into_iter()
+        .map(PlanChange::RoadmapProject),
+    );
+
+    changes.extend(
+        diff_items(
+            &old.technical_projects,
+            &new.technical_projects,
+            |p| (p.id, p.name.as_str()),
+            |a, b| {
+                let mut fields = vec![
+                    field(Field::Name, &a.name, &b.name),
+                    field(
+                        Field::RoadmapProject,
+                        names.roadmap_project(a.roadmap_project_id),
+                        names.roadmap_project(b.roadmap_project_id),
+                    ),
+                ];
+                fields.extend(estimate_fields(&a.estimates, &b.estimates));
+                fields.extend([
+                    field(Field::StartDate, a.start_date, b.start_date),
+                    field(
+                        Field::ExpectedCompletion,
+                        optional(&a.expected_completion),
+                        optional(&b.expected_completion),
+                    ),
+                    field(Field::Notes, optional(&a.notes), optional(&b.notes)),
+                    field(
+                        Field::RequiredSkills,
+                        a.required_skills.join(", "),
+                        b.required_skills.join(", "),
+                    ),
+                    field(
+                        Field::Dependencies,
+                        names.dependencies(&a.dependencies),
+                        names.dependencies(&b.dependencies),
+                    ),
+                ]);
+                fields.into_iter().flatten().collect()
+            },
+        )
+        .into_iter()
+        .map(PlanChange::TechnicalProject),
+    );
+
+    changes.extend(
+        diff_items(
+            &old.team_members,
+            &new.team_members,
+            |m| (m.id, m.name.as_str()),
+            |a, b| {
+                [
+                    field(Field::Name, &a.name, &b.name),
+                    field(Field::Role, &a.role, &b.role),
+                    field(Field::Capacity, a.capacity, b.capacity),
+                    field(Field::Email, optional(&a.email), optional(&b.email)),
+                    field(
+                        Field::EmploymentStart,
+                        optional(&a.start_date),
+                        optional(&b.start_date),
+                    ),
+                    field(
+                        Field::EmploymentEnd,
+                        optional(&a.end_date),
+                        optional(&b.end_date),
+                    ),
+                    field(Field::TimeOff, time_off(&a.time_off), time_off(&b.time_off)),
+                    field(Field::Skills, a.skills.join(", "), b.skills.join(", ")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect()
+            },
+        )
+        .into_iter()
+        .map(PlanChange::TeamMember),
+    );
+
+    changes.extend(
+        diff_items(
+            &old.scenarios,
+            &new.scenarios,
+            |s| (s.id, s.name.as_str()),
+            |a, b| {
+                let contents = (a.branch != b.branch).then(|| FieldChange {
+                    field: Field::Contents,
+                    before: scenario_contents(a),
+                    after: scenario_contents(b),
+                });
+                [field(Field::Name, &a.name, &b.name), contents]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            },
+        )
+        .into_iter()
+        .map(PlanChange::Scenario),
+    );
+
+    changes.extend(
+        diff_cells(old, new, &names)
+            .into_iter()
+            .map(PlanChange::Allocation),
+    );
+
+    PlanDiff { changes }
+}
+
+/// Resolves IDs to names, preferring the new export
+struct Names<'a> {
+    old: &'a PlanExport,
+    new: &'a PlanExport,
+}
+
+impl Names<'_> {
+    fn exports(&self) -> [&PlanExport; 2] {
+        [self.new, self.old]
+    }
+
+    fn roadmap_project(&self, id: Option<Uuid>) -> String {
+        let Some(id) = id else {
+            return "none".to_string();
+        };
+        self.exports()
+            .iter()
+            .find_map(|e| e.roadmap_projects.iter().find(|p| p.id == id))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn technical_project(&self, id: Uuid) -> String {
+        self.exports()
+            .iter()
+            .find_map(|e| e.technical_projects.iter().find(|p| p.id == id))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn team_member(&self, id: Uuid) -> String {
+        self.exports()
+            .iter()
+            .find_map(|e| e.team_members.iter().find(|m| m.id == id))
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn dependencies(&self, dependencies: &[ProjectDependency]) -> String {
+        dependencies
+            .iter()
+            .map(|d| {
+                format!(
+                    "{} ({})",
+                    self.technical_project(d.predecessor_id),
+                    d.kind.label()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A field change if the rendered values differ
+fn field(field: Field, before: impl ToString, after: impl ToString) -> Option<FieldChange> {
+    let (before, after) = (before.to_string(), after.to_string());
+    (before != after).then_some(FieldChange {
+        field,
+        before,
+        after,
+    })
+}
+
+fn optional(value: &Option<impl ToString>) -> String {
+    value
+        .as_ref()
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+fn estimate_fields(before: &RoleWeeks, after: &RoleWeeks) -> Vec<Option<FieldChange>> {
+    let roles: BTreeSet<&Role> = before.iter().chain(after.iter()).map(|(r, _)| r).collect();
+    roles
+        .into_iter()
+        .map(|role| {
+            field(
+                Field::Estimate(role.clone()),
+                before.get(role),
+                after.get(role),
+            )
+        })
+        .collect()
+}
+
+fn time_off(entries: &[crate::models::TimeOff]) -> String {
+    entries
+        .iter()
+        .map(|t| {
+            if t.fraction < 1.0 {
+                format!(
+                    "{} to {} ({}%)",
+                    t.start_date,
+                    t.end_date,
+                    t.fraction * 100.0
+                )
+            } else {
+                format!("{} to {}", t.start_date, t.end_date)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn scenario_contents(scenario: &Scenario) -> String {
+    format!(
+        "{} projects, {} allocations",
+        scenario.branch.technical_projects.len(),
+        scenario.branch.allocations.len()
+    )
+}
+
+/// Added, removed and modified items, matched by ID
+///
+/// Follows the new export's order, with removed items after.
+fn diff_items<T>(
+    old: &[T],
+    new: &[T],
+    identity: impl Fn(&T) -> (Uuid, &str),
+    fields: impl Fn(&T, &T) -> Vec<FieldChange>,
+) -> Vec<ItemChange> {
+    let find = |items: &'_ [T], id: Uuid| -> Option<usize> {
+        items.iter().position(|item| identity(item).0 == id)
+    };
+
+    let mut changes = Vec::new();
+    for item in new {
+        let (id, name) = identity(item);
+        let kind = match find(old, id) {
+            None => ChangeKind::Added,
+            Some(index) => {
+                let fields = fields(&old[index], item);
+                if fields.is_empty() {
+                    continue;
+                }
+                ChangeKind::Modified(fields)
+            }
+        };
+        changes.push(ItemChange {
+            id,
+            name: name.to_string(),
+            kind,
+        });
+    }
+    for item in old {
+        let (id, name) = identity(item);
+        if find(new, id).is_none() {
+            changes.push(ItemChange {
+                id,
+                name: name.to_string(),
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+    changes
+}
+
+/// Cell-by-cell allocation differences, ordered by member name then week
+fn diff_cells(old: &PlanExport, new: &PlanExport, names: &Names) -> Vec<CellChange> {
+    let cell = |export: &PlanExport, key: &(Uuid, NaiveDate)| {
+        export
+            .allocations
+            .iter()
+            .find(|a| (a.team_member_id, a.week_start_date) == *key)
+            .map(|a| {
+                a.assignments
+                    .iter()
+                    .map(|assignment| CellAssignment {
+                        technical_project_id: assignment.technical_project_id,
+                        project_name: names.technical_project(assignment.technical_project_id),
+                        percentage: assignment.percentage,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let keys: BTreeSet<(Uuid, NaiveDate)> = old
+        .allocations
+        .iter()
+        .chain(&new.allocations)
+        .map(|a| (a.team_member_id, a.week_start_date))
+        .collect();
+
+    let mut changes: Vec<CellChange> = keys
+        .iter()
+        .filter_map(|key| {
+            let (before, after) = (cell(old, key), cell(new, key));
+            (before != after).then(|| CellChange {
+                team_member_id: key.0,
+                member_name: names.team_member(key.0),
+                week_start_date: key.1,
+                before,
+                after,
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| {
+        (&a.member_name, a.week_start_date).cmp(&(&b.member_name, b.week_start_date))
+    });
+    changes
+}
+
+fn describe_cell(assignments: &[CellAssignment]) -> String {
+    if assignments.is_empty() {
+        return "unallocated".to_string();
+    }
+    assignments
+        .iter()
+        .map(|a| format!("{} {}%", a.project_name, a.percentage))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: \"{}\" → \"{}\"",
+            self.field.label(),
+            self.before,
+            self.after
+        )
+    }
+}
+
+impl fmt::Display for PlanChange {
+    /// One line per change: `+` added, `-` removed, `~` modified
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (noun, item) = match self {
+            PlanChange::Plan(change) => return write!(f, "~ Plan {}", change),
+            PlanChange::Allocation(cell) => {
+                return write!(
+                    f,
+                    "~ {}, week of {}: {} → {}",
+                    cell.member_name,
+                    cell.week_start_date,
+                    describe_cell(&cell.before),
+                    describe_cell(&cell.after)
+                )
+            }
+            PlanChange::RoadmapProject(item) => ("Roadmap project", item),
+            PlanChange::TechnicalProject(item) => ("Technical project", item),
+            PlanChange::TeamMember(item) => ("Team member", item),
+            PlanChange::Scenario(item) => ("Scenario", item),
+        };
+        match &item.kind {
+            ChangeKind::Added => write!(f, "+ {} \"{}\"", noun, item.name),
+            ChangeKind::Removed => write!(f, "- {} \"{}\"", noun, item.name),
+            ChangeKind::Modified(fields) => {
+                let fields: Vec<String> = fields.iter().map(|c| c.to_string()).collect();
+                write!(f, "~ {} \"{}\": {}", noun, item.name, fields.join("; "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for PlanDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, PlanState, Preferences, ProjectColor, RoadmapProject, TeamMember,
+        TechnicalProject,
+    };
+    use chrono::Duration;
+
+    fn sample_export() -> PlanExport {
+        let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences {
+            team_members: vec![
+                TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0),
+                TeamMember::new("Bob Li".to_string(), Role::ENGINEERING, 12.0),
+            ],
+            ..Preferences::default()
+        };
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
+        let roadmap = RoadmapProject::new(
+            "Payment Gateway".to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start,
+            quarter_start + Duration::weeks(8),
+            ProjectColor::Blue,
+        );
+        let technical = TechnicalProject::new(
+            "Gateway API".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start,
+        );
+        let mut alloc = Allocation::new(prefs.team_members[0].id, quarter_start);
+        alloc.assignments.push(Assignment::new(technical.id, 100.0));
+        state.allocations.push(alloc);
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects.push(technical);
+        PlanExport::from_signals(prefs, state)
+    }
+
+    #[test]
+    fn test_identical_exports_have_no_changes() {
+        let export = sample_export();
+        let diff = diff_exports(&export, &export.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn test_project_changes() {
+        let old = sample_export();
+        let mut new = old.clone();
+        new.roadmap_projects[0].name = "Payments v2".to_string();
+        new.technical_projects[0].estimates.set(Role::SCIENCE, 2.0);
+        let added = TechnicalProject::new(
+            "Fraud Model".to_string(),
+            None,
+            RoleWeeks::from([(Role::SCIENCE, 3.0)]),
+            new.quarter_start_date,
+        );
+        new.technical_projects.push(added.clone());
+
+        let diff = diff_exports(&old, &new);
+        assert_eq!(diff.changes.len(), 3);
+
+        let PlanChange::RoadmapProject(renamed) = &diff.changes[0] else {
+            panic!("expected roadmap change, got {:?}", diff.changes[0]);
+        };
+        assert_eq!(renamed.renamed_from(), Some("Payment Gateway"));
+        assert_eq!(
+            diff.changes[0].to_string(),
+            "~ Roadmap project \"Payments v2\": name: \"Payment Gateway\" → \"Payments v2\""
+        );
+
+        // The link shows the renamed roadmap project's name on both sides, so it isn't a change
+        let PlanChange::TechnicalProject(estimate) = &diff.changes[1] else {
+            panic!("expected technical change, got {:?}", diff.changes[1]);
+        };
+        assert_eq!(
+            estimate.kind,
+            ChangeKind::Modified(vec![FieldChange {
+                field: Field::Estimate(Role::SCIENCE),
+                before: "0".to_string(),
+                after: "2".to_string(),
+            }])
+        );
+        assert_eq!(
+            diff.changes[2],
+            PlanChange::TechnicalProject(ItemChange {
+                id: added.id,
+                name: "Fraud Model".to_string(),
+                kind: ChangeKind::Added,
+            })
+        );
+    }
+
+    #[test]
+    fn test_member_changes() {
+        let old = sample_export();
+        let mut new = old.clone();
+        new.team_members[1].capacity = 10.0;
+        new.team_members[1].skills = vec!["Kafka".to_string()];
+        let removed = new.team_members.remove(0);
+        new.allocations.clear();
+
+        let diff = diff_exports(&old, &new);
+        let lines: Vec<String> = diff.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ Team member \"Bob Li\": capacity: \"12\" → \"10\"; skills: \"\" → \"Kafka\"",
+                "- Team member \"Alice Kim\"",
+                "~ Alice Kim, week of 2025-01-06: Gateway API 100% → unallocated",
+            ]
+        );
+        assert_eq!(
+            diff.cell_changes().next().unwrap().team_member_id,
+            removed.id
+        );
+    }
+
+    #[test]
+    fn test_allocation_cell_changes() {
+        let old = sample_export();
+        let mut new = old.clone();
+        let project_id = new.technical_projects[0].id;
+        let (alice, bob) = (new.team_members[0].id, new.team_members[1].id);
+        let week_two = new.quarter_start_date + Duration::weeks(1);
+
+        // Split Alice's first week, add a week for Bob, and an empty cell that isn't a change
+        new.allocations[0].assignments = vec![
+            Assignment::new(project_id, 50.0),
+            Assignment::new(project_id, 50.0),
+        ];
+        let mut bob_alloc = Allocation::new(bob, week_two);
+        bob_alloc
+            .assignments
+            .push(Assignment::new(project_id, 100.0));
+        new.allocations.push(bob_alloc);
+        new.allocations.push(Allocation::new(alice, week_two));
+
+        let diff = diff_exports(&old, &new);
+        let cells: Vec<_> = diff.cell_changes().collect();
+        assert_eq!(cells.len(), 2);
+        assert_eq!((cells[0].team_member_id, cells[0].after.len()), (alice, 2));
+        assert_eq!(cells[1].team_member_id, bob);
+        assert!(cells[1].before.is_empty());
+        assert_eq!(
+            diff.changes[1].to_string(),
+            "~ Bob Li, week of 2025-01-13: unallocated → Gateway API 100%"
+        );
+    }
+
+    #[test]
+    fn test_plan_and_scenario_changes() {
+        let old = sample_export();
+        let mut new = old.clone();
+        new.quarter_name = "Q2 2025".to_string();
+        let (_, mut state) = new.clone().into_signals();
+        let scenario_id = state.create_scenario("Pipeline first");
+        new.scenarios = state.scenarios.clone();
+
+        let diff = diff_exports(&old, &new);
+        assert_eq!(
+            diff.changes[0].to_string(),
+            "~ Plan quarter name: \"Q1 2025\" → \"Q2 2025\""
+        );
+        assert_eq!(diff.changes[1].to_string(), "+ Scenario \"Pipeline first\"");
+
+        // Editing the scenario's contents
+        let old = new.clone();
+        new.scenarios[0].branch.allocations.clear();
+        let diff = diff_exports(&old, &new);
+        let PlanChange::Scenario(change) = &diff.changes[0] else {
+            panic!("expected scenario change, got {:?}", diff.changes[0]);
+        };
+        assert_eq!(change.id, scenario_id);
+        assert_eq!(
+            change.kind,
+            ChangeKind::Modified(vec![FieldChange {
+                field: Field::Contents,
+                before: "1 projects, 1 allocations".to_string(),
+                after: "1 projects, 0 allocations".to_string(),
+            }])
+        );
+    }
+}
@@ -0,0 +1,997 @@
+//! CSV export and import of allocations
+//!
+//! Two layouts for spreadsheets:
+//! - Matrix: one row per quarter week, one column per team member, each cell
+//!   listing the projects that member works on that week
+//! - Long: one row per assignment (member, week, project, roadmap project,
+//!   percentage), for pivot tables and filtering
+//!
+//! Output follows RFC 4180: comma-separated, CRLF line endings, and fields
+//! quoted when they contain a comma, quote or line break.
+//!
+//! Import reads a team roster (name, role, capacity) and the long layout
+//! back in. Like calendar import it is two-step: `plan_roster_import` and
+//! `plan_allocation_import` build a preview with per-row errors without
+//! touching any state, and the preview's `apply` writes the accepted rows.
+//! Columns are matched by header name, case-insensitively, in any order.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use uuid::Uuid;
+
+use crate::models::{
+    Allocation, Assignment, PlanIndex, PlanState, Preferences, Role, RoleWeeks, TeamMember,
+    TechnicalProject,
+};
+use crate::utils::generate_quarter_weeks;
+
+/// Line ending required by RFC 4180
+const LINE_END: &str = "\r\n";
+
+/// Allocation grid as a matrix: weeks down, team members across
+///
+/// Cells read like `Payments API (50%); Search (50%)`. Allocations outside
+/// the quarter's weeks aren't included.
+pub fn allocation_matrix_csv(prefs: &Preferences, state: &PlanState) -> String {
+    let index = PlanIndex::new(state.clone(), &prefs.team_members);
+    let mut out = String::new();
+
+    let mut header = vec![
+        "Week".to_string(),
+        "Sprint".to_string(),
+        "Week Of".to_string(),
+    ];
+    header.extend(prefs.team_members.iter().map(|m| m.name.clone()));
+    push_row(&mut out, &header);
+
+    let weeks = generate_quarter_weeks(
+        state.quarter_start_date,
+        state.num_weeks,
+        prefs.sprint_length_weeks,
+    );
+    for week in weeks {
+        let mut row = vec![
+            week.week_number.to_string(),
+            week.sprint_number.to_string(),
+            week.start_date.to_string(),
+        ];
+        row.extend(prefs.team_members.iter().map(|member| {
+            index
+                .allocation(&member.id, week.start_date)
+                .map(|alloc| cell_text(alloc, &index))
+                .unwrap_or_default()
+        }));
+        push_row(&mut out, &row);
+    }
+    out
+}
+
+/// One row per assignment, ordered by week and then by roster order
+///
+/// Allocations for people no longer on the roster come last in each week,
+/// under "Unknown member".
+pub fn allocations_long_csv(prefs: &Preferences, state: &PlanState) -> String {
+    let index = PlanIndex::new(state.clone(), &prefs.team_members);
+    let mut out = String::new();
+    push_row(
+        &mut out,
+        &[
+            "Member",
+            "Week Of",
+            "Project",
+            "Roadmap Project",
+            "Percentage",
+        ]
+        .map(String::from),
+    );
+
+    let roster_position = |alloc: &Allocation| {
+        prefs
+            .team_members
+            .iter()
+            .position(|m| m.id == alloc.team_member_id)
+            .unwrap_or(usize::MAX)
+    };
+    let mut allocations: Vec<&Allocation> = state.allocations.iter().collect();
+    allocations.sort_by_key(|alloc| (alloc.week_start_date, roster_position(alloc)));
+
+    for alloc in allocations {
+        let member = prefs
+            .get_team_member(&alloc.team_member_id)
+            .map_or("Unknown member", |m| m.name.as_str());
+        for assignment in &alloc.assignments {
+            let project = index.technical_project(&assignment.technical_project_id);
+            let roadmap = project
+                .and_then(|p| p.roadmap_project_id)
+                .and_then(|id| index.roadmap_project(&id))
+                .map(|rp| rp.name.clone())
+                .unwrap_or_default();
+            push_row(
+                &mut out,
+                &[
+                    member.to_string(),
+                    alloc.week_start_date.to_string(),
+                    project
+                        .map_or("Unknown project", |p| p.name.as_str())
+                        .to_string(),
+                    roadmap,
+                    assignment.percentage.to_string(),
+                ],
+            );
+        }
+    }
+    out
+}
+
+/// Projects and percentages in one allocation
+fn cell_text(alloc: &Allocation, index: &PlanIndex) -> String {
+    alloc
+        .assignments
+        .iter()
+        .map(|assignment| {
+            let name = index
+                .technical_project(&assignment.technical_project_id)
+                .map_or("Unknown project", |p| p.name.as_str());
+            format!("{} ({}%)", name, assignment.percentage)
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn push_row(out: &mut String, fields: &[String]) {
+    let fields: Vec<String> = fields.iter().map(|f| escape_field(f)).collect();
+    out.push_str(&fields.join(","));
+    out.push_str(LINE_END);
+}
+
+/// Quote a field if it contains a delimiter, quote or line break
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Errors that prevent a CSV file from being read at all
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvParseError {
+    /// The file has no header row
+    Empty,
+    /// A quoted field that is never closed (line where the field starts)
+    UnterminatedQuote { line: usize },
+    /// A required column is missing from the header row
+    MissingColumn(&'static str),
+}
+
+impl fmt::Display for CsvParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvParseError::Empty => write!(f, "File is empty"),
+            CsvParseError::UnterminatedQuote { line } => {
+                write!(f, "Quoted field starting on line {} is never closed", line)
+            }
+            CsvParseError::MissingColumn(column) => {
+                write!(f, "Missing \"{}\" column", column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvParseError {}
+
+/// A row that will not be imported, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    /// Line in the file where the row starts (the header is line 1)
+    pub line: usize,
+    pub message: String,
+}
+
+/// A record and the line it starts on
+type Record = (usize, Vec<String>);
+
+/// Split a CSV document into records
+///
+/// Accepts CRLF or LF line endings and quoted fields with embedded commas,
+/// quotes and line breaks. Blank lines are dropped.
+fn parse_records(input: &str) -> Result<Vec<Record>, CsvParseError> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quote_line = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quote_line.is_some() {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quote_line = None,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quote_line = Some(line),
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut fields));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if let Some(line) = quote_line {
+        return Err(CsvParseError::UnterminatedQuote { line });
+    }
+    fields.push(field);
+    push_record(&mut records, record_line, fields);
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<Record>, line: usize, fields: Vec<String>) {
+    if fields.iter().any(|f| !f.trim().is_empty()) {
+        records.push((line, fields));
+    }
+}
+
+/// Column positions, looked up by header name
+struct Columns(Vec<String>);
+
+impl Columns {
+    fn new(header: &[String]) -> Self {
+        Self(header.iter().map(|h| h.trim().to_lowercase()).collect())
+    }
+
+    /// Position of the first column with any of the given names
+    fn find(&self, names: &[&str]) -> Option<usize> {
+        self.0.iter().position(|h| names.contains(&h.as_str()))
+    }
+
+    fn require(&self, names: &[&'static str]) -> Result<usize, CsvParseError> {
+        self.find(names)
+            .ok_or(CsvParseError::MissingColumn(names[0]))
+    }
+}
+
+/// Trimmed field at a column, or "" if the row is short
+fn field(record: &[String], column: Option<usize>) -> &str {
+    column.and_then(|i| record.get(i)).map_or("", |f| f.trim())
+}
+
+/// Split a document into its header columns and data records
+fn read_table(input: &str) -> Result<(Columns, Vec<Record>), CsvParseError> {
+    let mut records = parse_records(input)?.into_iter();
+    let (_, header) = records.next().ok_or(CsvParseError::Empty)?;
+    Ok((Columns::new(&header), records.collect()))
+}
+
+/// Result of reading a roster sheet, before applying it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RosterImportPreview {
+    /// New team members, in file order
+    pub members: Vec<TeamMember>,
+    pub errors: Vec<RowError>,
+}
+
+impl RosterImportPreview {
+    /// True if applying the preview would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Add the previewed team members to the roster
+    pub fn apply(&self, prefs: &mut Preferences) {
+        prefs.team_members.extend(self.members.iter().cloned());
+    }
+}
+
+/// Read a roster sheet without modifying the preferences
+///
+/// Requires Name and Role columns; Capacity (weeks for the quarter) and
+/// Email are optional. Roles match a configured role's name, short name or
+/// key. Members already on the roster (by name) are reported, not updated.
+pub fn plan_roster_import(
+    input: &str,
+    prefs: &Preferences,
+) -> Result<RosterImportPreview, CsvParseError> {
+    let (columns, records) = read_table(input)?;
+    let name_column = columns.require(&["name", "member", "team member"])?;
+    let role_column = columns.require(&["role"])?;
+    let capacity_column = columns.find(&["capacity", "capacity (weeks)"]);
+    let email_column = columns.find(&["email", "e-mail"]);
+
+    let mut preview = RosterImportPreview::default();
+    for (line, record) in records {
+        let name = field(&record, Some(name_column));
+        let role = field(&record, Some(role_column));
+        let capacity = field(&record, capacity_column);
+        let email = field(&record, email_column);
+        let error = |message: String| RowError { line, message };
+
+        if name.is_empty() {
+            preview.errors.push(error("Missing name".to_string()));
+            continue;
+        }
+        if prefs
+            .team_members
+            .iter()
+            .any(|m| m.name.trim().eq_ignore_ascii_case(name))
+        {
+            preview
+                .errors
+                .push(error(format!("{} is already on the roster", name)));
+            continue;
+        }
+        if preview
+            .members
+            .iter()
+            .any(|m| m.name.eq_ignore_ascii_case(name))
+        {
+            preview
+                .errors
+                .push(error(format!("{} appears more than once", name)));
+            continue;
+        }
+        let Some(role) = find_role(prefs, role) else {
+            preview
+                .errors
+                .push(error(format!("Unknown role \"{}\"", role)));
+            continue;
+        };
+        let capacity = if capacity.is_empty() {
+            prefs.default_capacity
+        } else {
+            match capacity.parse::<f32>() {
+                Ok(weeks) if weeks.is_finite() && weeks >= 0.0 => weeks,
+                _ => {
+                    preview
+                        .errors
+                        .push(error(format!("Invalid capacity \"{}\"", capacity)));
+                    continue;
+                }
+            }
+        };
+
+        let mut member = TeamMember::new(name.to_string(), role, capacity);
+        if !email.is_empty() {
+            member.email = Some(email.to_string());
+        }
+        preview.members.push(member);
+    }
+    Ok(preview)
+}
+
+/// Configured role by name, short name or key
+fn find_role(prefs: &Preferences, text: &str) -> Option<Role> {
+    if text.is_empty() {
+        return None;
+    }
+    prefs
+        .roles
+        .iter()
+        .find(|r| {
+            r.name.eq_ignore_ascii_case(text)
+                || r.short_name.eq_ignore_ascii_case(text)
+                || r.id.as_str().eq_ignore_ascii_case(text)
+        })
+        .map(|r| r.id.clone())
+}
+
+/// One accepted row of an allocation sheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllocationRow {
+    pub line: usize,
+    pub team_member_id: Uuid,
+    pub member_name: String,
+    pub week_start_date: NaiveDate,
+    pub technical_project_id: Uuid,
+    pub project_name: String,
+    pub percentage: f32,
+    /// True if the project doesn't exist yet and will be created
+    pub new_project: bool,
+}
+
+/// Result of reading an allocation sheet, before applying it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AllocationImportPreview {
+    /// Accepted rows, in file order
+    pub rows: Vec<AllocationRow>,
+    /// One allocation per member and week, replacing any existing one
+    pub allocations: Vec<Allocation>,
+    /// Technical projects to create, in first-mentioned order
+    pub new_projects: Vec<TechnicalProject>,
+    pub errors: Vec<RowError>,
+}
+
+impl AllocationImportPreview {
+    /// True if applying the preview would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.allocations.is_empty()
+    }
+
+    /// Create the new projects and write the allocations into the plan
+    ///
+    /// Each imported member-week replaces that cell's existing allocation;
+    /// other cells are left as they are. Projects staffed in a replaced cell,
+    /// before or after, get their dates recomputed from their allocations,
+    /// as painting the grid does.
+    pub fn apply(&self, prefs: &Preferences, state: &mut PlanState) {
+        state
+            .technical_projects
+            .extend(self.new_projects.iter().cloned());
+        let replaced = |existing: &Allocation| {
+            self.allocations.iter().any(|a| {
+                a.team_member_id == existing.team_member_id
+                    && a.week_start_date == existing.week_start_date
+            })
+        };
+        let affected_projects: HashSet<Uuid> = state
+            .allocations
+            .iter()
+            .filter(|existing| replaced(existing))
+            .chain(&self.allocations)
+            .flat_map(|a| &a.assignments)
+            .map(|assignment| assignment.technical_project_id)
+            .collect();
+        state.allocations.retain(|existing| !replaced(existing));
+        state.allocations.extend(self.allocations.iter().cloned());
+        for project_id in affected_projects {
+            state.update_technical_project_dates(
+                &project_id,
+                prefs.sprint_anchor_date,
+                prefs.sprint_length_weeks,
+            );
+        }
+        state.mark_modified();
+    }
+}
+
+/// Read a long-format allocation sheet without modifying the plan
+///
+/// Uses the same columns as `allocations_long_csv`: Member, Week Of, Project
+/// and Percentage, plus an optional Roadmap Project that links projects
+/// created by the import. Members and projects are matched by name,
+/// case-insensitively. Weeks are `YYYY-MM-DD` or `MM/DD/YYYY` Mondays within
+/// the quarter and the member's employment dates. Rows for the same member
+/// and week are combined; a week that adds up to more than 100% or more than
+/// `MAX_PROJECTS_PER_WEEK` (two) projects is rejected as a whole.
+pub fn plan_allocation_import(
+    input: &str,
+    prefs: &Preferences,
+    state: &PlanState,
+) -> Result<AllocationImportPreview, CsvParseError> {
+    let (columns, records) = read_table(input)?;
+    let member_column = columns.require(&["member", "name", "team member"])?;
+    let week_column = columns.require(&["week of", "week", "week start"])?;
+    let project_column = columns.require(&["project", "technical project"])?;
+    let percentage_column = columns.require(&["percentage", "percent", "%"])?;
+    let roadmap_column = columns.find(&["roadmap project", "roadmap"]);
+
+    let quarter_weeks: Vec<NaiveDate> = generate_quarter_weeks(
+        state.quarter_start_date,
+        state.num_weeks,
+        prefs.sprint_length_weeks,
+    )
+    .into_iter()
+    .map(|w| w.start_date)
+    .collect();
+
+    let mut preview = AllocationImportPreview::default();
+    for (line, record) in records {
+        let member_name = field(&record, Some(member_column));
+        let week = field(&record, Some(week_column));
+        let project_name = field(&record, Some(project_column));
+        let percentage = field(&record, Some(percentage_column));
+        let roadmap_name = field(&record, roadmap_column);
+        let error = |message: String| RowError { line, message };
+
+        let Some(member) = prefs
+            .team_members
+            .iter()
+            .find(|m| m.name.trim().eq_ignore_ascii_case(member_name))
+        else {
+            preview
+                .errors
+                .push(error(format!("No team member named \"{}\"", member_name)));
+            continue;
+        };
+        let Some(week_start_date) = parse_date(week) else {
+            preview.errors.push(error(format!(
+                "Invalid week \"{}\" (expected YYYY-MM-DD)",
+                week
+            )));
+            continue;
+        };
+        if week_start_date.weekday() != Weekday::Mon {
+            preview.errors.push(error(format!(
+                "Week of {} is not a Monday",
+                week_start_date
+            )));
+            continue;
+        }
+        if !quarter_weeks.contains(&week_start_date) {
+            preview.errors.push(error(format!(
+                "Week of {} is outside {}",
+                week_start_date, state.quarter_name
+            )));
+            continue;
+        }
+        if !member.is_active_in_week(week_start_date) {
+            preview.errors.push(error(format!(
+                "{} isn't on the team in the week of {}",
+                member.name, week_start_date
+            )));
+            continue;
+        }
+        if project_name.is_empty() {
+            preview.errors.push(error("Missing project".to_string()));
+            continue;
+        }
+        let Some(percentage) = parse_percentage(percentage) else {
+            preview.errors.push(error(format!(
+                "Invalid percentage \"{}\" (expected more than 0, up to 100)",
+                percentage
+            )));
+            continue;
+        };
+
+        let existing = state
+            .technical_projects
+            .iter()
+            .find(|p| p.name.trim().eq_ignore_ascii_case(project_name));
+        let technical_project_id = match existing {
+            Some(project) => project.id,
+            None => match preview
+                .new_projects
+                .iter_mut()
+                .find(|p| p.name.eq_ignore_ascii_case(project_name))
+            {
+                Some(project) => {
+                    project.start_date = project.start_date.min(week_start_date);
+                    project.id
+                }
+                None => {
+                    let roadmap_project_id = if roadmap_name.is_empty() {
+                        None
+                    } else {
+                        match state
+                            .roadmap_projects
+                            .iter()
+                            .find(|p| p.name.trim().eq_ignore_ascii_case(roadmap_name))
+                        {
+                            Some(roadmap) => Some(roadmap.id),
+                            None => {
+                                preview.errors.push(error(format!(
+                                    "No roadmap project named \"{}\"",
+                                    roadmap_name
+                                )));
+                                continue;
+                            }
+                        }
+                    };
+                    let project = TechnicalProject::new(
+                        project_name.to_string(),
+                        roadmap_project_id,
+                        RoleWeeks::new(),
+                        week_start_date,
+                    );
+                    let id = project.id;
+                    preview.new_projects.push(project);
+                    id
+                }
+            },
+        };
+
+        preview.rows.push(AllocationRow {
+            line,
+            team_member_id: member.id,
+            member_name: member.name.clone(),
+            week_start_date,
+            technical_project_id,
+            project_name: project_name.to_string(),
+            percentage,
+            new_project: existing.is_none(),
+        });
+    }
+
+    build_allocations(&mut preview);
+    Ok(preview)
+}
+
+/// Combine accepted rows into one allocation per member and week
+///
+/// Weeks over 100% or with more projects than the grid can show are dropped
+/// along with their rows, and projects that no longer have any rows aren't
+/// created.
+fn build_allocations(preview: &mut AllocationImportPreview) {
+    let mut cells: HashMap<(Uuid, NaiveDate), Vec<usize>> = HashMap::new();
+    let mut order = Vec::new();
+    for (i, row) in preview.rows.iter().enumerate() {
+        let key = (row.team_member_id, row.week_start_date);
+        cells
+            .entry(key)
+            .or_insert_with(|| {
+                order.push(key);
+                Vec::new()
+            })
+            .push(i);
+    }
+
+    let mut rejected_lines = Vec::new();
+    let mut allocations = Vec::new();
+    for key in order {
+        let rows = &cells[&key];
+        let mut allocation = Allocation::new(key.0, key.1);
+        for &i in rows {
+            let row = &preview.rows[i];
+            match allocation
+                .assignments
+                .iter_mut()
+                .find(|a| a.technical_project_id == row.technical_project_id)
+            {
+                Some(assignment) => assignment.percentage += row.percentage,
+                None => allocation
+                    .assignments
+                    .push(Assignment::new(row.technical_project_id, row.percentage)),
+            }
+        }
+
+        let first = &preview.rows[rows[0]];
+        let total = allocation.total_percentage();
+        let problem = if total > 100.0 + PERCENTAGE_TOLERANCE {
+            Some(format!(
+                "{} is allocated {}% in the week of {}",
+                first.member_name, total, first.week_start_date
+            ))
+        } else if allocation.assignments.len() > MAX_PROJECTS_PER_WEEK {
+            Some(format!(
+                "{} has {} projects in the week of {} (at most {})",
+                first.member_name,
+                allocation.assignments.len(),
+                first.week_start_date,
+                MAX_PROJECTS_PER_WEEK
+            ))
+        } else {
+            None
+        };
+        if let Some(message) = problem {
+            for &i in rows {
+                preview.errors.push(RowError {
+                    line: preview.rows[i].line,
+                    message: message.clone(),
+                });
+            }
+            rejected_lines.extend(rows.iter().map(|&i| preview.rows[i].line));
+            continue;
+        }
+        allocations.push(allocation);
+    }
+
+    preview
+        .rows
+        .retain(|row| !rejected_lines.contains(&row.line));
+    preview.errors.sort_by_key(|e| e.line);
+    preview.new_projects.retain(|p| {
+        allocations.iter().any(|a| {
+            a.assignments
+                .iter()
+                .any(|assignment| assignment.technical_project_id == p.id)
+        })
+    });
+    preview.allocations = allocations;
+}
+
+/// Rounding slack when checking that a week adds up to at most 100%
+const PERCENTAGE_TOLERANCE: f32 = 0.01;
+
+/// Most projects in one member-week
+///
+/// The grid shows a week as one project or a two-way split, so an imported
+/// week naming more projects would be unreadable there; it's rejected with its
+/// rows instead of being truncated.
+const MAX_PROJECTS_PER_WEEK: usize = 2;
+
+/// Parse `2025-01-06` or the US spreadsheet format `1/6/2025`
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(text, "%m/%d/%Y"))
+        .ok()
+}
+
+/// Parse `50` or `50%`, which must be more than 0 and at most 100
+fn parse_percentage(text: &str) -> Option<f32> {
+    let value: f32 = text.strip_suffix('%').unwrap_or(text).trim().parse().ok()?;
+    (value > 0.0 && value <= 100.0).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Assignment, ProjectColor, RoadmapProject, Role, RoleWeeks, TeamMember, TechnicalProject,
+    };
+    use chrono::{Duration, NaiveDate};
+
+    fn sample() -> (Preferences, PlanState) {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.sprint_length_weeks = 2;
+        prefs.team_members = vec![
+            TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0),
+            TeamMember::new("Bob \"The Builder\"".to_string(), Role::ENGINEERING, 12.0),
+        ];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 3);
+        let roadmap = RoadmapProject::new(
+            "Checkout, v2".to_string(),
+            RoleWeeks::new(),
+            start,
+            start + Duration::weeks(3),
+            ProjectColor::Blue,
+        );
+        let api =
+            TechnicalProject::new("API".to_string(), Some(roadmap.id), RoleWeeks::new(), start);
+        let docs = TechnicalProject::new("Docs".to_string(), None, RoleWeeks::new(), start);
+
+        let alice = prefs.team_members[0].id;
+        let bob = prefs.team_members[1].id;
+        let mut split = Allocation::new(bob, start);
+        split.assignments.push(Assignment::new(api.id, 50.0));
+        split.assignments.push(Assignment::new(docs.id, 50.0));
+        let mut full = Allocation::new(alice, start + Duration::weeks(2));
+        full.assignments.push(Assignment::new(api.id, 100.0));
+        let mut first = Allocation::new(alice, start);
+        first.assignments.push(Assignment::new(docs.id, 100.0));
+        state.allocations = vec![split, full, first];
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![api, docs];
+        (prefs, state)
+    }
+
+    #[test]
+    fn test_matrix_export() {
+        let (prefs, state) = sample();
+        let csv = allocation_matrix_csv(&prefs, &state);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines,
+            [
+                "Week,Sprint,Week Of,Alice Kim,\"Bob \"\"The Builder\"\"\"",
+                "1,1,2025-01-06,Docs (100%),API (50%); Docs (50%)",
+                "2,1,2025-01-13,,",
+                "3,2,2025-01-20,API (100%),",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_export() {
+        let (prefs, state) = sample();
+        let csv = allocations_long_csv(&prefs, &state);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "Member,Week Of,Project,Roadmap Project,Percentage",
+                "Alice Kim,2025-01-06,Docs,,100",
+                "\"Bob \"\"The Builder\"\"\",2025-01-06,API,\"Checkout, v2\",50",
+                "\"Bob \"\"The Builder\"\"\",2025-01-06,Docs,,50",
+                "Alice Kim,2025-01-20,API,\"Checkout, v2\",100",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let records =
+            parse_records("\u{feff}a,b\r\n\"x, \"\"y\"\"\",\"two\nlines\"\n\n,\nlast").unwrap();
+        assert_eq!(
+            records,
+            [
+                (1, vec!["a".to_string(), "b".to_string()]),
+                (2, vec!["x, \"y\"".to_string(), "two\nlines".to_string()]),
+                (6, vec!["last".to_string()]),
+            ]
+        );
+        assert_eq!(
+            parse_records("a\n\"open,b"),
+            Err(CsvParseError::UnterminatedQuote { line: 2 })
+        );
+    }
+
+    #[test]
+    fn test_roster_import() {
+        let (prefs, _) = sample();
+        let csv = "Name,Role,Capacity,Email\n\
+                   Dana Lee,SDE,10,dana@example.com\n\
+                   Eli Park,science,,\n\
+                   alice kim,SDE,12,\n\
+                   Fay,Design,12,\n\
+                   Gus,Engineering,lots,\n\
+                   Dana Lee,AS,6,\n";
+        let preview = plan_roster_import(csv, &prefs).unwrap();
+
+        let members: Vec<_> = preview
+            .members
+            .iter()
+            .map(|m| {
+                (
+                    m.name.as_str(),
+                    m.role.clone(),
+                    m.capacity,
+                    m.email.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            members,
+            [
+                (
+                    "Dana Lee",
+                    Role::ENGINEERING,
+                    10.0,
+                    Some("dana@example.com")
+                ),
+                ("Eli Park", Role::SCIENCE, prefs.default_capacity, None),
+            ]
+        );
+        let errors: Vec<usize> = preview.errors.iter().map(|e| e.line).collect();
+        assert_eq!(errors, [4, 5, 6, 7]);
+
+        let mut applied = prefs.clone();
+        preview.apply(&mut applied);
+        assert_eq!(applied.team_members.len(), 4);
+
+        assert_eq!(
+            plan_roster_import("Name,Capacity\nDana,10", &prefs),
+            Err(CsvParseError::MissingColumn("role"))
+        );
+    }
+
+    #[test]
+    fn test_allocation_import_round_trip() {
+        let (prefs, state) = sample();
+        let csv = allocations_long_csv(&prefs, &state);
+        let mut cleared = state.clone();
+        cleared.allocations.clear();
+
+        let preview = plan_allocation_import(&csv, &prefs, &cleared).unwrap();
+        assert!(preview.errors.is_empty());
+        assert!(preview.new_projects.is_empty());
+        assert_eq!(preview.rows.len(), 4);
+
+        preview.apply(&prefs, &mut cleared);
+        assert_eq!(allocations_long_csv(&prefs, &cleared), csv);
+    }
+
+    #[test]
+    fn test_allocation_import_rows() {
+        let (prefs, mut state) = sample();
+        let alice = prefs.team_members[0].id;
+        let csv = "Member,Week Of,Project,Roadmap Project,Percentage\n\
+                   alice kim,1/13/2025,Search,\"Checkout, v2\",60%\n\
+                   Alice Kim,2025-01-13,docs,,40\n\
+                   Zed,2025-01-13,Docs,,50\n\
+                   Alice Kim,2025-01-14,Docs,,50\n\
+                   Alice Kim,2025-03-31,Docs,,50\n\
+                   Alice Kim,2025-01-20,Docs,,0\n\
+                   Alice Kim,2025-01-20,Infra,Unknown,50\n\
+                   Alice Kim,2025-01-06,Docs,,50\n\
+                   Alice Kim,2025-01-06,Cache,,60\n";
+        let preview = plan_allocation_import(csv, &prefs, &state).unwrap();
+
+        let errors: Vec<usize> = preview.errors.iter().map(|e| e.line).collect();
+        assert_eq!(errors, [4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(preview.rows.len(), 2);
+        assert!(preview.rows[0].new_project);
+        assert!(!preview.rows[1].new_project);
+
+        // Only projects with accepted rows are created, linked by roadmap name
+        assert_eq!(preview.new_projects.len(), 1);
+        let search = &preview.new_projects[0];
+        assert_eq!(search.name, "Search");
+        assert_eq!(
+            search.roadmap_project_id,
+            Some(state.roadmap_projects[0].id)
+        );
+
+        preview.apply(&prefs, &mut state);
+        let week = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+        let alloc = state
+            .allocations
+            .iter()
+            .find(|a| a.team_member_id == alice && a.week_start_date == week)
+            .unwrap();
+        assert_eq!(alloc.total_percentage(), 100.0);
+        assert_eq!(alloc.assignments[0].technical_project_id, search.id);
+        assert_eq!(state.technical_projects.len(), 3);
+        // Weeks not in the sheet keep their allocations
+        assert_eq!(state.allocations.len(), 4);
+    }
+
+    #[test]
+    fn test_allocation_import_updates_project_dates() {
+        use crate::utils::date_helpers::get_sprint_boundaries;
+
+        let (prefs, mut state) = sample();
+        let first = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let last = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+        let sprint =
+            |week| get_sprint_boundaries(week, prefs.sprint_anchor_date, prefs.sprint_length_weeks);
+        // Alice's last week moves from API to Docs
+        let csv = "Member,Week Of,Project,Percentage\nAlice Kim,2025-01-20,Docs,100\n";
+        let preview = plan_allocation_import(csv, &prefs, &state).unwrap();
+        preview.apply(&prefs, &mut state);
+
+        let api = &state.technical_projects[0];
+        assert_eq!(api.start_date, sprint(first).0);
+        assert_eq!(api.expected_completion, Some(sprint(first).1));
+        let docs = &state.technical_projects[1];
+        assert_eq!(docs.start_date, sprint(first).0);
+        assert_eq!(docs.expected_completion, Some(sprint(last).1));
+    }
+
+    #[test]
+    fn test_allocation_import_rejects_unshowable_weeks() {
+        let (mut prefs, state) = sample();
+        // Alice leaves after the first week
+        prefs.team_members[0].end_date = NaiveDate::from_ymd_opt(2025, 1, 10);
+        let csv = "Member,Week Of,Project,Percentage
+                   Alice Kim,2025-01-06,Docs,100
+                   Alice Kim,2025-01-13,Docs,100
+                   Bob \"The Builder\",2025-01-13,API,30
+                   Bob \"The Builder\",2025-01-13,Docs,30
+                   Bob \"The Builder\",2025-01-13,Cache,30
+                   Bob \"The Builder\",2025-01-20,API,50
+                   Bob \"The Builder\",2025-01-20,api,20
+                   Bob \"The Builder\",2025-01-20,Docs,30
+";
+        let preview = plan_allocation_import(csv, &prefs, &state).unwrap();
+
+        let errors: Vec<(usize, &str)> = preview
+            .errors
+            .iter()
+            .map(|e| (e.line, e.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (3, "Alice Kim isn't on the team in the week of 2025-01-13"),
+                (
+                    4,
+                    "Bob \"The Builder\" has 3 projects in the week of 2025-01-13 (at most 2)"
+                ),
+                (
+                    5,
+                    "Bob \"The Builder\" has 3 projects in the week of 2025-01-13 (at most 2)"
+                ),
+                (
+                    6,
+                    "Bob \"The Builder\" has 3 projects in the week of 2025-01-13 (at most 2)"
+                ),
+            ]
+        );
+        // Rows for the same project count once
+        let lines: Vec<usize> = preview.rows.iter().map(|r| r.line).collect();
+        assert_eq!(lines, [2, 7, 8, 9]);
+        assert_eq!(preview.allocations.len(), 2);
+        assert!(preview.new_projects.is_empty());
+    }
+}
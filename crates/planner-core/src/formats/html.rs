@@ -0,0 +1,319 @@
+//! Self-contained HTML quarterly plan report
+//!
+//! Renders a `PlanReport` as a single HTML file with its styles inlined and
+//! no scripts or external resources, so it can be emailed, attached to a
+//! ticket or opened offline and look the same everywhere.
+
+use std::fmt::Write;
+
+use crate::models::BadgeType;
+use crate::report::{format_date_range, status_label, PlanReport};
+
+/// Styles for the report, kept print-friendly
+const STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; color: #1f2328; max-width: 960px; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; }
+h1 { margin-bottom: 0.25rem; }
+h2 { margin-top: 2rem; border-bottom: 1px solid #d0d7de; padding-bottom: 0.25rem; }
+h3 { margin-bottom: 0.25rem; }
+.meta { color: #59636e; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.375rem 0.5rem; border-bottom: 1px solid #d0d7de; }
+th { font-weight: 600; }
+td.num, th.num { text-align: right; font-variant-numeric: tabular-nums; }
+.badge { display: inline-block; padding: 0 0.5rem; border-radius: 999px; font-size: 0.85em; font-weight: 600; white-space: nowrap; }
+.badge-success { background: #dafbe1; color: #1a7f37; }
+.badge-warning { background: #fff8c5; color: #9a6700; }
+.badge-error { background: #ffebe9; color: #cf222e; }
+.badge-neutral { background: #eff2f5; color: #59636e; }
+.swatch { display: inline-block; width: 0.75em; height: 0.75em; border-radius: 2px; margin-right: 0.375rem; vertical-align: baseline; }
+.callouts li { margin-bottom: 0.25rem; }
+.empty { color: #59636e; font-style: italic; }
+";
+
+/// Render a plan report as a standalone HTML document
+pub fn report_html(report: &PlanReport) -> String {
+    let mut out = String::new();
+    // Writing to a String can't fail
+    let _ = write_report(&mut out, report);
+    out
+}
+
+fn write_report(out: &mut String, report: &PlanReport) -> std::fmt::Result {
+    let title = format!("{}: {} Plan", report.team_name, report.quarter_name);
+    writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(
+        out,
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+    )?;
+    writeln!(out, "<title>{}</title>", escape(&title))?;
+    writeln!(out, "<style>\n{}</style>\n</head>\n<body>", STYLE)?;
+
+    writeln!(out, "<h1>{}</h1>", escape(&title))?;
+    writeln!(
+        out,
+        "<p class=\"meta\">{} · {} weeks · {} team members</p>",
+        format_date_range(report.quarter_start, report.quarter_end),
+        report.num_weeks,
+        report.members.len()
+    )?;
+    writeln!(
+        out,
+        "<p><strong>Capacity:</strong> {:.1} weeks · <strong>Allocated:</strong> {:.1} weeks ({:.0}%) {}</p>",
+        report.capacity,
+        report.allocated,
+        report.utilization_pct(),
+        badge(report.status)
+    )?;
+
+    writeln!(out, "<h2>Callouts</h2>")?;
+    if report.callouts.is_empty() {
+        writeln!(
+            out,
+            "<p class=\"empty\">Everyone is allocated within their capacity.</p>"
+        )?;
+    } else {
+        writeln!(out, "<ul class=\"callouts\">")?;
+        for callout in &report.callouts {
+            writeln!(
+                out,
+                "<li>{} {}</li>",
+                badge(callout.status),
+                escape(&callout.message())
+            )?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    writeln!(out, "<h2>Team</h2>")?;
+    if report.members.is_empty() {
+        writeln!(out, "<p class=\"empty\">No team members.</p>")?;
+    } else {
+        writeln!(
+            out,
+            "<table>\n<thead><tr><th>Member</th><th>Role</th><th class=\"num\">Capacity</th><th class=\"num\">Allocated</th><th class=\"num\">Utilization</th><th>Status</th></tr></thead>\n<tbody>"
+        )?;
+        for member in &report.members {
+            writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{:.1}</td><td class=\"num\">{:.1}</td><td class=\"num\">{:.0}%</td><td>{}</td></tr>",
+                escape(&member.name),
+                escape(&member.role_name),
+                member.capacity,
+                member.allocated,
+                member.utilization_pct(),
+                badge(member.status)
+            )?;
+        }
+        writeln!(out, "</tbody>\n</table>")?;
+    }
+
+    writeln!(out, "<h2>Roadmap Projects</h2>")?;
+    if report.roadmap_projects.is_empty() {
+        writeln!(out, "<p class=\"empty\">No roadmap projects.</p>")?;
+    } else {
+        writeln!(
+            out,
+            "<table>\n<thead><tr><th>Project</th><th>Launch</th><th class=\"num\">Estimate</th><th class=\"num\">Allocated</th><th>Status</th></tr></thead>\n<tbody>"
+        )?;
+        for project in &report.roadmap_projects {
+            writeln!(
+                out,
+                "<tr><td><span class=\"swatch\" style=\"background: {}\"></span>{}</td><td>{}</td><td class=\"num\">{:.1}</td><td class=\"num\">{:.1}</td><td>{}</td></tr>",
+                project.color.to_hex(),
+                escape(&project.name),
+                project.launch_date.format("%b %-d, %Y"),
+                project.estimate,
+                project.allocated,
+                badge(project.status)
+            )?;
+        }
+        writeln!(out, "</tbody>\n</table>")?;
+    }
+
+    writeln!(out, "<h2>Technical Projects</h2>")?;
+    if report.technical_projects.is_empty() {
+        writeln!(out, "<p class=\"empty\">No technical projects.</p>")?;
+    } else {
+        writeln!(
+            out,
+            "<table>\n<thead><tr><th>Project</th><th>Roadmap Project</th><th>Dates</th><th class=\"num\">Estimate</th><th class=\"num\">Allocated</th><th>Status</th></tr></thead>\n<tbody>"
+        )?;
+        for project in &report.technical_projects {
+            writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{:.1}</td><td class=\"num\">{:.1}</td><td>{}</td></tr>",
+                escape(&project.name),
+                project.roadmap_name.as_deref().map_or("—".to_string(), escape),
+                project
+                    .dates
+                    .map_or("Not scheduled".to_string(), |(start, end)| {
+                        format_date_range(start, end)
+                    }),
+                project.estimate,
+                project.allocated,
+                badge(project.status)
+            )?;
+        }
+        writeln!(out, "</tbody>\n</table>")?;
+    }
+
+    writeln!(out, "<h2>Assignments</h2>")?;
+    for member in &report.members {
+        writeln!(out, "<h3>{}</h3>", escape(&member.name))?;
+        if member.assignments.is_empty() {
+            writeln!(out, "<p class=\"empty\">No assignments.</p>")?;
+            continue;
+        }
+        writeln!(out, "<ul>")?;
+        for (project, weeks) in &member.assignments {
+            writeln!(out, "<li>{}: {:.1} weeks</li>", escape(project), weeks)?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    writeln!(out, "</body>\n</html>")
+}
+
+fn badge(status: BadgeType) -> String {
+    let class = match status {
+        BadgeType::Success => "badge-success",
+        BadgeType::Warning => "badge-warning",
+        BadgeType::Error => "badge-error",
+        BadgeType::Info | BadgeType::Neutral => "badge-neutral",
+    };
+    format!(
+        "<span class=\"badge {}\">{}</span>",
+        class,
+        status_label(status)
+    )
+}
+
+/// Escape text for element content and quoted attribute values
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, PlanState, Preferences, ProjectColor, RoadmapProject, Role,
+        RoleWeeks, TeamMember, TechnicalProject,
+    };
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn test_report_html() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("R&D <Platform>".to_string());
+        prefs.team_members = vec![TeamMember::new(
+            "Dana \"DJ\" O'Neil".to_string(),
+            Role::SCIENCE,
+            4.0,
+        )];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 4);
+        state.roadmap_projects.push(RoadmapProject::new(
+            "Search".to_string(),
+            RoleWeeks::from([(Role::SCIENCE, 4.0)]),
+            start,
+            start + Duration::weeks(4),
+            ProjectColor::Teal,
+        ));
+
+        let html = report_html(&PlanReport::new(&prefs, &state));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>\n"));
+        assert!(html.contains("<title>R&amp;D &lt;Platform&gt;: Q1 2025 Plan</title>"));
+        assert!(html.contains("<td>Dana &quot;DJ&quot; O&#39;Neil</td><td>Science</td>"));
+        assert!(html.contains(
+            "<span class=\"swatch\" style=\"background: #2DD4BF\"></span>Search</td><td>Feb 3, 2025</td>"
+        ));
+        assert!(html.contains("<span class=\"badge badge-error\">Off target</span>"));
+        // Self-contained: nothing loaded from elsewhere
+        assert!(!html.contains("<script") && !html.contains("<link") && !html.contains("src="));
+    }
+
+    #[test]
+    fn test_report_html_empty_plan() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new("Payments".to_string());
+        let state = PlanState::new("Q1 2025".to_string(), start, 13);
+
+        let html = report_html(&PlanReport::new(&prefs, &state));
+        assert!(html.ends_with("<h2>Assignments</h2>\n</body>\n</html>\n"));
+        assert!(html.contains(
+            "<strong>Capacity:</strong> 0.0 weeks · <strong>Allocated:</strong> 0.0 weeks (0%) <span class=\"badge badge-neutral\">Not planned</span>"
+        ));
+        for empty in [
+            "Everyone is allocated within their capacity.",
+            "No team members.",
+            "No roadmap projects.",
+            "No technical projects.",
+        ] {
+            assert!(
+                html.contains(&format!("<p class=\"empty\">{}</p>", empty)),
+                "missing: {}",
+                empty
+            );
+        }
+        assert!(!html.contains("<table>"));
+    }
+
+    #[test]
+    fn test_report_html_escapes_project_names() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.team_members = vec![TeamMember::new(
+            "Alice Kim".to_string(),
+            Role::ENGINEERING,
+            2.0,
+        )];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 2);
+        let roadmap = RoadmapProject::new(
+            "<Launch> & \"Learn\"".to_string(),
+            RoleWeeks::new(),
+            start,
+            start + Duration::weeks(2),
+            ProjectColor::Blue,
+        );
+        let linked = TechnicalProject::new(
+            "A<B & \"C\"".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::new(),
+            start,
+        );
+        let idle = TechnicalProject::new(
+            "Idle".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 1.0)]),
+            start,
+        );
+        let mut alloc = Allocation::new(prefs.team_members[0].id, start);
+        alloc.assignments.push(Assignment::new(linked.id, 100.0));
+        state.allocations.push(alloc);
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![linked, idle];
+
+        let html = report_html(&PlanReport::new(&prefs, &state));
+        assert!(html.contains("</span>&lt;Launch&gt; &amp; &quot;Learn&quot;</td>"));
+        assert!(html.contains(
+            "<tr><td>A&lt;B &amp; &quot;C&quot;</td><td>&lt;Launch&gt; &amp; &quot;Learn&quot;</td>"
+        ));
+        assert!(html.contains("<li>A&lt;B &amp; &quot;C&quot;: 1.0 weeks</li>"));
+        // A project nobody works on has no dates
+        assert!(html.contains("<tr><td>Idle</td><td>—</td><td>Not scheduled</td>"));
+        assert!(!html.contains("A<B") && !html.contains("<Launch>"));
+    }
+}
@@ -0,0 +1,1081 @@
+//! iCalendar (.ics) support
+//!
+//! Parses VEVENTs from company holiday calendars and PTO exports, and maps
+//! them onto team-wide holidays or per-member time off. Import is two-step:
+//! `plan_import` builds an `IcsImportPreview` without touching any state, and
+//! `IcsImportPreview::apply` writes the accepted entries into `Preferences`.
+//!
+//! Recurrence rules (RRULE) are not expanded; only the first occurrence of a
+//! recurring event is imported. Time zones are ignored: the calendar date of
+//! DTSTART/DTEND is used as written.
+//!
+//! Export goes the other way: `plan_calendar_ics` writes the plan's project
+//! timelines and launch dates (and optionally who works on what, week by
+//! week) as all-day events. Event UIDs are derived from plan IDs, so
+//! re-importing an updated export into a calendar app replaces the old
+//! events instead of duplicating them.
+
+use std::fmt;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use uuid::Uuid;
+
+use crate::models::{Holiday, PlanState, Preferences, TeamMember, TimeOff};
+
+/// Hours in a working day, used to convert timed events into day fractions
+const WORKING_HOURS_PER_DAY: f32 = 8.0;
+
+/// A single VEVENT reduced to the fields relevant for availability
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: Option<String>,
+    pub summary: String,
+    /// First day of the event (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of the event (inclusive)
+    pub end_date: NaiveDate,
+    /// Fraction of each day covered (1.0 for all-day events)
+    pub day_fraction: f32,
+    /// Attendee email addresses (from `mailto:` values)
+    pub attendees: Vec<String>,
+    /// Organizer email address
+    pub organizer: Option<String>,
+}
+
+/// Errors that prevent a calendar file from being parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcsParseError {
+    /// The input has no `BEGIN:VCALENDAR`
+    NotACalendar,
+    /// A `BEGIN:VEVENT` without a matching `END:VEVENT`
+    UnterminatedEvent,
+    /// An event without a DTSTART (line number of its `BEGIN:VEVENT`)
+    MissingStart { line: usize },
+    /// A DTSTART/DTEND/DURATION value that could not be parsed
+    InvalidValue { line: usize, value: String },
+}
+
+impl fmt::Display for IcsParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcsParseError::NotACalendar => write!(f, "File is not an iCalendar file"),
+            IcsParseError::UnterminatedEvent => write!(f, "Calendar ends inside an event"),
+            IcsParseError::MissingStart { line } => {
+                write!(f, "Event starting on line {} has no start date", line)
+            }
+            IcsParseError::InvalidValue { line, value } => {
+                write!(f, "Invalid date or duration on line {}: {}", line, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IcsParseError {}
+
+/// A content line split into name, parameters, and value
+struct ContentLine {
+    line: usize,
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Join folded lines (continuations start with a space or tab), keeping the
+/// line number where each logical line starts
+fn unfold(input: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in input.lines().enumerate() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some((_, last)) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !raw.is_empty() {
+            lines.push((index + 1, raw.to_string()));
+        }
+    }
+    lines
+}
+
+fn parse_content_line(line: usize, text: &str) -> Option<ContentLine> {
+    // The value starts at the first colon outside a quoted parameter value
+    let mut in_quotes = false;
+    let colon = text.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&text[..colon], &text[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_ascii_uppercase(),
+                v.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+
+    Some(ContentLine {
+        line,
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn parse_mailto(value: &str) -> Option<String> {
+    let value = value.trim();
+    let address = match value.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => &value[7..],
+        _ => value,
+    };
+    address.contains('@').then(|| address.to_ascii_lowercase())
+}
+
+/// A DTSTART/DTEND value: a date, or a date with a time of day
+#[derive(Debug, Clone, Copy)]
+enum EventTime {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl EventTime {
+    fn date(self) -> NaiveDate {
+        match self {
+            EventTime::Date(date) => date,
+            EventTime::DateTime(dt) => dt.date(),
+        }
+    }
+}
+
+fn parse_event_time(content: &ContentLine) -> Result<EventTime, IcsParseError> {
+    let invalid = || IcsParseError::InvalidValue {
+        line: content.line,
+        value: content.value.clone(),
+    };
+    let value = content.value.trim().trim_end_matches('Z');
+    let is_date = content
+        .param("VALUE")
+        .is_some_and(|v| v.eq_ignore_ascii_case("DATE"))
+        || !value.contains('T');
+
+    if is_date {
+        let date = NaiveDate::parse_from_str(value.get(..8).ok_or_else(invalid)?, "%Y%m%d")
+            .map_err(|_| invalid())?;
+        Ok(EventTime::Date(date))
+    } else {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .map(EventTime::DateTime)
+            .map_err(|_| invalid())
+    }
+}
+
+/// Parse an RFC 5545 duration such as `P1D`, `P2W`, or `PT4H30M`
+fn parse_duration(content: &ContentLine) -> Result<Duration, IcsParseError> {
+    let invalid = || IcsParseError::InvalidValue {
+        line: content.line,
+        value: content.value.clone(),
+    };
+    let value = content.value.trim();
+    let value = value.strip_prefix('+').unwrap_or(value);
+    let body = value.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in body.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            unit => {
+                let n: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return Err(invalid()),
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Properties collected for the event currently being parsed
+#[derive(Default)]
+struct EventBuilder {
+    begin_line: usize,
+    uid: Option<String>,
+    summary: Option<String>,
+    start: Option<EventTime>,
+    end: Option<EventTime>,
+    duration: Option<Duration>,
+    attendees: Vec<String>,
+    organizer: Option<String>,
+    cancelled: bool,
+}
+
+impl EventBuilder {
+    fn build(self) -> Result<Option<CalendarEvent>, IcsParseError> {
+        if self.cancelled {
+            return Ok(None);
+        }
+        let start = self.start.ok_or(IcsParseError::MissingStart {
+            line: self.begin_line,
+        })?;
+
+        let (end_date, day_fraction) = match start {
+            EventTime::Date(start_date) => {
+                // All-day DTEND is exclusive
+                let exclusive_end = match (self.end, self.duration) {
+                    (Some(end), _) => Some(end.date()),
+                    (None, Some(duration)) => Some(start_date + duration),
+                    (None, None) => None,
+                };
+                let end_date = exclusive_end
+                    .map(|end| end - Duration::days(1))
+                    .filter(|end| *end >= start_date)
+                    .unwrap_or(start_date);
+                (end_date, 1.0)
+            }
+            EventTime::DateTime(start_dt) => {
+                let end_dt = match (self.end, self.duration) {
+                    (Some(EventTime::DateTime(end)), _) => end,
+                    (Some(EventTime::Date(end)), _) => end.and_time(NaiveTime::MIN),
+                    (None, Some(duration)) => start_dt + duration,
+                    (None, None) => start_dt,
+                };
+                if end_dt.date() == start_dt.date() || end_dt <= start_dt {
+                    let hours = (end_dt - start_dt).num_minutes() as f32 / 60.0;
+                    let fraction = (hours / WORKING_HOURS_PER_DAY).clamp(0.0, 1.0);
+                    // Zero-length timed events are treated as a full day
+                    let fraction = if fraction > 0.0 { fraction } else { 1.0 };
+                    (start_dt.date(), fraction)
+                } else {
+                    // Ending at midnight means the previous day was the last
+                    let mut end_date = end_dt.date();
+                    if end_dt.time() == NaiveTime::MIN {
+                        end_date -= Duration::days(1);
+                    }
+                    (end_date.max(start_dt.date()), 1.0)
+                }
+            }
+        };
+
+        Ok(Some(CalendarEvent {
+            uid: self.uid,
+            summary: self.summary.unwrap_or_default(),
+            start_date: start.date(),
+            end_date,
+            day_fraction,
+            attendees: self.attendees,
+            organizer: self.organizer,
+        }))
+    }
+}
+
+/// Parse all events from an iCalendar document
+///
+/// Cancelled events are dropped. Alarms and other nested components inside
+/// an event are ignored.
+pub fn parse_ics(input: &str) -> Result<Vec<CalendarEvent>, IcsParseError> {
+    let lines = unfold(input);
+    if !lines
+        .iter()
+        .any(|(_, l)| l.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err(IcsParseError::NotACalendar);
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<EventBuilder> = None;
+    // Depth of components nested inside the current event (e.g., VALARM)
+    let mut nested = 0usize;
+
+    for (line, text) in lines {
+        let Some(content) = parse_content_line(line, &text) else {
+            continue;
+        };
+
+        match (content.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if content.value.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(EventBuilder {
+                    begin_line: line,
+                    ..Default::default()
+                });
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if content.value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(builder) = current.take() {
+                    events.extend(builder.build()?);
+                }
+            }
+            (_, Some(_)) if nested > 0 => {}
+            (name, Some(event)) => match name {
+                "UID" => event.uid = Some(content.value.trim().to_string()),
+                "SUMMARY" => event.summary = Some(unescape_text(content.value.trim())),
+                "DTSTART" => event.start = Some(parse_event_time(&content)?),
+                "DTEND" => event.end = Some(parse_event_time(&content)?),
+                "DURATION" => event.duration = Some(parse_duration(&content)?),
+                "ATTENDEE" => event.attendees.extend(parse_mailto(&content.value)),
+                "ORGANIZER" => event.organizer = parse_mailto(&content.value),
+                "STATUS" => event.cancelled = content.value.eq_ignore_ascii_case("CANCELLED"),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    if current.is_some() {
+        return Err(IcsParseError::UnterminatedEvent);
+    }
+    Ok(events)
+}
+
+/// How imported events should be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcsImportMode {
+    /// Every event becomes a team-wide holiday
+    Holidays,
+    /// Events are matched to team members and become their time off
+    TimeOff,
+}
+
+/// Why an event was left out of an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// An identical holiday or time off entry already exists
+    Duplicate,
+    /// No team member matched the event's attendees or summary
+    NoMatchingMember,
+}
+
+/// An event that will not be imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedEvent {
+    pub event: CalendarEvent,
+    pub reason: SkipReason,
+}
+
+/// Time off to add to a specific team member
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberTimeOff {
+    pub member_id: Uuid,
+    pub member_name: String,
+    pub time_off: TimeOff,
+}
+
+/// Result of mapping calendar events onto the team, before applying it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IcsImportPreview {
+    pub holidays: Vec<Holiday>,
+    pub time_off: Vec<MemberTimeOff>,
+    pub skipped: Vec<SkippedEvent>,
+}
+
+impl IcsImportPreview {
+    /// True if applying the preview would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.holidays.is_empty() && self.time_off.is_empty()
+    }
+
+    /// Add the previewed holidays and time off to the preferences
+    pub fn apply(&self, prefs: &mut Preferences) {
+        prefs.holidays.extend(self.holidays.iter().cloned());
+        prefs.holidays.sort_by_key(|h| h.start_date);
+
+        for entry in &self.time_off {
+            if let Some(member) = prefs
+                .team_members
+                .iter_mut()
+                .find(|m| m.id == entry.member_id)
+            {
+                member.time_off.push(entry.time_off.clone());
+                member.time_off.sort_by_key(|t| t.start_date);
+            }
+        }
+    }
+}
+
+/// Lowercase and collapse a name to letters separated by single spaces
+fn normalize_name(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Team members an event belongs to
+///
+/// Matches by email first, then by the member's full name appearing in the
+/// summary, then by an email local part that spells the member's name
+/// (e.g., `alice.kim@` for "Alice Kim").
+fn match_members<'a>(event: &CalendarEvent, members: &'a [TeamMember]) -> Vec<&'a TeamMember> {
+    let emails: Vec<&str> = event
+        .attendees
+        .iter()
+        .chain(event.organizer.iter())
+        .map(String::as_str)
+        .collect();
+
+    let by_email: Vec<&TeamMember> = members
+        .iter()
+        .filter(|m| {
+            m.email
+                .as_deref()
+                .is_some_and(|email| emails.iter().any(|e| e.eq_ignore_ascii_case(email)))
+        })
+        .collect();
+    if !by_email.is_empty() {
+        return by_email;
+    }
+
+    let summary = format!(" {} ", normalize_name(&event.summary));
+    let by_name: Vec<&TeamMember> = members
+        .iter()
+        .filter(|m| {
+            let name = normalize_name(&m.name);
+            !name.is_empty() && summary.contains(&format!(" {} ", name))
+        })
+        .collect();
+    if !by_name.is_empty() {
+        return by_name;
+    }
+
+    members
+        .iter()
+        .filter(|m| {
+            let name = normalize_name(&m.name);
+            !name.is_empty()
+                && emails.iter().any(|email| {
+                    email
+                        .split('@')
+                        .next()
+                        .is_some_and(|local| normalize_name(local) == name)
+                })
+        })
+        .collect()
+}
+
+/// Map parsed events onto holidays or member time off without modifying
+/// the preferences
+pub fn plan_import(
+    events: &[CalendarEvent],
+    mode: IcsImportMode,
+    prefs: &Preferences,
+) -> IcsImportPreview {
+    let mut preview = IcsImportPreview::default();
+
+    for event in events {
+        match mode {
+            IcsImportMode::Holidays => {
+                let is_duplicate = prefs
+                    .holidays
+                    .iter()
+                    .chain(preview.holidays.iter())
+                    .any(|h| h.start_date == event.start_date && h.end_date == event.end_date);
+                if is_duplicate {
+                    preview.skipped.push(SkippedEvent {
+                        event: event.clone(),
+                        reason: SkipReason::Duplicate,
+                    });
+                } else {
+                    let name = if event.summary.trim().is_empty() {
+                        "Holiday".to_string()
+                    } else {
+                        event.summary.trim().to_string()
+                    };
+                    preview
+                        .holidays
+                        .push(Holiday::new(name, event.start_date, event.end_date));
+                }
+            }
+            IcsImportMode::TimeOff => {
+                let members = match_members(event, &prefs.team_members);
+                if members.is_empty() {
+                    preview.skipped.push(SkippedEvent {
+                        event: event.clone(),
+                        reason: SkipReason::NoMatchingMember,
+                    });
+                    continue;
+                }
+
+                let mut added = false;
+                for member in members {
+                    let is_duplicate = member
+                        .time_off
+                        .iter()
+                        .chain(
+                            preview
+                                .time_off
+                                .iter()
+                                .filter(|e| e.member_id == member.id)
+                                .map(|e| &e.time_off),
+                        )
+                        .any(|t| t.start_date == event.start_date && t.end_date == event.end_date);
+                    if is_duplicate {
+                        continue;
+                    }
+
+                    let mut time_off =
+                        TimeOff::new(event.start_date, event.end_date, event.day_fraction);
+                    if !event.summary.trim().is_empty() {
+                        time_off.note = Some(event.summary.trim().to_string());
+                    }
+                    preview.time_off.push(MemberTimeOff {
+                        member_id: member.id,
+                        member_name: member.name.clone(),
+                        time_off,
+                    });
+                    added = true;
+                }
+
+                if !added {
+                    preview.skipped.push(SkippedEvent {
+                        event: event.clone(),
+                        reason: SkipReason::Duplicate,
+                    });
+                }
+            }
+        }
+    }
+
+    preview
+}
+
+/// Options controlling a calendar export
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct IcsExportOptions {
+    /// Add an event per team member for each run of consecutive weeks on
+    /// the same technical project
+    pub member_assignments: bool,
+}
+
+/// Line ending required by RFC 5545
+const ICS_LINE_END: &str = "\r\n";
+
+/// Longest content line, in octets, before it must be folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Domain part of exported event UIDs
+const UID_DOMAIN: &str = "quarterly-planner";
+
+/// An all-day event to export
+struct ExportEvent {
+    uid: String,
+    summary: String,
+    description: String,
+    /// First day (inclusive)
+    start: NaiveDate,
+    /// Last day (inclusive)
+    end: NaiveDate,
+}
+
+/// Write the plan's project dates as an iCalendar document
+///
+/// Each roadmap project becomes an event spanning its start date to its
+/// launch date plus a one-day launch event; each technical project spans its
+/// start date to its expected completion (or is a one-day start event if it
+/// has none).
+pub fn plan_calendar_ics(
+    prefs: &Preferences,
+    state: &PlanState,
+    options: IcsExportOptions,
+) -> String {
+    let mut events = Vec::new();
+
+    for project in &state.roadmap_projects {
+        events.push(ExportEvent {
+            uid: format!("roadmap-{}@{}", project.id, UID_DOMAIN),
+            summary: project.name.clone(),
+            description: format!(
+                "Roadmap project, launching {}",
+                project.launch_date.format("%b %-d, %Y")
+            ),
+            start: project.start_date,
+            end: project.launch_date.max(project.start_date),
+        });
+        events.push(ExportEvent {
+            uid: format!("launch-{}@{}", project.id, UID_DOMAIN),
+            summary: format!("Launch: {}", project.name),
+            description: format!("Launch date for {}", project.name),
+            start: project.launch_date,
+            end: project.launch_date,
+        });
+    }
+
+    for project in &state.technical_projects {
+        let roadmap = project
+            .roadmap_project_id
+            .and_then(|id| state.get_roadmap_project(&id))
+            .map(|p| format!(" for {}", p.name))
+            .unwrap_or_default();
+        let (summary, end) = match project.expected_completion {
+            Some(end) => (project.name.clone(), end.max(project.start_date)),
+            None => (format!("Start: {}", project.name), project.start_date),
+        };
+        events.push(ExportEvent {
+            uid: format!("technical-{}@{}", project.id, UID_DOMAIN),
+            summary,
+            description: format!("Technical project{}", roadmap),
+            start: project.start_date,
+            end,
+        });
+    }
+
+    if options.member_assignments {
+        events.extend(assignment_events(prefs, state));
+    }
+
+    let mut out = String::new();
+    let stamp = state.metadata.modified_at.format("%Y%m%dT%H%M%SZ");
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//Quarterly Planner//Plan Export//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(
+        &mut out,
+        &format!(
+            "X-WR-CALNAME:{}",
+            escape_text(&format!("{} {}", prefs.team_name, state.quarter_name))
+        ),
+    );
+    for event in events {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}", event.uid));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(
+            &mut out,
+            &format!("DTSTART;VALUE=DATE:{}", event.start.format("%Y%m%d")),
+        );
+        // DTEND is exclusive for all-day events
+        push_line(
+            &mut out,
+            &format!(
+                "DTEND;VALUE=DATE:{}",
+                (event.end + Duration::days(1)).format("%Y%m%d")
+            ),
+        );
+        push_line(
+            &mut out,
+            &format!("SUMMARY:{}", escape_text(&event.summary)),
+        );
+        push_line(
+            &mut out,
+            &format!("DESCRIPTION:{}", escape_text(&event.description)),
+        );
+        push_line(&mut out, "TRANSP:TRANSPARENT");
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// One event per member per run of consecutive weeks on a project, Monday
+/// of the first week to Friday of the last
+fn assignment_events(prefs: &Preferences, state: &PlanState) -> Vec<ExportEvent> {
+    let mut events = Vec::new();
+    for member in &prefs.team_members {
+        let mut weeks: Vec<(Uuid, NaiveDate, f32)> = state
+            .allocations
+            .iter()
+            .filter(|a| a.team_member_id == member.id)
+            .flat_map(|a| {
+                a.assignments.iter().map(|assignment| {
+                    (
+                        assignment.technical_project_id,
+                        a.week_start_date,
+                        assignment.percentage,
+                    )
+                })
+            })
+            .collect();
+        weeks.sort_by_key(|&(project_id, week, _)| (project_id, week));
+
+        // (project, first week, last week, allocated weeks)
+        let mut runs: Vec<(Uuid, NaiveDate, NaiveDate, f32)> = Vec::new();
+        for (project_id, week, percentage) in weeks {
+            match runs.last_mut() {
+                Some(run) if run.0 == project_id && run.2 + Duration::weeks(1) == week => {
+                    run.2 = week;
+                    run.3 += percentage / 100.0;
+                }
+                _ => runs.push((project_id, week, week, percentage / 100.0)),
+            }
+        }
+        runs.sort_by_key(|&(_, first, _, _)| first);
+
+        for (project_id, first, last, allocated) in runs {
+            let project = state
+                .get_technical_project(&project_id)
+                .map_or("Unknown project", |p| p.name.as_str());
+            let calendar_weeks = (last - first).num_weeks() + 1;
+            events.push(ExportEvent {
+                uid: format!(
+                    "assignment-{}-{}-{}@{}",
+                    member.id,
+                    project_id,
+                    first.format("%Y%m%d"),
+                    UID_DOMAIN
+                ),
+                summary: format!("{}: {}", member.name, project),
+                description: format!(
+                    "{:.1} weeks allocated over {} calendar weeks",
+                    allocated, calendar_weeks
+                ),
+                start: first,
+                end: last + Duration::days(4),
+            });
+        }
+    }
+    events
+}
+
+/// Escape a TEXT value (RFC 5545 section 3.3.11)
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append a content line, folded so no physical line exceeds 75 octets
+fn push_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts toward the limit
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str(ICS_LINE_END);
+            out.push(' ');
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str(ICS_LINE_END);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Role;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    const HOLIDAYS_ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//Holidays//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:mlk-2025@example.com\r\n\
+DTSTART;VALUE=DATE:20250120\r\n\
+DTEND;VALUE=DATE:20250121\r\n\
+SUMMARY:Martin Luther King\\, Jr. Day\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:winter-break@example.com\r\n\
+DTSTART;VALUE=DATE:20251224\r\n\
+DURATION:P1W\r\n\
+SUMMARY:Winter\r\n  \
+break\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+DESCRIPTION:Reminder\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20250704\r\n\
+SUMMARY:Cancelled offsite\r\n\
+STATUS:CANCELLED\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_all_day_events() {
+        let events = parse_ics(HOLIDAYS_ICS).unwrap();
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0].uid.as_deref(), Some("mlk-2025@example.com"));
+        assert_eq!(events[0].summary, "Martin Luther King, Jr. Day");
+        assert_eq!(events[0].start_date, date(2025, 1, 20));
+        assert_eq!(events[0].end_date, date(2025, 1, 20));
+        assert_eq!(events[0].day_fraction, 1.0);
+
+        // Folded summary, DURATION instead of DTEND, VALARM ignored
+        assert_eq!(events[1].summary, "Winter break");
+        assert_eq!(events[1].start_date, date(2025, 12, 24));
+        assert_eq!(events[1].end_date, date(2025, 12, 30));
+    }
+
+    #[test]
+    fn test_parse_timed_events() {
+        let ics = "BEGIN:VCALENDAR\n\
+BEGIN:VEVENT\n\
+DTSTART;TZID=America/New_York:20250207T130000\n\
+DTEND;TZID=America/New_York:20250207T170000\n\
+SUMMARY:Dentist\n\
+ORGANIZER;CN=Bob:mailto:Bob@Example.com\n\
+END:VEVENT\n\
+BEGIN:VEVENT\n\
+DTSTART:20250210T000000Z\n\
+DTEND:20250215T000000Z\n\
+SUMMARY:Ski trip\n\
+ATTENDEE;CN=\"Smith, Carol\";ROLE=REQ-PARTICIPANT:mailto:carol@example.com\n\
+END:VEVENT\n\
+END:VCALENDAR\n";
+        let events = parse_ics(ics).unwrap();
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0].start_date, date(2025, 2, 7));
+        assert_eq!(events[0].end_date, date(2025, 2, 7));
+        assert!((events[0].day_fraction - 0.5).abs() < 0.001);
+        assert_eq!(events[0].organizer.as_deref(), Some("bob@example.com"));
+
+        // Ending at midnight excludes the final day
+        assert_eq!(events[1].end_date, date(2025, 2, 14));
+        assert_eq!(events[1].day_fraction, 1.0);
+        assert_eq!(events[1].attendees, vec!["carol@example.com".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_ics("hello"), Err(IcsParseError::NotACalendar));
+        assert_eq!(
+            parse_ics("BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Open\n"),
+            Err(IcsParseError::UnterminatedEvent)
+        );
+        assert_eq!(
+            parse_ics("BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:No date\nEND:VEVENT\nEND:VCALENDAR"),
+            Err(IcsParseError::MissingStart { line: 2 })
+        );
+        assert!(matches!(
+            parse_ics(
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:2025-01-01\nEND:VEVENT\nEND:VCALENDAR"
+            ),
+            Err(IcsParseError::InvalidValue { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_plan_holiday_import_skips_duplicates() {
+        let events = parse_ics(HOLIDAYS_ICS).unwrap();
+        let mut prefs = Preferences::default();
+        prefs.holidays.push(Holiday::new(
+            "MLK Day".to_string(),
+            date(2025, 1, 20),
+            date(2025, 1, 20),
+        ));
+
+        let preview = plan_import(&events, IcsImportMode::Holidays, &prefs);
+        assert_eq!(preview.holidays.len(), 1);
+        assert_eq!(preview.holidays[0].name, "Winter break");
+        assert_eq!(preview.skipped.len(), 1);
+        assert_eq!(preview.skipped[0].reason, SkipReason::Duplicate);
+
+        preview.apply(&mut prefs);
+        assert_eq!(prefs.holidays.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_time_off_import_matches_members() {
+        let mut alice = TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0);
+        alice.email = Some("akim@example.com".to_string());
+        let bob = TeamMember::new("Bob Lee".to_string(), Role::SCIENCE, 12.0);
+        let carol = TeamMember::new("Carol Smith".to_string(), Role::ENGINEERING, 12.0);
+        let mut prefs = Preferences {
+            team_members: vec![alice.clone(), bob.clone(), carol.clone()],
+            ..Default::default()
+        };
+
+        let event = |summary: &str, attendee: Option<&str>| CalendarEvent {
+            uid: None,
+            summary: summary.to_string(),
+            start_date: date(2025, 3, 3),
+            end_date: date(2025, 3, 7),
+            day_fraction: 1.0,
+            attendees: attendee.map(str::to_string).into_iter().collect(),
+            organizer: None,
+        };
+        let events = [
+            event("Vacation", Some("AKIM@example.com")), // email match
+            event("OOO: bob lee", None),                 // name in summary
+            event("PTO", Some("carol.smith@example.com")), // email local part
+            event("Someone else", Some("dave@example.com")),
+        ];
+
+        let preview = plan_import(&events, IcsImportMode::TimeOff, &prefs);
+        let matched: Vec<Uuid> = preview.time_off.iter().map(|e| e.member_id).collect();
+        assert_eq!(matched, vec![alice.id, bob.id, carol.id]);
+        assert_eq!(
+            preview.time_off[0].time_off.note.as_deref(),
+            Some("Vacation")
+        );
+        assert_eq!(preview.skipped.len(), 1);
+        assert_eq!(preview.skipped[0].reason, SkipReason::NoMatchingMember);
+
+        preview.apply(&mut prefs);
+        assert_eq!(prefs.team_members[1].time_off.len(), 1);
+
+        // Importing the same events again adds nothing
+        let again = plan_import(&events, IcsImportMode::TimeOff, &prefs);
+        assert!(again.is_empty());
+        assert_eq!(again.skipped.len(), 4);
+    }
+
+    #[test]
+    fn test_name_match_requires_whole_words() {
+        let al = TeamMember::new("Al".to_string(), Role::ENGINEERING, 12.0);
+        let event = CalendarEvent {
+            uid: None,
+            summary: "Alice out".to_string(),
+            start_date: date(2025, 3, 3),
+            end_date: date(2025, 3, 3),
+            day_fraction: 1.0,
+            attendees: Vec::new(),
+            organizer: None,
+        };
+        assert!(match_members(&event, std::slice::from_ref(&al)).is_empty());
+    }
+
+    #[test]
+    fn test_plan_calendar_export() {
+        use crate::models::{
+            Allocation, Assignment, ProjectColor, RoadmapProject, RoleWeeks, TechnicalProject,
+        };
+
+        let mut prefs = Preferences::new("Payments".to_string());
+        let alice = TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0);
+        prefs.team_members.push(alice.clone());
+        let start = date(2025, 1, 6);
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 13);
+        let roadmap = RoadmapProject::new(
+            "Checkout, v2; with a name long enough to need folding across lines".to_string(),
+            RoleWeeks::new(),
+            start,
+            date(2025, 3, 28),
+            ProjectColor::Blue,
+        );
+        let mut api =
+            TechnicalProject::new("API".to_string(), Some(roadmap.id), RoleWeeks::new(), start);
+        api.expected_completion = Some(date(2025, 2, 14));
+        let docs = TechnicalProject::new("Docs".to_string(), None, RoleWeeks::new(), start);
+        // Weeks 0-1 on API, week 3 on API again, week 1 also on Docs
+        for (week, project_id, percentage) in [
+            (0, api.id, 100.0),
+            (1, api.id, 50.0),
+            (1, docs.id, 50.0),
+            (3, api.id, 100.0),
+        ] {
+            let week = start + Duration::weeks(week);
+            match state
+                .allocations
+                .iter_mut()
+                .find(|a| a.week_start_date == week)
+            {
+                Some(alloc) => alloc
+                    .assignments
+                    .push(Assignment::new(project_id, percentage)),
+                None => {
+                    let mut alloc = Allocation::new(alice.id, week);
+                    alloc
+                        .assignments
+                        .push(Assignment::new(project_id, percentage));
+                    state.allocations.push(alloc);
+                }
+            }
+        }
+        state.roadmap_projects.push(roadmap.clone());
+        state.technical_projects = vec![api, docs];
+
+        let ics = plan_calendar_ics(&prefs, &state, IcsExportOptions::default());
+        assert!(ics.lines().all(|line| line.len() <= MAX_LINE_OCTETS + 1));
+        assert!(ics.contains(&format!("UID:launch-{}@{}", roadmap.id, UID_DOMAIN)));
+        let events = parse_ics(&ics).unwrap();
+        let summary: Vec<(&str, NaiveDate, NaiveDate)> = events
+            .iter()
+            .map(|e| (e.summary.as_str(), e.start_date, e.end_date))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (roadmap.name.as_str(), start, date(2025, 3, 28)),
+                (
+                    "Launch: Checkout, v2; with a name long enough to need folding across lines",
+                    date(2025, 3, 28),
+                    date(2025, 3, 28)
+                ),
+                ("API", start, date(2025, 2, 14)),
+                ("Start: Docs", start, start),
+            ]
+        );
+
+        let with_assignments = IcsExportOptions {
+            member_assignments: true,
+        };
+        let events = parse_ics(&plan_calendar_ics(&prefs, &state, with_assignments)).unwrap();
+        let assignments: Vec<(&str, NaiveDate, NaiveDate)> = events[4..]
+            .iter()
+            .map(|e| (e.summary.as_str(), e.start_date, e.end_date))
+            .collect();
+        assert_eq!(
+            assignments,
+            [
+                ("Alice Kim: API", start, date(2025, 1, 17)),
+                ("Alice Kim: Docs", date(2025, 1, 13), date(2025, 1, 17)),
+                ("Alice Kim: API", date(2025, 1, 27), date(2025, 1, 31)),
+            ]
+        );
+    }
+}
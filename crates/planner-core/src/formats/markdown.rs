@@ -0,0 +1,282 @@
+//! Markdown quarterly plan report
+//!
+//! Renders a `PlanReport` as a planning doc that pastes into wikis, READMEs
+//! and issue trackers. Statuses are shown as colored circles plus a label,
+//! since Markdown has no badge styling.
+
+use std::fmt::Write;
+
+use crate::models::BadgeType;
+use crate::report::{format_date_range, status_label, PlanReport, Utilization};
+
+/// Render a plan report as GitHub-flavored Markdown
+pub fn report_markdown(report: &PlanReport) -> String {
+    let mut out = String::new();
+    // Writing to a String can't fail
+    let _ = write_report(&mut out, report);
+    out
+}
+
+fn write_report(out: &mut String, report: &PlanReport) -> std::fmt::Result {
+    writeln!(
+        out,
+        "# {}: {} Plan\n",
+        escape(&report.team_name),
+        escape(&report.quarter_name)
+    )?;
+    writeln!(
+        out,
+        "{} · {} weeks · {} team members\n",
+        format_date_range(report.quarter_start, report.quarter_end),
+        report.num_weeks,
+        report.members.len()
+    )?;
+    writeln!(
+        out,
+        "**Capacity:** {:.1} weeks · **Allocated:** {:.1} weeks ({:.0}%) · {}\n",
+        report.capacity,
+        report.allocated,
+        report.utilization_pct(),
+        status(report.status)
+    )?;
+
+    writeln!(out, "## Callouts\n")?;
+    if report.callouts.is_empty() {
+        writeln!(out, "_Everyone is allocated within their capacity._\n")?;
+    } else {
+        for callout in &report.callouts {
+            let action = match callout.utilization {
+                Utilization::Over => "move work to someone with room",
+                Utilization::Under => "room for more work",
+            };
+            writeln!(
+                out,
+                "- {} {} ({})",
+                marker(callout.status),
+                escape(&callout.message()),
+                action
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(out, "## Team\n")?;
+    if report.members.is_empty() {
+        writeln!(out, "_No team members._\n")?;
+    } else {
+        writeln!(
+            out,
+            "| Member | Role | Capacity | Allocated | Utilization |\n| --- | --- | ---: | ---: | ---: |"
+        )?;
+        for member in &report.members {
+            writeln!(
+                out,
+                "| {} | {} | {:.1} | {:.1} | {:.0}% |",
+                escape(&member.name),
+                escape(&member.role_name),
+                member.capacity,
+                member.allocated,
+                member.utilization_pct()
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(out, "## Roadmap Projects\n")?;
+    if report.roadmap_projects.is_empty() {
+        writeln!(out, "_No roadmap projects._\n")?;
+    } else {
+        writeln!(
+            out,
+            "| Project | Launch | Estimate | Allocated | Status |\n| --- | --- | ---: | ---: | --- |"
+        )?;
+        for project in &report.roadmap_projects {
+            writeln!(
+                out,
+                "| {} | {} | {:.1} | {:.1} | {} |",
+                escape(&project.name),
+                project.launch_date.format("%b %-d, %Y"),
+                project.estimate,
+                project.allocated,
+                status(project.status)
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(out, "## Technical Projects\n")?;
+    if report.technical_projects.is_empty() {
+        writeln!(out, "_No technical projects._\n")?;
+    } else {
+        writeln!(
+            out,
+            "| Project | Roadmap Project | Dates | Estimate | Allocated | Status |\n| --- | --- | --- | ---: | ---: | --- |"
+        )?;
+        for project in &report.technical_projects {
+            writeln!(
+                out,
+                "| {} | {} | {} | {:.1} | {:.1} | {} |",
+                escape(&project.name),
+                project
+                    .roadmap_name
+                    .as_deref()
+                    .map_or("—".to_string(), escape),
+                project
+                    .dates
+                    .map_or("Not scheduled".to_string(), |(start, end)| {
+                        format_date_range(start, end)
+                    }),
+                project.estimate,
+                project.allocated,
+                status(project.status)
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(out, "## Assignments")?;
+    for member in &report.members {
+        writeln!(out, "\n### {}\n", escape(&member.name))?;
+        if member.assignments.is_empty() {
+            writeln!(out, "_No assignments._")?;
+        }
+        for (project, weeks) in &member.assignments {
+            writeln!(out, "- {}: {:.1} weeks", escape(project), weeks)?;
+        }
+    }
+    Ok(())
+}
+
+/// Colored circle standing in for a badge color
+fn marker(status: BadgeType) -> &'static str {
+    match status {
+        BadgeType::Success => "🟢",
+        BadgeType::Warning => "🟡",
+        BadgeType::Error => "🔴",
+        BadgeType::Info | BadgeType::Neutral => "⚪",
+    }
+}
+
+fn status(status: BadgeType) -> String {
+    format!("{} {}", marker(status), status_label(status))
+}
+
+/// Backslash-escape characters Markdown would treat as formatting, and
+/// keep table cells on one line
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' | '\r' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, PlanState, Preferences, Role, RoleWeeks, TeamMember,
+        TechnicalProject,
+    };
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_report_markdown() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments | Core".to_string());
+        prefs.team_members = vec![
+            TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 2.0),
+            TeamMember::new("Bob Li".to_string(), Role::ENGINEERING, 2.0),
+        ];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 2);
+        let api = TechnicalProject::new(
+            "API_v2".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+            start,
+        );
+        for week in [0, 1] {
+            let mut alloc = Allocation::new(
+                prefs.team_members[0].id,
+                start + chrono::Duration::weeks(week),
+            );
+            alloc.assignments.push(Assignment::new(api.id, 100.0));
+            state.allocations.push(alloc);
+        }
+        state.technical_projects.push(api);
+
+        let markdown = report_markdown(&PlanReport::new(&prefs, &state));
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "# Payments \\| Core: Q1 2025 Plan");
+        assert_eq!(lines[2], "Jan 6 – Jan 19, 2025 · 2 weeks · 2 team members");
+        for expected in [
+            "- 🔴 Bob Li is under-allocated: 0.0 of 2.0 weeks (room for more work)",
+            "| Alice Kim | Engineering | 2.0 | 2.0 | 100% |",
+            "_No roadmap projects._",
+            "| API\\_v2 | — | Jan 6 – Jan 19, 2025 | 2.0 | 2.0 | 🟢 On target |",
+            "- API\\_v2: 2.0 weeks",
+            "_No assignments._",
+        ] {
+            assert!(lines.contains(&expected), "missing line: {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_report_markdown_empty_plan() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new("Payments".to_string());
+        let state = PlanState::new("Q1 2025".to_string(), start, 13);
+
+        let markdown = report_markdown(&PlanReport::new(&prefs, &state));
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(
+            lines[4],
+            "**Capacity:** 0.0 weeks · **Allocated:** 0.0 weeks (0%) · ⚪ Not planned"
+        );
+        for expected in [
+            "_Everyone is allocated within their capacity._",
+            "_No team members._",
+            "_No roadmap projects._",
+            "_No technical projects._",
+        ] {
+            assert!(lines.contains(&expected), "missing line: {}", expected);
+        }
+        assert_eq!(lines.last(), Some(&"## Assignments"));
+    }
+
+    #[test]
+    fn test_report_markdown_unallocated_project() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.team_members = vec![TeamMember::new(
+            "<Alice> & \"Al\"".to_string(),
+            Role::ENGINEERING,
+            0.0,
+        )];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 2);
+        state.technical_projects.push(TechnicalProject::new(
+            "[Spike] #1".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+            start,
+        ));
+
+        let markdown = report_markdown(&PlanReport::new(&prefs, &state));
+        let lines: Vec<&str> = markdown.lines().collect();
+        for expected in [
+            "| \\<Alice\\> & \"Al\" | Engineering | 0.0 | 0.0 | 0% |",
+            "| \\[Spike\\] \\#1 | — | Not scheduled | 2.0 | 0.0 | 🔴 Off target |",
+            "### \\<Alice\\> & \"Al\"",
+            "_No assignments._",
+        ] {
+            assert!(lines.contains(&expected), "missing line: {}", expected);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Mermaid gantt export
+//!
+//! Writes the plan as a Mermaid `gantt` diagram for Markdown renderers that
+//! support Mermaid: one section per roadmap project with its launch date as
+//! a milestone and a task per linked technical project, then a section for
+//! unlinked projects.
+//!
+//! Task dates come from allocations when there are any, so the diagram shows
+//! when work is actually planned; otherwise from the project's start date and
+//! expected completion. A project with neither allocations nor a completion
+//! date is shown as a milestone on its start date.
+
+use std::fmt::Write;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{PlanState, Preferences, TechnicalProject};
+
+/// Shown for a roadmap project whose name has nothing Mermaid can display
+const UNTITLED_SECTION: &str = "Untitled roadmap project";
+/// Shown for a technical project whose name has nothing Mermaid can display
+const UNTITLED_TASK: &str = "Untitled project";
+
+/// Render the plan's projects as a Mermaid gantt diagram (without a code
+/// fence)
+pub fn gantt_mermaid(prefs: &Preferences, state: &PlanState) -> String {
+    let mut out = String::new();
+    // Writing to a String can't fail
+    let _ = write_gantt(&mut out, prefs, state);
+    out
+}
+
+fn write_gantt(out: &mut String, prefs: &Preferences, state: &PlanState) -> std::fmt::Result {
+    writeln!(out, "gantt")?;
+    writeln!(
+        out,
+        "    title {}",
+        label(
+            &format!("{} {}", prefs.team_name, state.quarter_name),
+            "Plan"
+        )
+    )?;
+    writeln!(out, "    dateFormat YYYY-MM-DD")?;
+    writeln!(out, "    axisFormat %b %d")?;
+
+    // Mermaid needs unique task IDs; plan UUIDs are too long to read
+    let mut next_id = 1;
+    for roadmap in &state.roadmap_projects {
+        writeln!(
+            out,
+            "    section {}",
+            label(&roadmap.name, UNTITLED_SECTION)
+        )?;
+        for project in state
+            .technical_projects
+            .iter()
+            .filter(|p| p.roadmap_project_id == Some(roadmap.id))
+        {
+            write_task(out, project, state, &mut next_id)?;
+        }
+        writeln!(
+            out,
+            "    Launch :milestone, m{}, {}, 0d",
+            next_id, roadmap.launch_date
+        )?;
+        next_id += 1;
+    }
+
+    let unlinked: Vec<&TechnicalProject> = state
+        .technical_projects
+        .iter()
+        .filter(|p| {
+            p.roadmap_project_id
+                .is_none_or(|id| state.get_roadmap_project(&id).is_none())
+        })
+        .collect();
+    if !unlinked.is_empty() {
+        writeln!(out, "    section Unlinked")?;
+        for project in unlinked {
+            write_task(out, project, state, &mut next_id)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_task(
+    out: &mut String,
+    project: &TechnicalProject,
+    state: &PlanState,
+    next_id: &mut usize,
+) -> std::fmt::Result {
+    let id = *next_id;
+    *next_id += 1;
+    match task_dates(project, state) {
+        // Mermaid end dates are exclusive
+        Some((start, end)) => writeln!(
+            out,
+            "    {} :t{}, {}, {}",
+            label(&project.name, UNTITLED_TASK),
+            id,
+            start,
+            end + Duration::days(1)
+        ),
+        None => writeln!(
+            out,
+            "    {} :milestone, t{}, {}, 0d",
+            label(&project.name, UNTITLED_TASK),
+            id,
+            project.start_date
+        ),
+    }
+}
+
+/// First and last day of a project: allocated weeks, else stored dates
+fn task_dates(project: &TechnicalProject, state: &PlanState) -> Option<(NaiveDate, NaiveDate)> {
+    if let Some((first_week, last_week)) = state.get_project_allocation_date_range(&project.id) {
+        return Some((first_week, last_week + Duration::days(6)));
+    }
+    project
+        .expected_completion
+        .map(|end| (project.start_date, end.max(project.start_date)))
+}
+
+/// Keep a name from breaking the diagram syntax
+///
+/// `:` separates a task's name from its data, `;` and line breaks end a
+/// statement, and `#` starts an entity code. A section or task with an empty
+/// name doesn't parse, so a name with nothing left becomes `placeholder`.
+fn label(text: &str, placeholder: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| match c {
+            ':' | ';' | '#' | '\n' | '\r' => ' ',
+            _ => c,
+        })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    if words.is_empty() {
+        placeholder.to_string()
+    } else {
+        words.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, ProjectColor, RoadmapProject, Role, RoleWeeks, TeamMember,
+    };
+
+    #[test]
+    fn test_gantt_mermaid() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        let alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        prefs.team_members.push(alice.clone());
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 13);
+        let roadmap = RoadmapProject::new(
+            "Checkout: v2".to_string(),
+            RoleWeeks::new(),
+            start,
+            NaiveDate::from_ymd_opt(2025, 3, 28).unwrap(),
+            ProjectColor::Blue,
+        );
+        let api = TechnicalProject::new(
+            "API; #1".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::new(),
+            start,
+        );
+        let mut design = TechnicalProject::new(
+            "Design".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::new(),
+            start,
+        );
+        design.expected_completion = Some(NaiveDate::from_ymd_opt(2025, 1, 24).unwrap());
+        let docs = TechnicalProject::new("Docs".to_string(), None, RoleWeeks::new(), start);
+        for week in [2, 4] {
+            let mut alloc = Allocation::new(alice.id, start + Duration::weeks(week));
+            alloc.assignments.push(Assignment::new(api.id, 100.0));
+            state.allocations.push(alloc);
+        }
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![api, design, docs];
+
+        assert_eq!(
+            gantt_mermaid(&prefs, &state),
+            "gantt\n\
+             \x20   title Payments Q1 2025\n\
+             \x20   dateFormat YYYY-MM-DD\n\
+             \x20   axisFormat %b %d\n\
+             \x20   section Checkout v2\n\
+             \x20   API 1 :t1, 2025-01-20, 2025-02-10\n\
+             \x20   Design :t2, 2025-01-06, 2025-01-25\n\
+             \x20   Launch :milestone, m3, 2025-03-28, 0d\n\
+             \x20   section Unlinked\n\
+             \x20   Docs :milestone, t4, 2025-01-06, 0d\n"
+        );
+    }
+
+    #[test]
+    fn test_gantt_mermaid_empty_plan() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new(String::new());
+        let state = PlanState::new("Q1 2025".to_string(), start, 13);
+
+        assert_eq!(
+            gantt_mermaid(&prefs, &state),
+            "gantt\n\
+             \x20   title Q1 2025\n\
+             \x20   dateFormat YYYY-MM-DD\n\
+             \x20   axisFormat %b %d\n"
+        );
+    }
+
+    #[test]
+    fn test_gantt_mermaid_placeholder_labels() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new(":".to_string());
+        let mut state = PlanState::new("#;".to_string(), start, 13);
+        let roadmap = RoadmapProject::new(
+            " : # ".to_string(),
+            RoleWeeks::new(),
+            start,
+            start + Duration::weeks(4),
+            ProjectColor::Blue,
+        );
+        let linked = TechnicalProject::new(
+            "::;;##".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::new(),
+            start,
+        );
+        let unnamed = TechnicalProject::new(String::new(), None, RoleWeeks::new(), start);
+        let ratio =
+            TechnicalProject::new("Ratio 3:1 #2".to_string(), None, RoleWeeks::new(), start);
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![linked, unnamed, ratio];
+
+        assert_eq!(
+            gantt_mermaid(&prefs, &state),
+            "gantt\n\
+             \x20   title Plan\n\
+             \x20   dateFormat YYYY-MM-DD\n\
+             \x20   axisFormat %b %d\n\
+             \x20   section Untitled roadmap project\n\
+             \x20   Untitled project :milestone, t1, 2025-01-06, 0d\n\
+             \x20   Launch :milestone, m2, 2025-02-03, 0d\n\
+             \x20   section Unlinked\n\
+             \x20   Untitled project :milestone, t3, 2025-01-06, 0d\n\
+             \x20   Ratio 3 1 2 :milestone, t4, 2025-01-06, 0d\n"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! Import and export formats for plans, team data and reports
+//!
+//! Each submodule handles one external format. Parsers produce plain model
+//! types so the app can preview results before applying them.
+
+pub mod csv;
+pub mod html;
+pub mod ical;
+pub mod markdown;
+pub mod mermaid;
+pub mod svg;
@@ -0,0 +1,489 @@
+//! SVG Gantt chart of roadmap and technical projects
+//!
+//! Draws the quarter's weeks across and one row per project down: each
+//! roadmap project from its start date to its launch date, followed by its
+//! linked technical projects, then any unlinked ones. Sprints are shaded
+//! alternately and launch dates are marked with a diamond.
+//!
+//! The output is a standalone SVG with presentation attributes only (no CSS
+//! or scripts), so the same markup renders in the app, in browsers, and when
+//! dropped into slides. Text width isn't measured; long names are truncated.
+
+use std::fmt::Write;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{PlanState, Preferences, ProjectColor, TechnicalProject};
+use crate::utils::generate_quarter_weeks;
+
+const PADDING: f32 = 16.0;
+const TITLE_HEIGHT: f32 = 32.0;
+/// Sprint labels above week labels
+const HEADER_HEIGHT: f32 = 44.0;
+const LABEL_WIDTH: f32 = 220.0;
+const WEEK_WIDTH: f32 = 48.0;
+const ROW_HEIGHT: f32 = 28.0;
+const ROADMAP_BAR_HEIGHT: f32 = 16.0;
+const TECHNICAL_BAR_HEIGHT: f32 = 10.0;
+/// Longest label, in characters, before it's truncated
+const MAX_LABEL_CHARS: usize = 30;
+
+/// Color scheme for a chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GanttTheme {
+    /// Dark background, matching the app
+    Dark,
+    /// White background, for slides and printing
+    #[default]
+    Light,
+}
+
+struct Palette {
+    background: &'static str,
+    text: &'static str,
+    muted: &'static str,
+    grid: &'static str,
+    sprint_shade: &'static str,
+    launch: &'static str,
+}
+
+impl GanttTheme {
+    fn palette(self) -> Palette {
+        match self {
+            GanttTheme::Dark => Palette {
+                background: "#0f0f11",
+                text: "#f4f4f5",
+                muted: "#a1a1aa",
+                grid: "#27272a",
+                sprint_shade: "#18181b",
+                launch: "#f4f4f5",
+            },
+            GanttTheme::Light => Palette {
+                background: "#ffffff",
+                text: "#1f2328",
+                muted: "#59636e",
+                grid: "#d0d7de",
+                sprint_shade: "#f6f8fa",
+                launch: "#1f2328",
+            },
+        }
+    }
+}
+
+/// One row of the chart
+struct Row {
+    label: String,
+    /// Technical projects are indented under their roadmap project
+    indent: bool,
+    /// First and last day of the bar (inclusive)
+    dates: Option<(NaiveDate, NaiveDate)>,
+    color: ProjectColor,
+    launch_date: Option<NaiveDate>,
+    /// Hover text
+    tooltip: String,
+}
+
+/// Render the plan's roadmap and technical projects as an SVG Gantt chart
+pub fn gantt_svg(prefs: &Preferences, state: &PlanState, theme: GanttTheme) -> String {
+    let palette = theme.palette();
+    let rows = chart_rows(state);
+    let weeks = generate_quarter_weeks(
+        state.quarter_start_date,
+        state.num_weeks,
+        prefs.sprint_length_weeks,
+    );
+
+    let chart_left = PADDING + LABEL_WIDTH;
+    let chart_width = WEEK_WIDTH * weeks.len() as f32;
+    let chart_top = PADDING + TITLE_HEIGHT + HEADER_HEIGHT;
+    let body_height = ROW_HEIGHT * rows.len().max(1) as f32;
+    let width = chart_left + chart_width + PADDING;
+    let height = chart_top + body_height + PADDING;
+
+    // Position of the start of a day, clamped to the quarter and rounded
+    // to a tenth of a pixel to keep the markup short
+    let x_for = |date: NaiveDate| {
+        let weeks = (date - state.quarter_start_date).num_days() as f32 / 7.0;
+        let x = chart_left + (weeks * WEEK_WIDTH).clamp(0.0, chart_width);
+        (x * 10.0).round() / 10.0
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="-apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif" font-size="12">"#,
+        width, height, width, height
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect width="{}" height="{}" fill="{}"/>"#,
+        width, height, palette.background
+    );
+    let _ = writeln!(
+        out,
+        r#"<text x="{}" y="{}" font-size="16" font-weight="600" fill="{}">{}</text>"#,
+        PADDING,
+        PADDING + 18.0,
+        palette.text,
+        escape(&format!("{} · {}", prefs.team_name, state.quarter_name))
+    );
+
+    // Sprint shading and labels, then week labels and grid lines
+    let header_top = PADDING + TITLE_HEIGHT;
+    for (i, week) in weeks.iter().enumerate() {
+        let x = chart_left + WEEK_WIDTH * i as f32;
+        if week.is_sprint_start() {
+            let sprint_weeks = week.sprint_length_weeks.min(weeks.len() - i);
+            let sprint_width = WEEK_WIDTH * sprint_weeks as f32;
+            if week.sprint_number % 2 == 0 {
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    x,
+                    header_top,
+                    sprint_width,
+                    HEADER_HEIGHT + body_height,
+                    palette.sprint_shade
+                );
+            }
+            let _ = writeln!(
+                out,
+                r#"<text x="{}" y="{}" fill="{}" font-weight="600">Sprint {}</text>"#,
+                x + 4.0,
+                header_top + 14.0,
+                palette.muted,
+                week.sprint_number
+            );
+        }
+        let _ = writeln!(
+            out,
+            r#"<text x="{}" y="{}" fill="{}" font-size="10">{}</text>"#,
+            x + 4.0,
+            header_top + 36.0,
+            palette.muted,
+            week.start_date.format("%b %-d")
+        );
+        let stroke = if week.is_sprint_start() { 1.0 } else { 0.5 };
+        let _ = writeln!(
+            out,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            x,
+            header_top + if week.is_sprint_start() { 0.0 } else { 22.0 },
+            x,
+            chart_top + body_height,
+            palette.grid,
+            stroke
+        );
+    }
+    let _ = writeln!(
+        out,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}"/>"#,
+        chart_left,
+        chart_top,
+        chart_left + chart_width,
+        chart_top,
+        palette.grid
+    );
+
+    if rows.is_empty() {
+        let _ = writeln!(
+            out,
+            r#"<text x="{}" y="{}" fill="{}" font-style="italic">No projects yet</text>"#,
+            PADDING,
+            chart_top + 18.0,
+            palette.muted
+        );
+    }
+
+    let quarter_end = state.quarter_start_date + Duration::weeks(weeks.len() as i64);
+    for (i, row) in rows.iter().enumerate() {
+        let top = chart_top + ROW_HEIGHT * i as f32;
+        let middle = top + ROW_HEIGHT / 2.0;
+        let (label_x, weight) = if row.indent {
+            (PADDING + 12.0, "400")
+        } else {
+            (PADDING, "600")
+        };
+        let _ = writeln!(
+            out,
+            r#"<text x="{}" y="{}" fill="{}" font-weight="{}">{}</text>"#,
+            label_x,
+            middle + 4.0,
+            palette.text,
+            weight,
+            escape(&truncate(&row.label))
+        );
+
+        if let Some((start, end)) = row.dates {
+            // Only the part of the bar inside the quarter is drawn
+            if start < quarter_end && end >= state.quarter_start_date {
+                let x = x_for(start);
+                let bar_width = ((x_for(end + Duration::days(1)) - x) * 10.0).round() / 10.0;
+                let bar_width = bar_width.max(2.0);
+                let bar_height = if row.indent {
+                    TECHNICAL_BAR_HEIGHT
+                } else {
+                    ROADMAP_BAR_HEIGHT
+                };
+                let opacity = if row.indent { 0.7 } else { 1.0 };
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" rx="3" fill="{}" fill-opacity="{}"><title>{}</title></rect>"#,
+                    x,
+                    middle - bar_height / 2.0,
+                    bar_width,
+                    bar_height,
+                    row.color.to_hex(),
+                    opacity,
+                    escape(&row.tooltip)
+                );
+            }
+        }
+
+        if let Some(launch) = row
+            .launch_date
+            .filter(|d| *d >= state.quarter_start_date && *d < quarter_end)
+        {
+            // Diamond centered on the launch day
+            let cx = x_for(launch) + (WEEK_WIDTH / 14.0).round();
+            let r = 6.0;
+            let _ = writeln!(
+                out,
+                r#"<polygon points="{},{} {},{} {},{} {},{}" fill="{}" stroke="{}" stroke-width="1.5"><title>Launch: {}</title></polygon>"#,
+                cx,
+                middle - r,
+                cx + r,
+                middle,
+                cx,
+                middle + r,
+                cx - r,
+                middle,
+                row.color.to_hex(),
+                palette.launch,
+                launch.format("%b %-d, %Y")
+            );
+        }
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Roadmap projects with their technical projects, then unlinked projects
+fn chart_rows(state: &PlanState) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for roadmap in &state.roadmap_projects {
+        rows.push(Row {
+            label: roadmap.name.clone(),
+            indent: false,
+            dates: Some((
+                roadmap.start_date,
+                roadmap.launch_date.max(roadmap.start_date),
+            )),
+            color: roadmap.color,
+            launch_date: Some(roadmap.launch_date),
+            tooltip: format!(
+                "{}: {} to launch {}",
+                roadmap.name,
+                roadmap.start_date.format("%b %-d"),
+                roadmap.launch_date.format("%b %-d, %Y")
+            ),
+        });
+        rows.extend(
+            state
+                .technical_projects
+                .iter()
+                .filter(|p| p.roadmap_project_id == Some(roadmap.id))
+                .map(|p| technical_row(p, state)),
+        );
+    }
+
+    // Projects whose roadmap project is missing count as unlinked
+    let unlinked: Vec<&TechnicalProject> = state
+        .technical_projects
+        .iter()
+        .filter(|p| {
+            p.roadmap_project_id
+                .is_none_or(|id| state.get_roadmap_project(&id).is_none())
+        })
+        .collect();
+    if !unlinked.is_empty() {
+        rows.push(Row {
+            label: "Unlinked".to_string(),
+            indent: false,
+            dates: None,
+            color: ProjectColor::Blue,
+            launch_date: None,
+            tooltip: String::new(),
+        });
+        rows.extend(unlinked.into_iter().map(|p| technical_row(p, state)));
+    }
+    rows
+}
+
+/// A technical project from its start date to its expected completion, or
+/// through its last allocated week if no completion date is set
+fn technical_row(project: &TechnicalProject, state: &PlanState) -> Row {
+    let end = project.expected_completion.or_else(|| {
+        state
+            .get_project_allocation_date_range(&project.id)
+            .map(|(_, last_week)| last_week + Duration::days(6))
+    });
+    let dates = end.map(|end| (project.start_date, end.max(project.start_date)));
+    let tooltip = match dates {
+        Some((start, end)) => format!(
+            "{}: {} – {}",
+            project.name,
+            start.format("%b %-d"),
+            end.format("%b %-d, %Y")
+        ),
+        None => project.name.clone(),
+    };
+    Row {
+        label: project.name.clone(),
+        indent: true,
+        dates,
+        color: project.get_color_from_state(state),
+        launch_date: None,
+        tooltip,
+    }
+}
+
+fn truncate(label: &str) -> String {
+    if label.chars().count() > MAX_LABEL_CHARS {
+        let mut short: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+        short.push('…');
+        short
+    } else {
+        label.to_string()
+    }
+}
+
+/// Escape text for element content and quoted attribute values
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{RoadmapProject, RoleWeeks};
+
+    #[test]
+    fn test_gantt_svg() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("R&D".to_string());
+        prefs.sprint_length_weeks = 2;
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 4);
+        let roadmap = RoadmapProject::new(
+            "Checkout <v2>".to_string(),
+            RoleWeeks::new(),
+            start + Duration::weeks(1),
+            start + Duration::weeks(2) + Duration::days(2),
+            ProjectColor::Teal,
+        );
+        let mut api =
+            TechnicalProject::new("API".to_string(), Some(roadmap.id), RoleWeeks::new(), start);
+        api.expected_completion = Some(start + Duration::weeks(8));
+        let docs = TechnicalProject::new(
+            "A very long technical project name that won't fit".to_string(),
+            None,
+            RoleWeeks::new(),
+            start,
+        );
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![api, docs];
+
+        let svg = gantt_svg(&prefs, &state, GanttTheme::Light);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"444\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">R&amp;D · Q1 2025</text>"));
+        assert!(svg.contains(">Sprint 1</text>") && svg.contains(">Sprint 2</text>"));
+        assert!(svg.contains(">Jan 27</text>"));
+
+        // Roadmap bar: week 2 through the Wednesday of week 3, in its color
+        let x = PADDING + LABEL_WIDTH;
+        assert!(svg.contains(&format!(
+            r##"<rect x="{}" y="{}" width="{}" height="16" rx="3" fill="#2DD4BF""##,
+            x + WEEK_WIDTH,
+            PADDING + TITLE_HEIGHT + HEADER_HEIGHT + 6.0,
+            68.6
+        )));
+        assert!(svg.contains("<title>Launch: Jan 22, 2025</title>"));
+        // Technical bar is clipped to the quarter; the unscheduled one has none
+        assert!(svg.contains(&format!(r#"width="{}" height="10""#, WEEK_WIDTH * 4.0)));
+        assert_eq!(svg.matches("height=\"10\"").count(), 1);
+        assert!(svg.contains(">Unlinked</text>"));
+        assert!(svg.contains(">A very long technical project…</text>"));
+    }
+
+    #[test]
+    fn test_gantt_svg_empty_plan() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new("Payments".to_string());
+        let state = PlanState::new("Q1 2025".to_string(), start, 13);
+
+        for theme in [GanttTheme::Light, GanttTheme::Dark] {
+            let svg = gantt_svg(&prefs, &state, theme);
+            assert!(svg.ends_with("</svg>\n"));
+            assert!(svg.contains(">No projects yet</text>"));
+            // Room for the placeholder row, but no bars or launch markers
+            let height = PADDING * 2.0 + TITLE_HEIGHT + HEADER_HEIGHT + ROW_HEIGHT;
+            assert!(svg.contains(&format!(r#"height="{}" viewBox"#, height)));
+            assert!(!svg.contains("rx=\"3\""));
+            assert!(!svg.contains("<polygon"));
+        }
+    }
+
+    #[test]
+    fn test_gantt_svg_escapes_names() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new("\"Core\" <Platform>".to_string());
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 4);
+        let roadmap = RoadmapProject::new(
+            "Q&A <beta>".to_string(),
+            RoleWeeks::new(),
+            start,
+            start + Duration::weeks(1),
+            ProjectColor::Blue,
+        );
+        let mut linked = TechnicalProject::new(
+            "Say \"hi\"".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::new(),
+            start,
+        );
+        linked.expected_completion = Some(start + Duration::days(6));
+        // Never allocated and no completion date, so there's no bar to draw
+        let idle = TechnicalProject::new(
+            "Idle & <waiting>".to_string(),
+            None,
+            RoleWeeks::new(),
+            start,
+        );
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![linked, idle];
+
+        let svg = gantt_svg(&prefs, &state, GanttTheme::Light);
+        assert!(svg.contains(">&quot;Core&quot; &lt;Platform&gt; · Q1 2025</text>"));
+        assert!(svg.contains(">Q&amp;A &lt;beta&gt;</text>"));
+        assert!(svg.contains("<title>Q&amp;A &lt;beta&gt;: Jan 6 to launch Jan 13, 2025</title>"));
+        assert!(svg.contains(">Say &quot;hi&quot;</text>"));
+        assert!(svg.contains("<title>Say &quot;hi&quot;: Jan 6 – Jan 12, 2025</title>"));
+        assert!(svg.contains(">Idle &amp; &lt;waiting&gt;</text>"));
+        assert!(!svg.contains("Idle &amp; &lt;waiting&gt;</title>"));
+        assert_eq!(svg.matches("height=\"10\"").count(), 1);
+        assert!(!svg.contains("<beta>") && !svg.contains("\"hi\""));
+    }
+}
@@ -3,8 +3,17 @@
 //! This crate contains all data models and utility functions that don't depend on
 //! platform-specific code (web, desktop). This allows testing on any platform.
 
+pub mod comparison;
+pub mod diff;
+pub mod formats;
+pub mod merge;
+pub mod migration;
 pub mod models;
+pub mod portfolio;
+pub mod report;
+pub mod scheduler;
 pub mod utils;
+pub mod validation;
 
 // Re-export commonly used types for convenience
 pub use models::*;
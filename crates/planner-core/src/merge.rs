@@ -0,0 +1,711 @@
+//! Three-way merge of plan exports
+//!
+//! Given a common ancestor and two edited copies ("ours" and "theirs"), every
+//! change made on only one side is taken automatically. Changes made on both
+//! sides to the same thing become conflicts that the user resolves by picking
+//! a side.
+//!
+//! The merge works on the serialized form so every entity type merges the
+//! same way:
+//! - Plan settings (team name, quarter) merge field by field
+//! - Projects, team members, holidays, roles and scenarios are matched by ID
+//!   and merge field by field; deleting an item the other side edited is a
+//!   conflict on the whole item
+//! - Allocations merge cell by cell, keyed by `(team_member_id, week_start_date)`
+//!
+//! Merged collections keep our order, with items only the other side added
+//! appended in their order.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::models::PlanExport;
+
+/// Which descendant to take a conflicting change from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+    Ours,
+    Theirs,
+}
+
+/// What a conflict is about
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictSubject {
+    /// A plan-wide setting (team name, quarter)
+    Plan,
+    RoadmapProject(Uuid),
+    TechnicalProject(Uuid),
+    TeamMember(Uuid),
+    Holiday(Uuid),
+    Role(String),
+    Scenario(Uuid),
+    /// One allocation cell
+    Allocation {
+        team_member_id: Uuid,
+        week_start_date: NaiveDate,
+    },
+}
+
+/// A change both sides made differently
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub subject: ConflictSubject,
+    /// Human-readable description, e.g. `Technical project "API": estimates`
+    pub label: String,
+    /// Our version, rendered for display
+    pub ours: String,
+    /// Their version, rendered for display
+    pub theirs: String,
+    location: Location,
+    ours_value: Option<Value>,
+    theirs_value: Option<Value>,
+}
+
+/// Where a conflicting value goes in the merged document
+#[derive(Debug, Clone, PartialEq)]
+enum Location {
+    /// Top-level field
+    Plan(&'static str),
+    /// Whole item in a keyed collection (None removes it)
+    Item {
+        collection: &'static str,
+        key: Value,
+    },
+    /// One field of an item
+    Field {
+        collection: &'static str,
+        key: Value,
+        field: String,
+    },
+    /// Assignments of an allocation cell (None clears it)
+    Cell { key: Value },
+}
+
+/// Errors from merging or resolving
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeError {
+    /// An export couldn't be converted to or from its serialized form
+    Serialization(String),
+    /// `resolve` needs exactly one choice per conflict
+    ResolutionCount { expected: usize, got: usize },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::Serialization(message) => write!(f, "Couldn't merge plans: {}", message),
+            MergeError::ResolutionCount { expected, got } => write!(
+                f,
+                "Expected a resolution for each of {} conflicts, got {}",
+                expected, got
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Outcome of a three-way merge: the automatically merged plan plus conflicts
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeResult {
+    /// Merged document, with our side filled in wherever there's a conflict
+    merged: Value,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// True if everything merged without conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Build the merged plan, taking each conflict from the chosen side
+    ///
+    /// `choices` holds one side per conflict, in the order of `conflicts`.
+    pub fn resolve(&self, choices: &[MergeSide]) -> Result<PlanExport, MergeError> {
+        if choices.len() != self.conflicts.len() {
+            return Err(MergeError::ResolutionCount {
+                expected: self.conflicts.len(),
+                got: choices.len(),
+            });
+        }
+
+        let mut merged = self.merged.clone();
+        for (conflict, side) in self.conflicts.iter().zip(choices) {
+            let value = match side {
+                MergeSide::Ours => conflict.ours_value.clone(),
+                MergeSide::Theirs => conflict.theirs_value.clone(),
+            };
+            apply(&mut merged, &conflict.location, value);
+        }
+        serde_json::from_value(merged).map_err(|e| MergeError::Serialization(e.to_string()))
+    }
+}
+
+/// Three-way merge `ours` and `theirs`, both descended from `base`
+pub fn merge_exports(
+    base: &PlanExport,
+    ours: &PlanExport,
+    theirs: &PlanExport,
+) -> Result<MergeResult, MergeError> {
+    let to_value = |export: &PlanExport| {
+        serde_json::to_value(export).map_err(|e| MergeError::Serialization(e.to_string()))
+    };
+    let (base, ours, theirs) = (to_value(base)?, to_value(ours)?, to_value(theirs)?);
+    let mut merger = Merger {
+        base: &base,
+        ours: &ours,
+        theirs: &theirs,
+        merged: ours.clone(),
+        conflicts: Vec::new(),
+    };
+
+    for field in [
+        "team_name",
+        "quarter_name",
+        "quarter_start_date",
+        "num_weeks",
+    ] {
+        merger.merge_plan_field(field);
+    }
+    merger.merge_collection("roadmap_projects", "Roadmap project", |key| {
+        ConflictSubject::RoadmapProject(uuid_key(key))
+    });
+    merger.merge_collection("technical_projects", "Technical project", |key| {
+        ConflictSubject::TechnicalProject(uuid_key(key))
+    });
+    merger.merge_collection("team_members", "Team member", |key| {
+        ConflictSubject::TeamMember(uuid_key(key))
+    });
+    merger.merge_collection("holidays", "Holiday", |key| {
+        ConflictSubject::Holiday(uuid_key(key))
+    });
+    merger.merge_collection("roles", "Role", |key| ConflictSubject::Role(render(key)));
+    merger.merge_collection("scenarios", "Scenario", |key| {
+        ConflictSubject::Scenario(uuid_key(key))
+    });
+    merger.merge_allocations();
+
+    Ok(MergeResult {
+        merged: merger.merged,
+        conflicts: merger.conflicts,
+    })
+}
+
+struct Merger<'a> {
+    base: &'a Value,
+    ours: &'a Value,
+    theirs: &'a Value,
+    merged: Value,
+    conflicts: Vec<MergeConflict>,
+}
+
+/// Result of merging a single value three ways
+enum Merged<T> {
+    Take(T),
+    Conflict,
+}
+
+/// Three-way merge of one value: a side that didn't change defers to the other
+fn merge3<T: PartialEq + Clone>(base: &T, ours: &T, theirs: &T) -> Merged<T> {
+    if ours == theirs || theirs == base {
+        Merged::Take(ours.clone())
+    } else if ours == base {
+        Merged::Take(theirs.clone())
+    } else {
+        Merged::Conflict
+    }
+}
+
+impl Merger<'_> {
+    fn merge_plan_field(&mut self, field: &'static str) {
+        let get = |doc: &Value| doc.get(field).cloned().unwrap_or(Value::Null);
+        let (ours, theirs) = (get(self.ours), get(self.theirs));
+        match merge3(&get(self.base), &ours, &theirs) {
+            Merged::Take(value) => {
+                apply(&mut self.merged, &Location::Plan(field), Some(value));
+            }
+            Merged::Conflict => self.conflicts.push(MergeConflict {
+                subject: ConflictSubject::Plan,
+                label: format!("Plan: {}", field.replace('_', " ")),
+                ours: render(&ours),
+                theirs: render(&theirs),
+                location: Location::Plan(field),
+                ours_value: Some(ours),
+                theirs_value: Some(theirs),
+            }),
+        }
+    }
+
+    /// Merge a collection of objects identified by their "id"
+    fn merge_collection(
+        &mut self,
+        collection: &'static str,
+        noun: &str,
+        subject: impl Fn(&Value) -> ConflictSubject,
+    ) {
+        let items = |doc: &Value| -> Vec<Value> {
+            doc.get(collection)
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let (base, ours, theirs) = (items(self.base), items(self.ours), items(self.theirs));
+        let find =
+            |list: &[Value], key: &Value| list.iter().find(|item| &item["id"] == key).cloned();
+
+        let mut keys: Vec<Value> = ours.iter().map(|item| item["id"].clone()).collect();
+        for item in theirs.iter().chain(&base) {
+            if !keys.contains(&item["id"]) {
+                keys.push(item["id"].clone());
+            }
+        }
+
+        let mut merged = Vec::new();
+        for key in keys {
+            let (b, o, t) = (find(&base, &key), find(&ours, &key), find(&theirs, &key));
+            let name = [&o, &t, &b]
+                .into_iter()
+                .flatten()
+                .find_map(|item| item.get("name").and_then(Value::as_str))
+                .unwrap_or_default()
+                .to_string();
+            let label = format!("{} \"{}\"", noun, name);
+            let conflict_subject = subject(&key);
+
+            match merge3(&b, &o, &t) {
+                Merged::Take(Some(item)) => merged.push(item),
+                Merged::Take(None) => {}
+                // Edited on both sides: merge field by field
+                Merged::Conflict if o.is_some() && t.is_some() => {
+                    let (o, t) = (o.unwrap_or_default(), t.unwrap_or_default());
+                    let empty = Value::Object(Map::new());
+                    let b = b.unwrap_or(empty);
+                    let mut item = o.clone();
+                    let fields: Vec<String> = o
+                        .as_object()
+                        .into_iter()
+                        .chain(t.as_object())
+                        .flat_map(|object| object.keys().cloned())
+                        .collect();
+                    let mut seen = Vec::new();
+                    for field in fields {
+                        if seen.contains(&field) {
+                            continue;
+                        }
+                        seen.push(field.clone());
+                        let get = |v: &Value| v.get(&field).cloned().unwrap_or(Value::Null);
+                        let (ours_field, theirs_field) = (get(&o), get(&t));
+                        match merge3(&get(&b), &ours_field, &theirs_field) {
+                            Merged::Take(value) => item[&field] = value,
+                            Merged::Conflict => self.conflicts.push(MergeConflict {
+                                subject: conflict_subject.clone(),
+                                label: format!("{}: {}", label, field.replace('_', " ")),
+                                ours: render(&ours_field),
+                                theirs: render(&theirs_field),
+                                location: Location::Field {
+                                    collection,
+                                    key: key.clone(),
+                                    field,
+                                },
+                                ours_value: Some(ours_field),
+                                theirs_value: Some(theirs_field),
+                            }),
+                        }
+                    }
+                    merged.push(item);
+                }
+                // Deleted on one side, edited on the other
+                Merged::Conflict => {
+                    let describe = |side: &Option<Value>| match side {
+                        Some(_) => "Edited".to_string(),
+                        None => "Deleted".to_string(),
+                    };
+                    self.conflicts.push(MergeConflict {
+                        subject: conflict_subject,
+                        label,
+                        ours: describe(&o),
+                        theirs: describe(&t),
+                        location: Location::Item {
+                            collection,
+                            key: key.clone(),
+                        },
+                        ours_value: o.clone(),
+                        theirs_value: t,
+                    });
+                    // Keep our side until resolved
+                    if let Some(item) = o {
+                        merged.push(item);
+                    }
+                }
+            }
+        }
+        self.merged[collection] = Value::Array(merged);
+    }
+
+    /// Merge allocations cell by cell
+    fn merge_allocations(&mut self) {
+        let cells = |doc: &Value| -> Vec<(Value, Value)> {
+            doc.get("allocations")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|alloc| (cell_key(alloc), alloc["assignments"].clone()))
+                .collect()
+        };
+        let (base, ours, theirs) = (cells(self.base), cells(self.ours), cells(self.theirs));
+        // A missing cell is the same as one with no assignments
+        let find = |list: &[(Value, Value)], key: &Value| {
+            list.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, assignments)| assignments.clone())
+                .filter(|assignments| !is_empty_array(assignments))
+        };
+
+        let mut keys: Vec<Value> = ours.iter().map(|(key, _)| key.clone()).collect();
+        for (key, _) in theirs.iter().chain(&base) {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+
+        let mut merged = Vec::new();
+        for key in keys {
+            let (b, o, t) = (find(&base, &key), find(&ours, &key), find(&theirs, &key));
+            let assignments = match merge3(&b, &o, &t) {
+                Merged::Take(assignments) => assignments,
+                Merged::Conflict => {
+                    self.conflicts.push(MergeConflict {
+                        subject: ConflictSubject::Allocation {
+                            team_member_id: uuid_key(&key["team_member_id"]),
+                            week_start_date: key["week_start_date"]
+                                .as_str()
+                                .and_then(|d| d.parse().ok())
+                                .unwrap_or_default(),
+                        },
+                        label: format!(
+                            "Allocation: {}, week of {}",
+                            self.lookup_name("team_members", &key["team_member_id"]),
+                            render(&key["week_start_date"])
+                        ),
+                        ours: self.describe_cell(&o),
+                        theirs: self.describe_cell(&t),
+                        location: Location::Cell { key: key.clone() },
+                        ours_value: o.clone(),
+                        theirs_value: t,
+                    });
+                    o
+                }
+            };
+            if let Some(assignments) = assignments {
+                merged.push(cell_value(&key, assignments));
+            }
+        }
+        self.merged["allocations"] = Value::Array(merged);
+    }
+
+    /// Name of an item in any of the three versions
+    fn lookup_name(&self, collection: &str, id: &Value) -> String {
+        [self.ours, self.theirs, self.base]
+            .into_iter()
+            .filter_map(|doc| doc.get(collection)?.as_array())
+            .flatten()
+            .find(|item| &item["id"] == id)
+            .and_then(|item| item.get("name")?.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| render(id))
+    }
+
+    fn describe_cell(&self, assignments: &Option<Value>) -> String {
+        let Some(assignments) = assignments.as_ref().and_then(Value::as_array) else {
+            return "Unallocated".to_string();
+        };
+        assignments
+            .iter()
+            .map(|a| {
+                format!(
+                    "{} {}%",
+                    self.lookup_name("technical_projects", &a["technical_project_id"]),
+                    render(&a["percentage"])
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Parse an ID from its serialized form
+fn uuid_key(key: &Value) -> Uuid {
+    key.as_str()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .unwrap_or_default()
+}
+
+fn cell_key(alloc: &Value) -> Value {
+    serde_json::json!({
+        "team_member_id": alloc["team_member_id"],
+        "week_start_date": alloc["week_start_date"],
+    })
+}
+
+fn cell_value(key: &Value, assignments: Value) -> Value {
+    let mut alloc = key.clone();
+    alloc["assignments"] = assignments;
+    alloc
+}
+
+fn is_empty_array(value: &Value) -> bool {
+    value.as_array().is_some_and(|a| a.is_empty())
+}
+
+/// Write a resolved value into the merged document
+fn apply(merged: &mut Value, location: &Location, value: Option<Value>) {
+    match location {
+        Location::Plan(field) => merged[*field] = value.unwrap_or(Value::Null),
+        Location::Field {
+            collection,
+            key,
+            field,
+        } => {
+            let item = merged[*collection]
+                .as_array_mut()
+                .and_then(|items| items.iter_mut().find(|item| &item["id"] == key));
+            if let Some(item) = item {
+                item[field.as_str()] = value.unwrap_or(Value::Null);
+            }
+        }
+        Location::Item { collection, key } => {
+            let Some(items) = merged[*collection].as_array_mut() else {
+                return;
+            };
+            match (items.iter().position(|item| &item["id"] == key), value) {
+                (Some(index), Some(item)) => items[index] = item,
+                (Some(index), None) => {
+                    items.remove(index);
+                }
+                (None, Some(item)) => items.push(item),
+                (None, None) => {}
+            }
+        }
+        Location::Cell { key } => {
+            let Some(items) = merged["allocations"].as_array_mut() else {
+                return;
+            };
+            let index = items.iter().position(|alloc| &cell_key(alloc) == key);
+            match (index, value) {
+                (Some(index), Some(assignments)) => items[index]["assignments"] = assignments,
+                (Some(index), None) => {
+                    items.remove(index);
+                }
+                (None, Some(assignments)) => items.push(cell_value(key, assignments)),
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+/// Render a JSON value for display
+fn render(value: &Value) -> String {
+    match value {
+        Value::Null => "none".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(render).collect::<Vec<_>>().join(", "),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, v)| format!("{}: {}", key, render(v)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, PlanState, Preferences, Role, RoleWeeks, TeamMember,
+        TechnicalProject,
+    };
+    use chrono::Duration;
+
+    fn base_export() -> PlanExport {
+        let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences {
+            team_members: vec![
+                TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0),
+                TeamMember::new("Bob Li".to_string(), Role::ENGINEERING, 12.0),
+            ],
+            ..Preferences::default()
+        };
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
+        for name in ["Gateway API", "Data Pipeline"] {
+            state.technical_projects.push(TechnicalProject::new(
+                name.to_string(),
+                None,
+                RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+                quarter_start,
+            ));
+        }
+        PlanExport::from_signals(prefs, state)
+    }
+
+    fn assign(export: &mut PlanExport, member: usize, week: i64, project: usize) {
+        let member_id = export.team_members[member].id;
+        let week_start = export.quarter_start_date + Duration::weeks(week);
+        let project_id = export.technical_projects[project].id;
+        export
+            .allocations
+            .retain(|a| (a.team_member_id, a.week_start_date) != (member_id, week_start));
+        let mut alloc = Allocation::new(member_id, week_start);
+        alloc.assignments.push(Assignment::new(project_id, 100.0));
+        export.allocations.push(alloc);
+    }
+
+    #[test]
+    fn test_non_overlapping_edits_merge_cleanly() {
+        let base = base_export();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+
+        // Field-level: we rename, they re-estimate the same project
+        ours.technical_projects[0].name = "Gateway v2".to_string();
+        theirs.technical_projects[0]
+            .estimates
+            .set(Role::ENGINEERING, 6.0);
+        // Different cells
+        assign(&mut ours, 0, 0, 0);
+        assign(&mut theirs, 1, 0, 1);
+        // They add a member, we remove a project
+        theirs.team_members.push(TeamMember::new(
+            "Cara Diaz".to_string(),
+            Role::SCIENCE,
+            12.0,
+        ));
+        ours.technical_projects.remove(1);
+        theirs.allocations.clear();
+        assign(&mut theirs, 1, 0, 0);
+
+        let result = merge_exports(&base, &ours, &theirs).unwrap();
+        assert!(result.is_clean(), "{:?}", result.conflicts);
+        let merged = result.resolve(&[]).unwrap();
+
+        assert_eq!(merged.technical_projects.len(), 1);
+        assert_eq!(merged.technical_projects[0].name, "Gateway v2");
+        assert_eq!(
+            merged.technical_projects[0]
+                .estimates
+                .get(&Role::ENGINEERING),
+            6.0
+        );
+        assert_eq!(merged.team_members.len(), 3);
+        assert_eq!(merged.allocations.len(), 2);
+        assert!(merged.validate().is_ok());
+    }
+
+    #[test]
+    fn test_conflicting_edits() {
+        let base = base_export();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+
+        ours.technical_projects[0]
+            .estimates
+            .set(Role::ENGINEERING, 5.0);
+        theirs.technical_projects[0]
+            .estimates
+            .set(Role::ENGINEERING, 8.0);
+        assign(&mut ours, 0, 2, 0);
+        assign(&mut theirs, 0, 2, 1);
+
+        let result = merge_exports(&base, &ours, &theirs).unwrap();
+        assert_eq!(result.conflicts.len(), 2);
+
+        let estimate = &result.conflicts[0];
+        assert_eq!(
+            estimate.subject,
+            ConflictSubject::TechnicalProject(base.technical_projects[0].id)
+        );
+        assert_eq!(
+            estimate.label,
+            "Technical project \"Gateway API\": estimates"
+        );
+        assert_eq!(estimate.ours, "eng: 5.0");
+        assert_eq!(estimate.theirs, "eng: 8.0");
+
+        let cell = &result.conflicts[1];
+        assert_eq!(cell.label, "Allocation: Alice Kim, week of 2025-01-20");
+        assert_eq!(cell.ours, "Gateway API 100.0%");
+        assert_eq!(cell.theirs, "Data Pipeline 100.0%");
+
+        let merged = result
+            .resolve(&[MergeSide::Theirs, MergeSide::Ours])
+            .unwrap();
+        assert_eq!(
+            merged.technical_projects[0]
+                .estimates
+                .get(&Role::ENGINEERING),
+            8.0
+        );
+        assert_eq!(
+            merged.allocations[0].assignments[0].technical_project_id,
+            base.technical_projects[0].id
+        );
+
+        assert_eq!(
+            result.resolve(&[MergeSide::Ours]),
+            Err(MergeError::ResolutionCount {
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_delete_versus_edit_conflict() {
+        let base = base_export();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        let bob = base.team_members[1].id;
+
+        ours.team_members.remove(1);
+        theirs.team_members[1].capacity = 10.0;
+
+        let result = merge_exports(&base, &ours, &theirs).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(
+            result.conflicts[0].subject,
+            ConflictSubject::TeamMember(bob)
+        );
+        assert_eq!(
+            (
+                result.conflicts[0].ours.as_str(),
+                result.conflicts[0].theirs.as_str()
+            ),
+            ("Deleted", "Edited")
+        );
+
+        let kept = result.resolve(&[MergeSide::Theirs]).unwrap();
+        assert_eq!(kept.team_members.len(), 2);
+        assert_eq!(kept.team_members[1].capacity, 10.0);
+        let deleted = result.resolve(&[MergeSide::Ours]).unwrap();
+        assert_eq!(deleted.team_members.len(), 1);
+    }
+
+    #[test]
+    fn test_identical_edits_are_not_conflicts() {
+        let base = base_export();
+        let mut ours = base.clone();
+        ours.quarter_name = "Q1 2025 (final)".to_string();
+        assign(&mut ours, 0, 0, 0);
+        let theirs = ours.clone();
+
+        let result = merge_exports(&base, &ours, &theirs).unwrap();
+        assert!(result.is_clean());
+        let merged = result.resolve(&[]).unwrap();
+        assert_eq!(merged.quarter_name, "Q1 2025 (final)");
+        assert_eq!(merged.allocations, ours.allocations);
+    }
+}
@@ -0,0 +1,495 @@
+//! Schema migrations for saved plans and preferences
+//!
+//! Every stored document carries a format version: `schema_version` for
+//! `Preferences`, `metadata.version` for `PlanState`, and `version` for
+//! `PlanExport`. Loading goes through `load_json`, which upgrades older JSON
+//! one step at a time on the untyped `serde_json::Value` before deserializing,
+//! so a model change never has to keep reading its old shape.
+//!
+//! To change a model's format:
+//! 1. Bump `PREFERENCES_SCHEMA_VERSION` or `PLAN_SCHEMA_VERSION`
+//! 2. Add a `Migration` from the old version to the new one to the matching
+//!    list below (plan migrations run on both `PlanState` and `PlanExport`,
+//!    so they must handle both shapes)
+//!
+//! Small additive changes can still use `#[serde(default)]` instead.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::models::{
+    default_roles, legacy_estimates, PlanExport, PlanState, Preferences, PLAN_SCHEMA_VERSION,
+    PREFERENCES_SCHEMA_VERSION,
+};
+
+/// The kinds of versioned document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    Preferences,
+    PlanState,
+    PlanExport,
+}
+
+impl DocumentKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            DocumentKind::Preferences => "preferences",
+            DocumentKind::PlanState => "saved plan",
+            DocumentKind::PlanExport => "plan file",
+        }
+    }
+
+    /// Version this build reads and writes
+    pub fn current_version(self) -> &'static str {
+        match self {
+            DocumentKind::Preferences => PREFERENCES_SCHEMA_VERSION,
+            DocumentKind::PlanState | DocumentKind::PlanExport => PLAN_SCHEMA_VERSION,
+        }
+    }
+
+    fn migrations(self) -> &'static [Migration] {
+        match self {
+            DocumentKind::Preferences => PREFERENCES_MIGRATIONS,
+            DocumentKind::PlanState | DocumentKind::PlanExport => PLAN_MIGRATIONS,
+        }
+    }
+
+    /// JSON pointers to the version field(s); the first one is read
+    fn version_pointers(self) -> &'static [&'static str] {
+        match self {
+            DocumentKind::Preferences => &["/schema_version"],
+            DocumentKind::PlanState => &["/metadata/version"],
+            DocumentKind::PlanExport => &["/version", "/metadata/version"],
+        }
+    }
+}
+
+/// One upgrade step between adjacent versions
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub from: &'static str,
+    pub to: &'static str,
+    /// Human-readable summary of the change
+    pub description: &'static str,
+    pub apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// Upgrade steps for `Preferences`, oldest first
+const PREFERENCES_MIGRATIONS: &[Migration] = &[Migration {
+    from: "1.0",
+    to: "1.1",
+    description: "Store role definitions (Engineering and Science before roles were configurable)",
+    apply: add_default_roles,
+}];
+
+/// Upgrade steps for `PlanState` and `PlanExport`, oldest first
+const PLAN_MIGRATIONS: &[Migration] = &[Migration {
+    from: "1.0",
+    to: "1.1",
+    description: "Replace eng_estimate/sci_estimate with per-role estimates",
+    apply: role_estimates,
+}];
+
+/// Version documents had before they carried one
+const UNVERSIONED: &str = "1.0";
+
+/// Errors from loading a versioned document
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// The input isn't JSON
+    InvalidJson(String),
+    /// The version field isn't a version string
+    InvalidVersion { kind: DocumentKind, found: String },
+    /// Saved by a newer build than this one
+    NewerVersion {
+        kind: DocumentKind,
+        found: String,
+        supported: &'static str,
+    },
+    /// An older version no migration starts from
+    UnsupportedVersion { kind: DocumentKind, found: String },
+    /// A migration step rejected the data
+    StepFailed {
+        from: &'static str,
+        to: &'static str,
+        message: String,
+    },
+    /// The migrated JSON doesn't match the current model
+    Deserialize(String),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::InvalidJson(message) => write!(f, "Not valid JSON: {}", message),
+            MigrationError::InvalidVersion { kind, found } => {
+                write!(f, "The {} has an invalid version \"{}\"", kind.label(), found)
+            }
+            MigrationError::NewerVersion {
+                kind,
+                found,
+                supported,
+            } => write!(
+                f,
+                "The {} is from a newer version of Planner (format {}; this version supports up to {}). Update Planner to open it.",
+                kind.label(),
+                found,
+                supported
+            ),
+            MigrationError::UnsupportedVersion { kind, found } => write!(
+                f,
+                "The {} uses format {}, which this version of Planner can't upgrade",
+                kind.label(),
+                found
+            ),
+            MigrationError::StepFailed { from, to, message } => {
+                write!(f, "Couldn't upgrade from format {} to {}: {}", from, to, message)
+            }
+            MigrationError::Deserialize(message) => write!(f, "Failed to parse: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// A document type with a versioned format
+pub trait Versioned: DeserializeOwned {
+    const KIND: DocumentKind;
+}
+
+impl Versioned for Preferences {
+    const KIND: DocumentKind = DocumentKind::Preferences;
+}
+
+impl Versioned for PlanState {
+    const KIND: DocumentKind = DocumentKind::PlanState;
+}
+
+impl Versioned for PlanExport {
+    const KIND: DocumentKind = DocumentKind::PlanExport;
+}
+
+/// Parse a document, upgrading it from an older format first
+pub fn load_json<T: Versioned>(json: &str) -> Result<T, MigrationError> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| MigrationError::InvalidJson(e.to_string()))?;
+    let value = migrate(T::KIND, value)?;
+    serde_json::from_value(value).map_err(|e| MigrationError::Deserialize(e.to_string()))
+}
+
+/// Upgrade a document to the current format
+pub fn migrate(kind: DocumentKind, value: Value) -> Result<Value, MigrationError> {
+    run_migrations(kind, value, kind.current_version(), kind.migrations())
+}
+
+fn run_migrations(
+    kind: DocumentKind,
+    mut value: Value,
+    current: &'static str,
+    migrations: &[Migration],
+) -> Result<Value, MigrationError> {
+    let pointers = kind.version_pointers();
+    let mut version = match value.pointer(pointers[0]) {
+        None | Some(Value::Null) => UNVERSIONED.to_string(),
+        Some(Value::String(version)) => version.clone(),
+        Some(other) => {
+            return Err(MigrationError::InvalidVersion {
+                kind,
+                found: other.to_string(),
+            })
+        }
+    };
+    let parsed = parse_version(&version).ok_or_else(|| MigrationError::InvalidVersion {
+        kind,
+        found: version.clone(),
+    })?;
+    if parsed > parse_version(current).unwrap_or_default() {
+        return Err(MigrationError::NewerVersion {
+            kind,
+            found: version,
+            supported: current,
+        });
+    }
+
+    while parse_version(&version) != parse_version(current) {
+        let step = migrations
+            .iter()
+            .find(|step| parse_version(step.from) == parse_version(&version))
+            .ok_or_else(|| MigrationError::UnsupportedVersion {
+                kind,
+                found: version.clone(),
+            })?;
+        (step.apply)(&mut value).map_err(|message| MigrationError::StepFailed {
+            from: step.from,
+            to: step.to,
+            message,
+        })?;
+        for pointer in pointers {
+            set_version(&mut value, pointer, step.to);
+        }
+        version = step.to.to_string();
+    }
+    Ok(value)
+}
+
+/// Write the version at `pointer`, adding a missing top-level field
+fn set_version(value: &mut Value, pointer: &str, version: &str) {
+    if let Some(field) = value.pointer_mut(pointer) {
+        *field = Value::String(version.to_string());
+    } else if let (Some(key), Some(object)) = (
+        pointer.strip_prefix('/').filter(|key| !key.contains('/')),
+        value.as_object_mut(),
+    ) {
+        object.insert(key.to_string(), Value::String(version.to_string()));
+    }
+}
+
+/// Preferences 1.0 -> 1.1
+fn add_default_roles(prefs: &mut Value) -> Result<(), String> {
+    let prefs = prefs
+        .as_object_mut()
+        .ok_or("preferences aren't an object")?;
+    if !prefs.contains_key("roles") {
+        let roles = serde_json::to_value(default_roles()).map_err(|e| e.to_string())?;
+        prefs.insert("roles".to_string(), roles);
+    }
+    Ok(())
+}
+
+/// Plan 1.0 -> 1.1
+///
+/// Projects live in the plan itself, in every scenario, and (for a saved
+/// `PlanState` with an active scenario) in the stashed main plan.
+fn role_estimates(plan: &mut Value) -> Result<(), String> {
+    branch_role_estimates(plan)?;
+    if let Some(scenarios) = plan.get_mut("scenarios").and_then(Value::as_array_mut) {
+        for scenario in scenarios {
+            branch_role_estimates(scenario)?;
+        }
+    }
+    match plan.get_mut("stashed_main") {
+        None | Some(Value::Null) => Ok(()),
+        Some(main) => branch_role_estimates(main),
+    }
+}
+
+fn branch_role_estimates(branch: &mut Value) -> Result<(), String> {
+    for list in ["roadmap_projects", "technical_projects"] {
+        let Some(projects) = branch.get_mut(list).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for project in projects {
+            let project = project
+                .as_object_mut()
+                .ok_or_else(|| format!("{} contains a non-object", list))?;
+            let eng = project.remove("eng_estimate");
+            let sci = project.remove("sci_estimate");
+            if project.contains_key("estimates") {
+                continue;
+            }
+            let weeks =
+                |value: Option<Value>| value.as_ref().and_then(Value::as_f64).map(|w| w as f32);
+            let estimates = serde_json::to_value(legacy_estimates(weeks(eng), weeks(sci)))
+                .map_err(|e| e.to_string())?;
+            project.insert("estimates".to_string(), estimates);
+        }
+    }
+    Ok(())
+}
+
+/// Parse "1.2" into comparable parts
+fn parse_version(version: &str) -> Option<Vec<u32>> {
+    let parts: Option<Vec<u32>> = version.split('.').map(|part| part.parse().ok()).collect();
+    // "1" and "1.0" are the same version
+    parts.map(|mut parts| {
+        while parts.len() > 1 && parts.last() == Some(&0) {
+            parts.pop();
+        }
+        parts
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{default_roles, Role};
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn rename_team(value: &mut Value) -> Result<(), String> {
+        let name = value
+            .get_mut("team")
+            .map(Value::take)
+            .ok_or("missing team")?;
+        value["team_name"] = name;
+        Ok(())
+    }
+
+    fn add_num_weeks(value: &mut Value) -> Result<(), String> {
+        value["num_weeks"] = json!(13);
+        Ok(())
+    }
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            from: "0.8",
+            to: "0.9",
+            description: "Rename team to team_name",
+            apply: rename_team,
+        },
+        Migration {
+            from: "0.9",
+            to: "1.0",
+            description: "Add num_weeks",
+            apply: add_num_weeks,
+        },
+    ];
+
+    #[test]
+    fn test_steps_run_in_order_and_update_versions() {
+        let old = json!({
+            "version": "0.8",
+            "metadata": { "version": "0.8" },
+            "team": "Payments",
+        });
+        let migrated =
+            run_migrations(DocumentKind::PlanExport, old, "1.0", TEST_MIGRATIONS).unwrap();
+        assert_eq!(migrated["team_name"], "Payments");
+        assert_eq!(migrated["num_weeks"], 13);
+        assert_eq!(migrated["version"], "1.0");
+        assert_eq!(migrated["metadata"]["version"], "1.0");
+
+        let failing = json!({ "version": "0.8", "metadata": { "version": "0.8" } });
+        assert!(matches!(
+            run_migrations(DocumentKind::PlanExport, failing, "1.0", TEST_MIGRATIONS),
+            Err(MigrationError::StepFailed { from: "0.8", .. })
+        ));
+    }
+
+    #[test]
+    fn test_version_errors() {
+        let newer = json!({ "schema_version": "2.1" });
+        let error = migrate(DocumentKind::Preferences, newer).unwrap_err();
+        assert_eq!(
+            error,
+            MigrationError::NewerVersion {
+                kind: DocumentKind::Preferences,
+                found: "2.1".to_string(),
+                supported: PREFERENCES_SCHEMA_VERSION,
+            }
+        );
+        assert!(error.to_string().contains("newer version of Planner"));
+
+        let ancient = json!({ "metadata": { "version": "0.5" } });
+        assert_eq!(
+            run_migrations(DocumentKind::PlanState, ancient, "1.0", TEST_MIGRATIONS),
+            Err(MigrationError::UnsupportedVersion {
+                kind: DocumentKind::PlanState,
+                found: "0.5".to_string(),
+            })
+        );
+
+        let garbled = json!({ "version": "one", "metadata": { "version": "one" } });
+        assert!(matches!(
+            migrate(DocumentKind::PlanExport, garbled),
+            Err(MigrationError::InvalidVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_current_documents() {
+        let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(load_json::<PlanState>(&json).unwrap(), state);
+
+        let export = PlanExport::from_signals(Preferences::default(), state);
+        let json = serde_json::to_string(&export).unwrap();
+        assert_eq!(load_json::<PlanExport>(&json).unwrap(), export);
+
+        // Preferences saved before schema_version existed
+        let prefs: Preferences = load_json(
+            r#"{
+                "team_name": "Payments",
+                "team_members": [],
+                "sprint_anchor_date": "2024-01-01",
+                "sprint_length_weeks": 2,
+                "default_capacity": 12.0
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(prefs.schema_version, PREFERENCES_SCHEMA_VERSION);
+
+        assert!(matches!(
+            load_json::<PlanExport>("not json"),
+            Err(MigrationError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_archived_1_0_documents() {
+        let export: PlanExport =
+            load_json(include_str!("../fixtures/plan-export-1.0.json")).unwrap();
+        assert_eq!(export.version, PLAN_SCHEMA_VERSION);
+        assert_eq!(export.metadata.version, PLAN_SCHEMA_VERSION);
+        let relevance = &export.roadmap_projects[0].estimates;
+        assert_eq!(relevance.get(&Role::ENGINEERING), 6.0);
+        assert_eq!(relevance.get(&Role::SCIENCE), 2.5);
+        let ranking = &export.technical_projects[0];
+        assert_eq!(ranking.estimates.get(&Role::ENGINEERING), 3.0);
+        assert_eq!(ranking.estimates.get(&Role::SCIENCE), 2.0);
+        assert!(ranking.required_skills.is_empty());
+        assert_eq!(export.roles, default_roles());
+        assert!(export.validate().is_ok());
+
+        let state: PlanState = load_json(include_str!("../fixtures/plan-state-1.0.json")).unwrap();
+        assert_eq!(state.metadata.version, PLAN_SCHEMA_VERSION);
+        assert_eq!(
+            state.technical_projects[0]
+                .estimates
+                .get(&Role::ENGINEERING),
+            3.0
+        );
+        // Saved with per-role estimates only
+        let saved = serde_json::to_value(&state).unwrap();
+        assert!(saved["technical_projects"][0].get("eng_estimate").is_none());
+        assert_eq!(load_json::<PlanState>(&saved.to_string()).unwrap(), state);
+
+        let prefs: Preferences =
+            load_json(include_str!("../fixtures/preferences-1.0.json")).unwrap();
+        assert_eq!(prefs.schema_version, PREFERENCES_SCHEMA_VERSION);
+        assert_eq!(prefs.roles, default_roles());
+        assert_eq!(prefs.team_members[1].role, Role::SCIENCE);
+        assert!(prefs.validate().is_ok());
+    }
+
+    #[test]
+    fn test_role_estimates_reach_scenarios() {
+        let legacy_project = json!({
+            "id": "00000000-0000-0000-0000-000000000201",
+            "name": "Ranking model",
+            "eng_estimate": 4.0,
+        });
+        let mut plan = json!({
+            "roadmap_projects": [],
+            "technical_projects": [legacy_project.clone()],
+            "scenarios": [{ "roadmap_projects": [], "technical_projects": [legacy_project.clone()] }],
+            "stashed_main": { "roadmap_projects": [], "technical_projects": [legacy_project] },
+        });
+        role_estimates(&mut plan).unwrap();
+
+        let expected = json!({ "eng": 4.0 });
+        assert_eq!(plan["technical_projects"][0]["estimates"], expected);
+        assert_eq!(
+            plan["scenarios"][0]["technical_projects"][0]["estimates"],
+            expected
+        );
+        assert_eq!(
+            plan["stashed_main"]["technical_projects"][0]["estimates"],
+            expected
+        );
+        assert!(plan["technical_projects"][0].get("eng_estimate").is_none());
+    }
+}
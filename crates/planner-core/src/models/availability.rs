@@ -0,0 +1,251 @@
+//! Team availability: per-member time off and team-wide holidays
+//!
+//! `TeamMember.capacity` is the nominal number of weeks a member works in a
+//! quarter. Holidays and time off reduce that number: each working day off
+//! removes 1/5 of a week. Partial days (e.g., half-day Fridays) are expressed
+//! as a fraction of each day in the range. Days before a member's start date
+//! or after their end date count as days off.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::TeamMember;
+
+/// Working days in a week (Mon-Fri)
+pub const WORKING_DAYS_PER_WEEK: f32 = 5.0;
+
+fn default_time_off_fraction() -> f32 {
+    1.0
+}
+
+/// A range of days a team member is away (vacation, leave, etc.)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeOff {
+    pub id: Uuid,
+    /// First day off (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day off (inclusive)
+    pub end_date: NaiveDate,
+    /// Fraction of each working day taken off (1.0 = full day, 0.5 = half day)
+    #[serde(default = "default_time_off_fraction")]
+    pub fraction: f32,
+    /// Optional description (e.g., "Parental leave")
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl TimeOff {
+    pub fn new(start_date: NaiveDate, end_date: NaiveDate, fraction: f32) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            start_date,
+            end_date,
+            fraction: fraction.clamp(0.0, 1.0),
+            note: None,
+        }
+    }
+
+    /// Fraction of the given day taken off (0.0 if outside the range)
+    pub fn fraction_on(&self, date: NaiveDate) -> f32 {
+        if date >= self.start_date && date <= self.end_date {
+            self.fraction.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A team-wide non-working day or range (company holiday, office closure)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Holiday {
+    pub id: Uuid,
+    pub name: String,
+    /// First day of the holiday (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of the holiday (inclusive)
+    pub end_date: NaiveDate,
+}
+
+impl Holiday {
+    pub fn new(name: String, start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            start_date,
+            end_date,
+        }
+    }
+
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        date >= self.start_date && date <= self.end_date
+    }
+}
+
+/// Working days (Mon-Fri) in the week starting at `week_start`
+fn working_days(week_start: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    (0..7)
+        .map(move |offset| week_start + Duration::days(offset))
+        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+}
+
+impl TeamMember {
+    /// Whether the member is on the team on the given day
+    pub fn is_employed_on(&self, date: NaiveDate) -> bool {
+        self.start_date.is_none_or(|start| date >= start)
+            && self.end_date.is_none_or(|end| date <= end)
+    }
+
+    /// Whether the member works at least one day of the week starting at
+    /// `week_start` (weeks outside their tenure can't be allocated)
+    pub fn is_active_in_week(&self, week_start: NaiveDate) -> bool {
+        working_days(week_start).any(|date| self.is_employed_on(date))
+    }
+
+    /// Fraction of the week (0.0-1.0) this member is available
+    pub fn week_availability(&self, holidays: &[Holiday], week_start: NaiveDate) -> f32 {
+        let days_off: f32 = working_days(week_start)
+            .map(|date| {
+                if !self.is_employed_on(date) || holidays.iter().any(|h| h.covers(date)) {
+                    1.0
+                } else {
+                    self.time_off
+                        .iter()
+                        .map(|t| t.fraction_on(date))
+                        .sum::<f32>()
+                        .min(1.0)
+                }
+            })
+            .sum();
+
+        (1.0 - days_off / WORKING_DAYS_PER_WEEK).clamp(0.0, 1.0)
+    }
+
+    /// Weeks lost to holidays and time off within the quarter
+    pub fn unavailable_weeks(
+        &self,
+        holidays: &[Holiday],
+        quarter_start: NaiveDate,
+        num_weeks: usize,
+    ) -> f32 {
+        (0..num_weeks)
+            .map(|i| quarter_start + Duration::weeks(i as i64))
+            .map(|week| 1.0 - self.week_availability(holidays, week))
+            .sum()
+    }
+
+    /// Capacity for the quarter after subtracting holidays and time off
+    pub fn available_capacity(
+        &self,
+        holidays: &[Holiday],
+        quarter_start: NaiveDate,
+        num_weeks: usize,
+    ) -> f32 {
+        (self.capacity - self.unavailable_weeks(holidays, quarter_start, num_weeks)).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Role;
+
+    fn monday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    fn member() -> TeamMember {
+        TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0)
+    }
+
+    #[test]
+    fn test_full_availability_without_time_off() {
+        let m = member();
+        assert_eq!(m.week_availability(&[], monday()), 1.0);
+        assert_eq!(m.available_capacity(&[], monday(), 13), 12.0);
+    }
+
+    #[test]
+    fn test_full_week_time_off() {
+        let mut m = member();
+        m.time_off
+            .push(TimeOff::new(monday(), monday() + Duration::days(4), 1.0));
+
+        assert_eq!(m.week_availability(&[], monday()), 0.0);
+        assert_eq!(m.week_availability(&[], monday() + Duration::weeks(1)), 1.0);
+        assert_eq!(m.available_capacity(&[], monday(), 13), 11.0);
+    }
+
+    #[test]
+    fn test_weekends_do_not_count() {
+        let mut m = member();
+        // Saturday through Monday: only Monday is a working day
+        m.time_off.push(TimeOff::new(
+            monday() + Duration::days(5),
+            monday() + Duration::days(7),
+            1.0,
+        ));
+
+        assert_eq!(m.week_availability(&[], monday()), 1.0);
+        assert!((m.week_availability(&[], monday() + Duration::weeks(1)) - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_partial_days() {
+        let mut m = member();
+        // Half days all week = half a week off
+        m.time_off
+            .push(TimeOff::new(monday(), monday() + Duration::days(4), 0.5));
+
+        assert!((m.week_availability(&[], monday()) - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_holiday_overrides_time_off() {
+        let mut m = member();
+        m.time_off.push(TimeOff::new(monday(), monday(), 0.5));
+        let holidays = [Holiday::new("New Year".to_string(), monday(), monday())];
+
+        // Holiday counts as one full day, not 1.5
+        assert!((m.week_availability(&holidays, monday()) - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_available_capacity_never_negative() {
+        let mut m = member();
+        m.capacity = 1.0;
+        m.time_off
+            .push(TimeOff::new(monday(), monday() + Duration::weeks(3), 1.0));
+
+        assert_eq!(m.available_capacity(&[], monday(), 13), 0.0);
+    }
+
+    #[test]
+    fn test_tenure_limits_availability() {
+        let mut m = member();
+        // Joins on Wednesday of week 6, leaves after Tuesday of week 11
+        m.start_date = Some(monday() + Duration::weeks(5) + Duration::days(2));
+        m.end_date = Some(monday() + Duration::weeks(10) + Duration::days(1));
+
+        assert!(!m.is_active_in_week(monday()));
+        assert!(m.is_active_in_week(monday() + Duration::weeks(5)));
+        assert!(m.is_active_in_week(monday() + Duration::weeks(10)));
+        assert!(!m.is_active_in_week(monday() + Duration::weeks(11)));
+
+        assert_eq!(m.week_availability(&[], monday()), 0.0);
+        assert!((m.week_availability(&[], monday() + Duration::weeks(5)) - 0.6).abs() < 0.001);
+        assert!((m.week_availability(&[], monday() + Duration::weeks(10)) - 0.4).abs() < 0.001);
+
+        // 5 weeks before, 2 after, plus 0.4 + 0.6 partial weeks = 8 weeks unavailable
+        assert!((m.unavailable_weeks(&[], monday(), 13) - 8.0).abs() < 0.001);
+        assert!((m.available_capacity(&[], monday(), 13) - 4.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_time_off_deserializes_default_fraction() {
+        let json = r#"{"id":"00000000-0000-0000-0000-000000000000","start_date":"2025-01-06","end_date":"2025-01-10"}"#;
+        let time_off: TimeOff = serde_json::from_str(json).unwrap();
+        assert_eq!(time_off.fraction, 1.0);
+        assert!(time_off.note.is_none());
+    }
+}
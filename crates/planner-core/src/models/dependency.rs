@@ -0,0 +1,244 @@
+//! Ordering dependencies between technical projects
+//!
+//! A dependency is stored on the dependent project and points at its
+//! predecessor. Ordering is checked against allocated weeks rather than the
+//! projects' nominal dates, since allocations are what actually schedule work.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{PlanState, TechnicalProject};
+
+/// How a project's timing is constrained by its predecessor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DependencyKind {
+    /// Can't start until the predecessor finishes
+    FinishToStart,
+    /// Can't start until the predecessor starts
+    StartToStart,
+}
+
+impl DependencyKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            DependencyKind::FinishToStart => "Finish-to-start",
+            DependencyKind::StartToStart => "Start-to-start",
+        }
+    }
+}
+
+/// Dependency on another technical project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectDependency {
+    pub predecessor_id: Uuid,
+    pub kind: DependencyKind,
+}
+
+impl ProjectDependency {
+    pub fn new(predecessor_id: Uuid, kind: DependencyKind) -> Self {
+        Self {
+            predecessor_id,
+            kind,
+        }
+    }
+}
+
+/// A dependent project allocated earlier than its dependency allows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DependencyViolation {
+    pub project_id: Uuid,
+    pub predecessor_id: Uuid,
+    pub kind: DependencyKind,
+}
+
+/// Find a dependency cycle among technical projects
+///
+/// Returns the project IDs along the cycle (starting and ending with the
+/// same project), or None if the dependency graph is acyclic. Dependencies
+/// on unknown projects are ignored.
+pub fn find_dependency_cycle(projects: &[TechnicalProject]) -> Option<Vec<Uuid>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        InProgress,
+        Done,
+    }
+
+    fn visit(
+        index: usize,
+        projects: &[TechnicalProject],
+        marks: &mut [Mark],
+        path: &mut Vec<Uuid>,
+    ) -> Option<Vec<Uuid>> {
+        marks[index] = Mark::InProgress;
+        path.push(projects[index].id);
+
+        for dependency in &projects[index].dependencies {
+            let Some(next) = projects
+                .iter()
+                .position(|p| p.id == dependency.predecessor_id)
+            else {
+                continue;
+            };
+            match marks[next] {
+                Mark::InProgress => {
+                    let start = path.iter().position(|id| *id == projects[next].id)?;
+                    let mut cycle = path[start..].to_vec();
+                    cycle.push(projects[next].id);
+                    return Some(cycle);
+                }
+                Mark::Unvisited => {
+                    if let Some(cycle) = visit(next, projects, marks, path) {
+                        return Some(cycle);
+                    }
+                }
+                Mark::Done => {}
+            }
+        }
+
+        path.pop();
+        marks[index] = Mark::Done;
+        None
+    }
+
+    let mut marks = vec![Mark::Unvisited; projects.len()];
+    let mut path = Vec::new();
+    for index in 0..projects.len() {
+        if marks[index] == Mark::Unvisited {
+            if let Some(cycle) = visit(index, projects, &mut marks, &mut path) {
+                return Some(cycle);
+            }
+        }
+    }
+    None
+}
+
+impl PlanState {
+    /// Dependencies whose ordering is broken by the current allocations
+    ///
+    /// Finish-to-start is violated when the dependent's first allocated week
+    /// precedes the predecessor's last; start-to-start when it precedes the
+    /// predecessor's first. Projects without allocations can't violate.
+    pub fn dependency_violations(&self) -> Vec<DependencyViolation> {
+        let mut violations = Vec::new();
+        for project in &self.technical_projects {
+            let Some((first_week, _)) = self.get_project_allocation_date_range(&project.id) else {
+                continue;
+            };
+            for dependency in &project.dependencies {
+                let Some((pred_first, pred_last)) =
+                    self.get_project_allocation_date_range(&dependency.predecessor_id)
+                else {
+                    continue;
+                };
+                let violated = match dependency.kind {
+                    DependencyKind::FinishToStart => first_week < pred_last,
+                    DependencyKind::StartToStart => first_week < pred_first,
+                };
+                if violated {
+                    violations.push(DependencyViolation {
+                        project_id: project.id,
+                        predecessor_id: dependency.predecessor_id,
+                        kind: dependency.kind,
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Allocation, Assignment, Role, RoleWeeks};
+    use chrono::NaiveDate;
+
+    fn project(name: &str) -> TechnicalProject {
+        TechnicalProject::new(
+            name.to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+            NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        )
+    }
+
+    fn allocate(state: &mut PlanState, project_id: Uuid, week: usize) {
+        let week_start = state.quarter_start_date + chrono::Duration::weeks(week as i64);
+        let mut alloc = Allocation::new(Uuid::new_v4(), week_start);
+        alloc.assignments.push(Assignment::new(project_id, 100.0));
+        state.allocations.push(alloc);
+    }
+
+    #[test]
+    fn test_find_dependency_cycle() {
+        let mut api = project("API migration");
+        let mut client = project("Client rollout");
+        let docs = project("Docs");
+        client.dependencies.push(ProjectDependency::new(
+            api.id,
+            DependencyKind::FinishToStart,
+        ));
+        api.dependencies.push(ProjectDependency::new(
+            docs.id,
+            DependencyKind::StartToStart,
+        ));
+        assert_eq!(
+            find_dependency_cycle(&[api.clone(), client.clone(), docs.clone()]),
+            None
+        );
+
+        // Closing the loop: api -> docs -> client -> api
+        let mut docs = docs;
+        docs.dependencies.push(ProjectDependency::new(
+            client.id,
+            DependencyKind::FinishToStart,
+        ));
+        let cycle = find_dependency_cycle(&[api.clone(), client.clone(), docs.clone()]).unwrap();
+        assert_eq!(cycle, [api.id, docs.id, client.id, api.id]);
+    }
+
+    #[test]
+    fn test_self_dependency_is_a_cycle() {
+        let mut api = project("API migration");
+        api.dependencies.push(ProjectDependency::new(
+            api.id,
+            DependencyKind::FinishToStart,
+        ));
+        assert_eq!(
+            find_dependency_cycle(&[api.clone()]),
+            Some(vec![api.id, api.id])
+        );
+    }
+
+    #[test]
+    fn test_dependency_violations() {
+        let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
+        let api = project("API migration");
+        let mut client = project("Client rollout");
+        client.dependencies.push(ProjectDependency::new(
+            api.id,
+            DependencyKind::FinishToStart,
+        ));
+        let (api_id, client_id) = (api.id, client.id);
+        state.technical_projects = vec![api, client];
+
+        // API runs weeks 0-2; nothing allocated to the client yet
+        for week in 0..3 {
+            allocate(&mut state, api_id, week);
+        }
+        assert!(state.dependency_violations().is_empty());
+
+        // Client starting in week 1 breaks finish-to-start
+        allocate(&mut state, client_id, 1);
+        let violations = state.dependency_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].project_id, client_id);
+        assert_eq!(violations[0].predecessor_id, api_id);
+
+        // ...but satisfies start-to-start
+        state.technical_projects[1].dependencies[0].kind = DependencyKind::StartToStart;
+        assert!(state.dependency_violations().is_empty());
+    }
+}
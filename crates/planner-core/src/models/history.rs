@@ -0,0 +1,707 @@
+//! Undo/redo history for plan and roster edits
+//!
+//! Every user action is recorded as a `HistoryEntry`: a label plus the
+//! entity-level changes it made (allocation cells, roadmap projects, technical
+//! projects, team members, holidays, roles). Entries are computed by diffing snapshots
+//! taken before and after a mutation, so call sites never describe their own
+//! inverse.
+//! Allocations in saved scenario branches (and the stashed main plan) are
+//! diffed too, since roster edits clean those up as well.
+//! Each change stores both sides, which makes it reversible in either direction.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    Allocation, Holiday, PlanState, Preferences, RoadmapProject, Role, RoleDefinition, TeamMember,
+    TechnicalProject,
+};
+
+/// Default number of undo steps kept in history
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Identity of an allocation cell in the grid (team member, week)
+pub type CellKey = (Uuid, NaiveDate);
+
+/// A single entity change (insert, update, or remove)
+///
+/// `index` is the entity's position in the state that holds it: the "before"
+/// position for removals and the "after" position for insertions. This lets
+/// undo/redo restore collection order exactly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityChange<K, T> {
+    pub key: K,
+    pub index: usize,
+    pub before: Option<T>,
+    pub after: Option<T>,
+}
+
+/// One undoable step (e.g., "Paint cells", "Delete roadmap project")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Human-readable label for menus ("Undo Paint cells")
+    pub label: String,
+    #[serde(default)]
+    pub allocations: Vec<EntityChange<CellKey, Allocation>>,
+    #[serde(default)]
+    pub roadmap_projects: Vec<EntityChange<Uuid, RoadmapProject>>,
+    #[serde(default)]
+    pub technical_projects: Vec<EntityChange<Uuid, TechnicalProject>>,
+    #[serde(default)]
+    pub team_members: Vec<EntityChange<Uuid, TeamMember>>,
+    #[serde(default)]
+    pub holidays: Vec<EntityChange<Uuid, Holiday>>,
+    #[serde(default)]
+    pub roles: Vec<EntityChange<Role, RoleDefinition>>,
+    #[serde(default)]
+    pub saved_branches: Vec<BranchChanges>,
+}
+
+/// Allocation changes in a branch other than the working one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BranchChanges {
+    /// Scenario whose saved branch changed (None = the stashed main plan)
+    pub scenario_id: Option<Uuid>,
+    pub allocations: Vec<EntityChange<CellKey, Allocation>>,
+}
+
+impl HistoryEntry {
+    /// Build an entry from the difference between two snapshots
+    pub fn from_diff(
+        label: impl Into<String>,
+        before_prefs: &Preferences,
+        before_state: &PlanState,
+        after_prefs: &Preferences,
+        after_state: &PlanState,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            allocations: diff_entities(&before_state.allocations, &after_state.allocations, |a| {
+                (a.team_member_id, a.week_start_date)
+            }),
+            roadmap_projects: diff_entities(
+                &before_state.roadmap_projects,
+                &after_state.roadmap_projects,
+                |p| p.id,
+            ),
+            technical_projects: diff_entities(
+                &before_state.technical_projects,
+                &after_state.technical_projects,
+                |p| p.id,
+            ),
+            team_members: diff_entities(
+                &before_prefs.team_members,
+                &after_prefs.team_members,
+                |m| m.id,
+            ),
+            holidays: diff_entities(&before_prefs.holidays, &after_prefs.holidays, |h| h.id),
+            roles: diff_entities(&before_prefs.roles, &after_prefs.roles, |r| r.id.clone()),
+            saved_branches: diff_saved_branches(before_state, after_state),
+        }
+    }
+
+    /// True if the entry records no changes
+    pub fn is_empty(&self) -> bool {
+        self.allocations.is_empty()
+            && self.roadmap_projects.is_empty()
+            && self.technical_projects.is_empty()
+            && self.team_members.is_empty()
+            && self.holidays.is_empty()
+            && self.roles.is_empty()
+            && self.saved_branches.is_empty()
+    }
+
+    /// Apply this entry forward (redo) or backward (undo)
+    fn apply(&self, prefs: &mut Preferences, state: &mut PlanState, forward: bool) {
+        apply_changes(
+            &mut state.allocations,
+            &self.allocations,
+            |a| (a.team_member_id, a.week_start_date),
+            forward,
+        );
+        apply_changes(
+            &mut state.roadmap_projects,
+            &self.roadmap_projects,
+            |p| p.id,
+            forward,
+        );
+        apply_changes(
+            &mut state.technical_projects,
+            &self.technical_projects,
+            |p| p.id,
+            forward,
+        );
+        apply_changes(
+            &mut prefs.team_members,
+            &self.team_members,
+            |m| m.id,
+            forward,
+        );
+        apply_changes(&mut prefs.holidays, &self.holidays, |h| h.id, forward);
+        apply_changes(&mut prefs.roles, &self.roles, |r| r.id.clone(), forward);
+        for branch in &self.saved_branches {
+            let allocations = match branch.scenario_id {
+                Some(id) => state
+                    .scenarios
+                    .iter_mut()
+                    .find(|s| s.id == id)
+                    .map(|s| &mut s.branch.allocations),
+                None => state
+                    .stashed_main
+                    .as_mut()
+                    .map(|main| &mut main.allocations),
+            };
+            if let Some(allocations) = allocations {
+                apply_changes(
+                    allocations,
+                    &branch.allocations,
+                    |a| (a.team_member_id, a.week_start_date),
+                    forward,
+                );
+            }
+        }
+        state.mark_modified();
+    }
+}
+
+/// Snapshot taken at the start of a batched edit (e.g., a paintbrush drag)
+#[derive(Debug, Clone, PartialEq)]
+struct PendingBatch {
+    label: String,
+    preferences: Preferences,
+    plan_state: PlanState,
+}
+
+/// Bounded undo/redo stacks
+///
+/// The stacks are serializable so history survives reloads; an in-progress
+/// batch is transient and never persisted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditHistory {
+    undo_stack: Vec<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+    limit: usize,
+    #[serde(skip)]
+    pending: Option<PendingBatch>,
+}
+
+impl EditHistory {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            limit: limit.max(1),
+            pending: None,
+        }
+    }
+
+    /// Record a completed edit. Empty entries are ignored.
+    /// Recording a new edit discards anything that could have been redone.
+    pub fn record(&mut self, entry: HistoryEntry) {
+        if entry.is_empty() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.undo_stack.push(entry);
+        if self.undo_stack.len() > self.limit {
+            let overflow = self.undo_stack.len() - self.limit;
+            self.undo_stack.drain(..overflow);
+        }
+    }
+
+    /// Start a batched edit; changes until `end_batch` become one undo step
+    pub fn begin_batch(
+        &mut self,
+        label: impl Into<String>,
+        prefs: &Preferences,
+        state: &PlanState,
+    ) {
+        if self.pending.is_none() {
+            self.pending = Some(PendingBatch {
+                label: label.into(),
+                preferences: prefs.clone(),
+                plan_state: state.clone(),
+            });
+        }
+    }
+
+    /// Finish a batched edit, recording everything changed since `begin_batch`
+    pub fn end_batch(&mut self, prefs: &Preferences, state: &PlanState) {
+        if let Some(batch) = self.pending.take() {
+            self.record(HistoryEntry::from_diff(
+                batch.label,
+                &batch.preferences,
+                &batch.plan_state,
+                prefs,
+                state,
+            ));
+        }
+    }
+
+    /// True while a batched edit is in progress
+    pub fn is_batching(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Undo the most recent edit. Returns its label, or None if nothing to undo.
+    pub fn undo(&mut self, prefs: &mut Preferences, state: &mut PlanState) -> Option<String> {
+        let entry = self.undo_stack.pop()?;
+        entry.apply(prefs, state, false);
+        let label = entry.label.clone();
+        self.redo_stack.push(entry);
+        Some(label)
+    }
+
+    /// Redo the most recently undone edit. Returns its label, or None if nothing to redo.
+    pub fn redo(&mut self, prefs: &mut Preferences, state: &mut PlanState) -> Option<String> {
+        let entry = self.redo_stack.pop()?;
+        entry.apply(prefs, state, true);
+        let label = entry.label.clone();
+        self.undo_stack.push(entry);
+        Some(label)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Label of the edit that `undo` would revert
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo_stack.last().map(|e| e.label.as_str())
+    }
+
+    /// Label of the edit that `redo` would reapply
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo_stack.last().map(|e| e.label.as_str())
+    }
+
+    /// Drop all history (e.g., when switching to a different plan)
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.pending = None;
+    }
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+/// Compute entity-level changes between two collections keyed by `key`
+fn diff_entities<K, T>(before: &[T], after: &[T], key: impl Fn(&T) -> K) -> Vec<EntityChange<K, T>>
+where
+    K: PartialEq + Clone,
+    T: Clone + PartialEq,
+{
+    let mut changes = Vec::new();
+
+    for (index, old) in before.iter().enumerate() {
+        let k = key(old);
+        match after.iter().find(|new| key(new) == k) {
+            Some(new) if new != old => changes.push(EntityChange {
+                key: k.clone(),
+                index,
+                before: Some(old.clone()),
+                after: Some(new.clone()),
+            }),
+            Some(_) => {}
+            None => changes.push(EntityChange {
+                key: k,
+                index,
+                before: Some(old.clone()),
+                after: None,
+            }),
+        }
+    }
+
+    for (index, new) in after.iter().enumerate() {
+        let k = key(new);
+        if !before.iter().any(|old| key(old) == k) {
+            changes.push(EntityChange {
+                key: k,
+                index,
+                before: None,
+                after: Some(new.clone()),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Allocation changes in saved branches present in both snapshots
+///
+/// Creating or deleting a scenario isn't undoable, so branches that exist on
+/// only one side are skipped.
+fn diff_saved_branches(before: &PlanState, after: &PlanState) -> Vec<BranchChanges> {
+    fn saved(state: &PlanState) -> Vec<(Option<Uuid>, &[Allocation])> {
+        let mut branches: Vec<(Option<Uuid>, &[Allocation])> = state
+            .scenarios
+            .iter()
+            .map(|s| (Some(s.id), s.branch.allocations.as_slice()))
+            .collect();
+        if let Some(main) = &state.stashed_main {
+            branches.push((None, &main.allocations));
+        }
+        branches
+    }
+    let before = saved(before);
+    saved(after)
+        .into_iter()
+        .filter_map(|(scenario_id, after_allocations)| {
+            let (_, before_allocations) = before.iter().find(|(id, _)| *id == scenario_id)?;
+            let allocations = diff_entities(before_allocations, after_allocations, |a| {
+                (a.team_member_id, a.week_start_date)
+            });
+            (!allocations.is_empty()).then_some(BranchChanges {
+                scenario_id,
+                allocations,
+            })
+        })
+        .collect()
+}
+
+/// Apply entity changes in one direction
+///
+/// Removals happen first, then insertions in ascending target position, then
+/// in-place updates. This restores the original order of the collection.
+fn apply_changes<K, T>(
+    items: &mut Vec<T>,
+    changes: &[EntityChange<K, T>],
+    key: impl Fn(&T) -> K,
+    forward: bool,
+) where
+    K: PartialEq,
+    T: Clone,
+{
+    let sides = |c: &EntityChange<K, T>| {
+        if forward {
+            (c.before.is_some(), c.after.clone())
+        } else {
+            (c.after.is_some(), c.before.clone())
+        }
+    };
+
+    // Removals
+    for change in changes {
+        if let (true, None) = sides(change) {
+            items.retain(|item| key(item) != change.key);
+        }
+    }
+
+    // Insertions (ascending target index)
+    let mut insertions: Vec<(usize, T)> = changes
+        .iter()
+        .filter_map(|c| match sides(c) {
+            (false, Some(target)) => Some((c.index, target)),
+            _ => None,
+        })
+        .collect();
+    insertions.sort_by_key(|(index, _)| *index);
+    for (index, target) in insertions {
+        items.insert(index.min(items.len()), target);
+    }
+
+    // Updates
+    for change in changes {
+        if let (true, Some(target)) = sides(change) {
+            if let Some(item) = items.iter_mut().find(|item| key(item) == change.key) {
+                *item = target;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ProjectColor, RoleWeeks};
+
+    fn setup() -> (Preferences, PlanState) {
+        let mut prefs = Preferences::default();
+        prefs.team_members.push(TeamMember::new(
+            "Alice".to_string(),
+            Role::ENGINEERING,
+            12.0,
+        ));
+        prefs
+            .team_members
+            .push(TeamMember::new("Bob".to_string(), Role::SCIENCE, 12.0));
+
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 13);
+        state.roadmap_projects.push(RoadmapProject::new(
+            "Roadmap".to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            start,
+            start + chrono::Duration::weeks(8),
+            ProjectColor::Blue,
+        ));
+        state.technical_projects.push(TechnicalProject::new(
+            "Tech".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            start,
+        ));
+        (prefs, state)
+    }
+
+    fn paint(state: &mut PlanState, member: Uuid, week: NaiveDate, project: Uuid) {
+        state
+            .allocations
+            .retain(|a| !(a.team_member_id == member && a.week_start_date == week));
+        let mut alloc = Allocation::new(member, week);
+        alloc.assignments.push(Assignment::new(project, 100.0));
+        state.allocations.push(alloc);
+    }
+
+    fn record(
+        history: &mut EditHistory,
+        label: &str,
+        prefs: &mut Preferences,
+        state: &mut PlanState,
+        edit: impl FnOnce(&mut Preferences, &mut PlanState),
+    ) {
+        let (before_prefs, before_state) = (prefs.clone(), state.clone());
+        edit(prefs, state);
+        history.record(HistoryEntry::from_diff(
+            label,
+            &before_prefs,
+            &before_state,
+            prefs,
+            state,
+        ));
+    }
+
+    #[test]
+    fn test_undo_redo_allocation() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+        let member = prefs.team_members[0].id;
+        let project = state.technical_projects[0].id;
+        let week = state.quarter_start_date;
+        let original = state.allocations.clone();
+
+        record(&mut history, "Paint", &mut prefs, &mut state, |_, s| {
+            paint(s, member, week, project)
+        });
+        assert_eq!(state.allocations.len(), 1);
+        let painted = state.allocations.clone();
+
+        assert_eq!(
+            history.undo(&mut prefs, &mut state),
+            Some("Paint".to_string())
+        );
+        assert_eq!(state.allocations, original);
+
+        assert_eq!(
+            history.redo(&mut prefs, &mut state),
+            Some("Paint".to_string())
+        );
+        assert_eq!(state.allocations, painted);
+    }
+
+    #[test]
+    fn test_undo_restores_removed_entity_position() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+        let original = prefs.team_members.clone();
+        let alice = original[0].id;
+
+        record(&mut history, "Delete", &mut prefs, &mut state, |p, _| {
+            p.team_members.retain(|m| m.id != alice)
+        });
+        assert_eq!(prefs.team_members.len(), 1);
+
+        history.undo(&mut prefs, &mut state);
+        assert_eq!(prefs.team_members, original);
+    }
+
+    #[test]
+    fn test_undo_cascade_delete() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+        let member = prefs.team_members[0].id;
+        let project = state.technical_projects[0].id;
+        let week = state.quarter_start_date;
+        paint(&mut state, member, week, project);
+        let before = state.clone();
+
+        record(
+            &mut history,
+            "Delete project",
+            &mut prefs,
+            &mut state,
+            |_, s| {
+                s.allocations.clear();
+                s.technical_projects.clear();
+            },
+        );
+        history.undo(&mut prefs, &mut state);
+
+        assert_eq!(state.allocations, before.allocations);
+        assert_eq!(state.technical_projects, before.technical_projects);
+    }
+
+    #[test]
+    fn test_undo_member_delete_across_scenarios() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+        let member = prefs.team_members[0].id;
+        let project = state.technical_projects[0].id;
+        let week = state.quarter_start_date;
+        paint(&mut state, member, week, project);
+        let first = state.create_scenario("First");
+        let second = state.create_scenario("Second");
+        state.switch_scenario(Some(first));
+        paint(
+            &mut state,
+            member,
+            week + chrono::Duration::weeks(1),
+            project,
+        );
+        let before = state.clone();
+
+        record(
+            &mut history,
+            "Delete team member",
+            &mut prefs,
+            &mut state,
+            |p, s| {
+                s.remove_member_everywhere(&member);
+                p.team_members.retain(|m| m.id != member);
+            },
+        );
+        assert!(state
+            .get_scenario(&second)
+            .unwrap()
+            .branch
+            .allocations
+            .is_empty());
+
+        history.undo(&mut prefs, &mut state);
+        assert_eq!(state.allocations, before.allocations);
+        assert_eq!(state.scenarios, before.scenarios);
+        assert_eq!(state.stashed_main, before.stashed_main);
+
+        history.redo(&mut prefs, &mut state);
+        assert!(state.allocations.is_empty());
+        assert!(state
+            .scenarios
+            .iter()
+            .all(|s| s.branch.allocations.is_empty()));
+        assert!(state.stashed_main.as_ref().unwrap().allocations.is_empty());
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+
+        record(&mut history, "Rename", &mut prefs, &mut state, |_, s| {
+            s.roadmap_projects[0].name = "Renamed".to_string()
+        });
+        history.undo(&mut prefs, &mut state);
+        assert!(history.can_redo());
+
+        record(&mut history, "Estimate", &mut prefs, &mut state, |_, s| {
+            s.roadmap_projects[0].estimates.set(Role::ENGINEERING, 8.0)
+        });
+        assert!(!history.can_redo());
+        assert_eq!(history.undo_label(), Some("Estimate"));
+    }
+
+    #[test]
+    fn test_empty_edits_not_recorded() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+
+        record(&mut history, "Nothing", &mut prefs, &mut state, |_, _| {});
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_history_limit() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::new(2);
+
+        for i in 0..5 {
+            record(
+                &mut history,
+                &format!("Edit {}", i),
+                &mut prefs,
+                &mut state,
+                |_, s| {
+                    s.roadmap_projects[0]
+                        .estimates
+                        .set(Role::ENGINEERING, i as f32)
+                },
+            );
+        }
+
+        assert_eq!(history.undo_label(), Some("Edit 4"));
+        history.undo(&mut prefs, &mut state);
+        history.undo(&mut prefs, &mut state);
+        assert!(!history.can_undo());
+        assert_eq!(
+            state.roadmap_projects[0].estimates.get(&Role::ENGINEERING),
+            2.0
+        );
+    }
+
+    #[test]
+    fn test_batch_records_single_entry() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+        let member = prefs.team_members[0].id;
+        let project = state.technical_projects[0].id;
+
+        let start = state.quarter_start_date;
+
+        history.begin_batch("Paint cells", &prefs, &state);
+        for week in 0..3 {
+            paint(
+                &mut state,
+                member,
+                start + chrono::Duration::weeks(week),
+                project,
+            );
+        }
+        // Revisiting a cell during the same drag is collapsed into one change
+        paint(&mut state, member, start, project);
+        history.end_batch(&prefs, &state);
+
+        assert!(!history.is_batching());
+        assert_eq!(history.undo_label(), Some("Paint cells"));
+        history.undo(&mut prefs, &mut state);
+        assert!(state.allocations.is_empty());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_history_serialization_round_trip() {
+        let (mut prefs, mut state) = setup();
+        let mut history = EditHistory::default();
+        let member = prefs.team_members[1].id;
+        let project = state.technical_projects[0].id;
+        let week = state.quarter_start_date;
+
+        record(&mut history, "Paint", &mut prefs, &mut state, |_, s| {
+            paint(s, member, week, project)
+        });
+
+        let json = serde_json::to_string(&history).unwrap();
+        let mut restored: EditHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, history);
+
+        restored.undo(&mut prefs, &mut state);
+        assert!(state.allocations.is_empty());
+    }
+}
@@ -1,11 +1,16 @@
 //! Data models for the Quarterly Planner application
 //!
 //! These models represent the core domain entities:
-//! - Engineers/Scientists with capacity
+//! - Team members with capacity
+//! - User-defined roles and per-role estimates
+//! - Availability (time off, team holidays)
 //! - Roadmap projects (high-level initiatives)
-//! - Technical projects (implementation work)
-//! - Weekly allocations
+//! - Technical projects (implementation work) and their dependencies
+//! - Weekly allocations, and an index over them for fast per-cell and
+//!   per-project queries
+//! - Named what-if scenarios (branches of projects and allocations)
 //! - Project color assignments
+//! - Undo/redo history of edits
 //!
 //! ## State Architecture (Milestone 9)
 //!
@@ -13,18 +18,33 @@
 //! - `Preferences`: Team config (persisted to localStorage)
 //! - `PlanState`: Planning data (exported/imported per quarter)
 //! - `PlanExport`: Self-contained export format (combines both)
+//! - `PlanView`: Read-only view over both, for roster-aware calculations
 //!
 //! See ADR-004 for design rationale.
 
+mod availability;
+mod dependency;
+mod history;
 mod plan;
 mod plan_export;
+mod plan_index;
 mod plan_state;
+mod plan_view;
 mod preferences;
+mod role;
+mod scenario;
 mod status;
 
+pub use availability::*;
+pub use dependency::*;
+pub use history::*;
 pub use plan::*;
 #[allow(unused_imports)] // Used in M13+ for plan export/import
 pub use plan_export::*;
+pub use plan_index::*;
 pub use plan_state::*;
+pub use plan_view::*;
 pub use preferences::*;
+pub use role::*;
+pub use scenario::*;
 pub use status::*;
@@ -2,6 +2,8 @@ use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{ProjectDependency, Role, RoleWeeks, TimeOff};
+
 /// Epsilon for floating point percentage comparisons
 #[allow(dead_code)] // Reserved for future validation UI
 const PERCENTAGE_EPSILON: f32 = 0.01;
@@ -9,25 +11,6 @@ const PERCENTAGE_EPSILON: f32 = 0.01;
 /// Valid percentage range (0.0 to 100.0 inclusive)
 const VALID_PERCENTAGE_RANGE: std::ops::RangeInclusive<f32> = 0.0..=100.0;
 
-/// Role of a team member (Software Engineer or Applied Scientist)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Role {
-    #[serde(rename = "eng")]
-    Engineering,
-    #[serde(rename = "sci")]
-    Science,
-}
-
-impl Role {
-    /// Short display name for badges (e.g., "SDE", "AS")
-    pub fn short_name(&self) -> &'static str {
-        match self {
-            Role::Engineering => "SDE",
-            Role::Science => "AS",
-        }
-    }
-}
-
 /// Project color for visual differentiation in the allocation grid
 /// Reference: docs/ui-design.md section 2 (Project Color Palette)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -76,7 +59,7 @@ impl ProjectColor {
     }
 }
 
-/// Team member (Software Engineer or Applied Scientist) with capacity
+/// Team member with a role and capacity
 /// Reference: docs/ui-design.md section 9.2
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TeamMember {
@@ -85,6 +68,21 @@ pub struct TeamMember {
     pub role: Role,
     /// Capacity in weeks for the quarter (e.g., 12 weeks for full-time team member)
     pub capacity: f32,
+    /// Planned time off (vacation, leave) that reduces weekly availability
+    #[serde(default)]
+    pub time_off: Vec<TimeOff>,
+    /// Work email (used to match calendar imports)
+    #[serde(default)]
+    pub email: Option<String>,
+    /// First working day on the team (None = joined before the plan)
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    /// Last working day on the team, inclusive (None = no planned departure)
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    /// Skills and tags (e.g., "Kafka", "ML modeling")
+    #[serde(default)]
+    pub skills: Vec<String>,
 }
 
 impl TeamMember {
@@ -94,8 +92,30 @@ impl TeamMember {
             name,
             role,
             capacity,
+            time_off: Vec::new(),
+            email: None,
+            start_date: None,
+            end_date: None,
+            skills: Vec::new(),
+        }
+    }
+
+    /// Check whether the member has a skill (case-insensitive)
+    pub fn has_skill(&self, skill: &str) -> bool {
+        self.skills.iter().any(|s| s.eq_ignore_ascii_case(skill))
+    }
+}
+
+/// Parse a comma-separated skill list, trimming entries and dropping
+/// blanks and case-insensitive duplicates
+pub fn parse_skills(input: &str) -> Vec<String> {
+    let mut skills: Vec<String> = Vec::new();
+    for skill in input.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if !skills.iter().any(|s| s.eq_ignore_ascii_case(skill)) {
+            skills.push(skill.to_string());
         }
     }
+    skills
 }
 
 /// High-level roadmap project (initiative)
@@ -104,10 +124,8 @@ impl TeamMember {
 pub struct RoadmapProject {
     pub id: Uuid,
     pub name: String,
-    /// Engineering estimate in weeks
-    pub eng_estimate: f32,
-    /// Science estimate in weeks
-    pub sci_estimate: f32,
+    /// Estimated weeks per role
+    pub estimates: RoleWeeks,
     /// Project start date
     pub start_date: NaiveDate,
     /// Target launch date
@@ -121,8 +139,7 @@ pub struct RoadmapProject {
 impl RoadmapProject {
     pub fn new(
         name: String,
-        eng_estimate: f32,
-        sci_estimate: f32,
+        estimates: RoleWeeks,
         start_date: NaiveDate,
         launch_date: NaiveDate,
         color: ProjectColor,
@@ -130,8 +147,7 @@ impl RoadmapProject {
         Self {
             id: Uuid::new_v4(),
             name,
-            eng_estimate,
-            sci_estimate,
+            estimates,
             start_date,
             launch_date,
             color,
@@ -139,10 +155,10 @@ impl RoadmapProject {
         }
     }
 
-    /// Total estimated weeks (engineering + science)
+    /// Total estimated weeks across all roles
     #[allow(dead_code)] // Reserved for roadmap calculations
     pub fn total_estimate(&self) -> f32 {
-        self.eng_estimate + self.sci_estimate
+        self.estimates.total()
     }
 }
 
@@ -154,16 +170,20 @@ pub struct TechnicalProject {
     pub name: String,
     /// Optional link to parent roadmap project
     pub roadmap_project_id: Option<Uuid>,
-    /// Estimated engineering weeks
-    pub eng_estimate: f32,
-    /// Estimated science weeks
-    pub sci_estimate: f32,
+    /// Estimated weeks per role
+    pub estimates: RoleWeeks,
     /// Project start date
     pub start_date: NaiveDate,
     /// Optional expected completion date
     pub expected_completion: Option<NaiveDate>,
     /// Optional notes
     pub notes: Option<String>,
+    /// Skills the project needs from the people staffed on it
+    #[serde(default)]
+    pub required_skills: Vec<String>,
+    /// Projects that must be scheduled before this one
+    #[serde(default)]
+    pub dependencies: Vec<ProjectDependency>,
 }
 
 #[allow(dead_code)] // Methods used in future milestones
@@ -171,25 +191,42 @@ impl TechnicalProject {
     pub fn new(
         name: String,
         roadmap_project_id: Option<Uuid>,
-        eng_estimate: f32,
-        sci_estimate: f32,
+        estimates: RoleWeeks,
         start_date: NaiveDate,
     ) -> Self {
         Self {
             id: Uuid::new_v4(),
             name,
             roadmap_project_id,
-            eng_estimate,
-            sci_estimate,
+            estimates,
             start_date,
             expected_completion: None,
             notes: None,
+            required_skills: Vec::new(),
+            dependencies: Vec::new(),
         }
     }
 
-    /// Get total estimated weeks (eng + sci)
+    /// Get total estimated weeks across all roles
     pub fn total_estimate(&self) -> f32 {
-        self.eng_estimate + self.sci_estimate
+        self.estimates.total()
+    }
+
+    /// Required skills the member lacks
+    pub fn missing_skills_for(&self, member: &TeamMember) -> Vec<&str> {
+        self.required_skills
+            .iter()
+            .filter(|skill| !member.has_skill(skill))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check whether the member has every required skill
+    ///
+    /// Always false for projects without required skills, so only real
+    /// matches are highlighted.
+    pub fn is_skill_match(&self, member: &TeamMember) -> bool {
+        !self.required_skills.is_empty() && self.missing_skills_for(member).is_empty()
     }
 
     /// Get the color for this technical project from PlanState
@@ -223,6 +260,13 @@ impl Assignment {
             percentage,
         }
     }
+
+    /// A positive share of the week, at most all of it
+    ///
+    /// `new` enforces the range, but loaded data may not respect it.
+    pub fn has_valid_percentage(&self) -> bool {
+        self.percentage > 0.0 && VALID_PERCENTAGE_RANGE.contains(&self.percentage)
+    }
 }
 
 /// Weekly allocation for a team member
@@ -262,6 +306,11 @@ impl Allocation {
         self.assignments.iter().map(|a| a.percentage).sum()
     }
 
+    /// More than the whole week is assigned (beyond rounding in splits)
+    pub fn is_overbooked(&self) -> bool {
+        self.total_percentage() > 100.0 + PERCENTAGE_EPSILON
+    }
+
     /// Check if this week is fully allocated
     #[allow(dead_code)] // Reserved for editing UI
     pub fn is_full(&self) -> bool {
@@ -275,223 +324,6 @@ impl Allocation {
     }
 }
 
-/// Complete quarterly plan data
-///
-/// **DEPRECATED (Milestone 9)**: This struct is kept for backward compatibility during
-/// the migration to two-signal architecture. New code should use `Preferences` and `PlanState`
-/// directly via the `use_preferences()` and `use_plan_state()` hooks.
-///
-/// This wrapper will be removed in Milestone 10+ once all components have migrated.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Plan {
-    /// File format version for future compatibility
-    pub version: String,
-    /// Quarter (e.g., "Q1 2025")
-    pub quarter: String,
-    /// Quarter start date (first Monday)
-    pub quarter_start_date: NaiveDate,
-    /// Number of weeks in the quarter (typically 13)
-    pub weeks_in_quarter: usize,
-    /// Sprint length in weeks (typically 2)
-    #[serde(default = "default_sprint_length")]
-    pub sprint_length_weeks: usize,
-    /// Sprint anchor date (global)
-    #[serde(default = "default_sprint_anchor")]
-    pub sprint_anchor_date: NaiveDate,
-    /// Team name
-    #[serde(default = "default_team_name")]
-    pub team_name: String,
-    /// All team members (engineers and scientists)
-    pub team_members: Vec<TeamMember>,
-    /// All roadmap projects
-    pub roadmap_projects: Vec<RoadmapProject>,
-    /// All technical projects
-    pub technical_projects: Vec<TechnicalProject>,
-    /// All allocations
-    pub allocations: Vec<Allocation>,
-}
-
-fn default_sprint_length() -> usize {
-    2
-}
-
-fn default_sprint_anchor() -> NaiveDate {
-    NaiveDate::from_ymd_opt(2024, 1, 1).expect("Valid sprint anchor")
-}
-
-fn default_team_name() -> String {
-    "My Team".to_string()
-}
-
-#[allow(dead_code)] // Plan wrapper kept for backward compatibility, will be removed in M10+
-impl Plan {
-    /// Get team member by ID
-    pub fn get_team_member(&self, id: &Uuid) -> Option<&TeamMember> {
-        self.team_members.iter().find(|e| &e.id == id)
-    }
-
-    /// Get roadmap project by ID
-    pub fn get_roadmap_project(&self, id: &Uuid) -> Option<&RoadmapProject> {
-        self.roadmap_projects.iter().find(|p| &p.id == id)
-    }
-
-    /// Get technical project by ID
-    pub fn get_technical_project(&self, id: &Uuid) -> Option<&TechnicalProject> {
-        self.technical_projects.iter().find(|p| &p.id == id)
-    }
-
-    /// Get all allocations for a specific team member
-    pub fn get_team_member_allocations(&self, team_member_id: &Uuid) -> Vec<&Allocation> {
-        self.allocations
-            .iter()
-            .filter(|a| &a.team_member_id == team_member_id)
-            .collect()
-    }
-
-    /// Calculate total allocated weeks for a team member
-    pub fn calculate_allocated_weeks(&self, team_member_id: &Uuid) -> f32 {
-        self.get_team_member_allocations(team_member_id)
-            .iter()
-            .map(|a| a.total_percentage() / 100.0)
-            .sum()
-    }
-
-    /// Calculate total allocated weeks for a technical project
-    pub fn calculate_project_allocated_weeks(&self, technical_project_id: &Uuid) -> f32 {
-        self.allocations
-            .iter()
-            .flat_map(|alloc| &alloc.assignments)
-            .filter(|assignment| &assignment.technical_project_id == technical_project_id)
-            .map(|assignment| assignment.percentage / 100.0)
-            .sum()
-    }
-
-    /// Calculate allocated weeks for a roadmap project (sum of all linked technical projects)
-    /// Returns (eng_allocated, sci_allocated, total_allocated)
-    pub fn calculate_roadmap_allocated_weeks(&self, roadmap_project_id: &Uuid) -> (f32, f32, f32) {
-        let mut eng_allocated = 0.0;
-        let mut sci_allocated = 0.0;
-
-        // Find all technical projects linked to this roadmap project
-        let linked_tech_projects: Vec<&TechnicalProject> = self
-            .technical_projects
-            .iter()
-            .filter(|tp| tp.roadmap_project_id.as_ref() == Some(roadmap_project_id))
-            .collect();
-
-        // For each linked technical project, sum up allocations by engineer role
-        for tech_project in linked_tech_projects {
-            for allocation in &self.allocations {
-                if let Some(engineer) = self.get_team_member(&allocation.team_member_id) {
-                    for assignment in &allocation.assignments {
-                        if assignment.technical_project_id == tech_project.id {
-                            let weeks = assignment.percentage / 100.0;
-                            match engineer.role {
-                                Role::Engineering => eng_allocated += weeks,
-                                Role::Science => sci_allocated += weeks,
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        let total_allocated = eng_allocated + sci_allocated;
-        (eng_allocated, sci_allocated, total_allocated)
-    }
-
-    /// Calculate total capacity by role
-    /// Returns (eng_capacity, sci_capacity, total_capacity)
-    pub fn calculate_total_capacity(&self) -> (f32, f32, f32) {
-        let mut eng_capacity = 0.0;
-        let mut sci_capacity = 0.0;
-
-        for member in &self.team_members {
-            match member.role {
-                Role::Engineering => eng_capacity += member.capacity,
-                Role::Science => sci_capacity += member.capacity,
-            }
-        }
-
-        let total_capacity = eng_capacity + sci_capacity;
-        (eng_capacity, sci_capacity, total_capacity)
-    }
-
-    /// Calculate total allocated weeks across all allocations by role
-    /// Returns (eng_allocated, sci_allocated, total_allocated)
-    pub fn calculate_total_allocated(&self) -> (f32, f32, f32) {
-        let mut eng_allocated = 0.0;
-        let mut sci_allocated = 0.0;
-
-        for allocation in &self.allocations {
-            if let Some(engineer) = self.get_team_member(&allocation.team_member_id) {
-                let weeks = allocation.total_percentage() / 100.0;
-                match engineer.role {
-                    Role::Engineering => eng_allocated += weeks,
-                    Role::Science => sci_allocated += weeks,
-                }
-            }
-        }
-
-        let total_allocated = eng_allocated + sci_allocated;
-        (eng_allocated, sci_allocated, total_allocated)
-    }
-
-    /// Update a technical project's start_date and expected_completion based on its allocations
-    ///
-    /// - start_date: Set to the start of the sprint containing the first allocation
-    /// - expected_completion: Set to the end of the sprint containing the last allocation
-    ///
-    /// Call this after adding, removing, or modifying allocations for a project.
-    ///
-    /// **Note**: Uses sprint_anchor_date (global) instead of quarter_start_date
-    pub fn update_technical_project_dates(&mut self, technical_project_id: &Uuid) {
-        use crate::utils::date_helpers::get_sprint_boundaries;
-
-        // Find all weeks with allocations for this project
-        let mut allocation_weeks: Vec<NaiveDate> = self
-            .allocations
-            .iter()
-            .filter(|alloc| {
-                alloc
-                    .assignments
-                    .iter()
-                    .any(|a| a.technical_project_id == *technical_project_id)
-            })
-            .map(|alloc| alloc.week_start_date)
-            .collect();
-
-        // If no allocations, leave dates unchanged
-        if allocation_weeks.is_empty() {
-            return;
-        }
-
-        // Sort to find first and last weeks
-        allocation_weeks.sort();
-        let first_week = allocation_weeks[0];
-        let last_week = allocation_weeks[allocation_weeks.len() - 1];
-
-        // Calculate sprint boundaries using global anchor
-        let (first_sprint_start, _) = get_sprint_boundaries(
-            first_week,
-            self.sprint_anchor_date,
-            self.sprint_length_weeks,
-        );
-        let (_, last_sprint_end) =
-            get_sprint_boundaries(last_week, self.sprint_anchor_date, self.sprint_length_weeks);
-
-        // Update the technical project
-        if let Some(project) = self
-            .technical_projects
-            .iter_mut()
-            .find(|p| p.id == *technical_project_id)
-        {
-            project.start_date = first_sprint_start;
-            project.expected_completion = Some(last_sprint_end);
-        }
-    }
-}
-
 /// Determine capacity badge status based on allocated vs estimated
 /// Neutral: 0/0 (no estimate, no allocation)
 /// Success: within 5% (±0.5 weeks per 10 weeks)
@@ -649,6 +481,14 @@ mod tests {
             .assignments
             .push(Assignment::new(Uuid::new_v4(), 60.0));
         assert!(!partial_alloc.is_valid());
+        assert!(!partial_alloc.is_overbooked());
+
+        // 60% + 50% is overbooked
+        partial_alloc
+            .assignments
+            .push(Assignment::new(Uuid::new_v4(), 50.0));
+        assert!(partial_alloc.is_overbooked());
+        assert!(!full_alloc.is_overbooked());
     }
 
     // ===========================================
@@ -695,157 +535,68 @@ mod tests {
         assert_eq!(get_capacity_status(1.0, 10.0), BadgeType::Error); // 90% under
     }
 
-    // ===========================================
-    // Plan Calculation Tests
-    // ===========================================
-
-    fn create_test_plan() -> Plan {
-        let eng1 = TeamMember {
-            id: Uuid::new_v4(),
-            name: "Engineer 1".to_string(),
-            role: Role::Engineering,
-            capacity: 10.0,
-        };
-        let eng2 = TeamMember {
-            id: Uuid::new_v4(),
-            name: "Engineer 2".to_string(),
-            role: Role::Engineering,
-            capacity: 8.0,
-        };
-        let sci1 = TeamMember {
-            id: Uuid::new_v4(),
-            name: "Scientist 1".to_string(),
-            role: Role::Science,
-            capacity: 6.0,
-        };
-
-        Plan {
-            version: "1.0".to_string(),
-            quarter: "Q1 2025".to_string(),
-            quarter_start_date: NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
-            weeks_in_quarter: 13,
-            sprint_length_weeks: 2,
-            sprint_anchor_date: NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
-            team_name: "Test Team".to_string(),
-            team_members: vec![eng1, eng2, sci1],
-            roadmap_projects: vec![],
-            technical_projects: vec![],
-            allocations: vec![],
-        }
-    }
-
-    #[test]
-    fn test_calculate_total_capacity() {
-        let plan = create_test_plan();
-
-        let (eng_cap, sci_cap, total_cap) = plan.calculate_total_capacity();
-
-        assert_eq!(eng_cap, 18.0); // 10 + 8
-        assert_eq!(sci_cap, 6.0);
-        assert_eq!(total_cap, 24.0);
-    }
-
-    #[test]
-    fn test_calculate_total_capacity_empty_team() {
-        let mut plan = create_test_plan();
-        plan.team_members.clear();
-
-        let (eng_cap, sci_cap, total_cap) = plan.calculate_total_capacity();
-
-        assert_eq!(eng_cap, 0.0);
-        assert_eq!(sci_cap, 0.0);
-        assert_eq!(total_cap, 0.0);
-    }
-
     #[test]
-    fn test_calculate_total_allocated_no_allocations() {
-        let plan = create_test_plan();
-
-        let (eng_alloc, sci_alloc, total_alloc) = plan.calculate_total_allocated();
+    fn test_technical_project_custom_role_estimates() {
+        let design = Role::new("design");
+        let project = TechnicalProject::new(
+            "Redesign".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 4.0), (design.clone(), 3.0)]),
+            NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        );
+        assert_eq!(project.total_estimate(), 7.0);
 
-        assert_eq!(eng_alloc, 0.0);
-        assert_eq!(sci_alloc, 0.0);
-        assert_eq!(total_alloc, 0.0);
+        let json = serde_json::to_string(&project).unwrap();
+        let reloaded: TechnicalProject = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.estimates.get(&design), 3.0);
     }
 
     #[test]
-    fn test_calculate_total_allocated_with_allocations() {
-        let mut plan = create_test_plan();
-        let eng1_id = plan.team_members[0].id;
-        let sci1_id = plan.team_members[2].id;
-        let project_id = Uuid::new_v4();
-
-        // Add allocations: 2 weeks for eng1 (100% each), 1 week for sci1 (50%)
-        let week1 = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
-        let week2 = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
-
-        let mut alloc1 = Allocation::new(eng1_id, week1);
-        alloc1.assignments.push(Assignment::new(project_id, 100.0));
-        plan.allocations.push(alloc1);
-
-        let mut alloc2 = Allocation::new(eng1_id, week2);
-        alloc2.assignments.push(Assignment::new(project_id, 100.0));
-        plan.allocations.push(alloc2);
-
-        let mut alloc3 = Allocation::new(sci1_id, week1);
-        alloc3.assignments.push(Assignment::new(project_id, 50.0));
-        plan.allocations.push(alloc3);
-
-        let (eng_alloc, sci_alloc, total_alloc) = plan.calculate_total_allocated();
-
-        assert_eq!(eng_alloc, 2.0); // 2 weeks at 100%
-        assert_eq!(sci_alloc, 0.5); // 1 week at 50%
-        assert_eq!(total_alloc, 2.5);
+    fn test_parse_skills() {
+        assert_eq!(
+            parse_skills(" Kafka, ML modeling,,kafka , Rust "),
+            ["Kafka", "ML modeling", "Rust"]
+        );
+        assert!(parse_skills(" , ").is_empty());
     }
 
     #[test]
-    fn test_calculate_allocated_weeks_for_member() {
-        let mut plan = create_test_plan();
-        let eng1_id = plan.team_members[0].id;
-        let eng2_id = plan.team_members[1].id;
-        let project_id = Uuid::new_v4();
-
-        // eng1 gets 3 weeks of allocation
-        for i in 0..3 {
-            let week = NaiveDate::from_ymd_opt(2025, 1, 6 + i * 7).unwrap();
-            let mut alloc = Allocation::new(eng1_id, week);
-            alloc.assignments.push(Assignment::new(project_id, 100.0));
-            plan.allocations.push(alloc);
-        }
+    fn test_project_skill_match() {
+        let mut member = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        member.skills = vec!["Kafka".to_string(), "Rust".to_string()];
+
+        let mut project = TechnicalProject::new(
+            "Streaming".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+        );
+        // No requirements: nothing missing, but not highlighted as a match
+        assert!(project.missing_skills_for(&member).is_empty());
+        assert!(!project.is_skill_match(&member));
 
-        // eng2 gets 1 week at 50%
-        let week = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
-        let mut alloc = Allocation::new(eng2_id, week);
-        alloc.assignments.push(Assignment::new(project_id, 50.0));
-        plan.allocations.push(alloc);
+        project.required_skills = vec!["kafka".to_string()];
+        assert!(project.is_skill_match(&member));
 
-        assert_eq!(plan.calculate_allocated_weeks(&eng1_id), 3.0);
-        assert_eq!(plan.calculate_allocated_weeks(&eng2_id), 0.5);
+        project.required_skills.push("Flink".to_string());
+        assert_eq!(project.missing_skills_for(&member), ["Flink"]);
+        assert!(!project.is_skill_match(&member));
     }
 
     #[test]
-    fn test_calculate_project_allocated_weeks() {
-        let mut plan = create_test_plan();
-        let eng1_id = plan.team_members[0].id;
-        let project1_id = Uuid::new_v4();
-        let project2_id = Uuid::new_v4();
-
-        // Project 1: 2 full weeks from eng1
-        for i in 0..2 {
-            let week = NaiveDate::from_ymd_opt(2025, 1, 6 + i * 7).unwrap();
-            let mut alloc = Allocation::new(eng1_id, week);
-            alloc.assignments.push(Assignment::new(project1_id, 100.0));
-            plan.allocations.push(alloc);
-        }
-
-        // Project 2: 1 week split allocation (50%)
-        let week = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
-        let mut alloc = Allocation::new(eng1_id, week);
-        alloc.assignments.push(Assignment::new(project1_id, 50.0));
-        alloc.assignments.push(Assignment::new(project2_id, 50.0));
-        plan.allocations.push(alloc);
-
-        assert_eq!(plan.calculate_project_allocated_weeks(&project1_id), 2.5); // 2 full + 0.5 split
-        assert_eq!(plan.calculate_project_allocated_weeks(&project2_id), 0.5); // 0.5 from split
+    fn test_legacy_members_and_projects_have_no_skills() {
+        let member: TeamMember = serde_json::from_str(
+            r#"{"id": "00000000-0000-0000-0000-000000000001", "name": "Alice", "role": "eng", "capacity": 12.0}"#,
+        )
+        .unwrap();
+        assert!(member.skills.is_empty());
+
+        let project: TechnicalProject = serde_json::from_str(
+            r#"{"id": "00000000-0000-0000-0000-000000000002", "name": "API", "roadmap_project_id": null,
+                "estimates": {"eng": 2.0}, "start_date": "2025-01-06",
+                "expected_completion": null, "notes": null}"#,
+        )
+        .unwrap();
+        assert!(project.required_skills.is_empty());
     }
 }
@@ -2,7 +2,8 @@ use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Allocation, PlanMetadata, PlanState, Preferences, RoadmapProject, TeamMember, TechnicalProject,
+    default_roles, Allocation, Holiday, PlanMetadata, PlanState, Preferences, RoadmapProject,
+    RoleDefinition, Scenario, TeamMember, TechnicalProject,
 };
 
 /// Self-contained plan export format
@@ -32,6 +33,16 @@ pub struct PlanExport {
     /// Allows importing user to see all context without requiring local team data
     pub team_members: Vec<TeamMember>,
 
+    /// Team-wide holidays (snapshot)
+    /// Needed to reproduce capacity numbers on the importing side
+    #[serde(default)]
+    pub holidays: Vec<Holiday>,
+
+    /// Role definitions (snapshot)
+    /// Exports saved before roles were configurable get Engineering and Science
+    #[serde(default = "default_roles")]
+    pub roles: Vec<RoleDefinition>,
+
     // ========== PLANNING DATA ==========
     /// Quarter name (e.g., "Q1 2025")
     pub quarter_name: String,
@@ -50,6 +61,11 @@ pub struct PlanExport {
 
     /// All allocations
     pub allocations: Vec<Allocation>,
+
+    /// Named what-if scenarios
+    /// The fields above always hold the main plan, whichever branch was active
+    #[serde(default)]
+    pub scenarios: Vec<Scenario>,
 }
 
 #[allow(dead_code)] // Methods used in M13 for plan import/export
@@ -59,6 +75,7 @@ impl PlanExport {
     /// Takes snapshots of both signals to create a portable, self-contained export
     /// that can be shared with other users or loaded for multi-team aggregation.
     pub fn from_signals(prefs: Preferences, state: PlanState) -> Self {
+        let state = state.with_main_plan_active();
         Self {
             version: state.metadata.version.clone(),
             metadata: state.metadata.clone(),
@@ -66,6 +83,8 @@ impl PlanExport {
             // Team context snapshot
             team_name: prefs.team_name,
             team_members: prefs.team_members,
+            holidays: prefs.holidays,
+            roles: prefs.roles,
 
             // Planning data
             quarter_name: state.quarter_name,
@@ -74,6 +93,7 @@ impl PlanExport {
             roadmap_projects: state.roadmap_projects,
             technical_projects: state.technical_projects,
             allocations: state.allocations,
+            scenarios: state.scenarios,
         }
     }
 
@@ -93,6 +113,8 @@ impl PlanExport {
                 .expect("Valid sprint anchor"),
             sprint_length_weeks: 2,
             default_capacity: 12.0,
+            holidays: self.holidays,
+            roles: self.roles,
         };
 
         let state = PlanState {
@@ -102,6 +124,9 @@ impl PlanExport {
             roadmap_projects: self.roadmap_projects,
             technical_projects: self.technical_projects,
             allocations: self.allocations,
+            scenarios: self.scenarios,
+            active_scenario_id: None,
+            stashed_main: None,
             metadata: self.metadata,
         };
 
@@ -147,6 +172,24 @@ impl PlanExport {
             }
         }
 
+        // Every member's role must be defined
+        for member in &self.team_members {
+            if !self.roles.iter().any(|r| r.id == member.role) {
+                return Err(ExportValidationError::UndefinedRole(member.role.clone()));
+            }
+        }
+
+        // Employment dates must be ordered. Allocations outside them are only
+        // a warning (see `warnings`): the grid flags them, and changing a
+        // member's dates after planning creates them.
+        for member in &self.team_members {
+            if let (Some(start), Some(end)) = (member.start_date, member.end_date) {
+                if end < start {
+                    return Err(ExportValidationError::InvalidTenure(member.id));
+                }
+            }
+        }
+
         // All assignments should reference valid technical projects
         for allocation in &self.allocations {
             for assignment in &allocation.assignments {
@@ -173,8 +216,86 @@ impl PlanExport {
             }
         }
 
+        // Dependencies must point at known projects and must not form a cycle
+        for tech_project in &self.technical_projects {
+            for dependency in &tech_project.dependencies {
+                if !self
+                    .technical_projects
+                    .iter()
+                    .any(|p| p.id == dependency.predecessor_id)
+                {
+                    return Err(ExportValidationError::InvalidDependencyReference {
+                        project_id: tech_project.id,
+                        predecessor_id: dependency.predecessor_id,
+                    });
+                }
+            }
+        }
+
+        if let Some(cycle) = super::find_dependency_cycle(&self.technical_projects) {
+            return Err(ExportValidationError::DependencyCycle(cycle));
+        }
+
+        // Each scenario must hold up on its own against the shared roster
+        for scenario in &self.scenarios {
+            let branch = Self {
+                roadmap_projects: scenario.branch.roadmap_projects.clone(),
+                technical_projects: scenario.branch.technical_projects.clone(),
+                allocations: scenario.branch.allocations.clone(),
+                scenarios: Vec::new(),
+                ..self.clone()
+            };
+            branch
+                .validate()
+                .map_err(|error| ExportValidationError::InvalidScenario {
+                    scenario_id: scenario.id,
+                    error: Box::new(error),
+                })?;
+        }
+
         Ok(())
     }
+
+    /// Problems that don't stop the export from loading, in the main plan
+    /// and each scenario
+    ///
+    /// `validate` passing doesn't mean these are absent.
+    pub fn warnings(&self) -> Vec<ExportValidationWarning> {
+        let branches = std::iter::once((None, &self.allocations)).chain(
+            self.scenarios
+                .iter()
+                .map(|s| (Some(s.id), &s.branch.allocations)),
+        );
+        let mut warnings = Vec::new();
+        for (scenario_id, allocations) in branches {
+            for allocation in allocations {
+                let outside_tenure = self
+                    .team_members
+                    .iter()
+                    .find(|m| m.id == allocation.team_member_id)
+                    .is_some_and(|m| !m.is_active_in_week(allocation.week_start_date));
+                if outside_tenure {
+                    warnings.push(ExportValidationWarning::AllocationOutsideTenure {
+                        scenario_id,
+                        team_member_id: allocation.team_member_id,
+                        week_start_date: allocation.week_start_date,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+/// Non-fatal findings for plan exports
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValidationWarning {
+    /// An allocation in a week the member isn't employed
+    AllocationOutsideTenure {
+        scenario_id: Option<uuid::Uuid>,
+        team_member_id: uuid::Uuid,
+        week_start_date: NaiveDate,
+    },
 }
 
 /// Validation errors for plan exports
@@ -189,6 +310,19 @@ pub enum ExportValidationError {
     InvalidTeamMemberReference(uuid::Uuid),
     InvalidTechnicalProjectReference(uuid::Uuid),
     InvalidRoadmapProjectReference(uuid::Uuid),
+    InvalidTenure(uuid::Uuid),
+    UndefinedRole(super::Role),
+    InvalidDependencyReference {
+        project_id: uuid::Uuid,
+        predecessor_id: uuid::Uuid,
+    },
+    /// Project IDs along the cycle, first and last being the same project
+    DependencyCycle(Vec<uuid::Uuid>),
+    /// A scenario's own data failed validation
+    InvalidScenario {
+        scenario_id: uuid::Uuid,
+        error: Box<ExportValidationError>,
+    },
 }
 
 #[cfg(test)]
@@ -202,12 +336,14 @@ mod tests {
             team_name: "Backend Team".to_string(),
             team_members: vec![TeamMember::new(
                 "Alice Kim".to_string(),
-                Role::Engineering,
+                Role::ENGINEERING,
                 12.0,
             )],
             sprint_anchor_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             sprint_length_weeks: 2,
             default_capacity: 12.0,
+            holidays: Vec::new(),
+            roles: crate::models::default_roles(),
         };
 
         let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
@@ -220,7 +356,7 @@ mod tests {
     fn test_plan_export_creation() {
         let export = create_sample_export();
 
-        assert_eq!(export.version, "1.0");
+        assert_eq!(export.version, crate::models::PLAN_SCHEMA_VERSION);
         assert_eq!(export.team_name, "Backend Team");
         assert_eq!(export.team_members.len(), 1);
         assert_eq!(export.quarter_name, "Q1 2025");
@@ -247,10 +383,202 @@ mod tests {
         assert_eq!(export.validate(), Err(ExportValidationError::NoTeamMembers));
     }
 
+    #[test]
+    fn test_export_validation_tenure() {
+        use crate::models::Allocation;
+
+        let mut export = create_sample_export();
+        let member_id = export.team_members[0].id;
+        let week = export.quarter_start_date + chrono::Duration::weeks(2);
+        export.allocations.push(Allocation::new(member_id, week));
+        assert!(export.validate().is_ok());
+
+        assert!(export.warnings().is_empty());
+
+        // Joining after the allocated week is flagged, not rejected
+        export.team_members[0].start_date = Some(week + chrono::Duration::weeks(1));
+        assert!(export.validate().is_ok());
+        assert_eq!(
+            export.warnings(),
+            [ExportValidationWarning::AllocationOutsideTenure {
+                scenario_id: None,
+                team_member_id: member_id,
+                week_start_date: week,
+            }]
+        );
+
+        // End date before start date
+        export.team_members[0].end_date = Some(week);
+        assert_eq!(
+            export.validate(),
+            Err(ExportValidationError::InvalidTenure(member_id))
+        );
+    }
+
+    #[test]
+    fn test_tenure_change_round_trip() {
+        use crate::migration::load_json;
+        use crate::models::{Allocation, Assignment, TechnicalProject};
+        use crate::validation::{validate_export, Severity};
+
+        let (mut prefs, mut state) = create_sample_export().into_signals();
+        let project = TechnicalProject::new(
+            "API".to_string(),
+            None,
+            crate::models::RoleWeeks::new(),
+            state.quarter_start_date,
+        );
+        let week = state.quarter_start_date + chrono::Duration::weeks(4);
+        let mut allocation = Allocation::new(prefs.team_members[0].id, week);
+        allocation
+            .assignments
+            .push(Assignment::new(project.id, 100.0));
+        state.technical_projects.push(project);
+        state.allocations.push(allocation);
+
+        // The member leaves before a week that's already planned
+        prefs.team_members[0].end_date = Some(week - chrono::Duration::weeks(1));
+        let json = serde_json::to_string(&PlanExport::from_signals(prefs, state)).unwrap();
+
+        let imported: PlanExport = load_json(&json).unwrap();
+        assert_eq!(imported.validate(), Ok(()));
+        assert_eq!(imported.warnings().len(), 1);
+        let report = validate_export(&imported);
+        assert!(!report.has_errors(), "{}", report);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].severity, Severity::Warning);
+        assert_eq!(imported.into_signals().1.allocations.len(), 1);
+    }
+
+    #[test]
+    fn test_export_validation_undefined_role() {
+        let mut export = create_sample_export();
+        let designer = crate::models::Role::new("design");
+        export.team_members[0].role = designer.clone();
+        assert_eq!(
+            export.validate(),
+            Err(ExportValidationError::UndefinedRole(designer.clone()))
+        );
+
+        export
+            .roles
+            .push(RoleDefinition::new(designer, "Design", "UX"));
+        assert!(export.validate().is_ok());
+    }
+
+    #[test]
+    fn test_export_validation_dependencies() {
+        use crate::models::{DependencyKind, ProjectDependency, RoleWeeks, TechnicalProject};
+
+        let mut export = create_sample_export();
+        let new_project = |name: &str| {
+            TechnicalProject::new(
+                name.to_string(),
+                None,
+                RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+                export.quarter_start_date,
+            )
+        };
+        let api = new_project("API migration");
+        let mut client = new_project("Client rollout");
+
+        // Unknown predecessor
+        let missing_id = uuid::Uuid::new_v4();
+        client.dependencies.push(ProjectDependency::new(
+            missing_id,
+            DependencyKind::FinishToStart,
+        ));
+        export.technical_projects = vec![api.clone(), client.clone()];
+        assert_eq!(
+            export.validate(),
+            Err(ExportValidationError::InvalidDependencyReference {
+                project_id: client.id,
+                predecessor_id: missing_id,
+            })
+        );
+
+        client.dependencies[0].predecessor_id = api.id;
+        export.technical_projects = vec![api.clone(), client.clone()];
+        assert!(export.validate().is_ok());
+
+        // Cycle
+        export.technical_projects[0]
+            .dependencies
+            .push(ProjectDependency::new(
+                client.id,
+                DependencyKind::StartToStart,
+            ));
+        assert_eq!(
+            export.validate(),
+            Err(ExportValidationError::DependencyCycle(vec![
+                api.id, client.id, api.id
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_export_includes_scenarios() {
+        use crate::models::Allocation;
+
+        let prefs = Preferences {
+            team_members: vec![TeamMember::new(
+                "Alice Kim".to_string(),
+                Role::ENGINEERING,
+                12.0,
+            )],
+            ..Preferences::default()
+        };
+        let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
+        let scenario_id = state.create_scenario("Pipeline first");
+        state.switch_scenario(Some(scenario_id));
+        let week = quarter_start + chrono::Duration::weeks(1);
+        state
+            .allocations
+            .push(Allocation::new(prefs.team_members[0].id, week));
+
+        // Exported with the main plan on top, even though the scenario was active
+        let mut export = PlanExport::from_signals(prefs, state);
+        assert!(export.allocations.is_empty());
+        assert_eq!(export.scenarios.len(), 1);
+        assert_eq!(export.scenarios[0].branch.allocations.len(), 1);
+        assert!(export.validate().is_ok());
+
+        let (_, restored) = export.clone().into_signals();
+        assert_eq!(restored.active_scenario_id, None);
+        assert_eq!(restored.scenarios, export.scenarios);
+
+        // Scenario data is validated like the main plan
+        let stranger = uuid::Uuid::new_v4();
+        export.scenarios[0].branch.allocations[0].team_member_id = stranger;
+        assert_eq!(
+            export.validate(),
+            Err(ExportValidationError::InvalidScenario {
+                scenario_id,
+                error: Box::new(ExportValidationError::InvalidTeamMemberReference(stranger)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_legacy_export_gets_default_roles() {
+        let mut json = serde_json::to_value(create_sample_export()).unwrap();
+        json.as_object_mut().unwrap().remove("roles");
+
+        let export: PlanExport = serde_json::from_value(json).unwrap();
+        assert_eq!(export.roles, default_roles());
+        assert!(export.validate().is_ok());
+    }
+
     #[test]
     fn test_export_round_trip() {
-        let original_prefs = Preferences::default();
+        let mut original_prefs = Preferences::default();
         let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        original_prefs.holidays.push(Holiday::new(
+            "New Year".to_string(),
+            quarter_start,
+            quarter_start,
+        ));
         let original_state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
 
         let export = PlanExport::from_signals(original_prefs.clone(), original_state.clone());
@@ -258,6 +586,7 @@ mod tests {
 
         assert_eq!(original_prefs.team_name, restored_prefs.team_name);
         assert_eq!(original_prefs.team_members, restored_prefs.team_members);
+        assert_eq!(original_prefs.holidays, restored_prefs.holidays);
         assert_eq!(original_state.quarter_name, restored_state.quarter_name);
         assert_eq!(
             original_state.quarter_start_date,
@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::{Allocation, PlanState, RoadmapProject, Role, RoleWeeks, TeamMember, TechnicalProject};
+
+/// Lookup tables over a plan, built in one pass over its allocations
+///
+/// The `PlanState` query methods scan every allocation per call; views that
+/// ask per project, per member or per grid cell use this instead. Results
+/// match the corresponding `PlanState` methods, up to floating-point
+/// rounding. Rebuild it whenever the plan or roster changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanIndex {
+    state: PlanState,
+    roadmap_positions: HashMap<Uuid, usize>,
+    technical_positions: HashMap<Uuid, usize>,
+    cells: HashMap<(Uuid, NaiveDate), usize>,
+    members: HashMap<Uuid, MemberTotals>,
+    projects: HashMap<Uuid, ProjectTotals>,
+    roadmaps: HashMap<Uuid, RoleWeeks>,
+    total_allocated: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MemberTotals {
+    allocated_weeks: f32,
+    /// Assigned projects, in first-assigned order
+    project_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProjectTotals {
+    allocated_weeks: f32,
+    allocated_by_role: RoleWeeks,
+    /// (role, weeks) per assignment in allocation order, for roadmap totals
+    role_weeks: Vec<(Role, f32)>,
+    /// Sorted and deduplicated once built
+    member_ids: Vec<Uuid>,
+    first_week: NaiveDate,
+    last_week: NaiveDate,
+}
+
+impl PlanIndex {
+    /// Index a plan; `members` supplies roles for the per-role totals
+    pub fn new(state: PlanState, members: &[TeamMember]) -> Self {
+        let mut member_roles = HashMap::new();
+        for member in members {
+            member_roles
+                .entry(member.id)
+                .or_insert_with(|| member.role.clone());
+        }
+
+        // First entry wins, like the `find`-based lookups
+        let mut roadmap_positions = HashMap::new();
+        for (i, project) in state.roadmap_projects.iter().enumerate() {
+            roadmap_positions.entry(project.id).or_insert(i);
+        }
+        let mut technical_positions = HashMap::new();
+        for (i, project) in state.technical_projects.iter().enumerate() {
+            technical_positions.entry(project.id).or_insert(i);
+        }
+
+        let mut cells = HashMap::new();
+        let mut member_totals: HashMap<Uuid, MemberTotals> = HashMap::new();
+        let mut projects: HashMap<Uuid, ProjectTotals> = HashMap::new();
+        let mut total_allocated = 0.0;
+        for (i, allocation) in state.allocations.iter().enumerate() {
+            let week = allocation.week_start_date;
+            cells.entry((allocation.team_member_id, week)).or_insert(i);
+
+            let weeks = allocation.total_percentage() / 100.0;
+            let role = member_roles.get(&allocation.team_member_id);
+            if role.is_some() {
+                total_allocated += weeks;
+            }
+            let member = member_totals
+                .entry(allocation.team_member_id)
+                .or_insert_with(|| MemberTotals {
+                    allocated_weeks: 0.0,
+                    project_ids: Vec::new(),
+                });
+            member.allocated_weeks += weeks;
+
+            for assignment in &allocation.assignments {
+                let project_id = assignment.technical_project_id;
+                if !member.project_ids.contains(&project_id) {
+                    member.project_ids.push(project_id);
+                }
+
+                let project = projects.entry(project_id).or_insert_with(|| ProjectTotals {
+                    allocated_weeks: 0.0,
+                    allocated_by_role: RoleWeeks::new(),
+                    role_weeks: Vec::new(),
+                    member_ids: Vec::new(),
+                    first_week: week,
+                    last_week: week,
+                });
+                let weeks = assignment.percentage / 100.0;
+                project.allocated_weeks += weeks;
+                if let Some(role) = role {
+                    project.allocated_by_role.add(role, weeks);
+                    project.role_weeks.push((role.clone(), weeks));
+                }
+                project.member_ids.push(allocation.team_member_id);
+                project.first_week = project.first_week.min(week);
+                project.last_week = project.last_week.max(week);
+            }
+        }
+        for project in projects.values_mut() {
+            project.member_ids.sort();
+            project.member_ids.dedup();
+        }
+
+        // Linked projects in list order, then allocations in order
+        let mut roadmaps: HashMap<Uuid, RoleWeeks> = HashMap::new();
+        for project in &state.technical_projects {
+            let Some(roadmap_id) = project.roadmap_project_id else {
+                continue;
+            };
+            let allocated = roadmaps.entry(roadmap_id).or_default();
+            for (role, weeks) in projects.get(&project.id).map_or(&[][..], |p| &p.role_weeks) {
+                allocated.add(role, *weeks);
+            }
+        }
+
+        Self {
+            state,
+            roadmap_positions,
+            technical_positions,
+            cells,
+            members: member_totals,
+            projects,
+            roadmaps,
+            total_allocated,
+        }
+    }
+
+    /// The indexed plan
+    pub fn state(&self) -> &PlanState {
+        &self.state
+    }
+
+    /// Get roadmap project by ID
+    pub fn roadmap_project(&self, id: &Uuid) -> Option<&RoadmapProject> {
+        self.roadmap_positions
+            .get(id)
+            .map(|&i| &self.state.roadmap_projects[i])
+    }
+
+    /// Get technical project by ID
+    pub fn technical_project(&self, id: &Uuid) -> Option<&TechnicalProject> {
+        self.technical_positions
+            .get(id)
+            .map(|&i| &self.state.technical_projects[i])
+    }
+
+    /// Allocation for a team member's week, if any
+    pub fn allocation(
+        &self,
+        team_member_id: &Uuid,
+        week_start_date: NaiveDate,
+    ) -> Option<&Allocation> {
+        self.cells
+            .get(&(*team_member_id, week_start_date))
+            .map(|&i| &self.state.allocations[i])
+    }
+
+    /// Same as `PlanState::calculate_project_allocated_weeks`
+    pub fn project_allocated_weeks(&self, technical_project_id: &Uuid) -> f32 {
+        self.projects
+            .get(technical_project_id)
+            .map_or(0.0, |p| p.allocated_weeks)
+    }
+
+    /// Same as `PlanState::calculate_technical_project_allocated_by_role`
+    pub fn technical_project_allocated_by_role(&self, technical_project_id: &Uuid) -> RoleWeeks {
+        self.projects
+            .get(technical_project_id)
+            .map(|p| p.allocated_by_role.clone())
+            .unwrap_or_default()
+    }
+
+    /// Same as `PlanState::get_assigned_team_members`
+    pub fn assigned_team_members(&self, technical_project_id: &Uuid) -> &[Uuid] {
+        self.projects
+            .get(technical_project_id)
+            .map_or(&[], |p| &p.member_ids)
+    }
+
+    /// Same as `PlanState::get_project_allocation_date_range`
+    pub fn project_allocation_date_range(
+        &self,
+        technical_project_id: &Uuid,
+    ) -> Option<(NaiveDate, NaiveDate)> {
+        self.projects
+            .get(technical_project_id)
+            .map(|p| (p.first_week, p.last_week))
+    }
+
+    /// Same as `PlanState::calculate_team_member_allocated_weeks`
+    pub fn team_member_allocated_weeks(&self, team_member_id: &Uuid) -> f32 {
+        self.members
+            .get(team_member_id)
+            .map_or(0.0, |m| m.allocated_weeks)
+    }
+
+    /// Same as `PlanState::get_assigned_project_names_for_member`, in
+    /// first-assigned order
+    pub fn assigned_project_names_for_member(&self, team_member_id: &Uuid) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let project_ids = self
+            .members
+            .get(team_member_id)
+            .map_or(&[][..], |m| &m.project_ids);
+        for project in project_ids
+            .iter()
+            .filter_map(|id| self.technical_project(id))
+        {
+            if !names.contains(&project.name) {
+                names.push(project.name.clone());
+            }
+        }
+        names
+    }
+
+    /// Same as `PlanState::calculate_roadmap_allocated_weeks`
+    pub fn roadmap_allocated_weeks(&self, roadmap_project_id: &Uuid) -> RoleWeeks {
+        self.roadmaps
+            .get(roadmap_project_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Allocated weeks for members on the roster, like `PlanView::total_allocated`
+    pub fn total_allocated(&self) -> f32 {
+        self.total_allocated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ProjectColor};
+    use chrono::Duration;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_close_roles(actual: &RoleWeeks, expected: &RoleWeeks) {
+        for (role, _) in actual.iter().chain(expected.iter()) {
+            assert_close(actual.get(role), expected.get(role));
+        }
+    }
+
+    /// Check every index query against the `PlanState` method it replaces
+    fn assert_matches_plan_state(state: &PlanState, members: &[TeamMember]) {
+        let index = PlanIndex::new(state.clone(), members);
+        let get_role = |id: &Uuid| members.iter().find(|m| &m.id == id).map(|m| m.role.clone());
+
+        for project in &state.technical_projects {
+            let id = &project.id;
+            assert_close(
+                index.project_allocated_weeks(id),
+                state.calculate_project_allocated_weeks(id),
+            );
+            assert_close_roles(
+                &index.technical_project_allocated_by_role(id),
+                &state.calculate_technical_project_allocated_by_role(id, get_role),
+            );
+            assert_eq!(
+                index.assigned_team_members(id),
+                state.get_assigned_team_members(id)
+            );
+            assert_eq!(
+                index.project_allocation_date_range(id),
+                state.get_project_allocation_date_range(id)
+            );
+            assert_eq!(index.technical_project(id), state.get_technical_project(id));
+        }
+        let allocated_ids = state.allocations.iter().map(|a| a.team_member_id);
+        let member_ids = members.iter().map(|m| m.id);
+        for id in member_ids.chain(allocated_ids).chain([Uuid::new_v4()]) {
+            assert_close(
+                index.team_member_allocated_weeks(&id),
+                state.calculate_team_member_allocated_weeks(&id),
+            );
+            let mut names = index.assigned_project_names_for_member(&id);
+            let mut expected = state.get_assigned_project_names_for_member(&id);
+            names.sort();
+            expected.sort();
+            assert_eq!(names, expected);
+        }
+        for roadmap in &state.roadmap_projects {
+            assert_close_roles(
+                &index.roadmap_allocated_weeks(&roadmap.id),
+                &state.calculate_roadmap_allocated_weeks(&roadmap.id, get_role),
+            );
+            assert_eq!(index.roadmap_project(&roadmap.id), Some(roadmap));
+        }
+        // Allocations for members not on the roster aren't counted
+        let on_roster: f32 = state
+            .allocations
+            .iter()
+            .filter(|a| members.iter().any(|m| m.id == a.team_member_id))
+            .map(|a| a.total_percentage() / 100.0)
+            .sum();
+        assert_close(index.total_allocated(), on_roster);
+
+        for alloc in &state.allocations {
+            assert_eq!(
+                index.allocation(&alloc.team_member_id, alloc.week_start_date),
+                Some(alloc)
+            );
+        }
+    }
+
+    fn sample() -> (PlanState, RoadmapProject, TechnicalProject) {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 13);
+        let roadmap = RoadmapProject::new(
+            "Platform".to_string(),
+            RoleWeeks::new(),
+            start,
+            start + Duration::weeks(13),
+            ProjectColor::Blue,
+        );
+        let api =
+            TechnicalProject::new("API".to_string(), Some(roadmap.id), RoleWeeks::new(), start);
+        state.roadmap_projects.push(roadmap.clone());
+        state.technical_projects.push(api.clone());
+        (state, roadmap, api)
+    }
+
+    fn add_allocation(state: &mut PlanState, member: Uuid, week: i64, assignments: &[(Uuid, f32)]) {
+        let start = state.quarter_start_date;
+        let mut alloc = Allocation::new(member, start + Duration::weeks(week));
+        for &(project_id, percentage) in assignments {
+            alloc
+                .assignments
+                .push(Assignment::new(project_id, percentage));
+        }
+        state.allocations.push(alloc);
+    }
+
+    #[test]
+    fn test_matches_plan_state_queries() {
+        let (mut state, roadmap, api) = sample();
+        let alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        let carol = TeamMember::new("Carol".to_string(), Role::SCIENCE, 6.0);
+        let members = vec![alice.clone(), carol.clone()];
+
+        let start = state.quarter_start_date;
+        let model = TechnicalProject::new(
+            "Model".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::new(),
+            start,
+        );
+        let unlinked = TechnicalProject::new("Docs".to_string(), None, RoleWeeks::new(), start);
+        let idle = TechnicalProject::new("Idle".to_string(), None, RoleWeeks::new(), start);
+        let unknown_member = Uuid::new_v4();
+        add_allocation(&mut state, alice.id, 2, &[(api.id, 33.3), (model.id, 66.7)]);
+        add_allocation(&mut state, alice.id, 0, &[(api.id, 100.0)]);
+        add_allocation(
+            &mut state,
+            carol.id,
+            1,
+            &[(model.id, 10.1), (unlinked.id, 45.0)],
+        );
+        add_allocation(&mut state, carol.id, 3, &[(model.id, 70.0)]);
+        add_allocation(&mut state, unknown_member, 1, &[(api.id, 50.0)]);
+        state.technical_projects.extend([model, unlinked, idle]);
+
+        assert_matches_plan_state(&state, &members);
+        let index = PlanIndex::new(state.clone(), &members);
+        assert_eq!(
+            index.allocation(&alice.id, start + Duration::weeks(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty_plan() {
+        let (state, roadmap, api) = sample();
+        let alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        assert_matches_plan_state(&state, std::slice::from_ref(&alice));
+
+        let index = PlanIndex::new(state, &[alice]);
+        assert_eq!(index.total_allocated(), 0.0);
+        assert_eq!(index.project_allocated_weeks(&api.id), 0.0);
+        assert!(index.roadmap_allocated_weeks(&roadmap.id).is_empty());
+        assert!(index.assigned_team_members(&api.id).is_empty());
+        assert_eq!(index.project_allocation_date_range(&api.id), None);
+    }
+
+    #[test]
+    fn test_member_without_allocations() {
+        let (mut state, _, api) = sample();
+        let alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        let bench = TeamMember::new("Bench".to_string(), Role::SCIENCE, 12.0);
+        add_allocation(&mut state, alice.id, 0, &[(api.id, 100.0)]);
+        let members = vec![alice, bench.clone()];
+        assert_matches_plan_state(&state, &members);
+
+        let index = PlanIndex::new(state, &members);
+        assert_eq!(index.team_member_allocated_weeks(&bench.id), 0.0);
+        assert!(index
+            .assigned_project_names_for_member(&bench.id)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_project_repeated_in_one_week() {
+        let (mut state, roadmap, api) = sample();
+        let alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        add_allocation(&mut state, alice.id, 0, &[(api.id, 30.0), (api.id, 20.0)]);
+        add_allocation(&mut state, alice.id, 1, &[(api.id, 50.0)]);
+        let members = vec![alice.clone()];
+        assert_matches_plan_state(&state, &members);
+
+        let index = PlanIndex::new(state, &members);
+        assert_close(index.project_allocated_weeks(&api.id), 1.0);
+        assert_eq!(index.assigned_team_members(&api.id), [alice.id]);
+        assert_eq!(index.assigned_project_names_for_member(&alice.id), ["API"]);
+        assert_close(
+            index
+                .roadmap_allocated_weeks(&roadmap.id)
+                .get(&Role::ENGINEERING),
+            1.0,
+        );
+    }
+}
@@ -2,9 +2,15 @@ use chrono::{DateTime, Local, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{Allocation, RoadmapProject, TechnicalProject};
+use super::{
+    Allocation, Holiday, PlanBranch, Preferences, RoadmapProject, Role, RoleWeeks, Scenario,
+    TeamMember, TechnicalProject,
+};
 use crate::utils::get_next_quarter_info;
 
+/// Current file format version for PlanState and PlanExport
+pub const PLAN_SCHEMA_VERSION: &str = "1.1";
+
 /// Plan metadata for versioning and audit trail
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlanMetadata {
@@ -22,7 +28,7 @@ impl PlanMetadata {
     pub fn new() -> Self {
         let now = Utc::now();
         Self {
-            version: "1.0".to_string(),
+            version: PLAN_SCHEMA_VERSION.to_string(),
             created_at: now,
             modified_at: now,
         }
@@ -62,6 +68,18 @@ pub struct PlanState {
     /// All allocations for this quarter
     pub allocations: Vec<Allocation>,
 
+    /// Named what-if scenarios branched from this plan
+    #[serde(default)]
+    pub scenarios: Vec<Scenario>,
+
+    /// Scenario loaded into the working fields above (None = main plan)
+    #[serde(default)]
+    pub active_scenario_id: Option<Uuid>,
+
+    /// Main plan data, stashed while a scenario is active
+    #[serde(default)]
+    pub stashed_main: Option<PlanBranch>,
+
     /// Plan metadata (version, timestamps)
     pub metadata: PlanMetadata,
 }
@@ -76,6 +94,9 @@ impl PlanState {
             roadmap_projects: Vec::new(),
             technical_projects: Vec::new(),
             allocations: Vec::new(),
+            scenarios: Vec::new(),
+            active_scenario_id: None,
+            stashed_main: None,
             metadata: PlanMetadata::new(),
         }
     }
@@ -111,32 +132,24 @@ impl PlanState {
     }
 
     /// Calculate allocated weeks by role for a technical project
-    /// Returns (eng_allocated, sci_allocated, total_allocated)
     pub fn calculate_technical_project_allocated_by_role(
         &self,
         technical_project_id: &Uuid,
-        get_member_role: impl Fn(&Uuid) -> Option<super::Role>,
-    ) -> (f32, f32, f32) {
-        use super::Role;
-        let mut eng_allocated = 0.0;
-        let mut sci_allocated = 0.0;
+        get_member_role: impl Fn(&Uuid) -> Option<Role>,
+    ) -> RoleWeeks {
+        let mut allocated = RoleWeeks::new();
 
         for allocation in &self.allocations {
             if let Some(role) = get_member_role(&allocation.team_member_id) {
                 for assignment in &allocation.assignments {
                     if &assignment.technical_project_id == technical_project_id {
-                        let weeks = assignment.percentage / 100.0;
-                        match role {
-                            Role::Engineering => eng_allocated += weeks,
-                            Role::Science => sci_allocated += weeks,
-                        }
+                        allocated.add(&role, assignment.percentage / 100.0);
                     }
                 }
             }
         }
 
-        let total_allocated = eng_allocated + sci_allocated;
-        (eng_allocated, sci_allocated, total_allocated)
+        allocated
     }
 
     /// Get unique team member IDs assigned to a technical project
@@ -196,6 +209,66 @@ impl PlanState {
             .sum()
     }
 
+    /// Capacity for a team member this quarter, net of holidays and time off
+    pub fn calculate_member_capacity(&self, member: &TeamMember, holidays: &[Holiday]) -> f32 {
+        member.available_capacity(holidays, self.quarter_start_date, self.num_weeks)
+    }
+
+    /// Allocations that fall in weeks a member isn't on the team
+    /// (before their start date or after their end date)
+    pub fn allocations_outside_tenure<'a>(&'a self, members: &[TeamMember]) -> Vec<&'a Allocation> {
+        self.allocations
+            .iter()
+            .filter(|alloc| {
+                members
+                    .iter()
+                    .find(|m| m.id == alloc.team_member_id)
+                    .is_some_and(|m| !m.is_active_in_week(alloc.week_start_date))
+            })
+            .collect()
+    }
+
+    /// Required skills of a technical project that nobody staffed on it has
+    ///
+    /// Empty for unstaffed projects; the gap only matters once people are
+    /// assigned.
+    pub fn missing_project_skills(
+        &self,
+        technical_project_id: &Uuid,
+        members: &[TeamMember],
+    ) -> Vec<String> {
+        let Some(project) = self.get_technical_project(technical_project_id) else {
+            return Vec::new();
+        };
+        let assigned: Vec<&TeamMember> = self
+            .get_assigned_team_members(technical_project_id)
+            .iter()
+            .filter_map(|id| members.iter().find(|m| &m.id == id))
+            .collect();
+        if assigned.is_empty() {
+            return Vec::new();
+        }
+
+        project
+            .required_skills
+            .iter()
+            .filter(|skill| !assigned.iter().any(|m| m.has_skill(skill)))
+            .cloned()
+            .collect()
+    }
+
+    /// Total team capacity by role, net of holidays and time off
+    pub fn calculate_total_capacity(&self, prefs: &Preferences) -> RoleWeeks {
+        prefs
+            .team_members
+            .iter()
+            .map(|member| {
+                let capacity = self.calculate_member_capacity(member, &prefs.holidays);
+                (member.role.clone(), capacity)
+            })
+            .collect()
+    }
+
     /// Get unique project names assigned to a team member
     pub fn get_assigned_project_names_for_member(&self, team_member_id: &Uuid) -> Vec<String> {
         use std::collections::HashSet;
@@ -215,17 +288,14 @@ impl PlanState {
             .collect()
     }
 
-    /// Calculate allocated weeks for a roadmap project (sum of all linked technical projects)
-    /// Returns (eng_allocated, sci_allocated, total_allocated)
+    /// Calculate allocated weeks by role for a roadmap project (sum of all linked technical projects)
     /// Note: Requires team member data from Preferences to determine role
     pub fn calculate_roadmap_allocated_weeks(
         &self,
         roadmap_project_id: &Uuid,
-        get_member_role: impl Fn(&Uuid) -> Option<super::Role>,
-    ) -> (f32, f32, f32) {
-        use super::Role;
-        let mut eng_allocated = 0.0;
-        let mut sci_allocated = 0.0;
+        get_member_role: impl Fn(&Uuid) -> Option<Role>,
+    ) -> RoleWeeks {
+        let mut allocated = RoleWeeks::new();
 
         // Find all technical projects linked to this roadmap project
         let linked_tech_projects: Vec<&TechnicalProject> = self
@@ -240,19 +310,14 @@ impl PlanState {
                 if let Some(role) = get_member_role(&allocation.team_member_id) {
                     for assignment in &allocation.assignments {
                         if assignment.technical_project_id == tech_project.id {
-                            let weeks = assignment.percentage / 100.0;
-                            match role {
-                                Role::Engineering => eng_allocated += weeks,
-                                Role::Science => sci_allocated += weeks,
-                            }
+                            allocated.add(&role, assignment.percentage / 100.0);
                         }
                     }
                 }
             }
         }
 
-        let total_allocated = eng_allocated + sci_allocated;
-        (eng_allocated, sci_allocated, total_allocated)
+        allocated
     }
 
     /// Update a technical project's start_date and expected_completion based on its allocations
@@ -328,6 +393,9 @@ impl Default for PlanState {
             roadmap_projects: Vec::new(),
             technical_projects: Vec::new(),
             allocations: Vec::new(),
+            scenarios: Vec::new(),
+            active_scenario_id: None,
+            stashed_main: None,
             metadata: PlanMetadata::new(),
         }
     }
@@ -348,7 +416,7 @@ mod tests {
         assert_eq!(state.roadmap_projects.len(), 0);
         assert_eq!(state.technical_projects.len(), 0);
         assert_eq!(state.allocations.len(), 0);
-        assert_eq!(state.metadata.version, "1.0");
+        assert_eq!(state.metadata.version, PLAN_SCHEMA_VERSION);
     }
 
     #[test]
@@ -372,7 +440,8 @@ mod tests {
     // ===========================================
 
     use crate::models::{
-        Allocation, Assignment, ProjectColor, RoadmapProject, Role, TeamMember, TechnicalProject,
+        Allocation, Assignment, ProjectColor, RoadmapProject, Role, RoleWeeks, TeamMember,
+        TechnicalProject,
     };
 
     fn create_test_state() -> (PlanState, Uuid, Uuid, Uuid, Uuid) {
@@ -384,8 +453,7 @@ mod tests {
         state.roadmap_projects.push(RoadmapProject {
             id: roadmap_id,
             name: "Feature A".to_string(),
-            eng_estimate: 4.0,
-            sci_estimate: 2.0,
+            estimates: RoleWeeks::from([(Role::ENGINEERING, 4.0), (Role::SCIENCE, 2.0)]),
             start_date: quarter_start,
             launch_date: quarter_start + chrono::Duration::weeks(8),
             color: ProjectColor::Blue,
@@ -399,21 +467,23 @@ mod tests {
             id: tech1_id,
             name: "API Work".to_string(),
             roadmap_project_id: Some(roadmap_id),
-            eng_estimate: 3.0,
-            sci_estimate: 0.0,
+            estimates: RoleWeeks::from([(Role::ENGINEERING, 3.0)]),
             start_date: quarter_start,
             expected_completion: None,
             notes: None,
+            required_skills: Vec::new(),
+            dependencies: Vec::new(),
         });
         state.technical_projects.push(TechnicalProject {
             id: tech2_id,
             name: "ML Model".to_string(),
             roadmap_project_id: Some(roadmap_id),
-            eng_estimate: 0.0,
-            sci_estimate: 2.0,
+            estimates: RoleWeeks::from([(Role::SCIENCE, 2.0)]),
             start_date: quarter_start,
             expected_completion: None,
             notes: None,
+            required_skills: Vec::new(),
+            dependencies: Vec::new(),
         });
 
         (state, roadmap_id, tech1_id, tech2_id, Uuid::new_v4())
@@ -588,18 +658,29 @@ mod tests {
         // Create team members with specific roles
         let eng_id = Uuid::new_v4();
         let sci_id = Uuid::new_v4();
+        #[allow(clippy::useless_vec)]
         let team_members = vec![
             TeamMember {
                 id: eng_id,
                 name: "Engineer".to_string(),
-                role: Role::Engineering,
+                role: Role::ENGINEERING,
                 capacity: 10.0,
+                time_off: Vec::new(),
+                email: None,
+                start_date: None,
+                end_date: None,
+                skills: Vec::new(),
             },
             TeamMember {
                 id: sci_id,
                 name: "Scientist".to_string(),
-                role: Role::Science,
+                role: Role::SCIENCE,
                 capacity: 8.0,
+                time_off: Vec::new(),
+                email: None,
+                start_date: None,
+                end_date: None,
+                skills: Vec::new(),
             },
         ];
 
@@ -625,14 +706,89 @@ mod tests {
             team_members
                 .iter()
                 .find(|m| &m.id == member_id)
-                .map(|m| m.role)
+                .map(|m| m.role.clone())
         };
 
-        let (eng_alloc, sci_alloc, total) =
-            state.calculate_technical_project_allocated_by_role(&tech1_id, get_role);
+        let allocated = state.calculate_technical_project_allocated_by_role(&tech1_id, get_role);
 
-        assert_eq!(eng_alloc, 2.0);
-        assert_eq!(sci_alloc, 0.5);
-        assert_eq!(total, 2.5);
+        assert_eq!(allocated.get(&Role::ENGINEERING), 2.0);
+        assert_eq!(allocated.get(&Role::SCIENCE), 0.5);
+        assert_eq!(allocated.total(), 2.5);
+    }
+
+    #[test]
+    fn test_calculate_total_capacity_with_availability() {
+        let (state, _, _, _, _) = create_test_state();
+        let start = state.quarter_start_date;
+
+        let mut eng = TeamMember::new("Engineer".to_string(), Role::ENGINEERING, 12.0);
+        // One full week of vacation
+        eng.time_off.push(crate::models::TimeOff::new(
+            start,
+            start + chrono::Duration::days(4),
+            1.0,
+        ));
+        let sci = TeamMember::new("Scientist".to_string(), Role::SCIENCE, 6.0);
+
+        // Company holiday on a Monday in week 2
+        let prefs = Preferences {
+            team_members: vec![eng, sci],
+            holidays: vec![Holiday::new(
+                "Holiday".to_string(),
+                start + chrono::Duration::weeks(1),
+                start + chrono::Duration::weeks(1),
+            )],
+            ..Default::default()
+        };
+
+        let capacity = state.calculate_total_capacity(&prefs);
+        assert!((capacity.get(&Role::ENGINEERING) - 10.8).abs() < 0.001);
+        assert!((capacity.get(&Role::SCIENCE) - 5.8).abs() < 0.001);
+        assert!((capacity.total() - 16.6).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_allocations_outside_tenure() {
+        let (mut state, _, _, _, member_id) = create_test_state();
+        let week = state.quarter_start_date;
+        state.allocations.push(Allocation::new(member_id, week));
+        state.allocations.push(Allocation::new(
+            member_id,
+            week + chrono::Duration::weeks(4),
+        ));
+
+        let mut member = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        member.id = member_id;
+        assert!(state
+            .allocations_outside_tenure(&[member.clone()])
+            .is_empty());
+
+        // Starts in week 3: the week 1 allocation is now outside tenure
+        member.start_date = Some(week + chrono::Duration::weeks(2));
+        let outside = state.allocations_outside_tenure(&[member]);
+        assert_eq!(outside.len(), 1);
+        assert_eq!(outside[0].week_start_date, week);
+    }
+
+    #[test]
+    fn test_missing_project_skills() {
+        let (mut state, _, tech1_id, _, member_id) = create_test_state();
+        state
+            .get_technical_project_mut(&tech1_id)
+            .unwrap()
+            .required_skills = vec!["Kafka".to_string(), "Rust".to_string()];
+
+        let mut member = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        member.id = member_id;
+        member.skills = vec!["rust".to_string()];
+        let members = [member];
+
+        // Unstaffed projects report no gap
+        assert!(state.missing_project_skills(&tech1_id, &members).is_empty());
+
+        let mut alloc = Allocation::new(member_id, state.quarter_start_date);
+        alloc.assignments.push(Assignment::new(tech1_id, 100.0));
+        state.allocations.push(alloc);
+        assert_eq!(state.missing_project_skills(&tech1_id, &members), ["Kafka"]);
     }
 }
@@ -0,0 +1,232 @@
+use uuid::Uuid;
+
+use super::{PlanState, Preferences, Role, RoleWeeks, TeamMember};
+
+/// Read-only view of a plan together with the team it's for
+///
+/// Calculations that need both the roster and the plan (anything by role, or
+/// net of holidays) go through here, so every view and report gets the same
+/// numbers. Replaces the pre-Milestone 9 `Plan` struct; mutations still go
+/// through `PlanState` and `Preferences` directly.
+#[derive(Debug, Clone, Copy)]
+pub struct PlanView<'a> {
+    pub preferences: &'a Preferences,
+    pub state: &'a PlanState,
+}
+
+impl<'a> PlanView<'a> {
+    pub fn new(preferences: &'a Preferences, state: &'a PlanState) -> Self {
+        Self { preferences, state }
+    }
+
+    /// Get team member by ID
+    pub fn get_team_member(&self, id: &Uuid) -> Option<&'a TeamMember> {
+        self.preferences.get_team_member(id)
+    }
+
+    /// Role of a team member, if they're on the roster
+    pub fn member_role(&self, id: &Uuid) -> Option<Role> {
+        self.preferences.member_role(id)
+    }
+
+    /// Capacity for a team member this quarter, net of holidays and time off
+    pub fn member_capacity(&self, member: &TeamMember) -> f32 {
+        self.state
+            .calculate_member_capacity(member, &self.preferences.holidays)
+    }
+
+    /// Total team capacity by role, net of holidays and time off
+    pub fn total_capacity(&self) -> RoleWeeks {
+        self.state.calculate_total_capacity(self.preferences)
+    }
+
+    /// Allocated weeks by role across the whole plan
+    ///
+    /// Allocations for people no longer on the roster aren't counted.
+    pub fn total_allocated(&self) -> RoleWeeks {
+        self.state
+            .allocations
+            .iter()
+            .filter_map(|allocation| {
+                self.member_role(&allocation.team_member_id)
+                    .map(|role| (role, allocation.total_percentage() / 100.0))
+            })
+            .collect()
+    }
+
+    /// Total allocated weeks for a team member
+    pub fn team_member_allocated_weeks(&self, team_member_id: &Uuid) -> f32 {
+        self.state
+            .calculate_team_member_allocated_weeks(team_member_id)
+    }
+
+    /// Total allocated weeks for a technical project
+    pub fn project_allocated_weeks(&self, technical_project_id: &Uuid) -> f32 {
+        self.state
+            .calculate_project_allocated_weeks(technical_project_id)
+    }
+
+    /// Allocated weeks by role for a technical project
+    pub fn technical_project_allocated_by_role(&self, technical_project_id: &Uuid) -> RoleWeeks {
+        self.state
+            .calculate_technical_project_allocated_by_role(technical_project_id, |id| {
+                self.member_role(id)
+            })
+    }
+
+    /// Allocated weeks by role for a roadmap project (sum of all linked
+    /// technical projects)
+    pub fn roadmap_allocated_weeks(&self, roadmap_project_id: &Uuid) -> RoleWeeks {
+        self.state
+            .calculate_roadmap_allocated_weeks(roadmap_project_id, |id| self.member_role(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Allocation, Assignment, Holiday};
+    use chrono::NaiveDate;
+
+    fn create_test_plan() -> (Preferences, PlanState) {
+        let mut prefs = Preferences::new("Test Team".to_string());
+        prefs.team_members = vec![
+            TeamMember::new("Engineer 1".to_string(), Role::ENGINEERING, 10.0),
+            TeamMember::new("Engineer 2".to_string(), Role::ENGINEERING, 8.0),
+            TeamMember::new("Scientist 1".to_string(), Role::SCIENCE, 6.0),
+        ];
+        let state = PlanState::new(
+            "Q1 2025".to_string(),
+            NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+            13,
+        );
+        (prefs, state)
+    }
+
+    #[test]
+    fn test_calculate_total_capacity() {
+        let (mut prefs, state) = create_test_plan();
+
+        let capacity = PlanView::new(&prefs, &state).total_capacity();
+
+        assert_eq!(capacity.get(&Role::ENGINEERING), 18.0); // 10 + 8
+        assert_eq!(capacity.get(&Role::SCIENCE), 6.0);
+        assert_eq!(capacity.total(), 24.0);
+
+        // Holidays count against every member's capacity
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+        prefs
+            .holidays
+            .push(Holiday::new("MLK Day".to_string(), monday, monday));
+        let capacity = PlanView::new(&prefs, &state).total_capacity();
+        assert!(capacity.get(&Role::ENGINEERING) < 18.0);
+    }
+
+    #[test]
+    fn test_calculate_total_capacity_empty_team() {
+        let (mut prefs, state) = create_test_plan();
+        prefs.team_members.clear();
+
+        let capacity = PlanView::new(&prefs, &state).total_capacity();
+
+        assert_eq!(capacity.get(&Role::ENGINEERING), 0.0);
+        assert_eq!(capacity.get(&Role::SCIENCE), 0.0);
+        assert_eq!(capacity.total(), 0.0);
+    }
+
+    #[test]
+    fn test_calculate_total_allocated_no_allocations() {
+        let (prefs, state) = create_test_plan();
+
+        let allocated = PlanView::new(&prefs, &state).total_allocated();
+
+        assert_eq!(allocated.get(&Role::ENGINEERING), 0.0);
+        assert_eq!(allocated.get(&Role::SCIENCE), 0.0);
+        assert_eq!(allocated.total(), 0.0);
+    }
+
+    #[test]
+    fn test_calculate_total_allocated_with_allocations() {
+        let (prefs, mut state) = create_test_plan();
+        let eng1_id = prefs.team_members[0].id;
+        let sci1_id = prefs.team_members[2].id;
+        let project_id = Uuid::new_v4();
+
+        // Add allocations: 2 weeks for eng1 (100% each), 1 week for sci1 (50%)
+        let week1 = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let week2 = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+
+        let mut alloc1 = Allocation::new(eng1_id, week1);
+        alloc1.assignments.push(Assignment::new(project_id, 100.0));
+        state.allocations.push(alloc1);
+
+        let mut alloc2 = Allocation::new(eng1_id, week2);
+        alloc2.assignments.push(Assignment::new(project_id, 100.0));
+        state.allocations.push(alloc2);
+
+        let mut alloc3 = Allocation::new(sci1_id, week1);
+        alloc3.assignments.push(Assignment::new(project_id, 50.0));
+        state.allocations.push(alloc3);
+
+        let allocated = PlanView::new(&prefs, &state).total_allocated();
+
+        assert_eq!(allocated.get(&Role::ENGINEERING), 2.0); // 2 weeks at 100%
+        assert_eq!(allocated.get(&Role::SCIENCE), 0.5); // 1 week at 50%
+        assert_eq!(allocated.total(), 2.5);
+    }
+
+    #[test]
+    fn test_calculate_allocated_weeks_for_member() {
+        let (prefs, mut state) = create_test_plan();
+        let eng1_id = prefs.team_members[0].id;
+        let eng2_id = prefs.team_members[1].id;
+        let project_id = Uuid::new_v4();
+
+        // eng1 gets 3 weeks of allocation
+        for i in 0..3 {
+            let week = NaiveDate::from_ymd_opt(2025, 1, 6 + i * 7).unwrap();
+            let mut alloc = Allocation::new(eng1_id, week);
+            alloc.assignments.push(Assignment::new(project_id, 100.0));
+            state.allocations.push(alloc);
+        }
+
+        // eng2 gets 1 week at 50%
+        let week = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut alloc = Allocation::new(eng2_id, week);
+        alloc.assignments.push(Assignment::new(project_id, 50.0));
+        state.allocations.push(alloc);
+
+        let view = PlanView::new(&prefs, &state);
+        assert_eq!(view.team_member_allocated_weeks(&eng1_id), 3.0);
+        assert_eq!(view.team_member_allocated_weeks(&eng2_id), 0.5);
+    }
+
+    #[test]
+    fn test_calculate_project_allocated_weeks() {
+        let (prefs, mut state) = create_test_plan();
+        let eng1_id = prefs.team_members[0].id;
+        let project1_id = Uuid::new_v4();
+        let project2_id = Uuid::new_v4();
+
+        // Project 1: 2 full weeks from eng1
+        for i in 0..2 {
+            let week = NaiveDate::from_ymd_opt(2025, 1, 6 + i * 7).unwrap();
+            let mut alloc = Allocation::new(eng1_id, week);
+            alloc.assignments.push(Assignment::new(project1_id, 100.0));
+            state.allocations.push(alloc);
+        }
+
+        // Project 2: 1 week split allocation (50%)
+        let week = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+        let mut alloc = Allocation::new(eng1_id, week);
+        alloc.assignments.push(Assignment::new(project1_id, 50.0));
+        alloc.assignments.push(Assignment::new(project2_id, 50.0));
+        state.allocations.push(alloc);
+
+        let view = PlanView::new(&prefs, &state);
+        assert_eq!(view.project_allocated_weeks(&project1_id), 2.5); // 2 full + 0.5 split
+        assert_eq!(view.project_allocated_weeks(&project2_id), 0.5); // 0.5 from split
+        let by_role = view.technical_project_allocated_by_role(&project1_id);
+        assert_eq!(by_role.get(&Role::ENGINEERING), 2.5);
+    }
+}
@@ -1,15 +1,16 @@
 use chrono::{Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use super::TeamMember;
+use super::{default_roles, Holiday, Role, RoleDefinition, TeamMember};
 use crate::utils::get_next_quarter_info;
 
 /// Current schema version for Preferences
-pub const PREFERENCES_SCHEMA_VERSION: &str = "1.0";
+pub const PREFERENCES_SCHEMA_VERSION: &str = "1.1";
 
 /// Default schema version for deserialization (handles pre-versioned data)
 fn default_schema_version() -> String {
-    PREFERENCES_SCHEMA_VERSION.to_string()
+    "1.0".to_string()
 }
 
 /// Team preferences - persisted to localStorage
@@ -39,6 +40,15 @@ pub struct Preferences {
     /// Default capacity per team member in weeks
     /// Used when creating new team members
     pub default_capacity: f32,
+
+    /// Team-wide holidays (reduce every member's availability)
+    #[serde(default)]
+    pub holidays: Vec<Holiday>,
+
+    /// Roles team members can have (e.g., Engineering, Design, PM)
+    /// Defaults to Engineering and Science for data saved before roles were configurable
+    #[serde(default = "default_roles")]
+    pub roles: Vec<RoleDefinition>,
 }
 
 #[allow(dead_code)] // Validation used in M14
@@ -56,7 +66,51 @@ impl Preferences {
             sprint_anchor_date: quarter_start,
             sprint_length_weeks: 2,
             default_capacity: 12.0,
+            holidays: Vec::new(),
+            roles: default_roles(),
+        }
+    }
+
+    /// Get team member by ID
+    pub fn get_team_member(&self, id: &Uuid) -> Option<&TeamMember> {
+        self.team_members.iter().find(|m| &m.id == id)
+    }
+
+    /// Role of a team member, if they're on the roster
+    pub fn member_role(&self, id: &Uuid) -> Option<Role> {
+        self.get_team_member(id).map(|m| m.role.clone())
+    }
+
+    /// Look up the definition for a role
+    pub fn role_definition(&self, role: &Role) -> Option<&RoleDefinition> {
+        self.roles.iter().find(|r| &r.id == role)
+    }
+
+    /// Full display name for a role (falls back to the role key)
+    pub fn role_name<'a>(&'a self, role: &'a Role) -> &'a str {
+        self.role_definition(role)
+            .map(|r| r.name.as_str())
+            .unwrap_or(role.as_str())
+    }
+
+    /// Short badge name for a role (falls back to the role key)
+    pub fn role_short_name<'a>(&'a self, role: &'a Role) -> &'a str {
+        self.role_definition(role)
+            .map(|r| r.short_name.as_str())
+            .unwrap_or(role.as_str())
+    }
+
+    /// All skills held by team members, sorted case-insensitively
+    /// (the vocabulary offered when tagging members and projects)
+    pub fn skill_vocabulary(&self) -> Vec<String> {
+        let mut skills: Vec<String> = Vec::new();
+        for skill in self.team_members.iter().flat_map(|m| &m.skills) {
+            if !skills.iter().any(|s| s.eq_ignore_ascii_case(skill)) {
+                skills.push(skill.clone());
+            }
         }
+        skills.sort_by_key(|s| s.to_lowercase());
+        skills
     }
 
     /// Validate preferences
@@ -77,6 +131,35 @@ impl Preferences {
             ));
         }
 
+        for (i, role) in self.roles.iter().enumerate() {
+            if role.id.as_str().trim().is_empty() || role.name.trim().is_empty() {
+                return Err(PreferencesValidationError::EmptyRoleName);
+            }
+            if self.roles[..i].iter().any(|r| r.id == role.id) {
+                return Err(PreferencesValidationError::DuplicateRole(role.id.clone()));
+            }
+        }
+
+        for member in &self.team_members {
+            if self.role_definition(&member.role).is_none() {
+                return Err(PreferencesValidationError::UndefinedRole(
+                    member.role.clone(),
+                ));
+            }
+            if let (Some(start), Some(end)) = (member.start_date, member.end_date) {
+                if end < start {
+                    return Err(PreferencesValidationError::InvalidTenure(member.id));
+                }
+            }
+            if let Some(time_off) = member.time_off.iter().find(|t| t.end_date < t.start_date) {
+                return Err(PreferencesValidationError::InvalidTimeOffRange(time_off.id));
+            }
+        }
+
+        if let Some(holiday) = self.holidays.iter().find(|h| h.end_date < h.start_date) {
+            return Err(PreferencesValidationError::InvalidHolidayRange(holiday.id));
+        }
+
         Ok(())
     }
 }
@@ -94,6 +177,12 @@ pub enum PreferencesValidationError {
     EmptyTeamName,
     InvalidSprintLength(usize),
     InvalidDefaultCapacity(f32),
+    InvalidTimeOffRange(uuid::Uuid),
+    InvalidHolidayRange(uuid::Uuid),
+    InvalidTenure(uuid::Uuid),
+    EmptyRoleName,
+    DuplicateRole(Role),
+    UndefinedRole(Role),
 }
 
 #[cfg(test)]
@@ -103,7 +192,7 @@ mod tests {
     #[test]
     fn test_preferences_creation() {
         let prefs = Preferences::new("Backend Team".to_string());
-        assert_eq!(prefs.schema_version, "1.0");
+        assert_eq!(prefs.schema_version, PREFERENCES_SCHEMA_VERSION);
         assert_eq!(prefs.team_name, "Backend Team");
         assert_eq!(prefs.team_members.len(), 0);
         assert_eq!(prefs.sprint_length_weeks, 2);
@@ -113,7 +202,7 @@ mod tests {
     #[test]
     fn test_preferences_default() {
         let prefs = Preferences::default();
-        assert_eq!(prefs.schema_version, "1.0");
+        assert_eq!(prefs.schema_version, PREFERENCES_SCHEMA_VERSION);
         assert_eq!(prefs.team_name, "My Team");
         assert!(prefs.validate().is_ok());
     }
@@ -203,4 +292,96 @@ mod tests {
             Err(PreferencesValidationError::InvalidDefaultCapacity(_))
         ));
     }
+
+    #[test]
+    fn test_legacy_preferences_get_default_roles() {
+        let legacy_json = r#"{
+            "team_name": "Legacy Team",
+            "team_members": [
+                {"id": "00000000-0000-0000-0000-000000000001", "name": "Alice", "role": "sci", "capacity": 12.0}
+            ],
+            "sprint_anchor_date": "2024-01-01",
+            "sprint_length_weeks": 2,
+            "default_capacity": 12.0
+        }"#;
+
+        let prefs: Preferences = serde_json::from_str(legacy_json).unwrap();
+        assert_eq!(prefs.roles, default_roles());
+        assert_eq!(prefs.team_members[0].role, Role::SCIENCE);
+        assert_eq!(prefs.role_short_name(&Role::SCIENCE), "AS");
+        assert!(prefs.validate().is_ok());
+    }
+
+    #[test]
+    fn test_skill_vocabulary() {
+        let mut prefs = Preferences::default();
+        let mut alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        alice.skills = vec!["Rust".to_string(), "kafka".to_string()];
+        let mut bob = TeamMember::new("Bob".to_string(), Role::SCIENCE, 12.0);
+        bob.skills = vec!["Kafka".to_string(), "ML modeling".to_string()];
+        prefs.team_members = vec![alice, bob];
+
+        assert_eq!(prefs.skill_vocabulary(), ["kafka", "ML modeling", "Rust"]);
+    }
+
+    #[test]
+    fn test_validation_roles() {
+        use crate::models::Role;
+
+        let design = Role::new("design");
+        let mut prefs = Preferences {
+            team_members: vec![TeamMember::new("Dana".to_string(), design.clone(), 12.0)],
+            ..Default::default()
+        };
+        assert_eq!(
+            prefs.validate(),
+            Err(PreferencesValidationError::UndefinedRole(design.clone()))
+        );
+
+        prefs
+            .roles
+            .push(RoleDefinition::new(design.clone(), "Design", "UX"));
+        assert!(prefs.validate().is_ok());
+        assert_eq!(prefs.role_name(&design), "Design");
+
+        prefs
+            .roles
+            .push(RoleDefinition::new(design.clone(), "Design 2", "UX2"));
+        assert_eq!(
+            prefs.validate(),
+            Err(PreferencesValidationError::DuplicateRole(design))
+        );
+    }
+
+    #[test]
+    fn test_validation_invalid_availability_ranges() {
+        use crate::models::{Holiday, Role, TimeOff};
+
+        let start = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let before_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+
+        let mut member = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        let time_off = TimeOff::new(start, before_start, 1.0);
+        let time_off_id = time_off.id;
+        member.time_off.push(time_off);
+        let prefs = Preferences {
+            team_members: vec![member],
+            ..Default::default()
+        };
+        assert_eq!(
+            prefs.validate(),
+            Err(PreferencesValidationError::InvalidTimeOffRange(time_off_id))
+        );
+
+        let holiday = Holiday::new("Closure".to_string(), start, before_start);
+        let holiday_id = holiday.id;
+        let prefs = Preferences {
+            holidays: vec![holiday],
+            ..Default::default()
+        };
+        assert_eq!(
+            prefs.validate(),
+            Err(PreferencesValidationError::InvalidHolidayRange(holiday_id))
+        );
+    }
 }
@@ -0,0 +1,178 @@
+//! Team roles and per-role week totals
+//!
+//! Roles are user-defined in `Preferences`. A `Role` is the stable key stored
+//! on team members and estimates; its display names live in a
+//! `RoleDefinition`. The original two roles keep their legacy keys (`eng`,
+//! `sci`) so existing data maps onto them without conversion.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Key identifying a role (e.g., "eng", "design")
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Role(Cow<'static, str>);
+
+impl Role {
+    /// Software engineering (legacy `eng` role)
+    pub const ENGINEERING: Role = Role(Cow::Borrowed("eng"));
+    /// Applied science (legacy `sci` role)
+    pub const SCIENCE: Role = Role(Cow::Borrowed("sci"));
+
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(Cow::Owned(key.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Display configuration for a role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoleDefinition {
+    pub id: Role,
+    /// Full name (e.g., "Engineering")
+    pub name: String,
+    /// Short name for badges (e.g., "SDE")
+    pub short_name: String,
+}
+
+impl RoleDefinition {
+    pub fn new(id: Role, name: impl Into<String>, short_name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            short_name: short_name.into(),
+        }
+    }
+}
+
+/// Roles used when none are configured (and for data saved before roles
+/// were configurable)
+pub fn default_roles() -> Vec<RoleDefinition> {
+    vec![
+        RoleDefinition::new(Role::ENGINEERING, "Engineering", "SDE"),
+        RoleDefinition::new(Role::SCIENCE, "Science", "AS"),
+    ]
+}
+
+/// Weeks per role (estimates, allocations, or capacity)
+///
+/// Roles without an entry count as zero.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RoleWeeks(BTreeMap<Role, f32>);
+
+impl RoleWeeks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Weeks for a role (0.0 if not present)
+    pub fn get(&self, role: &Role) -> f32 {
+        self.0.get(role).copied().unwrap_or(0.0)
+    }
+
+    /// Set the weeks for a role (zero removes the entry)
+    pub fn set(&mut self, role: Role, weeks: f32) {
+        if weeks == 0.0 {
+            self.0.remove(&role);
+        } else {
+            self.0.insert(role, weeks);
+        }
+    }
+
+    /// Add weeks to a role's total
+    pub fn add(&mut self, role: &Role, weeks: f32) {
+        let total = self.get(role) + weeks;
+        self.set(role.clone(), total);
+    }
+
+    /// Sum across all roles; 0.0 when there are none
+    pub fn total(&self) -> f32 {
+        self.0.values().fold(0.0, |sum, weeks| sum + weeks)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Roles with a non-zero entry, in key order
+    pub fn iter(&self) -> impl Iterator<Item = (&Role, f32)> {
+        self.0.iter().map(|(role, weeks)| (role, *weeks))
+    }
+}
+
+impl FromIterator<(Role, f32)> for RoleWeeks {
+    fn from_iter<I: IntoIterator<Item = (Role, f32)>>(iter: I) -> Self {
+        let mut weeks = RoleWeeks::new();
+        for (role, value) in iter {
+            weeks.add(&role, value);
+        }
+        weeks
+    }
+}
+
+impl<const N: usize> From<[(Role, f32); N]> for RoleWeeks {
+    fn from(entries: [(Role, f32); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+/// Build estimates from legacy `eng_estimate`/`sci_estimate` fields
+pub(crate) fn legacy_estimates(eng: Option<f32>, sci: Option<f32>) -> RoleWeeks {
+    RoleWeeks::from([
+        (Role::ENGINEERING, eng.unwrap_or(0.0)),
+        (Role::SCIENCE, sci.unwrap_or(0.0)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_serializes_as_key() {
+        let design = Role::new("design");
+        assert_eq!(serde_json::to_string(&design).unwrap(), "\"design\"");
+        assert_eq!(
+            serde_json::to_string(&Role::ENGINEERING).unwrap(),
+            "\"eng\""
+        );
+
+        let loaded: Role = serde_json::from_str("\"sci\"").unwrap();
+        assert_eq!(loaded, Role::SCIENCE);
+    }
+
+    #[test]
+    fn test_role_weeks_totals() {
+        let design = Role::new("design");
+        let mut weeks = RoleWeeks::from([(Role::ENGINEERING, 4.0), (design.clone(), 2.0)]);
+        weeks.add(&Role::ENGINEERING, 1.0);
+
+        assert_eq!(weeks.get(&Role::ENGINEERING), 5.0);
+        assert_eq!(weeks.get(&design), 2.0);
+        assert_eq!(weeks.get(&Role::SCIENCE), 0.0);
+        assert_eq!(weeks.total(), 7.0);
+
+        // Zero entries are dropped
+        weeks.set(design, 0.0);
+        assert_eq!(weeks.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_role_weeks_serializes_as_map() {
+        let weeks = RoleWeeks::from([(Role::ENGINEERING, 3.0), (Role::SCIENCE, 0.0)]);
+        assert_eq!(serde_json::to_string(&weeks).unwrap(), r#"{"eng":3.0}"#);
+    }
+}
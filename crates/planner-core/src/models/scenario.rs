@@ -0,0 +1,356 @@
+//! Named what-if scenarios within a single plan
+//!
+//! A scenario is a branch of the plan's projects and allocations; the team
+//! roster in `Preferences` is shared by all branches. The branch being edited
+//! always lives in `PlanState`'s working fields, so every view and mutation
+//! works unchanged. Switching swaps branches in and out of those fields,
+//! stashing the main plan while a scenario is active.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Allocation, PlanState, RoadmapProject, TechnicalProject};
+
+/// The per-branch part of a plan
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlanBranch {
+    pub roadmap_projects: Vec<RoadmapProject>,
+    pub technical_projects: Vec<TechnicalProject>,
+    pub allocations: Vec<Allocation>,
+}
+
+/// A named what-if branch of the plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+
+    /// Branch data as of the last switch away from this scenario
+    /// (stale while the scenario is active; see `PlanState::scenario_branch`)
+    #[serde(flatten)]
+    pub branch: PlanBranch,
+}
+
+impl Scenario {
+    pub fn new(name: String, branch: PlanBranch) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            created_at: Utc::now(),
+            branch,
+        }
+    }
+}
+
+impl PlanState {
+    /// Copy of the branch currently held in the working fields
+    pub fn working_branch(&self) -> PlanBranch {
+        PlanBranch {
+            roadmap_projects: self.roadmap_projects.clone(),
+            technical_projects: self.technical_projects.clone(),
+            allocations: self.allocations.clone(),
+        }
+    }
+
+    fn load_branch(&mut self, branch: PlanBranch) {
+        self.roadmap_projects = branch.roadmap_projects;
+        self.technical_projects = branch.technical_projects;
+        self.allocations = branch.allocations;
+    }
+
+    /// Get scenario by ID
+    pub fn get_scenario(&self, id: &Uuid) -> Option<&Scenario> {
+        self.scenarios.iter().find(|s| &s.id == id)
+    }
+
+    /// Name of the active scenario, or None when editing the main plan
+    pub fn active_scenario_name(&self) -> Option<&str> {
+        self.active_scenario_id
+            .and_then(|id| self.get_scenario(&id))
+            .map(|s| s.name.as_str())
+    }
+
+    /// Up-to-date data for the main plan
+    pub fn main_branch(&self) -> PlanBranch {
+        match &self.stashed_main {
+            Some(branch) if self.active_scenario_id.is_some() => branch.clone(),
+            _ => self.working_branch(),
+        }
+    }
+
+    /// Up-to-date data for a scenario, including unsaved edits if it's active
+    pub fn scenario_branch(&self, id: &Uuid) -> Option<PlanBranch> {
+        if self.active_scenario_id == Some(*id) {
+            return Some(self.working_branch());
+        }
+        self.get_scenario(id).map(|s| s.branch.clone())
+    }
+
+    /// Create a scenario branched from the working data and return its ID
+    ///
+    /// The new scenario isn't activated; call `switch_scenario` to edit it.
+    pub fn create_scenario(&mut self, name: &str) -> Uuid {
+        let scenario = Scenario::new(name.trim().to_string(), self.working_branch());
+        let id = scenario.id;
+        self.scenarios.push(scenario);
+        self.mark_modified();
+        id
+    }
+
+    /// Load a scenario (or the main plan, for None) into the working fields
+    ///
+    /// The branch being left is saved first, so no edits are lost. Returns
+    /// false if the scenario doesn't exist.
+    pub fn switch_scenario(&mut self, target: Option<Uuid>) -> bool {
+        if target == self.active_scenario_id {
+            return true;
+        }
+        if let Some(id) = target {
+            if self.get_scenario(&id).is_none() {
+                return false;
+            }
+        }
+
+        // Save the branch being left
+        let current = self.working_branch();
+        match self.active_scenario_id {
+            Some(active_id) => {
+                if let Some(scenario) = self.scenarios.iter_mut().find(|s| s.id == active_id) {
+                    scenario.branch = current;
+                }
+            }
+            None => self.stashed_main = Some(current),
+        }
+
+        // Load the target branch
+        let next = match target {
+            Some(id) => self
+                .get_scenario(&id)
+                .map(|s| s.branch.clone())
+                .unwrap_or_default(),
+            None => self.stashed_main.take().unwrap_or_default(),
+        };
+        self.load_branch(next);
+        self.active_scenario_id = target;
+        self.mark_modified();
+        true
+    }
+
+    /// Rename a scenario; returns false if it doesn't exist
+    pub fn rename_scenario(&mut self, id: &Uuid, name: &str) -> bool {
+        let Some(scenario) = self.scenarios.iter_mut().find(|s| &s.id == id) else {
+            return false;
+        };
+        scenario.name = name.trim().to_string();
+        self.mark_modified();
+        true
+    }
+
+    /// Delete a scenario, switching back to the main plan if it was active
+    pub fn delete_scenario(&mut self, id: &Uuid) -> bool {
+        if self.get_scenario(id).is_none() {
+            return false;
+        }
+        if self.active_scenario_id == Some(*id) {
+            self.switch_scenario(None);
+        }
+        self.scenarios.retain(|s| &s.id != id);
+        self.mark_modified();
+        true
+    }
+
+    /// Replace the main plan with a scenario's data
+    ///
+    /// The promoted scenario is removed and the main plan becomes active;
+    /// other scenarios are kept.
+    pub fn promote_scenario(&mut self, id: &Uuid) -> bool {
+        let Some(branch) = self.scenario_branch(id) else {
+            return false;
+        };
+        self.switch_scenario(None);
+        self.load_branch(branch);
+        self.scenarios.retain(|s| &s.id != id);
+        self.mark_modified();
+        true
+    }
+
+    /// Drop a team member's allocations from every branch
+    ///
+    /// Team members are shared by the main plan and all scenarios, so removing
+    /// one from the roster has to clean up the working branch, each saved
+    /// scenario and the stashed main plan alike.
+    pub fn remove_member_everywhere(&mut self, team_member_id: &Uuid) {
+        let keep = |a: &Allocation| &a.team_member_id != team_member_id;
+        self.allocations.retain(keep);
+        for scenario in &mut self.scenarios {
+            scenario.branch.allocations.retain(keep);
+        }
+        if let Some(main) = &mut self.stashed_main {
+            main.allocations.retain(keep);
+        }
+        self.mark_modified();
+    }
+
+    /// Standalone copy of one branch (None = main plan), without scenarios
+    ///
+    /// For treating a scenario as a plan of its own, e.g., to compare or
+    /// render it. Returns None if the scenario doesn't exist.
+    pub fn branch_as_plan(&self, id: Option<Uuid>) -> Option<PlanState> {
+        let mut plan = self.clone();
+        if !plan.switch_scenario(id) {
+            return None;
+        }
+        plan.scenarios.clear();
+        plan.active_scenario_id = None;
+        plan.stashed_main = None;
+        Some(plan)
+    }
+
+    /// This plan with the main plan loaded and every scenario up to date
+    ///
+    /// Used for exports, so the top-level data is always the main plan.
+    pub fn with_main_plan_active(mut self) -> Self {
+        self.switch_scenario(None);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, Role, RoleWeeks};
+    use chrono::NaiveDate;
+
+    fn sample_state() -> PlanState {
+        let quarter_start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start, 13);
+        state.technical_projects.push(TechnicalProject::new(
+            "Payment Gateway".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start,
+        ));
+        state
+    }
+
+    fn allocate(state: &mut PlanState, week: usize) {
+        let project_id = state.technical_projects[0].id;
+        let week_start = state.quarter_start_date + chrono::Duration::weeks(week as i64);
+        let mut alloc = Allocation::new(Uuid::nil(), week_start);
+        alloc.assignments.push(Assignment::new(project_id, 100.0));
+        state.allocations.push(alloc);
+    }
+
+    #[test]
+    fn test_scenario_edits_stay_on_their_branch() {
+        let mut state = sample_state();
+        allocate(&mut state, 0);
+
+        let id = state.create_scenario(" Gateway first ");
+        assert_eq!(state.get_scenario(&id).unwrap().name, "Gateway first");
+        assert!(state.switch_scenario(Some(id)));
+        assert_eq!(state.active_scenario_name(), Some("Gateway first"));
+
+        // Edits while the scenario is active don't touch the main plan
+        allocate(&mut state, 1);
+        assert_eq!(state.allocations.len(), 2);
+        assert_eq!(state.main_branch().allocations.len(), 1);
+        assert_eq!(state.scenario_branch(&id).unwrap().allocations.len(), 2);
+
+        assert!(state.switch_scenario(None));
+        assert_eq!(state.active_scenario_id, None);
+        assert_eq!(state.stashed_main, None);
+        assert_eq!(state.allocations.len(), 1);
+        assert_eq!(state.get_scenario(&id).unwrap().branch.allocations.len(), 2);
+
+        assert!(!state.switch_scenario(Some(Uuid::new_v4())));
+    }
+
+    #[test]
+    fn test_remove_member_everywhere() {
+        let mut state = sample_state();
+        allocate(&mut state, 0);
+        let id = state.create_scenario("A");
+        state.switch_scenario(Some(id));
+        allocate(&mut state, 1);
+        let other = state.create_scenario("B");
+
+        state.remove_member_everywhere(&Uuid::nil());
+        assert!(state.allocations.is_empty());
+        assert!(state.stashed_main.as_ref().unwrap().allocations.is_empty());
+        assert!(state
+            .get_scenario(&other)
+            .unwrap()
+            .branch
+            .allocations
+            .is_empty());
+        state.switch_scenario(None);
+        assert!(state.allocations.is_empty());
+        assert!(state.scenario_branch(&id).unwrap().allocations.is_empty());
+    }
+
+    #[test]
+    fn test_switch_between_scenarios() {
+        let mut state = sample_state();
+        let a = state.create_scenario("A");
+        let b = state.create_scenario("B");
+
+        state.switch_scenario(Some(a));
+        allocate(&mut state, 0);
+        state.switch_scenario(Some(b));
+        assert!(state.allocations.is_empty());
+        allocate(&mut state, 1);
+        allocate(&mut state, 2);
+
+        state.switch_scenario(Some(a));
+        assert_eq!(state.allocations.len(), 1);
+        assert_eq!(state.scenario_branch(&b).unwrap().allocations.len(), 2);
+        assert!(state.main_branch().allocations.is_empty());
+    }
+
+    #[test]
+    fn test_delete_and_promote_scenario() {
+        let mut state = sample_state();
+        let keep = state.create_scenario("Keep");
+        let promoted = state.create_scenario("Promote");
+
+        state.switch_scenario(Some(promoted));
+        allocate(&mut state, 3);
+        state.switch_scenario(Some(keep));
+        assert!(state.promote_scenario(&promoted));
+        assert_eq!(state.active_scenario_id, None);
+        assert_eq!(state.allocations.len(), 1);
+        assert_eq!(state.scenarios.len(), 1);
+
+        // Deleting the active scenario falls back to the main plan
+        state.switch_scenario(Some(keep));
+        assert!(state.delete_scenario(&keep));
+        assert_eq!(state.active_scenario_id, None);
+        assert_eq!(state.allocations.len(), 1);
+        assert!(state.scenarios.is_empty());
+        assert!(!state.rename_scenario(&keep, "Gone"));
+    }
+
+    #[test]
+    fn test_with_main_plan_active() {
+        let mut state = sample_state();
+        let id = state.create_scenario("Pipeline first");
+        state.switch_scenario(Some(id));
+        allocate(&mut state, 0);
+
+        let standalone = state.branch_as_plan(None).unwrap();
+        assert!(standalone.allocations.is_empty());
+        assert!(standalone.scenarios.is_empty());
+        let standalone = state.branch_as_plan(Some(id)).unwrap();
+        assert_eq!(standalone.allocations.len(), 1);
+        assert_eq!(standalone.active_scenario_id, None);
+        assert!(state.branch_as_plan(Some(Uuid::new_v4())).is_none());
+
+        let exported = state.with_main_plan_active();
+        assert_eq!(exported.active_scenario_id, None);
+        assert!(exported.allocations.is_empty());
+        assert_eq!(exported.scenarios[0].branch.allocations.len(), 1);
+    }
+}
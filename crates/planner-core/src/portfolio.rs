@@ -0,0 +1,385 @@
+//! Multi-team portfolio: combined capacity across several team plans
+//!
+//! Each team's export carries its own roster snapshot, so a portfolio is built
+//! from exports alone. Numbers come from the same `calculate_*` methods each
+//! team's plan uses, so a team's row matches what its own plan shows.
+//!
+//! Roadmap projects are matched across teams by ID, falling back to a
+//! case-insensitive name match for teams that entered the same initiative
+//! independently.
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::{get_capacity_status, BadgeType, PlanExport, PlanView, Role};
+
+/// Capacity and allocation for one role
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleLoad {
+    pub role: Role,
+    /// Display name from the first team that defines the role
+    pub name: String,
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+}
+
+impl RoleLoad {
+    /// Allocated share of capacity, as a percentage
+    pub fn utilization_pct(&self) -> f32 {
+        percent(self.allocated, self.capacity)
+    }
+}
+
+/// One team's numbers for the quarter
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamSummary {
+    pub team_name: String,
+    pub quarter_name: String,
+    /// False if the team planned a different quarter than the portfolio
+    pub same_quarter: bool,
+    pub member_count: usize,
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+    pub roles: Vec<RoleLoad>,
+}
+
+impl TeamSummary {
+    /// Allocated share of capacity, as a percentage
+    pub fn utilization_pct(&self) -> f32 {
+        percent(self.allocated, self.capacity)
+    }
+}
+
+/// One team's part of a roadmap project
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamContribution {
+    pub team_name: String,
+    pub estimate: f32,
+    pub allocated: f32,
+    pub launch_date: NaiveDate,
+    pub status: BadgeType,
+}
+
+/// A roadmap project and every team working on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioProject {
+    /// ID in the first team that has the project
+    pub id: Uuid,
+    pub name: String,
+    pub teams: Vec<TeamContribution>,
+}
+
+impl PortfolioProject {
+    /// True if more than one team has the project
+    pub fn is_shared(&self) -> bool {
+        self.teams.len() > 1
+    }
+
+    pub fn estimate(&self) -> f32 {
+        self.teams.iter().map(|t| t.estimate).sum()
+    }
+
+    pub fn allocated(&self) -> f32 {
+        self.teams.iter().map(|t| t.allocated).sum()
+    }
+
+    pub fn status(&self) -> BadgeType {
+        get_capacity_status(self.allocated(), self.estimate())
+    }
+
+    /// Earliest launch date across teams
+    pub fn launch_date(&self) -> Option<NaiveDate> {
+        self.teams.iter().map(|t| t.launch_date).min()
+    }
+}
+
+/// Combined view of several teams' plans
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portfolio {
+    /// Quarter of the first team; other teams are checked against it
+    pub quarter_name: String,
+    pub quarter_start_date: NaiveDate,
+    pub num_weeks: usize,
+    pub teams: Vec<TeamSummary>,
+    /// Combined load per role, across all teams
+    pub roles: Vec<RoleLoad>,
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+    pub roadmap_projects: Vec<PortfolioProject>,
+}
+
+impl Portfolio {
+    /// Allocated share of combined capacity, as a percentage
+    pub fn utilization_pct(&self) -> f32 {
+        percent(self.allocated, self.capacity)
+    }
+
+    /// Roadmap projects more than one team is working on
+    pub fn shared_projects(&self) -> impl Iterator<Item = &PortfolioProject> {
+        self.roadmap_projects.iter().filter(|p| p.is_shared())
+    }
+
+    /// Teams planning a different quarter than the portfolio
+    pub fn mismatched_teams(&self) -> impl Iterator<Item = &TeamSummary> {
+        self.teams.iter().filter(|t| !t.same_quarter)
+    }
+}
+
+/// Aggregate team exports into a portfolio; None if there are no teams
+///
+/// Each export contributes its main plan. Teams and roles keep the order they
+/// first appear in.
+pub fn build_portfolio(exports: &[PlanExport]) -> Option<Portfolio> {
+    let first = exports.first()?;
+    let mut teams = Vec::new();
+    let mut roles: Vec<RoleLoad> = Vec::new();
+    let mut roadmap_projects: Vec<PortfolioProject> = Vec::new();
+    // IDs each portfolio project is known by across teams
+    let mut project_ids: Vec<Vec<Uuid>> = Vec::new();
+
+    for export in exports {
+        let (prefs, state) = export.clone().into_signals();
+        let view = PlanView::new(&prefs, &state);
+
+        // Per-role load, in the order roles first appear on the roster
+        let mut team_roles: Vec<RoleLoad> = Vec::new();
+        for member in &prefs.team_members {
+            let capacity = view.member_capacity(member);
+            let allocated = view.team_member_allocated_weeks(&member.id);
+            let index = match team_roles.iter().position(|r| r.role == member.role) {
+                Some(index) => index,
+                None => {
+                    team_roles.push(RoleLoad {
+                        role: member.role.clone(),
+                        name: prefs.role_name(&member.role).to_string(),
+                        capacity: 0.0,
+                        allocated: 0.0,
+                        status: BadgeType::Neutral,
+                    });
+                    team_roles.len() - 1
+                }
+            };
+            team_roles[index].capacity += capacity;
+            team_roles[index].allocated += allocated;
+        }
+        for load in &mut team_roles {
+            load.status = get_capacity_status(load.allocated, load.capacity);
+            match roles.iter_mut().find(|r| r.role == load.role) {
+                Some(total) => {
+                    total.capacity += load.capacity;
+                    total.allocated += load.allocated;
+                }
+                None => roles.push(load.clone()),
+            }
+        }
+
+        let capacity = team_roles.iter().map(|r| r.capacity).sum();
+        let allocated = team_roles.iter().map(|r| r.allocated).sum();
+        teams.push(TeamSummary {
+            team_name: export.team_name.clone(),
+            quarter_name: export.quarter_name.clone(),
+            same_quarter: export.quarter_start_date == first.quarter_start_date
+                && export.num_weeks == first.num_weeks,
+            member_count: prefs.team_members.len(),
+            capacity,
+            allocated,
+            status: get_capacity_status(allocated, capacity),
+            roles: team_roles,
+        });
+
+        // Roadmap projects, matched by ID and then by name
+        for project in &state.roadmap_projects {
+            let estimate = project.total_estimate();
+            let allocated = view.roadmap_allocated_weeks(&project.id).total();
+            let contribution = TeamContribution {
+                team_name: export.team_name.clone(),
+                estimate,
+                allocated,
+                launch_date: project.launch_date,
+                status: get_capacity_status(allocated, estimate),
+            };
+
+            let index = project_ids
+                .iter()
+                .position(|ids| ids.contains(&project.id))
+                .or_else(|| {
+                    roadmap_projects
+                        .iter()
+                        .position(|p| p.name.eq_ignore_ascii_case(&project.name))
+                });
+            match index {
+                Some(index) => {
+                    roadmap_projects[index].teams.push(contribution);
+                    project_ids[index].push(project.id);
+                }
+                None => {
+                    roadmap_projects.push(PortfolioProject {
+                        id: project.id,
+                        name: project.name.clone(),
+                        teams: vec![contribution],
+                    });
+                    project_ids.push(vec![project.id]);
+                }
+            }
+        }
+    }
+
+    for load in &mut roles {
+        load.status = get_capacity_status(load.allocated, load.capacity);
+    }
+    let capacity = teams.iter().map(|t| t.capacity).sum();
+    let allocated = teams.iter().map(|t| t.allocated).sum();
+
+    Some(Portfolio {
+        quarter_name: first.quarter_name.clone(),
+        quarter_start_date: first.quarter_start_date,
+        num_weeks: first.num_weeks,
+        teams,
+        roles,
+        capacity,
+        allocated,
+        status: get_capacity_status(allocated, capacity),
+        roadmap_projects,
+    })
+}
+
+fn percent(allocated: f32, capacity: f32) -> f32 {
+    if capacity > 0.0 {
+        allocated / capacity * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, PlanState, Preferences, ProjectColor, RoadmapProject, RoleWeeks,
+        TeamMember, TechnicalProject,
+    };
+    use chrono::Duration;
+
+    fn quarter_start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    /// A team with one member per role and one roadmap project, allocated for `weeks`
+    fn team(name: &str, roles: &[Role], project: &str, weeks: i64) -> PlanExport {
+        let prefs = Preferences {
+            team_name: name.to_string(),
+            team_members: roles
+                .iter()
+                .enumerate()
+                .map(|(i, role)| TeamMember::new(format!("{} {}", name, i), role.clone(), 12.0))
+                .collect(),
+            ..Preferences::default()
+        };
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start(), 13);
+        let roadmap = RoadmapProject::new(
+            project.to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start(),
+            quarter_start() + Duration::weeks(8),
+            ProjectColor::Blue,
+        );
+        let technical = TechnicalProject::new(
+            format!("{} work", project),
+            Some(roadmap.id),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start(),
+        );
+        for week in 0..weeks {
+            let mut alloc = Allocation::new(
+                prefs.team_members[0].id,
+                quarter_start() + Duration::weeks(week),
+            );
+            alloc.assignments.push(Assignment::new(technical.id, 100.0));
+            state.allocations.push(alloc);
+        }
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects.push(technical);
+        PlanExport::from_signals(prefs, state)
+    }
+
+    #[test]
+    fn test_combined_capacity_by_team_and_role() {
+        let payments = team(
+            "Payments",
+            &[Role::ENGINEERING, Role::SCIENCE],
+            "Checkout",
+            3,
+        );
+        let search = team("Search", &[Role::ENGINEERING], "Ranking", 6);
+
+        let portfolio = build_portfolio(&[payments, search]).unwrap();
+        assert_eq!(portfolio.quarter_name, "Q1 2025");
+        assert_eq!(portfolio.teams.len(), 2);
+        assert_eq!(portfolio.capacity, 36.0);
+        assert_eq!(portfolio.allocated, 9.0);
+        assert_eq!(portfolio.utilization_pct(), 25.0);
+
+        let payments = &portfolio.teams[0];
+        assert_eq!(payments.member_count, 2);
+        assert_eq!(payments.capacity, 24.0);
+        assert_eq!(payments.roles.len(), 2);
+        assert_eq!(payments.roles[1].allocated, 0.0);
+
+        let engineering = &portfolio.roles[0];
+        assert_eq!(engineering.role, Role::ENGINEERING);
+        assert_eq!(engineering.name, "Engineering");
+        assert_eq!(engineering.capacity, 24.0);
+        assert_eq!(engineering.allocated, 9.0);
+        assert_eq!(portfolio.roles[1].capacity, 12.0);
+
+        assert!(build_portfolio(&[]).is_none());
+    }
+
+    #[test]
+    fn test_shared_projects_match_by_id_or_name() {
+        let payments = team("Payments", &[Role::ENGINEERING], "Checkout", 2);
+        let mut fraud = team("Fraud", &[Role::ENGINEERING], "Checkout", 1);
+        // Same project carried over from the payments plan keeps its ID
+        fraud.roadmap_projects[0].name = "Checkout v2".to_string();
+        fraud.roadmap_projects[0].id = payments.roadmap_projects[0].id;
+        fraud.technical_projects[0].roadmap_project_id = Some(payments.roadmap_projects[0].id);
+        // Entered independently, matched by name
+        let ledger = team("Ledger", &[Role::ENGINEERING], "CHECKOUT", 4);
+        let search = team("Search", &[Role::ENGINEERING], "Ranking", 0);
+
+        let portfolio = build_portfolio(&[payments, fraud, ledger, search]).unwrap();
+        assert_eq!(portfolio.roadmap_projects.len(), 2);
+
+        let shared: Vec<_> = portfolio.shared_projects().collect();
+        assert_eq!(shared.len(), 1);
+        let checkout = shared[0];
+        assert_eq!(checkout.name, "Checkout");
+        let teams: Vec<_> = checkout
+            .teams
+            .iter()
+            .map(|t| t.team_name.as_str())
+            .collect();
+        assert_eq!(teams, ["Payments", "Fraud", "Ledger"]);
+        assert_eq!(checkout.estimate(), 12.0);
+        assert_eq!(checkout.allocated(), 7.0);
+        assert_eq!(checkout.status(), BadgeType::Error);
+    }
+
+    #[test]
+    fn test_teams_from_another_quarter_are_flagged() {
+        let current = team("Payments", &[Role::ENGINEERING], "Checkout", 1);
+        let mut stale = team("Search", &[Role::ENGINEERING], "Ranking", 1);
+        stale.quarter_name = "Q4 2024".to_string();
+        stale.quarter_start_date = quarter_start() - Duration::weeks(13);
+
+        let portfolio = build_portfolio(&[current, stale]).unwrap();
+        let mismatched: Vec<_> = portfolio
+            .mismatched_teams()
+            .map(|t| t.team_name.as_str())
+            .collect();
+        assert_eq!(mismatched, ["Search"]);
+    }
+}
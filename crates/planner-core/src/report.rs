@@ -0,0 +1,426 @@
+//! Quarterly plan report: the numbers behind a written planning doc
+//!
+//! Summarizes one plan the way the views show it — roster and capacity,
+//! roadmap projects against their estimates, technical project dates, who
+//! works on what, and who is over- or under-allocated. Rendering lives in
+//! `formats::markdown` and `formats::html`; both take a `PlanReport` so the
+//! two documents always agree.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use uuid::Uuid;
+
+use crate::models::{
+    get_capacity_status, BadgeType, PlanIndex, PlanState, PlanView, Preferences, ProjectColor,
+};
+
+/// A team member's line in the roster
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberReport {
+    pub id: Uuid,
+    pub name: String,
+    pub role_name: String,
+    /// Weeks available this quarter, net of holidays and time off
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+    /// Weeks per technical project, in first-assigned order
+    pub assignments: Vec<(String, f32)>,
+}
+
+impl MemberReport {
+    /// Allocated share of capacity, as a percentage
+    pub fn utilization_pct(&self) -> f32 {
+        percent(self.allocated, self.capacity)
+    }
+}
+
+/// A roadmap project against its estimate
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadmapReport {
+    pub name: String,
+    pub color: ProjectColor,
+    pub launch_date: NaiveDate,
+    pub estimate: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+}
+
+/// A technical project and when it's scheduled
+#[derive(Debug, Clone, PartialEq)]
+pub struct TechnicalReport {
+    pub name: String,
+    /// Linked roadmap project, if any
+    pub roadmap_name: Option<String>,
+    pub estimate: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+    /// First day of the first allocated week to last day of the last one
+    pub dates: Option<(NaiveDate, NaiveDate)>,
+}
+
+/// Which way a team member's load is off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utilization {
+    Over,
+    Under,
+}
+
+/// A team member whose allocation is off their capacity
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtilizationCallout {
+    pub member_name: String,
+    pub utilization: Utilization,
+    pub capacity: f32,
+    pub allocated: f32,
+    /// Warning when moderately off, Error when far off
+    pub status: BadgeType,
+}
+
+impl UtilizationCallout {
+    /// One-line description, e.g. "Alice Kim is over-allocated: 14.0 of 12.0 weeks"
+    pub fn message(&self) -> String {
+        let direction = match self.utilization {
+            Utilization::Over => "over",
+            Utilization::Under => "under",
+        };
+        format!(
+            "{} is {}-allocated: {:.1} of {:.1} weeks",
+            self.member_name, direction, self.allocated, self.capacity
+        )
+    }
+}
+
+/// Everything in a quarterly plan report
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanReport {
+    pub team_name: String,
+    pub quarter_name: String,
+    pub quarter_start: NaiveDate,
+    /// Last day of the quarter's last week
+    pub quarter_end: NaiveDate,
+    pub num_weeks: usize,
+    pub capacity: f32,
+    pub allocated: f32,
+    pub status: BadgeType,
+    pub members: Vec<MemberReport>,
+    pub roadmap_projects: Vec<RoadmapReport>,
+    pub technical_projects: Vec<TechnicalReport>,
+    /// Over-allocated members first, then under-allocated, each in roster order
+    pub callouts: Vec<UtilizationCallout>,
+}
+
+impl PlanReport {
+    /// Build a report for the plan (or active scenario) in `state`
+    pub fn new(prefs: &Preferences, state: &PlanState) -> Self {
+        let view = PlanView::new(prefs, state);
+        let index = PlanIndex::new(state.clone(), &prefs.team_members);
+
+        let members: Vec<MemberReport> = prefs
+            .team_members
+            .iter()
+            .map(|member| {
+                let capacity = view.member_capacity(member);
+                let allocated = index.team_member_allocated_weeks(&member.id);
+                let mut assignments: Vec<(String, f32)> = Vec::new();
+                for allocation in state
+                    .allocations
+                    .iter()
+                    .filter(|a| a.team_member_id == member.id)
+                {
+                    for assignment in &allocation.assignments {
+                        let name = index
+                            .technical_project(&assignment.technical_project_id)
+                            .map_or("Unknown project", |p| p.name.as_str());
+                        let weeks = assignment.percentage / 100.0;
+                        match assignments.iter_mut().find(|(n, _)| n == name) {
+                            Some((_, total)) => *total += weeks,
+                            None => assignments.push((name.to_string(), weeks)),
+                        }
+                    }
+                }
+                MemberReport {
+                    id: member.id,
+                    name: member.name.clone(),
+                    role_name: prefs.role_name(&member.role).to_string(),
+                    capacity,
+                    allocated,
+                    status: get_capacity_status(allocated, capacity),
+                    assignments,
+                }
+            })
+            .collect();
+
+        let roadmap_projects = state
+            .roadmap_projects
+            .iter()
+            .map(|project| {
+                let estimate = project.total_estimate();
+                let allocated = index.roadmap_allocated_weeks(&project.id).total();
+                RoadmapReport {
+                    name: project.name.clone(),
+                    color: project.color,
+                    launch_date: project.launch_date,
+                    estimate,
+                    allocated,
+                    status: get_capacity_status(allocated, estimate),
+                }
+            })
+            .collect();
+
+        let technical_projects = state
+            .technical_projects
+            .iter()
+            .map(|project| {
+                let estimate = project.total_estimate();
+                let allocated = index.project_allocated_weeks(&project.id);
+                TechnicalReport {
+                    name: project.name.clone(),
+                    roadmap_name: project
+                        .roadmap_project_id
+                        .and_then(|id| index.roadmap_project(&id))
+                        .map(|p| p.name.clone()),
+                    estimate,
+                    allocated,
+                    status: get_capacity_status(allocated, estimate),
+                    dates: index
+                        .project_allocation_date_range(&project.id)
+                        .map(|(first, last)| (first, last + Duration::days(6))),
+                }
+            })
+            .collect();
+
+        let mut callouts = Vec::new();
+        for utilization in [Utilization::Over, Utilization::Under] {
+            for member in &members {
+                let off = match utilization {
+                    Utilization::Over => member.allocated > member.capacity,
+                    Utilization::Under => member.allocated < member.capacity,
+                };
+                if off && matches!(member.status, BadgeType::Warning | BadgeType::Error) {
+                    callouts.push(UtilizationCallout {
+                        member_name: member.name.clone(),
+                        utilization,
+                        capacity: member.capacity,
+                        allocated: member.allocated,
+                        status: member.status,
+                    });
+                }
+            }
+        }
+
+        let capacity = view.total_capacity().total();
+        let allocated = index.total_allocated();
+        Self {
+            team_name: prefs.team_name.clone(),
+            quarter_name: state.quarter_name.clone(),
+            quarter_start: state.quarter_start_date,
+            quarter_end: state.quarter_start_date + Duration::weeks(state.num_weeks as i64)
+                - Duration::days(1),
+            num_weeks: state.num_weeks,
+            capacity,
+            allocated,
+            status: get_capacity_status(allocated, capacity),
+            members,
+            roadmap_projects,
+            technical_projects,
+            callouts,
+        }
+    }
+
+    /// Allocated share of team capacity, as a percentage
+    pub fn utilization_pct(&self) -> f32 {
+        percent(self.allocated, self.capacity)
+    }
+}
+
+/// Short label for a capacity status, for reports without badge colors
+pub fn status_label(status: BadgeType) -> &'static str {
+    match status {
+        BadgeType::Success => "On target",
+        BadgeType::Warning => "Slightly off",
+        BadgeType::Error => "Off target",
+        BadgeType::Info | BadgeType::Neutral => "Not planned",
+    }
+}
+
+/// "Jan 6 – Feb 2, 2025", or "Dec 29, 2025 – Jan 4, 2026" across years
+pub fn format_date_range(start: NaiveDate, end: NaiveDate) -> String {
+    if start.year() == end.year() {
+        format!("{} – {}", start.format("%b %-d"), end.format("%b %-d, %Y"))
+    } else {
+        format!(
+            "{} – {}",
+            start.format("%b %-d, %Y"),
+            end.format("%b %-d, %Y")
+        )
+    }
+}
+
+fn percent(part: f32, whole: f32) -> f32 {
+    if whole > 0.0 {
+        part / whole * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Allocation, Assignment, RoadmapProject, Role, RoleWeeks, TeamMember, TechnicalProject,
+    };
+
+    #[test]
+    fn test_plan_report() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.team_members = vec![
+            TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 2.0),
+            TeamMember::new("Bob Li".to_string(), Role::ENGINEERING, 4.0),
+            TeamMember::new("Carol Diaz".to_string(), Role::SCIENCE, 0.0),
+        ];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 4);
+        let roadmap = RoadmapProject::new(
+            "Checkout".to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 3.0)]),
+            start,
+            start + Duration::weeks(4),
+            ProjectColor::Green,
+        );
+        let api = TechnicalProject::new(
+            "API".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::from([(Role::ENGINEERING, 3.0)]),
+            start,
+        );
+        let docs = TechnicalProject::new("Docs".to_string(), None, RoleWeeks::new(), start);
+
+        let alice = prefs.team_members[0].id;
+        let bob = prefs.team_members[1].id;
+        for (member, week, assignments) in [
+            (alice, 0, vec![(api.id, 100.0)]),
+            (alice, 1, vec![(api.id, 50.0), (docs.id, 50.0)]),
+            (alice, 2, vec![(api.id, 100.0)]),
+            (bob, 1, vec![(api.id, 100.0)]),
+        ] {
+            let mut alloc = Allocation::new(member, start + Duration::weeks(week));
+            for (project_id, percentage) in assignments {
+                alloc
+                    .assignments
+                    .push(Assignment::new(project_id, percentage));
+            }
+            state.allocations.push(alloc);
+        }
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects = vec![api, docs];
+
+        let report = PlanReport::new(&prefs, &state);
+        assert_eq!(
+            report.quarter_end,
+            NaiveDate::from_ymd_opt(2025, 2, 2).unwrap()
+        );
+        assert_eq!(report.capacity, 6.0);
+        assert_eq!(report.allocated, 4.0);
+
+        let alice_report = &report.members[0];
+        assert_eq!(alice_report.role_name, "Engineering");
+        assert_eq!(
+            alice_report.assignments,
+            [("API".to_string(), 2.5), ("Docs".to_string(), 0.5)]
+        );
+
+        assert_eq!(report.roadmap_projects[0].allocated, 3.5);
+        assert_eq!(report.roadmap_projects[0].status, BadgeType::Warning);
+        let api_report = &report.technical_projects[0];
+        assert_eq!(api_report.roadmap_name.as_deref(), Some("Checkout"));
+        assert_eq!(
+            api_report.dates,
+            Some((start, NaiveDate::from_ymd_opt(2025, 1, 26).unwrap()))
+        );
+        assert_eq!(
+            report.technical_projects[1].dates,
+            Some((
+                start + Duration::weeks(1),
+                start + Duration::weeks(1) + Duration::days(6)
+            ))
+        );
+
+        // Alice 3 of 2 weeks, Bob 1 of 4; Carol has no capacity or work
+        let callouts: Vec<(&str, Utilization)> = report
+            .callouts
+            .iter()
+            .map(|c| (c.member_name.as_str(), c.utilization))
+            .collect();
+        assert_eq!(
+            callouts,
+            [
+                ("Alice Kim", Utilization::Over),
+                ("Bob Li", Utilization::Under)
+            ]
+        );
+        assert_eq!(
+            report.callouts[0].message(),
+            "Alice Kim is over-allocated: 3.0 of 2.0 weeks"
+        );
+    }
+
+    #[test]
+    fn test_empty_plan_report() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let prefs = Preferences::new("Payments".to_string());
+        let state = PlanState::new("Q1 2025".to_string(), start, 13);
+
+        let report = PlanReport::new(&prefs, &state);
+        assert_eq!(report.capacity, 0.0);
+        assert_eq!(report.allocated, 0.0);
+        assert!(report.capacity.is_sign_positive());
+        assert!(report.allocated.is_sign_positive());
+        assert_eq!(report.utilization_pct(), 0.0);
+        assert_eq!(report.status, BadgeType::Neutral);
+        assert!(report.members.is_empty());
+        assert!(report.roadmap_projects.is_empty());
+        assert!(report.technical_projects.is_empty());
+        assert!(report.callouts.is_empty());
+    }
+
+    #[test]
+    fn test_unallocated_project_report() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let mut prefs = Preferences::new("Payments".to_string());
+        prefs.team_members = vec![TeamMember::new(
+            "Alice Kim".to_string(),
+            Role::ENGINEERING,
+            0.0,
+        )];
+        let mut state = PlanState::new("Q1 2025".to_string(), start, 4);
+        let roadmap = RoadmapProject::new(
+            "Checkout".to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 3.0)]),
+            start,
+            start + Duration::weeks(4),
+            ProjectColor::Green,
+        );
+        let api = TechnicalProject::new(
+            "API".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::from([(Role::ENGINEERING, 3.0)]),
+            start,
+        );
+        state.roadmap_projects.push(roadmap);
+        state.technical_projects.push(api);
+
+        let report = PlanReport::new(&prefs, &state);
+        let api_report = &report.technical_projects[0];
+        assert_eq!(api_report.allocated, 0.0);
+        assert_eq!(api_report.dates, None);
+        assert_eq!(api_report.status, BadgeType::Error);
+        assert_eq!(report.roadmap_projects[0].allocated, 0.0);
+        assert!(report.members[0].assignments.is_empty());
+        // Zero sums are positive zero, so they never render as "-0.0"
+        assert!(report.allocated.is_sign_positive());
+        assert!(report.members[0].allocated.is_sign_positive());
+        assert!(api_report.allocated.is_sign_positive());
+        assert!(report.roadmap_projects[0].allocated.is_sign_positive());
+    }
+}
@@ -0,0 +1,616 @@
+//! Automatic allocation scheduling
+//!
+//! Proposes allocations that cover each technical project's per-role
+//! estimates using the team's remaining capacity. Proposals are computed
+//! against a snapshot and only touch the plan when applied, so the app can
+//! show them for review first.
+//!
+//! ## Strategy
+//!
+//! Projects are scheduled one at a time, earliest deadline first (the linked
+//! roadmap project's launch date, or the end of the quarter), with
+//! predecessors ahead of their dependents. For each role a project needs, the
+//! best-fitting member (already on the project, then matching its required
+//! skills, then most spare capacity) is booked week by week before moving on
+//! to the next member, which keeps people on one project for stretches rather
+//! than scattering them.
+//!
+//! Only a member-week's unbooked share (net of holidays and time off) is
+//! filled; existing assignments are never changed unless the caller opts to
+//! reschedule from a clear grid.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+use crate::models::{
+    Allocation, Assignment, DependencyKind, PlanState, PlanView, Preferences, Role, RoleWeeks,
+    TechnicalProject,
+};
+
+/// Amounts smaller than this are treated as zero
+const EPSILON: f32 = 0.01;
+
+/// Options controlling a scheduling run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleOptions {
+    /// Keep existing allocations (they count toward estimates and are never
+    /// moved); when false, the plan is rescheduled from an empty grid
+    pub keep_existing: bool,
+}
+
+impl Default for ScheduleOptions {
+    fn default() -> Self {
+        Self {
+            keep_existing: true,
+        }
+    }
+}
+
+/// Why part of a project's estimate couldn't be scheduled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortfallReason {
+    /// Nobody on the team holds the role
+    NoMembersWithRole,
+    /// Dependencies and the launch date leave no weeks to work in
+    NoSchedulingWindow,
+    /// Everyone with the role is booked before the deadline
+    InsufficientCapacity,
+}
+
+impl ShortfallReason {
+    pub fn description(self) -> &'static str {
+        match self {
+            ShortfallReason::NoMembersWithRole => "no team members have this role",
+            ShortfallReason::NoSchedulingWindow => {
+                "dependencies leave no weeks before the launch date"
+            }
+            ShortfallReason::InsufficientCapacity => {
+                "not enough free capacity before the launch date"
+            }
+        }
+    }
+}
+
+/// Estimated weeks the scheduler couldn't place
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortfall {
+    pub technical_project_id: Uuid,
+    pub role: Role,
+    pub weeks: f32,
+    pub reason: ShortfallReason,
+}
+
+/// A member's proposed work on one project, for review
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposedAssignment {
+    pub team_member_id: Uuid,
+    pub technical_project_id: Uuid,
+    pub first_week: NaiveDate,
+    pub last_week: NaiveDate,
+    pub weeks: f32,
+}
+
+/// Result of a scheduling run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleProposal {
+    pub options: ScheduleOptions,
+    /// Assignments to add, grouped into one allocation per member-week
+    /// (merged into any allocation the week already has)
+    pub allocations: Vec<Allocation>,
+    /// Existing allocations that applying will remove (rescheduling only)
+    pub removed: Vec<Allocation>,
+    /// Estimates that didn't fit
+    pub shortfalls: Vec<Shortfall>,
+}
+
+impl ScheduleProposal {
+    /// True if applying would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.allocations.is_empty() && self.removed.is_empty()
+    }
+
+    /// Total weeks proposed across all new allocations
+    pub fn total_weeks(&self) -> f32 {
+        self.allocations
+            .iter()
+            .map(|a| a.total_percentage() / 100.0)
+            .sum()
+    }
+
+    /// New allocations grouped by member and project
+    pub fn assignments(&self) -> Vec<ProposedAssignment> {
+        let mut grouped: Vec<ProposedAssignment> = Vec::new();
+        for allocation in &self.allocations {
+            for assignment in &allocation.assignments {
+                let weeks = assignment.percentage / 100.0;
+                let week = allocation.week_start_date;
+                match grouped.iter_mut().find(|g| {
+                    g.team_member_id == allocation.team_member_id
+                        && g.technical_project_id == assignment.technical_project_id
+                }) {
+                    Some(group) => {
+                        group.first_week = group.first_week.min(week);
+                        group.last_week = group.last_week.max(week);
+                        group.weeks += weeks;
+                    }
+                    None => grouped.push(ProposedAssignment {
+                        team_member_id: allocation.team_member_id,
+                        technical_project_id: assignment.technical_project_id,
+                        first_week: week,
+                        last_week: week,
+                        weeks,
+                    }),
+                }
+            }
+        }
+        grouped
+    }
+
+    /// Apply the proposal to a plan
+    pub fn apply(&self, state: &mut PlanState) {
+        if !self.options.keep_existing {
+            state
+                .allocations
+                .retain(|existing| !self.removed.contains(existing));
+        }
+        for allocation in &self.allocations {
+            for assignment in &allocation.assignments {
+                add_assignment(
+                    &mut state.allocations,
+                    allocation.team_member_id,
+                    allocation.week_start_date,
+                    assignment.clone(),
+                );
+            }
+        }
+    }
+}
+
+/// Propose allocations covering the plan's unallocated estimates
+pub fn schedule(
+    prefs: &Preferences,
+    state: &PlanState,
+    options: ScheduleOptions,
+) -> ScheduleProposal {
+    let weeks: Vec<NaiveDate> = (0..state.num_weeks)
+        .map(|i| state.quarter_start_date + Duration::weeks(i as i64))
+        .collect();
+
+    // Working copy of the plan: locked allocations plus everything proposed
+    let mut working = state.clone();
+    let removed = if options.keep_existing {
+        Vec::new()
+    } else {
+        std::mem::take(&mut working.allocations)
+    };
+
+    // Spare capacity per member, net of time off and existing work
+    let mut spare: HashMap<Uuid, f32> = prefs
+        .team_members
+        .iter()
+        .map(|m| {
+            let capacity = PlanView::new(prefs, &working).member_capacity(m);
+            let booked = working.calculate_team_member_allocated_weeks(&m.id);
+            (m.id, capacity - booked)
+        })
+        .collect();
+
+    let mut proposed: Vec<Allocation> = Vec::new();
+    let mut shortfalls: Vec<Shortfall> = Vec::new();
+
+    for project in scheduling_order(state) {
+        let deadline = project
+            .roadmap_project_id
+            .and_then(|id| state.get_roadmap_project(&id))
+            .map(|rp| rp.launch_date);
+        let earliest = earliest_start(&working, project);
+        let window: Vec<NaiveDate> = weeks
+            .iter()
+            .copied()
+            .filter(|week| *week + Duration::days(6) >= project.start_date)
+            .filter(|week| earliest.is_none_or(|e| *week >= e))
+            .filter(|week| deadline.is_none_or(|d| *week < d))
+            .collect();
+
+        let allocated =
+            PlanView::new(prefs, &working).technical_project_allocated_by_role(&project.id);
+
+        for (role, estimate) in project.estimates.iter() {
+            let mut remaining = estimate - allocated.get(role);
+            if remaining <= EPSILON {
+                continue;
+            }
+
+            let mut candidates: Vec<_> = prefs
+                .team_members
+                .iter()
+                .filter(|m| &m.role == role)
+                .collect();
+            let reason = if candidates.is_empty() {
+                Some(ShortfallReason::NoMembersWithRole)
+            } else if window.is_empty() {
+                Some(ShortfallReason::NoSchedulingWindow)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                shortfalls.push(Shortfall {
+                    technical_project_id: project.id,
+                    role: role.clone(),
+                    weeks: remaining,
+                    reason,
+                });
+                continue;
+            }
+
+            // Best fit first: already on the project, has the skills, most spare
+            let assigned = working.get_assigned_team_members(&project.id);
+            candidates.sort_by(|a, b| {
+                let rank = |m: &crate::models::TeamMember| {
+                    (
+                        assigned.contains(&m.id),
+                        project.is_skill_match(m),
+                        spare.get(&m.id).copied().unwrap_or(0.0),
+                    )
+                };
+                let (a_assigned, a_skills, a_spare) = rank(a);
+                let (b_assigned, b_skills, b_spare) = rank(b);
+                (b_assigned, b_skills)
+                    .cmp(&(a_assigned, a_skills))
+                    .then(b_spare.total_cmp(&a_spare))
+            });
+
+            for member in candidates {
+                for week in &window {
+                    let member_spare = spare.get(&member.id).copied().unwrap_or(0.0);
+                    if remaining <= EPSILON || member_spare <= EPSILON {
+                        break;
+                    }
+                    let booked: f32 = working
+                        .allocations
+                        .iter()
+                        .filter(|a| a.team_member_id == member.id && a.week_start_date == *week)
+                        .map(|a| a.total_percentage() / 100.0)
+                        .sum();
+                    let free = member.week_availability(&prefs.holidays, *week) - booked;
+                    // Book whole percentages and count what was actually booked
+                    let percentage = (remaining.min(member_spare).min(free) * 100.0).round();
+                    if percentage <= 0.0 {
+                        continue;
+                    }
+                    let amount = percentage / 100.0;
+
+                    let assignment = Assignment::new(project.id, percentage);
+                    add_assignment(
+                        &mut working.allocations,
+                        member.id,
+                        *week,
+                        assignment.clone(),
+                    );
+                    add_assignment(&mut proposed, member.id, *week, assignment);
+
+                    remaining -= amount;
+                    spare.insert(member.id, member_spare - amount);
+                }
+            }
+
+            if remaining > EPSILON {
+                shortfalls.push(Shortfall {
+                    technical_project_id: project.id,
+                    role: role.clone(),
+                    weeks: remaining,
+                    reason: ShortfallReason::InsufficientCapacity,
+                });
+            }
+        }
+    }
+
+    ScheduleProposal {
+        options,
+        allocations: proposed,
+        removed,
+        shortfalls,
+    }
+}
+
+/// Add an assignment to the member's allocation for the week, creating it
+/// if needed
+fn add_assignment(
+    allocations: &mut Vec<Allocation>,
+    team_member_id: Uuid,
+    week: NaiveDate,
+    assignment: Assignment,
+) {
+    match allocations
+        .iter_mut()
+        .find(|a| a.team_member_id == team_member_id && a.week_start_date == week)
+    {
+        Some(allocation) => allocation.assignments.push(assignment),
+        None => {
+            let mut allocation = Allocation::new(team_member_id, week);
+            allocation.assignments.push(assignment);
+            allocations.push(allocation);
+        }
+    }
+}
+
+/// Projects in scheduling order: earliest deadline first, but never ahead of
+/// a predecessor (cycles fall back to deadline order)
+fn scheduling_order(state: &PlanState) -> Vec<&TechnicalProject> {
+    let deadline = |project: &TechnicalProject| {
+        project
+            .roadmap_project_id
+            .and_then(|id| state.get_roadmap_project(&id))
+            .map(|rp| rp.launch_date)
+            .unwrap_or(NaiveDate::MAX)
+    };
+    let mut pending: Vec<&TechnicalProject> = state.technical_projects.iter().collect();
+    pending.sort_by_key(|p| deadline(p));
+
+    let mut ordered: Vec<&TechnicalProject> = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let ready = pending
+            .iter()
+            .position(|p| {
+                p.dependencies
+                    .iter()
+                    .all(|d| !pending.iter().any(|other| other.id == d.predecessor_id))
+            })
+            .unwrap_or(0);
+        ordered.push(pending.remove(ready));
+    }
+    ordered
+}
+
+/// First week a project may be scheduled given its predecessors' allocations
+fn earliest_start(state: &PlanState, project: &TechnicalProject) -> Option<NaiveDate> {
+    project
+        .dependencies
+        .iter()
+        .filter_map(|dependency| {
+            let (first, last) =
+                state.get_project_allocation_date_range(&dependency.predecessor_id)?;
+            Some(match dependency.kind {
+                DependencyKind::FinishToStart => last + Duration::weeks(1),
+                DependencyKind::StartToStart => first,
+            })
+        })
+        .max()
+}
+
+/// Estimated weeks per role not yet covered by allocations
+pub fn unallocated_estimates(prefs: &Preferences, state: &PlanState) -> RoleWeeks {
+    let view = PlanView::new(prefs, state);
+    let mut remaining = RoleWeeks::new();
+    for project in &state.technical_projects {
+        let allocated = view.technical_project_allocated_by_role(&project.id);
+        for (role, estimate) in project.estimates.iter() {
+            remaining.add(role, (estimate - allocated.get(role)).max(0.0));
+        }
+    }
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Holiday, ProjectDependency, RoadmapProject, TeamMember};
+    use crate::ProjectColor;
+
+    fn quarter_start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    fn week(n: i64) -> NaiveDate {
+        quarter_start() + Duration::weeks(n)
+    }
+
+    fn setup(estimate: RoleWeeks) -> (Preferences, PlanState, Uuid, Uuid) {
+        let mut prefs = Preferences::default();
+        let alice = TeamMember::new("Alice".to_string(), Role::ENGINEERING, 12.0);
+        let alice_id = alice.id;
+        prefs.team_members.push(alice);
+
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start(), 13);
+        let project = TechnicalProject::new("API".to_string(), None, estimate, quarter_start());
+        let project_id = project.id;
+        state.technical_projects.push(project);
+        (prefs, state, alice_id, project_id)
+    }
+
+    #[test]
+    fn test_schedule_fills_estimate() {
+        let (prefs, state, alice_id, project_id) =
+            setup(RoleWeeks::from([(Role::ENGINEERING, 2.5)]));
+        let proposal = schedule(&prefs, &state, ScheduleOptions::default());
+
+        assert!(proposal.shortfalls.is_empty());
+        assert_eq!(proposal.allocations.len(), 3);
+        assert_eq!(proposal.total_weeks(), 2.5);
+        assert_eq!(
+            proposal.assignments(),
+            [ProposedAssignment {
+                team_member_id: alice_id,
+                technical_project_id: project_id,
+                first_week: week(0),
+                last_week: week(2),
+                weeks: 2.5,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_schedule_keeps_existing_allocations() {
+        let (prefs, mut state, alice_id, project_id) =
+            setup(RoleWeeks::from([(Role::ENGINEERING, 3.0)]));
+        // Week 0 is already spent on this project; week 1 is locked to other work
+        let mut done = Allocation::new(alice_id, week(0));
+        done.assignments.push(Assignment::new(project_id, 100.0));
+        let mut other = Allocation::new(alice_id, week(1));
+        other
+            .assignments
+            .push(Assignment::new(Uuid::new_v4(), 100.0));
+        state.allocations = vec![done, other];
+
+        let proposal = schedule(&prefs, &state, ScheduleOptions::default());
+        let weeks: Vec<_> = proposal
+            .allocations
+            .iter()
+            .map(|a| a.week_start_date)
+            .collect();
+        assert_eq!(weeks, [week(2), week(3)]);
+
+        proposal.apply(&mut state);
+        assert_eq!(state.allocations.len(), 4);
+        assert_eq!(state.calculate_project_allocated_weeks(&project_id), 3.0);
+    }
+
+    #[test]
+    fn test_schedule_uses_spare_share_of_weeks() {
+        let (mut prefs, mut state, alice_id, project_id) =
+            setup(RoleWeeks::from([(Role::ENGINEERING, 3.0)]));
+        // Week 1 is a holiday, and week 3 loses one day to one
+        prefs.holidays = vec![
+            Holiday::new("Break".to_string(), week(1), week(1) + Duration::days(4)),
+            Holiday::new("Day off".to_string(), week(3), week(3)),
+        ];
+        // Half of week 0 is locked to other work
+        let other_id = Uuid::new_v4();
+        let mut other = Allocation::new(alice_id, week(0));
+        other.assignments.push(Assignment::new(other_id, 50.0));
+        state.allocations = vec![other];
+
+        let proposal = schedule(&prefs, &state, ScheduleOptions::default());
+        assert!(proposal.shortfalls.is_empty());
+        let booked: Vec<_> = proposal
+            .allocations
+            .iter()
+            .map(|a| (a.week_start_date, a.total_percentage()))
+            .collect();
+        assert_eq!(
+            booked,
+            [
+                (week(0), 50.0),
+                (week(2), 100.0),
+                (week(3), 80.0),
+                (week(4), 70.0)
+            ]
+        );
+
+        // The locked week gains a second assignment instead of a second allocation
+        proposal.apply(&mut state);
+        assert_eq!(state.allocations.len(), 4);
+        let first = &state.allocations[0];
+        assert_eq!(first.assignments.len(), 2);
+        assert!(first.is_full());
+        assert_eq!(state.calculate_project_allocated_weeks(&project_id), 3.0);
+    }
+
+    #[test]
+    fn test_reschedule_from_empty_grid() {
+        let (prefs, mut state, alice_id, project_id) =
+            setup(RoleWeeks::from([(Role::ENGINEERING, 1.0)]));
+        let mut other = Allocation::new(alice_id, week(0));
+        other
+            .assignments
+            .push(Assignment::new(Uuid::new_v4(), 100.0));
+        state.allocations = vec![other];
+
+        let proposal = schedule(
+            &prefs,
+            &state,
+            ScheduleOptions {
+                keep_existing: false,
+            },
+        );
+        assert_eq!(proposal.removed.len(), 1);
+
+        proposal.apply(&mut state);
+        assert_eq!(state.allocations.len(), 1);
+        assert_eq!(state.allocations[0].week_start_date, week(0));
+        assert_eq!(state.calculate_project_allocated_weeks(&project_id), 1.0);
+    }
+
+    #[test]
+    fn test_schedule_reports_shortfalls() {
+        let (prefs, mut state, _, project_id) = setup(RoleWeeks::from([
+            (Role::ENGINEERING, 6.0),
+            (Role::SCIENCE, 2.0),
+        ]));
+        // Launch in four weeks: only four engineering weeks fit
+        let roadmap = RoadmapProject::new(
+            "Launch".to_string(),
+            RoleWeeks::new(),
+            quarter_start(),
+            week(4),
+            ProjectColor::Blue,
+        );
+        state.technical_projects[0].roadmap_project_id = Some(roadmap.id);
+        state.roadmap_projects.push(roadmap);
+
+        let proposal = schedule(&prefs, &state, ScheduleOptions::default());
+        assert_eq!(proposal.total_weeks(), 4.0);
+        assert_eq!(
+            proposal.shortfalls,
+            [
+                Shortfall {
+                    technical_project_id: project_id,
+                    role: Role::ENGINEERING,
+                    weeks: 2.0,
+                    reason: ShortfallReason::InsufficientCapacity,
+                },
+                Shortfall {
+                    technical_project_id: project_id,
+                    role: Role::SCIENCE,
+                    weeks: 2.0,
+                    reason: ShortfallReason::NoMembersWithRole,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schedule_respects_dependencies() {
+        let (mut prefs, mut state, _, api_id) = setup(RoleWeeks::from([(Role::ENGINEERING, 2.0)]));
+        prefs
+            .team_members
+            .push(TeamMember::new("Bob".to_string(), Role::ENGINEERING, 12.0));
+        // Listed first, but must wait for the API to finish
+        let mut client = TechnicalProject::new(
+            "Client".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 1.0)]),
+            quarter_start(),
+        );
+        client.dependencies.push(ProjectDependency::new(
+            api_id,
+            DependencyKind::FinishToStart,
+        ));
+        let client_id = client.id;
+        state.technical_projects.insert(0, client);
+
+        let proposal = schedule(&prefs, &state, ScheduleOptions::default());
+        proposal.apply(&mut state);
+        assert_eq!(
+            state.get_project_allocation_date_range(&client_id),
+            Some((week(2), week(2)))
+        );
+        assert!(state.dependency_violations().is_empty());
+    }
+
+    #[test]
+    fn test_unallocated_estimates() {
+        let (prefs, mut state, alice_id, project_id) =
+            setup(RoleWeeks::from([(Role::ENGINEERING, 3.0)]));
+        let mut done = Allocation::new(alice_id, week(0));
+        done.assignments.push(Assignment::new(project_id, 100.0));
+        state.allocations.push(done);
+
+        assert_eq!(
+            unallocated_estimates(&prefs, &state).get(&Role::ENGINEERING),
+            2.0
+        );
+    }
+}
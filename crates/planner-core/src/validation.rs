@@ -0,0 +1,1043 @@
+//! Full validation report for a plan
+//!
+//! `PlanExport::validate` and `Preferences::validate` stop at the first
+//! structural problem and decide whether data can be loaded at all. This
+//! module collects every problem instead, each with a severity and the entity
+//! it concerns, for the Plan health panel and import checks:
+//! - Errors: data that is inconsistent or can't be shown correctly (dangling
+//!   references, duplicate IDs, overbooked weeks, negative estimates, ...)
+//! - Warnings: data that is valid but probably not intended (allocations
+//!   outside the quarter, launch dates before start dates, ...)
+//!
+//! Scenarios are checked like the main plan, against the shared roster.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use uuid::Uuid;
+
+use crate::models::{
+    find_dependency_cycle, Allocation, BadgeType, Holiday, PlanExport, PlanState, Preferences,
+    RoadmapProject, Role, RoleDefinition, RoleWeeks, TeamMember, TechnicalProject,
+};
+
+/// How serious an issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "Error",
+            Severity::Warning => "Warning",
+        }
+    }
+
+    pub fn badge_type(self) -> BadgeType {
+        match self {
+            Severity::Error => BadgeType::Error,
+            Severity::Warning => BadgeType::Warning,
+        }
+    }
+}
+
+/// The entity an issue concerns
+#[derive(Debug, Clone, PartialEq)]
+pub enum Subject {
+    /// Plan-wide settings (team name, quarter, sprint configuration)
+    Plan,
+    Role(Role),
+    Holiday(Uuid),
+    TeamMember(Uuid),
+    TimeOff {
+        team_member_id: Uuid,
+        time_off_id: Uuid,
+    },
+    RoadmapProject(Uuid),
+    TechnicalProject(Uuid),
+    Allocation {
+        team_member_id: Uuid,
+        week_start_date: NaiveDate,
+    },
+}
+
+impl Subject {
+    /// Kind of entity, for grouping in lists
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Subject::Plan => "Plan settings",
+            Subject::Role(_) => "Role",
+            Subject::Holiday(_) => "Holiday",
+            Subject::TeamMember(_) => "Team member",
+            Subject::TimeOff { .. } => "Time off",
+            Subject::RoadmapProject(_) => "Roadmap project",
+            Subject::TechnicalProject(_) => "Technical project",
+            Subject::Allocation { .. } => "Allocation",
+        }
+    }
+}
+
+/// One problem found in a plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub subject: Subject,
+    /// Scenario the issue was found in, or None for the main plan and roster
+    pub scenario_id: Option<Uuid>,
+    /// Description naming the entity, e.g. `Alice Kim, week of 2025-01-06: ...`
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity.label(), self.message)
+    }
+}
+
+/// Every issue found in a plan, errors first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors().count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.warnings().count()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+    }
+
+    /// One-line count, e.g. "2 errors, 1 warning"
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "No problems found".to_string();
+        }
+        let count = |n: usize, noun: &str| match n {
+            1 => format!("1 {}", noun),
+            n => format!("{} {}s", n, noun),
+        };
+        format!(
+            "{}, {}",
+            count(self.error_count(), "error"),
+            count(self.warning_count(), "warning")
+        )
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.summary())?;
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check team preferences on their own
+pub fn validate_preferences(prefs: &Preferences) -> ValidationReport {
+    let mut checker = Checker::default();
+    checker.team_name(&prefs.team_name);
+    checker.sprint_config(prefs);
+    checker.roster(&prefs.team_members, &prefs.roles, &prefs.holidays);
+    checker.finish()
+}
+
+/// Check a plan export, including its scenarios
+pub fn validate_export(export: &PlanExport) -> ValidationReport {
+    let mut checker = Checker::default();
+    if export.version.trim().is_empty() {
+        checker.error(Subject::Plan, "The file has no format version".to_string());
+    }
+    checker.team_name(&export.team_name);
+    checker.roster(&export.team_members, &export.roles, &export.holidays);
+    checker.plan(export);
+    checker.finish()
+}
+
+/// Check the open plan: preferences, main plan and every scenario
+pub fn validate_plan(prefs: &Preferences, state: &PlanState) -> ValidationReport {
+    let mut checker = Checker::default();
+    checker.team_name(&prefs.team_name);
+    checker.sprint_config(prefs);
+    checker.roster(&prefs.team_members, &prefs.roles, &prefs.holidays);
+    checker.plan(&PlanExport::from_signals(prefs.clone(), state.clone()));
+    checker.finish()
+}
+
+#[derive(Default)]
+struct Checker {
+    issues: Vec<Issue>,
+    /// Scenario whose branch is being checked
+    scenario: Option<Uuid>,
+    /// Prefix naming the scenario being checked
+    prefix: String,
+}
+
+/// The part of a plan that differs between branches
+struct Branch<'a> {
+    roadmap_projects: &'a [RoadmapProject],
+    technical_projects: &'a [TechnicalProject],
+    allocations: &'a [Allocation],
+}
+
+impl Checker {
+    fn push(&mut self, severity: Severity, subject: Subject, message: String) {
+        self.issues.push(Issue {
+            severity,
+            subject,
+            scenario_id: self.scenario,
+            message: format!("{}{}", self.prefix, message),
+        });
+    }
+
+    fn error(&mut self, subject: Subject, message: String) {
+        self.push(Severity::Error, subject, message);
+    }
+
+    fn warning(&mut self, subject: Subject, message: String) {
+        self.push(Severity::Warning, subject, message);
+    }
+
+    fn finish(mut self) -> ValidationReport {
+        // Stable, so issues keep their discovery order within a severity
+        self.issues.sort_by_key(|i| i.severity);
+        ValidationReport {
+            issues: self.issues,
+        }
+    }
+
+    fn team_name(&mut self, team_name: &str) {
+        if team_name.trim().is_empty() {
+            self.error(Subject::Plan, "Team name is empty".to_string());
+        }
+    }
+
+    fn sprint_config(&mut self, prefs: &Preferences) {
+        if !(1..=4).contains(&prefs.sprint_length_weeks) {
+            self.error(
+                Subject::Plan,
+                format!(
+                    "Sprint length of {} weeks is outside 1-4 weeks",
+                    prefs.sprint_length_weeks
+                ),
+            );
+        }
+        if prefs.default_capacity <= 0.0 {
+            self.error(
+                Subject::Plan,
+                format!(
+                    "Default capacity of {} weeks must be positive",
+                    prefs.default_capacity
+                ),
+            );
+        }
+    }
+
+    fn roster(&mut self, members: &[TeamMember], roles: &[RoleDefinition], holidays: &[Holiday]) {
+        for (i, role) in roles.iter().enumerate() {
+            let subject = Subject::Role(role.id.clone());
+            if role.id.as_str().trim().is_empty() || role.name.trim().is_empty() {
+                self.error(subject.clone(), "A role has no name".to_string());
+            }
+            if roles[..i].iter().any(|r| r.id == role.id) {
+                self.error(subject, format!("Role \"{}\" is defined twice", role.id));
+            }
+        }
+
+        if members.is_empty() {
+            self.error(Subject::Plan, "The team has no members".to_string());
+        }
+        self.duplicate_ids(
+            members.iter().map(|m| m.id),
+            Subject::TeamMember,
+            "Team member",
+        );
+        for member in members {
+            let subject = Subject::TeamMember(member.id);
+            let name = &member.name;
+            if !roles.iter().any(|r| r.id == member.role) {
+                self.error(
+                    subject.clone(),
+                    format!("{}: role \"{}\" isn't defined", name, member.role),
+                );
+            }
+            if member.capacity < 0.0 {
+                self.error(
+                    subject.clone(),
+                    format!(
+                        "{}: capacity of {} weeks is negative",
+                        name, member.capacity
+                    ),
+                );
+            }
+            if let (Some(start), Some(end)) = (member.start_date, member.end_date) {
+                if end < start {
+                    self.error(
+                        subject,
+                        format!("{}: leaves ({}) before joining ({})", name, end, start),
+                    );
+                }
+            }
+            for time_off in &member.time_off {
+                let subject = Subject::TimeOff {
+                    team_member_id: member.id,
+                    time_off_id: time_off.id,
+                };
+                if time_off.end_date < time_off.start_date {
+                    self.error(
+                        subject.clone(),
+                        format!(
+                            "{}: time off ends ({}) before it starts ({})",
+                            name, time_off.end_date, time_off.start_date
+                        ),
+                    );
+                }
+                if !(time_off.fraction > 0.0 && time_off.fraction <= 1.0) {
+                    self.error(
+                        subject,
+                        format!(
+                            "{}: time off from {} covers {}% of each day",
+                            name,
+                            time_off.start_date,
+                            time_off.fraction * 100.0
+                        ),
+                    );
+                }
+            }
+        }
+
+        self.duplicate_ids(holidays.iter().map(|h| h.id), Subject::Holiday, "Holiday");
+        for holiday in holidays {
+            if holiday.end_date < holiday.start_date {
+                self.error(
+                    Subject::Holiday(holiday.id),
+                    format!(
+                        "Holiday \"{}\" ends ({}) before it starts ({})",
+                        holiday.name, holiday.end_date, holiday.start_date
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Quarter settings, then the main plan and each scenario
+    fn plan(&mut self, export: &PlanExport) {
+        if export.quarter_name.trim().is_empty() {
+            self.error(Subject::Plan, "Quarter name is empty".to_string());
+        }
+        if export.num_weeks == 0 {
+            self.error(Subject::Plan, "The quarter has no weeks".to_string());
+        }
+        if export.quarter_start_date.weekday() != Weekday::Mon {
+            self.warning(
+                Subject::Plan,
+                format!(
+                    "Quarter starts on {}, which isn't a Monday",
+                    export.quarter_start_date
+                ),
+            );
+        }
+
+        self.branch(
+            export,
+            Branch {
+                roadmap_projects: &export.roadmap_projects,
+                technical_projects: &export.technical_projects,
+                allocations: &export.allocations,
+            },
+        );
+        for scenario in &export.scenarios {
+            self.scenario = Some(scenario.id);
+            self.prefix = format!("Scenario \"{}\": ", scenario.name);
+            self.branch(
+                export,
+                Branch {
+                    roadmap_projects: &scenario.branch.roadmap_projects,
+                    technical_projects: &scenario.branch.technical_projects,
+                    allocations: &scenario.branch.allocations,
+                },
+            );
+        }
+        self.scenario = None;
+        self.prefix.clear();
+    }
+
+    fn branch(&mut self, export: &PlanExport, branch: Branch) {
+        let members = &export.team_members;
+
+        // Roadmap projects
+        self.duplicate_ids(
+            branch.roadmap_projects.iter().map(|p| p.id),
+            Subject::RoadmapProject,
+            "Roadmap project",
+        );
+        for project in branch.roadmap_projects {
+            let subject = Subject::RoadmapProject(project.id);
+            let label = format!("Roadmap project \"{}\"", project.name);
+            self.estimates(&subject, &label, &project.estimates);
+            if project.launch_date < project.start_date {
+                self.warning(
+                    subject,
+                    format!(
+                        "{}: launches ({}) before it starts ({})",
+                        label, project.launch_date, project.start_date
+                    ),
+                );
+            }
+        }
+
+        // Technical projects
+        self.duplicate_ids(
+            branch.technical_projects.iter().map(|p| p.id),
+            Subject::TechnicalProject,
+            "Technical project",
+        );
+        let project_name = |id: &Uuid| {
+            branch
+                .technical_projects
+                .iter()
+                .find(|p| &p.id == id)
+                .map(|p| p.name.as_str())
+        };
+        for project in branch.technical_projects {
+            let subject = Subject::TechnicalProject(project.id);
+            let label = format!("Technical project \"{}\"", project.name);
+            self.estimates(&subject, &label, &project.estimates);
+            if let Some(roadmap_id) = project.roadmap_project_id {
+                if !branch.roadmap_projects.iter().any(|p| p.id == roadmap_id) {
+                    self.error(
+                        subject.clone(),
+                        format!("{}: linked roadmap project doesn't exist", label),
+                    );
+                }
+            }
+            for dependency in &project.dependencies {
+                if project_name(&dependency.predecessor_id).is_none() {
+                    self.error(
+                        subject.clone(),
+                        format!("{}: depends on a project that doesn't exist", label),
+                    );
+                }
+            }
+            if let Some(completion) = project.expected_completion {
+                if completion < project.start_date {
+                    self.warning(
+                        subject,
+                        format!(
+                            "{}: completes ({}) before it starts ({})",
+                            label, completion, project.start_date
+                        ),
+                    );
+                }
+            }
+        }
+        if let Some(cycle) = find_dependency_cycle(branch.technical_projects) {
+            let names: Vec<_> = cycle
+                .iter()
+                .map(|id| project_name(id).unwrap_or("?"))
+                .collect();
+            self.error(
+                Subject::TechnicalProject(cycle[0]),
+                format!("Dependency cycle: {}", names.join(" → ")),
+            );
+        }
+
+        // Allocations
+        let quarter_end = export.quarter_start_date + Duration::weeks(export.num_weeks as i64);
+        let mut seen_cells = HashSet::new();
+        for allocation in branch.allocations {
+            let week = allocation.week_start_date;
+            let subject = Subject::Allocation {
+                team_member_id: allocation.team_member_id,
+                week_start_date: week,
+            };
+            let member = members.iter().find(|m| m.id == allocation.team_member_id);
+            let label = format!(
+                "{}, week of {}",
+                member.map_or("Unknown member", |m| m.name.as_str()),
+                week
+            );
+
+            if member.is_none() {
+                self.error(
+                    subject.clone(),
+                    format!("{}: team member doesn't exist", label),
+                );
+            }
+            if !seen_cells.insert((allocation.team_member_id, week)) {
+                self.error(
+                    subject.clone(),
+                    format!("{}: week is allocated more than once", label),
+                );
+            }
+            if week.weekday() != Weekday::Mon {
+                self.error(
+                    subject.clone(),
+                    format!("{}: allocation doesn't start on a Monday", label),
+                );
+            }
+            if week < export.quarter_start_date || week >= quarter_end {
+                self.warning(
+                    subject.clone(),
+                    format!("{}: allocation is outside the quarter", label),
+                );
+            }
+            if member.is_some_and(|m| !m.is_active_in_week(week)) {
+                self.warning(
+                    subject.clone(),
+                    format!("{}: allocated outside the member's employment dates", label),
+                );
+            }
+            for assignment in &allocation.assignments {
+                if project_name(&assignment.technical_project_id).is_none() {
+                    self.error(
+                        subject.clone(),
+                        format!("{}: assigned project doesn't exist", label),
+                    );
+                }
+                if !assignment.has_valid_percentage() {
+                    self.error(
+                        subject.clone(),
+                        format!("{}: assignment of {}%", label, assignment.percentage),
+                    );
+                }
+            }
+            if allocation.is_overbooked() {
+                self.error(
+                    subject,
+                    format!(
+                        "{}: assignments add up to {}%",
+                        label,
+                        allocation.total_percentage()
+                    ),
+                );
+            }
+        }
+    }
+
+    fn estimates(&mut self, subject: &Subject, label: &str, estimates: &RoleWeeks) {
+        for (role, weeks) in estimates.iter() {
+            if weeks < 0.0 {
+                self.error(
+                    subject.clone(),
+                    format!(
+                        "{}: {} estimate of {} weeks is negative",
+                        label, role, weeks
+                    ),
+                );
+            }
+        }
+    }
+
+    fn duplicate_ids(
+        &mut self,
+        ids: impl Iterator<Item = Uuid>,
+        subject: impl Fn(Uuid) -> Subject,
+        noun: &str,
+    ) {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for id in ids {
+            if !seen.insert(id) && reported.insert(id) {
+                self.error(
+                    subject(id),
+                    format!("{} ID {} is used more than once", noun, id),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, DependencyKind, ProjectColor, ProjectDependency, TimeOff};
+
+    fn quarter_start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    fn sample() -> (Preferences, PlanState) {
+        let prefs = Preferences {
+            team_members: vec![TeamMember::new(
+                "Alice Kim".to_string(),
+                Role::ENGINEERING,
+                12.0,
+            )],
+            ..Preferences::default()
+        };
+        let mut state = PlanState::new("Q1 2025".to_string(), quarter_start(), 13);
+        let roadmap = RoadmapProject::new(
+            "Payment Gateway".to_string(),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start(),
+            quarter_start() + Duration::weeks(8),
+            ProjectColor::Blue,
+        );
+        state.technical_projects.push(TechnicalProject::new(
+            "Gateway API".to_string(),
+            Some(roadmap.id),
+            RoleWeeks::from([(Role::ENGINEERING, 4.0)]),
+            quarter_start(),
+        ));
+        state.roadmap_projects.push(roadmap);
+        let mut alloc = Allocation::new(prefs.team_members[0].id, quarter_start());
+        alloc
+            .assignments
+            .push(Assignment::new(state.technical_projects[0].id, 100.0));
+        state.allocations.push(alloc);
+        (prefs, state)
+    }
+
+    /// Error messages for the sample plan after `edit`
+    fn errors_after(edit: impl FnOnce(&mut Preferences, &mut PlanState)) -> Vec<String> {
+        let (mut prefs, mut state) = sample();
+        edit(&mut prefs, &mut state);
+        validate_plan(&prefs, &state)
+            .errors()
+            .map(|issue| issue.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_plan_has_no_issues() {
+        let (prefs, state) = sample();
+        let report = validate_plan(&prefs, &state);
+        assert!(report.is_empty(), "{}", report);
+        assert_eq!(report.summary(), "No problems found");
+        assert!(validate_preferences(&prefs).is_empty());
+    }
+
+    #[test]
+    fn test_collects_every_issue() {
+        let (mut prefs, mut state) = sample();
+        let alice = prefs.team_members[0].id;
+        let project = state.technical_projects[0].id;
+
+        prefs.team_members[0].time_off.push(TimeOff::new(
+            quarter_start() + Duration::days(3),
+            quarter_start(),
+            1.0,
+        ));
+        state.roadmap_projects[0].launch_date = quarter_start() - Duration::days(1);
+        state.technical_projects[0]
+            .estimates
+            .set(Role::ENGINEERING, -2.0);
+        // Overbooked, non-Monday, outside the quarter, dangling member
+        state.allocations[0]
+            .assignments
+            .push(Assignment::new(project, 50.0));
+        state
+            .allocations
+            .push(Allocation::new(alice, quarter_start() + Duration::days(9)));
+        state.allocations.push(Allocation::new(
+            alice,
+            quarter_start() + Duration::weeks(20),
+        ));
+        state
+            .allocations
+            .push(Allocation::new(Uuid::new_v4(), quarter_start()));
+
+        let report = validate_plan(&prefs, &state);
+        let messages: Vec<String> = report.issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Error: Alice Kim: time off ends (2025-01-06) before it starts (2025-01-09)",
+                "Error: Technical project \"Gateway API\": eng estimate of -2 weeks is negative",
+                "Error: Alice Kim, week of 2025-01-06: assignments add up to 150%",
+                "Error: Alice Kim, week of 2025-01-15: allocation doesn't start on a Monday",
+                "Error: Unknown member, week of 2025-01-06: team member doesn't exist",
+                "Warning: Roadmap project \"Payment Gateway\": launches (2025-01-05) before it starts (2025-01-06)",
+                "Warning: Alice Kim, week of 2025-05-26: allocation is outside the quarter",
+            ]
+        );
+        assert_eq!(report.summary(), "5 errors, 2 warnings");
+        assert_eq!(
+            report.issues[2].subject,
+            Subject::Allocation {
+                team_member_id: alice,
+                week_start_date: quarter_start()
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicates_and_scenarios() {
+        let (prefs, mut state) = sample();
+        let duplicate = state.technical_projects[0].clone();
+        state.technical_projects.push(duplicate);
+
+        let id = state.create_scenario("Lean");
+        state.switch_scenario(Some(id));
+        state.technical_projects.truncate(1);
+        state.roadmap_projects.clear();
+
+        let report = validate_plan(&prefs, &state);
+        let messages: Vec<&str> = report.issues.iter().map(|i| i.message.as_str()).collect();
+        let project_id = state.technical_projects[0].id;
+        assert_eq!(
+            messages,
+            [
+                format!("Technical project ID {} is used more than once", project_id).as_str(),
+                "Scenario \"Lean\": Technical project \"Gateway API\": linked roadmap project doesn't exist",
+            ]
+        );
+        assert_eq!(report.issues[0].scenario_id, None);
+        assert_eq!(report.issues[1].scenario_id, Some(id));
+
+        // Exports are checked the same way
+        let export = PlanExport::from_signals(prefs, state);
+        assert_eq!(validate_export(&export).issues, report.issues);
+    }
+
+    #[test]
+    fn test_error_missing_format_version() {
+        let (prefs, state) = sample();
+        let mut export = PlanExport::from_signals(prefs, state);
+        export.version = String::new();
+        let report = validate_export(&export);
+        let errors: Vec<_> = report.errors().map(|i| i.message.as_str()).collect();
+        assert_eq!(errors, ["The file has no format version"]);
+    }
+
+    #[test]
+    fn test_error_empty_team_name() {
+        let errors = errors_after(|p, _| p.team_name = " ".to_string());
+        assert_eq!(errors, ["Team name is empty"]);
+    }
+
+    #[test]
+    fn test_error_sprint_length() {
+        let errors = errors_after(|p, _| p.sprint_length_weeks = 6);
+        assert_eq!(errors, ["Sprint length of 6 weeks is outside 1-4 weeks"]);
+    }
+
+    #[test]
+    fn test_error_default_capacity() {
+        let errors = errors_after(|p, _| p.default_capacity = 0.0);
+        assert_eq!(errors, ["Default capacity of 0 weeks must be positive"]);
+    }
+
+    #[test]
+    fn test_error_unnamed_role() {
+        let errors = errors_after(|p, _| p.roles[1].name = String::new());
+        assert_eq!(errors, ["A role has no name"]);
+    }
+
+    #[test]
+    fn test_error_role_defined_twice() {
+        let errors = errors_after(|p, _| p.roles.push(p.roles[0].clone()));
+        assert_eq!(errors, ["Role \"eng\" is defined twice"]);
+    }
+
+    #[test]
+    fn test_error_empty_team() {
+        let errors = errors_after(|p, s| {
+            p.team_members.clear();
+            s.allocations.clear();
+        });
+        assert_eq!(errors, ["The team has no members"]);
+    }
+
+    #[test]
+    fn test_error_duplicate_member_ids() {
+        let mut id = Uuid::nil();
+        let errors = errors_after(|p, _| {
+            id = p.team_members[0].id;
+            p.team_members.push(p.team_members[0].clone());
+        });
+        assert_eq!(
+            errors,
+            [format!("Team member ID {} is used more than once", id)]
+        );
+    }
+
+    #[test]
+    fn test_error_undefined_member_role() {
+        let errors = errors_after(|p, _| p.team_members[0].role = Role::new("design"));
+        assert_eq!(errors, ["Alice Kim: role \"design\" isn't defined"]);
+    }
+
+    #[test]
+    fn test_error_negative_capacity() {
+        let errors = errors_after(|p, _| p.team_members[0].capacity = -1.0);
+        assert_eq!(errors, ["Alice Kim: capacity of -1 weeks is negative"]);
+    }
+
+    #[test]
+    fn test_error_leaves_before_joining() {
+        let errors = errors_after(|p, _| {
+            p.team_members[0].start_date = Some(quarter_start() + Duration::weeks(4));
+            p.team_members[0].end_date = Some(quarter_start());
+        });
+        assert_eq!(
+            errors,
+            ["Alice Kim: leaves (2025-01-06) before joining (2025-02-03)"]
+        );
+    }
+
+    #[test]
+    fn test_error_time_off_reversed() {
+        let errors = errors_after(|p, _| {
+            p.team_members[0].time_off.push(TimeOff::new(
+                quarter_start() + Duration::days(3),
+                quarter_start(),
+                1.0,
+            ))
+        });
+        assert_eq!(
+            errors,
+            ["Alice Kim: time off ends (2025-01-06) before it starts (2025-01-09)"]
+        );
+    }
+
+    #[test]
+    fn test_error_time_off_fraction() {
+        let errors = errors_after(|p, _| {
+            let mut time_off = TimeOff::new(quarter_start(), quarter_start(), 1.0);
+            time_off.fraction = 0.0;
+            p.team_members[0].time_off.push(time_off);
+        });
+        assert_eq!(
+            errors,
+            ["Alice Kim: time off from 2025-01-06 covers 0% of each day"]
+        );
+    }
+
+    #[test]
+    fn test_error_duplicate_holiday_ids() {
+        let holiday = Holiday::new("Break".to_string(), quarter_start(), quarter_start());
+        let id = holiday.id;
+        let errors = errors_after(|p, _| p.holidays = vec![holiday.clone(), holiday]);
+        assert_eq!(
+            errors,
+            [format!("Holiday ID {} is used more than once", id)]
+        );
+    }
+
+    #[test]
+    fn test_error_holiday_reversed() {
+        let errors = errors_after(|p, _| {
+            p.holidays.push(Holiday::new(
+                "Break".to_string(),
+                quarter_start() + Duration::days(4),
+                quarter_start(),
+            ))
+        });
+        assert_eq!(
+            errors,
+            ["Holiday \"Break\" ends (2025-01-06) before it starts (2025-01-10)"]
+        );
+    }
+
+    #[test]
+    fn test_error_empty_quarter_name() {
+        let errors = errors_after(|_, s| s.quarter_name = String::new());
+        assert_eq!(errors, ["Quarter name is empty"]);
+    }
+
+    #[test]
+    fn test_error_quarter_without_weeks() {
+        let errors = errors_after(|_, s| s.num_weeks = 0);
+        assert_eq!(errors, ["The quarter has no weeks"]);
+    }
+
+    #[test]
+    fn test_error_duplicate_roadmap_project_ids() {
+        let mut id = Uuid::nil();
+        let errors = errors_after(|_, s| {
+            id = s.roadmap_projects[0].id;
+            s.roadmap_projects.push(s.roadmap_projects[0].clone());
+        });
+        assert_eq!(
+            errors,
+            [format!("Roadmap project ID {} is used more than once", id)]
+        );
+    }
+
+    #[test]
+    fn test_error_negative_estimate() {
+        let errors =
+            errors_after(|_, s| s.roadmap_projects[0].estimates.set(Role::ENGINEERING, -1.0));
+        assert_eq!(
+            errors,
+            ["Roadmap project \"Payment Gateway\": eng estimate of -1 weeks is negative"]
+        );
+    }
+
+    #[test]
+    fn test_error_duplicate_technical_project_ids() {
+        let mut id = Uuid::nil();
+        let errors = errors_after(|_, s| {
+            id = s.technical_projects[0].id;
+            s.technical_projects.push(s.technical_projects[0].clone());
+        });
+        assert_eq!(
+            errors,
+            [format!(
+                "Technical project ID {} is used more than once",
+                id
+            )]
+        );
+    }
+
+    #[test]
+    fn test_error_missing_roadmap_project() {
+        let errors = errors_after(|_, s| s.roadmap_projects.clear());
+        assert_eq!(
+            errors,
+            ["Technical project \"Gateway API\": linked roadmap project doesn't exist"]
+        );
+    }
+
+    #[test]
+    fn test_error_missing_predecessor() {
+        let errors = errors_after(|_, s| {
+            s.technical_projects[0]
+                .dependencies
+                .push(ProjectDependency::new(
+                    Uuid::new_v4(),
+                    DependencyKind::FinishToStart,
+                ))
+        });
+        assert_eq!(
+            errors,
+            ["Technical project \"Gateway API\": depends on a project that doesn't exist"]
+        );
+    }
+
+    #[test]
+    fn test_error_dependency_cycle() {
+        let errors = errors_after(|_, s| {
+            let mut client = TechnicalProject::new(
+                "Client".to_string(),
+                None,
+                RoleWeeks::new(),
+                quarter_start(),
+            );
+            let api_id = s.technical_projects[0].id;
+            client.dependencies.push(ProjectDependency::new(
+                api_id,
+                DependencyKind::FinishToStart,
+            ));
+            s.technical_projects[0]
+                .dependencies
+                .push(ProjectDependency::new(
+                    client.id,
+                    DependencyKind::FinishToStart,
+                ));
+            s.technical_projects.push(client);
+        });
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("Dependency cycle: ")
+                && errors[0].contains("Gateway API")
+                && errors[0].contains("Client"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_error_unknown_allocation_member() {
+        let errors = errors_after(|_, s| s.allocations[0].team_member_id = Uuid::new_v4());
+        assert_eq!(
+            errors,
+            ["Unknown member, week of 2025-01-06: team member doesn't exist"]
+        );
+    }
+
+    #[test]
+    fn test_error_week_allocated_twice() {
+        let errors = errors_after(|_, s| s.allocations.push(s.allocations[0].clone()));
+        assert_eq!(
+            errors,
+            ["Alice Kim, week of 2025-01-06: week is allocated more than once"]
+        );
+    }
+
+    #[test]
+    fn test_error_non_monday_week() {
+        let errors = errors_after(|_, s| {
+            s.allocations[0].week_start_date = quarter_start() + Duration::days(1)
+        });
+        assert_eq!(
+            errors,
+            ["Alice Kim, week of 2025-01-07: allocation doesn't start on a Monday"]
+        );
+    }
+
+    #[test]
+    fn test_error_unknown_assigned_project() {
+        let errors = errors_after(|_, s| {
+            s.allocations[0].assignments[0].technical_project_id = Uuid::new_v4()
+        });
+        assert_eq!(
+            errors,
+            ["Alice Kim, week of 2025-01-06: assigned project doesn't exist"]
+        );
+    }
+
+    #[test]
+    fn test_error_non_positive_assignment() {
+        let errors = errors_after(|_, s| s.allocations[0].assignments[0].percentage = 0.0);
+        assert_eq!(errors, ["Alice Kim, week of 2025-01-06: assignment of 0%"]);
+    }
+
+    #[test]
+    fn test_error_total_over_100() {
+        let errors = errors_after(|_, s| {
+            let project = s.technical_projects[0].id;
+            s.allocations[0]
+                .assignments
+                .push(Assignment::new(project, 50.0));
+        });
+        assert_eq!(
+            errors,
+            ["Alice Kim, week of 2025-01-06: assignments add up to 150%"]
+        );
+    }
+
+    #[test]
+    fn test_error_in_scenario_branch() {
+        let (prefs, mut state) = sample();
+        let id = state.create_scenario("Lean");
+        state.switch_scenario(Some(id));
+        state.allocations[0].team_member_id = Uuid::new_v4();
+
+        let report = validate_plan(&prefs, &state);
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].scenario_id, Some(id));
+        assert_eq!(
+            errors[0].message,
+            "Scenario \"Lean\": Unknown member, week of 2025-01-06: team member doesn't exist"
+        );
+    }
+}
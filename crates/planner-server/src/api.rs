@@ -0,0 +1,553 @@
+//! REST routes over the plan store
+//!
+//! | Method | Path                              | Does                                  |
+//! |--------|-----------------------------------|---------------------------------------|
+//! | GET    | /plans                            | List plans                            |
+//! | GET    | /plans/{id}                       | Get a plan file                       |
+//! | PUT    | /plans/{id}                       | Create or replace a plan file         |
+//! | GET    | /plans/{id}/metrics/members       | Capacity and utilization per member   |
+//! | GET    | /plans/{id}/metrics/projects      | Estimate vs. allocation per project   |
+//! | PATCH  | /plans/{id}/allocations           | Replace allocation cells              |
+//!
+//! Patching allocations moves the affected technical projects' start and
+//! completion dates to the sprints they're now staffed in, as painting in the
+//! app does. Plan files carry no sprint settings, so the default sprint
+//! cadence applies.
+//!
+//! Writes are checked with the same validation the app's Plan health panel
+//! uses; a plan with errors is rejected with 422 and the issues, and warnings
+//! come back alongside a successful write.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use planner_core::models::{Allocation, PlanExport};
+use planner_core::report::{status_label, PlanReport};
+use planner_core::validation::{validate_export, Issue, ValidationReport};
+
+use crate::http::{error_response, Request, Response};
+use crate::store::{PlanStore, StoreError};
+
+/// Body of `PATCH /plans/{id}/allocations`
+///
+/// Each allocation replaces the team member's week in the main plan; one with
+/// no assignments clears the week.
+#[derive(Debug, Deserialize)]
+struct AllocationPatch {
+    allocations: Vec<Allocation>,
+}
+
+/// A failed request, as a status plus a structured error body
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    code: &'static str,
+    message: String,
+    issues: Vec<Issue>,
+}
+
+impl ApiError {
+    fn new(status: u16, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            issues: Vec::new(),
+        }
+    }
+
+    fn into_response(self) -> Response {
+        let issues = if self.issues.is_empty() {
+            Value::Null
+        } else {
+            issues_json(&self.issues)
+        };
+        error_response(self.status, self.code, &self.message, issues)
+    }
+}
+
+impl From<StoreError> for ApiError {
+    fn from(error: StoreError) -> Self {
+        let (status, code) = match &error {
+            StoreError::InvalidId(_) => (400, "invalid_id"),
+            StoreError::NotFound(_) => (404, "not_found"),
+            StoreError::Load { .. } => (500, "unreadable_plan"),
+            StoreError::Io(_) => (500, "io_error"),
+        };
+        ApiError::new(status, code, error.to_string())
+    }
+}
+
+/// Route a request and produce its response
+pub fn handle(store: &PlanStore, request: &Request) -> Response {
+    let segments: Vec<&str> = request
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let method = request.method.as_str();
+    let result = match segments.as_slice() {
+        ["plans"] => match method {
+            "GET" => list_plans(store),
+            _ => return method_not_allowed("GET"),
+        },
+        ["plans", id] => match method {
+            "GET" => get_plan(store, id),
+            "PUT" => put_plan(store, id, &request.body),
+            _ => return method_not_allowed("GET, PUT"),
+        },
+        ["plans", id, "metrics", "members"] => match method {
+            "GET" => member_metrics(store, id),
+            _ => return method_not_allowed("GET"),
+        },
+        ["plans", id, "metrics", "projects"] => match method {
+            "GET" => project_metrics(store, id),
+            _ => return method_not_allowed("GET"),
+        },
+        ["plans", id, "allocations"] => match method {
+            "PATCH" => patch_allocations(store, id, &request.body),
+            _ => return method_not_allowed("PATCH"),
+        },
+        _ => Err(ApiError::new(
+            404,
+            "not_found",
+            format!("No route for {}", request.path),
+        )),
+    };
+    result.unwrap_or_else(ApiError::into_response)
+}
+
+fn method_not_allowed(allow: &str) -> Response {
+    ApiError::new(
+        405,
+        "method_not_allowed",
+        format!("Supported methods: {}", allow),
+    )
+    .into_response()
+    .with_header("Allow", allow.to_string())
+}
+
+fn list_plans(store: &PlanStore) -> Result<Response, ApiError> {
+    let plans: Vec<Value> = store
+        .ids()?
+        .into_iter()
+        .map(|id| match store.load(&id) {
+            Ok(export) => json!({
+                "id": id,
+                "team_name": export.team_name,
+                "quarter_name": export.quarter_name,
+                "quarter_start_date": export.quarter_start_date,
+                "num_weeks": export.num_weeks,
+                "modified_at": export.metadata.modified_at,
+            }),
+            // Listed anyway so a broken file is visible, not silently missing
+            Err(e) => json!({ "id": id, "error": e.to_string() }),
+        })
+        .collect();
+    Ok(Response::ok(json!({ "plans": plans })))
+}
+
+fn get_plan(store: &PlanStore, id: &str) -> Result<Response, ApiError> {
+    let export = store.load(id)?;
+    Ok(Response::ok(json!(export)))
+}
+
+fn put_plan(store: &PlanStore, id: &str, body: &[u8]) -> Result<Response, ApiError> {
+    let json = std::str::from_utf8(body)
+        .map_err(|_| ApiError::new(400, "invalid_json", "The body isn't UTF-8"))?;
+    // Accept files from older versions, like opening them in the app does
+    let export: PlanExport = planner_core::migration::load_json(json)
+        .map_err(|e| ApiError::new(400, "invalid_plan", e.to_string()))?;
+
+    let _guard = store.lock();
+    let created = !store.exists(id)?;
+    let report = check(&export)?;
+    store.save(id, &export)?;
+    Ok(Response::new(
+        if created { 201 } else { 200 },
+        json!({ "id": id, "warnings": issues_json(&report.issues) }),
+    ))
+}
+
+fn patch_allocations(store: &PlanStore, id: &str, body: &[u8]) -> Result<Response, ApiError> {
+    let patch: AllocationPatch = serde_json::from_slice(body)
+        .map_err(|e| ApiError::new(400, "invalid_json", e.to_string()))?;
+
+    let _guard = store.lock();
+    let (prefs, mut state) = store.load(id)?.into_signals();
+    // Projects staffed in a replaced cell, before or after
+    let mut affected_projects = HashSet::new();
+    for allocation in &patch.allocations {
+        let same_cell = |a: &Allocation| {
+            a.team_member_id == allocation.team_member_id
+                && a.week_start_date == allocation.week_start_date
+        };
+        affected_projects.extend(
+            state
+                .allocations
+                .iter()
+                .filter(|a| same_cell(a))
+                .chain([allocation])
+                .flat_map(|a| &a.assignments)
+                .map(|assignment| assignment.technical_project_id),
+        );
+        state.allocations.retain(|a| !same_cell(a));
+    }
+    state.allocations.extend(
+        patch
+            .allocations
+            .into_iter()
+            .filter(|a| !a.assignments.is_empty()),
+    );
+    for project_id in affected_projects {
+        state.update_technical_project_dates(
+            &project_id,
+            prefs.sprint_anchor_date,
+            prefs.sprint_length_weeks,
+        );
+    }
+    state.mark_modified();
+    let export = PlanExport::from_signals(prefs, state);
+
+    let report = check(&export)?;
+    store.save(id, &export)?;
+    Ok(Response::ok(json!({
+        "id": id,
+        "allocations": export.allocations.len(),
+        "warnings": issues_json(&report.issues),
+    })))
+}
+
+/// Validate a plan before writing it; only warnings get through
+fn check(export: &PlanExport) -> Result<ValidationReport, ApiError> {
+    let report = validate_export(export);
+    if report.has_errors() {
+        return Err(ApiError {
+            status: 422,
+            code: "validation_failed",
+            message: report.summary(),
+            issues: report.issues,
+        });
+    }
+    Ok(report)
+}
+
+fn member_metrics(store: &PlanStore, id: &str) -> Result<Response, ApiError> {
+    let (prefs, state) = store.load(id)?.into_signals();
+    let report = PlanReport::new(&prefs, &state);
+    let members: Vec<Value> = report
+        .members
+        .iter()
+        .map(|member| {
+            json!({
+                "id": member.id,
+                "name": member.name,
+                "role": member.role_name,
+                "capacity_weeks": number(member.capacity),
+                "allocated_weeks": number(member.allocated),
+                "utilization_pct": number(member.utilization_pct()),
+                "status": status_label(member.status),
+                "assignments": member
+                    .assignments
+                    .iter()
+                    .map(|(project, weeks)| json!({ "project": project, "weeks": number(*weeks) }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    Ok(Response::ok(json!({
+        "capacity_weeks": number(report.capacity),
+        "allocated_weeks": number(report.allocated),
+        "utilization_pct": number(report.utilization_pct()),
+        "status": status_label(report.status),
+        "members": members,
+    })))
+}
+
+fn project_metrics(store: &PlanStore, id: &str) -> Result<Response, ApiError> {
+    let (prefs, state) = store.load(id)?.into_signals();
+    let report = PlanReport::new(&prefs, &state);
+    // The report keeps the plan's project order
+    let roadmap: Vec<Value> = state
+        .roadmap_projects
+        .iter()
+        .zip(&report.roadmap_projects)
+        .map(|(project, metrics)| {
+            json!({
+                "id": project.id,
+                "name": metrics.name,
+                "launch_date": metrics.launch_date,
+                "estimate_weeks": number(metrics.estimate),
+                "allocated_weeks": number(metrics.allocated),
+                "status": status_label(metrics.status),
+            })
+        })
+        .collect();
+    let technical: Vec<Value> = state
+        .technical_projects
+        .iter()
+        .zip(&report.technical_projects)
+        .map(|(project, metrics)| {
+            json!({
+                "id": project.id,
+                "name": metrics.name,
+                "roadmap_project_id": project.roadmap_project_id,
+                "estimate_weeks": number(metrics.estimate),
+                "allocated_weeks": number(metrics.allocated),
+                "status": status_label(metrics.status),
+                "start_date": metrics.dates.map(|(start, _)| start),
+                "end_date": metrics.dates.map(|(_, end)| end),
+            })
+        })
+        .collect();
+    Ok(Response::ok(json!({
+        "roadmap_projects": roadmap,
+        "technical_projects": technical,
+    })))
+}
+
+/// Weeks and percentages rounded to hundredths, so f32 sums read cleanly
+fn number(value: f32) -> f64 {
+    (f64::from(value) * 100.0).round() / 100.0
+}
+
+fn issues_json(issues: &[Issue]) -> Value {
+    issues
+        .iter()
+        .map(|issue| {
+            json!({
+                "severity": issue.severity.label().to_lowercase(),
+                "subject": issue.subject.kind(),
+                "scenario_id": issue.scenario_id,
+                "message": issue.message,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use chrono::{Duration, NaiveDate};
+    use planner_core::models::{
+        Assignment, PlanState, Preferences, Role, RoleWeeks, TeamMember, TechnicalProject,
+    };
+
+    /// A plan directory removed when the test ends
+    struct TestStore {
+        dir: PathBuf,
+        store: PlanStore,
+    }
+
+    impl TestStore {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "planner-server-{}-{}",
+                name,
+                std::process::id()
+            ));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self {
+                store: PlanStore::new(&dir),
+                dir,
+            }
+        }
+
+        fn send(&self, method: &str, path: &str, body: Value) -> Response {
+            let request = Request {
+                method: method.to_string(),
+                path: path.to_string(),
+                body: if body.is_null() {
+                    Vec::new()
+                } else {
+                    body.to_string().into_bytes()
+                },
+            };
+            handle(&self.store, &request)
+        }
+    }
+
+    impl Drop for TestStore {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    }
+
+    /// A one-member plan with one technical project and no allocations
+    fn sample_plan() -> (PlanExport, TeamMember, TechnicalProject) {
+        let mut prefs = Preferences::new("Payments".to_string());
+        let alice = TeamMember::new("Alice Kim".to_string(), Role::ENGINEERING, 12.0);
+        prefs.team_members.push(alice.clone());
+        let mut state = PlanState::new("Q1 2025".to_string(), start(), 13);
+        let api = TechnicalProject::new(
+            "API".to_string(),
+            None,
+            RoleWeeks::from([(Role::ENGINEERING, 2.0)]),
+            start(),
+        );
+        state.technical_projects.push(api.clone());
+        (PlanExport::from_signals(prefs, state), alice, api)
+    }
+
+    fn week_on(member: &TeamMember, project: &TechnicalProject, week: i64) -> Allocation {
+        let mut allocation = Allocation::new(member.id, start() + Duration::weeks(week));
+        allocation
+            .assignments
+            .push(Assignment::new(project.id, 100.0));
+        allocation
+    }
+
+    #[test]
+    fn test_put_and_list_plans() {
+        let test = TestStore::new("put");
+        let (export, _, _) = sample_plan();
+
+        let created = test.send("PUT", "/plans/q1", json!(export));
+        assert_eq!(created.status, 201, "{}", created.body);
+        let replaced = test.send("PUT", "/plans/q1", json!(export));
+        assert_eq!(replaced.status, 200, "{}", replaced.body);
+
+        let listed = test.send("GET", "/plans", Value::Null);
+        assert_eq!(listed.status, 200);
+        assert_eq!(listed.body["plans"][0]["id"], "q1");
+        assert_eq!(listed.body["plans"][0]["team_name"], "Payments");
+
+        let fetched = test.send("GET", "/plans/q1", Value::Null);
+        assert_eq!(fetched.status, 200);
+        assert_eq!(fetched.body["quarter_name"], "Q1 2025");
+    }
+
+    #[test]
+    fn test_invalid_ids_are_rejected() {
+        let test = TestStore::new("invalid-id");
+        let (export, _, _) = sample_plan();
+        for path in [
+            "/plans/..",
+            "/plans/.hidden",
+            "/plans/q1%2F..",
+            "/plans/a$b",
+        ] {
+            let response = test.send("GET", path, Value::Null);
+            assert_eq!(response.status, 400, "{}", path);
+            assert_eq!(response.body["error"]["code"], "invalid_id");
+        }
+        let put = test.send("PUT", "/plans/..", json!(export));
+        assert_eq!(put.status, 400);
+        assert!(test.store.ids().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_plans_and_routes_are_404() {
+        let test = TestStore::new("not-found");
+        for path in [
+            "/plans/q2",
+            "/plans/q2/metrics/members",
+            "/plans/q2/metrics/projects",
+            "/plans/q2/unknown",
+            "/reports",
+        ] {
+            let response = test.send("GET", path, Value::Null);
+            assert_eq!(response.status, 404, "{}", path);
+            assert_eq!(response.body["error"]["code"], "not_found");
+        }
+        let patch = test.send(
+            "PATCH",
+            "/plans/q2/allocations",
+            json!({ "allocations": [] }),
+        );
+        assert_eq!(patch.status, 404);
+    }
+
+    #[test]
+    fn test_wrong_method_is_405() {
+        let test = TestStore::new("method");
+        for (method, path, allow) in [
+            ("POST", "/plans", "GET"),
+            ("DELETE", "/plans/q1", "GET, PUT"),
+            ("PUT", "/plans/q1/metrics/members", "GET"),
+            ("GET", "/plans/q1/allocations", "PATCH"),
+        ] {
+            let response = test.send(method, path, Value::Null);
+            assert_eq!(response.status, 405, "{} {}", method, path);
+            assert_eq!(response.headers, [("Allow", allow.to_string())]);
+        }
+    }
+
+    #[test]
+    fn test_patch_allocations_round_trip() {
+        let test = TestStore::new("patch");
+        let (export, alice, api) = sample_plan();
+        test.send("PUT", "/plans/q1", json!(export));
+
+        // Weeks 1 and 2 (one sprint) on API, then clear week 2
+        let weeks = [week_on(&alice, &api, 1), week_on(&alice, &api, 2)];
+        let patched = test.send(
+            "PATCH",
+            "/plans/q1/allocations",
+            json!({ "allocations": weeks }),
+        );
+        assert_eq!(patched.status, 200, "{}", patched.body);
+        assert_eq!(patched.body["allocations"], 2);
+
+        let saved = test.store.load("q1").unwrap();
+        assert_eq!(saved.allocations, weeks);
+        // Moved to the sprints the project is staffed in
+        let project = &saved.technical_projects[0];
+        assert_eq!(project.start_date, start() + Duration::weeks(1));
+        assert_eq!(
+            project.expected_completion,
+            Some(start() + Duration::weeks(3) - Duration::days(1))
+        );
+
+        let cleared = Allocation::new(alice.id, start() + Duration::weeks(2));
+        let patched = test.send(
+            "PATCH",
+            "/plans/q1/allocations",
+            json!({ "allocations": [cleared] }),
+        );
+        assert_eq!(patched.status, 200, "{}", patched.body);
+        let fetched = test.send("GET", "/plans/q1", Value::Null);
+        assert_eq!(fetched.body["allocations"], json!([weeks[0]]));
+
+        let members = test.send("GET", "/plans/q1/metrics/members", Value::Null);
+        assert_eq!(members.body["members"][0]["allocated_weeks"], 1.0);
+        let projects = test.send("GET", "/plans/q1/metrics/projects", Value::Null);
+        assert_eq!(projects.body["technical_projects"][0]["id"], json!(api.id));
+        assert_eq!(
+            projects.body["technical_projects"][0]["end_date"],
+            "2025-01-19"
+        );
+    }
+
+    #[test]
+    fn test_patch_with_errors_is_not_written() {
+        let test = TestStore::new("patch-invalid");
+        let (export, alice, api) = sample_plan();
+        test.send("PUT", "/plans/q1", json!(export));
+
+        // A week for someone not on the team
+        let mut stranger = week_on(&alice, &api, 0);
+        stranger.team_member_id = api.id;
+        let rejected = test.send(
+            "PATCH",
+            "/plans/q1/allocations",
+            json!({ "allocations": [stranger] }),
+        );
+        assert_eq!(rejected.status, 422);
+        assert_eq!(rejected.body["error"]["code"], "validation_failed");
+        assert_eq!(rejected.body["error"]["issues"][0]["subject"], "Allocation");
+        assert!(test.store.load("q1").unwrap().allocations.is_empty());
+
+        let garbled = test.send("PATCH", "/plans/q1/allocations", json!({ "weeks": [] }));
+        assert_eq!(garbled.status, 400);
+        assert_eq!(garbled.body["error"]["code"], "invalid_json");
+    }
+}
@@ -0,0 +1,203 @@
+//! Minimal HTTP/1.1 for a local JSON API
+//!
+//! One request per connection, bodies sized by `Content-Length`, and JSON
+//! responses. That's all scripts on the same machine need, and it keeps the
+//! server free of an async runtime.
+
+use std::io::{self, BufRead, Write};
+
+use serde_json::{json, Value};
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Most header lines accepted
+const MAX_HEADERS: usize = 100;
+
+/// A parsed request
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// A JSON response
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Value,
+}
+
+impl Response {
+    pub fn new(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn ok(body: Value) -> Self {
+        Self::new(200, body)
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: String) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Write the response and end the exchange
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        let body =
+            serde_json::to_string_pretty(&self.body).unwrap_or_else(|_| "null".to_string()) + "\n";
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            reason(self.status),
+            body.len()
+        )?;
+        for (name, value) in &self.headers {
+            write!(out, "{}: {}\r\n", name, value)?;
+        }
+        write!(out, "\r\n{}", body)?;
+        out.flush()
+    }
+}
+
+/// A request that couldn't be read, as the response to send back
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Bad(Response),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+fn bad_request(status: u16, code: &str, message: &str) -> ReadError {
+    ReadError::Bad(error_response(status, code, message, Value::Null))
+}
+
+/// The error body every failure uses:
+/// `{"error": {"code": "...", "message": "...", "issues": [...]}}`
+pub fn error_response(status: u16, code: &str, message: &str, issues: Value) -> Response {
+    let mut error = json!({ "code": code, "message": message });
+    if !issues.is_null() {
+        error["issues"] = issues;
+    }
+    Response::new(status, json!({ "error": error }))
+}
+
+/// Read one request from a connection
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request, ReadError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(bad_request(400, "bad_request", "Malformed request line"));
+    };
+    let method = method.to_string();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut header_count = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADERS {
+            return Err(bad_request(431, "headers_too_large", "Too many headers"));
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(bad_request(400, "bad_request", "Malformed header"));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| bad_request(400, "bad_request", "Invalid Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(bad_request(
+                411,
+                "length_required",
+                "Send the body with a Content-Length instead of chunked encoding",
+            ));
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(bad_request(
+            413,
+            "body_too_large",
+            &format!("Request bodies are limited to {} bytes", MAX_BODY_BYTES),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request { method, path, body })
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_request() {
+        let raw = "PATCH /plans/q1/allocations?dry=1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 2\r\n\r\n{}";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(
+            request,
+            Request {
+                method: "PATCH".to_string(),
+                path: "/plans/q1/allocations".to_string(),
+                body: b"{}".to_vec(),
+            }
+        );
+
+        let chunked = "PUT /plans/q1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        match read_request(&mut chunked.as_bytes()) {
+            Err(ReadError::Bad(response)) => assert_eq!(response.status, 411),
+            other => panic!("expected a 411, got {:?}", other),
+        }
+
+        let mut out = Vec::new();
+        Response::new(404, json!({ "error": { "code": "not_found" } }))
+            .with_header("Allow", "GET".to_string())
+            .write_to(&mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(out.contains("\r\nAllow: GET\r\n\r\n{"));
+    }
+}
@@ -0,0 +1,155 @@
+//! planner-server: a local HTTP/JSON API over a directory of plan files
+//!
+//! Lets scripts and internal tools read and update plans without the app.
+//! Built on planner-core, so it reads and writes the same files the app saves
+//! and rejects writes the app's validation would flag as errors. Routes are
+//! listed in `api`.
+//!
+//! There's no authentication: it binds to localhost by default and is meant
+//! for tools on the same machine.
+
+use std::io::{self, BufReader};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use http::{read_request, ReadError};
+use store::PlanStore;
+
+mod api;
+mod http;
+mod store;
+
+const USAGE: &str = "\
+Usage: planner-server [--dir <DIR>] [--addr <ADDR>]
+
+Serves the plan files (*.json) in DIR over a JSON API.
+
+Options:
+  -d, --dir <DIR>    Directory of plan files (default: current directory)
+  -a, --addr <ADDR>  Address to listen on (default: 127.0.0.1:8787)
+  -h, --help         Print this help
+  -V, --version      Print the version";
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+
+/// A client that stops sending shouldn't hold a thread forever
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connections served at once; further ones wait for a free worker
+const WORKERS: usize = 8;
+
+fn main() -> ExitCode {
+    let mut dir = PathBuf::from(".");
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "-V" | "--version" => {
+                println!("planner-server {}", env!("CARGO_PKG_VERSION"));
+                return ExitCode::SUCCESS;
+            }
+            "-d" | "--dir" | "-a" | "--addr" => args.next(),
+            _ => {
+                eprintln!("error: Unknown argument \"{}\"\n\n{}", arg, USAGE);
+                return ExitCode::from(2);
+            }
+        };
+        let Some(value) = value else {
+            eprintln!("error: {} needs a value\n\n{}", arg, USAGE);
+            return ExitCode::from(2);
+        };
+        match arg.as_str() {
+            "-d" | "--dir" => dir = PathBuf::from(value),
+            _ => addr = value,
+        }
+    }
+
+    if !dir.is_dir() {
+        eprintln!("error: {} isn't a directory", dir.display());
+        return ExitCode::from(2);
+    }
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: Couldn't listen on {}: {}", addr, e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let store = Arc::new(PlanStore::new(dir));
+    eprintln!(
+        "Serving plans in {} on http://{}",
+        store.dir().display(),
+        addr
+    );
+    // A fixed pool, so a burst of connections can't exhaust threads; the
+    // queue is bounded too, which stops accepting until a worker frees up
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(WORKERS);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKERS {
+        let store = Arc::clone(&store);
+        let receiver = Arc::clone(&receiver);
+        std::thread::spawn(move || work(&store, &receiver));
+    }
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if sender.send(stream).is_err() {
+                    eprintln!("error: No workers left to serve requests");
+                    return ExitCode::FAILURE;
+                }
+            }
+            Err(e) => eprintln!("Connection failed: {}", e),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Serve queued connections until the queue closes
+fn work(store: &PlanStore, queue: &Mutex<Receiver<TcpStream>>) {
+    loop {
+        // Only wait for the next connection under the lock, not serve it
+        let next = match queue.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => return,
+        };
+        match next {
+            Ok(stream) => serve(store, stream),
+            Err(_) => return,
+        }
+    }
+}
+
+/// Answer one request on a connection
+fn serve(store: &PlanStore, mut stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let Ok(read_half) = stream.try_clone() else {
+        return;
+    };
+    let (label, response) = match read_request(&mut BufReader::new(read_half)) {
+        Ok(request) => (
+            format!("{} {}", request.method, request.path),
+            api::handle(store, &request),
+        ),
+        Err(ReadError::Bad(response)) => ("(bad request)".to_string(), response),
+        // The client went away or timed out; there's no one to answer
+        Err(ReadError::Io(e)) => {
+            if e.kind() != io::ErrorKind::UnexpectedEof {
+                eprintln!("Failed to read request: {}", e);
+            }
+            return;
+        }
+    };
+    eprintln!("{} -> {}", label, response.status);
+    if let Err(e) = response.write_to(&mut stream) {
+        eprintln!("Failed to send response: {}", e);
+    }
+}
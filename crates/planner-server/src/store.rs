@@ -0,0 +1,139 @@
+//! Plan files on disk
+//!
+//! Each plan is `<id>.json` in the served directory, in the same format the
+//! app saves. Ids are restricted to file-name-safe characters so a request
+//! can never reach outside the directory.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use planner_core::migration::{load_json, MigrationError};
+use planner_core::models::PlanExport;
+
+/// Errors reading or writing plan files
+#[derive(Debug)]
+pub enum StoreError {
+    /// The id isn't a valid plan name
+    InvalidId(String),
+    NotFound(String),
+    /// The file exists but isn't a plan this version can load
+    Load {
+        id: String,
+        error: MigrationError,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::InvalidId(id) => write!(
+                f,
+                "\"{}\" isn't a valid plan id (use letters, digits, '-', '_' and '.')",
+                id
+            ),
+            StoreError::NotFound(id) => write!(f, "No plan named \"{}\"", id),
+            StoreError::Load { id, error } => write!(f, "Plan \"{}\": {}", id, error),
+            StoreError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(error: io::Error) -> Self {
+        StoreError::Io(error)
+    }
+}
+
+/// A directory of plan files
+pub struct PlanStore {
+    dir: PathBuf,
+    /// Held across read-modify-write so concurrent requests don't interleave
+    write_lock: Mutex<()>,
+}
+
+impl PlanStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Ids of every plan file, sorted
+    pub fn ids(&self) -> Result<Vec<String>, StoreError> {
+        let mut ids = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    if is_valid_id(id) {
+                        ids.push(id.to_string());
+                    }
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    pub fn exists(&self, id: &str) -> Result<bool, StoreError> {
+        Ok(self.path(id)?.is_file())
+    }
+
+    /// Load a plan, upgrading older formats
+    pub fn load(&self, id: &str) -> Result<PlanExport, StoreError> {
+        let path = self.path(id)?;
+        let json = std::fs::read_to_string(&path).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => StoreError::NotFound(id.to_string()),
+            _ => StoreError::Io(error),
+        })?;
+        load_json(&json).map_err(|error| StoreError::Load {
+            id: id.to_string(),
+            error,
+        })
+    }
+
+    /// Write a plan, replacing the file atomically
+    pub fn save(&self, id: &str, export: &PlanExport) -> Result<(), StoreError> {
+        let path = self.path(id)?;
+        let json = serde_json::to_string_pretty(export)
+            .map_err(|e| StoreError::Io(io::Error::other(e)))?;
+        // Hidden, so a half-written file never shows up as a plan
+        let temp = self.dir.join(format!(".{}.json.tmp", id));
+        std::fs::write(&temp, json + "\n")?;
+        std::fs::rename(&temp, &path)?;
+        Ok(())
+    }
+
+    /// Serialize writers; hold the guard from load to save
+    pub fn lock(&self) -> MutexGuard<'_, ()> {
+        // A panicked writer leaves the files intact, so the lock is still usable
+        self.write_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, StoreError> {
+        if !is_valid_id(id) {
+            return Err(StoreError::InvalidId(id.to_string()));
+        }
+        Ok(self.dir.join(format!("{}.json", id)))
+    }
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}